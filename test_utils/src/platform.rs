@@ -1,15 +1,35 @@
-use alloc::boxed::Box;
+use alloc::{boxed::Box, string::String, sync::Arc, vec::Vec};
 use core::sync::atomic::{AtomicU64, Ordering};
 
-use wie_backend::{AudioSink, Instant, Platform};
+use spin::Mutex;
+
+use wie_backend::{
+    canvas::Image, AudioSink, DeviceEffect, DeviceEffects, DeviceProfile, FileStorage, FileStorageRepository, Instant, Platform, Screen,
+};
+use wie_util::Result;
 
 static TEST_EPOCH: AtomicU64 = AtomicU64::new(0);
 
-pub struct TestPlatform;
+#[derive(Default)]
+pub struct TestPlatform {
+    effects: Arc<Mutex<Vec<DeviceEffect>>>,
+    screen: TestScreen,
+}
+
+impl TestPlatform {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Device effects applied so far, shared so they can be checked after platform is moved into system.
+    pub fn effects(&self) -> Arc<Mutex<Vec<DeviceEffect>>> {
+        self.effects.clone()
+    }
+}
 
 impl Platform for TestPlatform {
-    fn screen(&mut self) -> &mut dyn wie_backend::Screen {
-        todo!()
+    fn screen(&mut self) -> &mut dyn Screen {
+        &mut self.screen
     }
 
     fn now(&self) -> Instant {
@@ -21,35 +41,84 @@ impl Platform for TestPlatform {
         todo!()
     }
 
+    fn file_storage_repository(&self) -> &dyn FileStorageRepository {
+        self
+    }
+
     fn audio_sink(&self) -> Box<dyn AudioSink> {
         Box::new(TestAudioSink)
     }
 
+    fn device_effects(&self) -> &dyn DeviceEffects {
+        self
+    }
+
+    fn device_profile(&self) -> DeviceProfile {
+        DeviceProfile::default()
+    }
+
     fn write_stdout(&self, _buf: &[u8]) {}
 
     fn write_stderr(&self, _buf: &[u8]) {}
+
+    fn exit(&mut self) {}
 }
 
-struct TestAudioSink;
+impl DeviceEffects for TestPlatform {
+    fn apply(&self, effect: DeviceEffect) {
+        self.effects.lock().push(effect);
+    }
+}
 
-impl AudioSink for TestAudioSink {
-    fn play_wave(&self, _channel: u8, _sampling_rate: u32, _wave_data: &[i16]) {
-        todo!()
+impl FileStorageRepository for TestPlatform {
+    fn open(&self, _app_id: &str) -> Box<dyn FileStorage> {
+        Box::new(TestFileStorage)
     }
+}
 
-    fn midi_note_on(&self, _channel_id: u8, _note: u8, _velocity: u8) {
-        todo!()
+/// Starts empty and discards writes, as tests don't persist anything.
+struct TestFileStorage;
+
+impl FileStorage for TestFileStorage {
+    fn files(&self) -> Vec<(String, Vec<u8>)> {
+        Vec::new()
     }
 
-    fn midi_note_off(&self, _channel_id: u8, _note: u8, _velocity: u8) {
-        todo!()
+    fn write(&mut self, _path: &str, _data: &[u8]) -> bool {
+        true
     }
 
-    fn midi_program_change(&self, _channel_id: u8, _program: u8) {
-        todo!()
+    fn remove(&mut self, _path: &str) -> bool {
+        true
     }
+}
 
-    fn midi_control_change(&self, _channel_id: u8, _control: u8, _value: u8) {
-        todo!()
+/// Discards painted images, so that code requesting redraw can be tested.
+#[derive(Default)]
+struct TestScreen;
+
+impl Screen for TestScreen {
+    fn request_redraw(&self) -> Result<()> {
+        Ok(())
+    }
+
+    fn paint(&mut self, _image: &dyn Image) {}
+
+    fn width(&self) -> u32 {
+        240
+    }
+
+    fn height(&self) -> u32 {
+        320
+    }
+}
+
+struct TestAudioSink;
+
+impl AudioSink for TestAudioSink {
+    fn sample_rate(&self) -> u32 {
+        8000
     }
+
+    fn write(&self, _samples: &[i16]) {}
 }
@@ -1,7 +1,6 @@
+/// Output of mixed audio, as interleaved stereo 16-bit pcm.
 pub trait AudioSink: Sync + Send {
-    fn play_wave(&self, channel: u8, sampling_rate: u32, wave_data: &[i16]);
-    fn midi_note_on(&self, channel_id: u8, note: u8, velocity: u8);
-    fn midi_note_off(&self, channel_id: u8, note: u8, velocity: u8);
-    fn midi_program_change(&self, channel_id: u8, program: u8);
-    fn midi_control_change(&self, channel_id: u8, control: u8, value: u8);
+    /// Output sampling rate. Every audio is resampled to this rate before being written.
+    fn sample_rate(&self) -> u32;
+    fn write(&self, samples: &[i16]);
 }
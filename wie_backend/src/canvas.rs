@@ -1,19 +1,25 @@
+mod animation;
+mod font;
 mod lbmp;
+pub mod raster;
+mod wbmp;
 
-use core::mem::size_of;
+use core::{mem::size_of, ops::BitXor};
 
-use ab_glyph::{Font, FontRef, ScaleFont};
 use bytemuck::{cast_slice, pod_collect_to_vec, Pod};
-use image::ImageReader;
+use image::{ImageReader, RgbaImage};
 use num_traits::{Num, Zero};
 
 use wie_util::{Result, WieError};
 
-use self::lbmp::decode_lbmp;
+use self::{
+    animation::decode_gif,
+    lbmp::decode_lbmp,
+    wbmp::{decode_wbmp, is_wbmp},
+};
 
-lazy_static::lazy_static! {
-    static ref FONT: FontRef<'static> = FontRef::try_from_slice(include_bytes!("../../fonts/neodgm.ttf")).unwrap();
-}
+pub use self::font::{BdfFont, DeviceFontMetrics, Font, FontFace, FontRegistry, FontSize, FontStyle};
+pub use self::lbmp::encode_lbmp;
 
 pub enum TextAlignment {
     Left,
@@ -29,6 +35,48 @@ pub struct Color {
     pub b: u8,
 }
 
+/// How drawn pixels are combined with pixels already in the canvas.
+#[derive(Clone, Copy)]
+pub enum CompositeOp {
+    /// Source over destination, with `alpha` multiplied to source alpha.
+    SourceOver { alpha: u8 },
+    /// Source xor destination, computed in destination pixel format.
+    Xor,
+    /// Result of a function called with source and destination colors.
+    Custom(fn(Color, Color) -> Color),
+}
+
+impl CompositeOp {
+    pub fn composite(&self, src: Color, dst: Color) -> Color {
+        match self {
+            Self::SourceOver { alpha } => {
+                let alpha = src.a as u32 * *alpha as u32 / 0xff;
+                let mix = |src: u8, dst: u8| ((src as u32 * alpha + dst as u32 * (0xff - alpha)) / 0xff) as u8;
+
+                Color {
+                    a: 0xff,
+                    r: mix(src.r, dst.r),
+                    g: mix(src.g, dst.g),
+                    b: mix(src.b, dst.b),
+                }
+            }
+            Self::Xor => Color {
+                a: dst.a,
+                r: src.r ^ dst.r,
+                g: src.g ^ dst.g,
+                b: src.b ^ dst.b,
+            },
+            Self::Custom(op) => op(src, dst),
+        }
+    }
+}
+
+impl Default for CompositeOp {
+    fn default() -> Self {
+        Self::SourceOver { alpha: 0xff }
+    }
+}
+
 pub trait Image: Send {
     fn width(&self) -> u32;
     fn height(&self) -> u32;
@@ -36,19 +84,38 @@ pub trait Image: Send {
     fn get_pixel(&self, x: i32, y: i32) -> Color;
     fn raw(&self) -> &[u8];
     fn colors(&self) -> Vec<Color>;
+
+    /// Frames of an animated image, empty for still images. Other methods refer to the first frame.
+    fn frames(&self) -> &[ImageFrame] {
+        &[]
+    }
+
+    /// Number of times an animation plays, 0 to repeat forever.
+    fn loop_count(&self) -> u32 {
+        0
+    }
+}
+
+pub struct ImageFrame {
+    pub image: Box<dyn Image>,
+    /// Display duration in milliseconds.
+    pub delay: u32,
 }
 
 pub trait ImageBuffer: Send {
     fn put_pixel(&mut self, x: i32, y: i32, color: Color);
     fn put_pixels(&mut self, x: i32, y: i32, width: u32, colors: &[Color]);
+    fn composite_pixel(&mut self, x: i32, y: i32, color: Color, op: CompositeOp);
 }
 
 #[allow(clippy::too_many_arguments)]
 pub trait Canvas: Send {
     fn image(&self) -> &dyn Image;
     fn draw(&mut self, dx: i32, dy: i32, w: u32, h: u32, src: &dyn Image, sx: i32, sy: i32, clip: Clip);
+    /// Draws line with both ends inclusive.
+    /// Shapes overwrite existing pixels while images and text are blended, unless composite operation is changed from default.
     fn draw_line(&mut self, x1: i32, y1: i32, x2: i32, y2: i32, color: Color);
-    fn draw_text(&mut self, string: &str, x: i32, y: i32, text_alignment: TextAlignment);
+    fn draw_text(&mut self, string: &str, x: i32, y: i32, font: &Font, color: Color, text_alignment: TextAlignment, clip: Clip);
     fn draw_rect(&mut self, x: i32, y: i32, w: u32, h: u32, color: Color, clip: Clip);
     fn draw_arc(&mut self, x: i32, y: i32, w: u32, h: u32, start_angle: u32, arc_angle: u32, color: Color, clip: Clip);
     fn draw_round_rect(&mut self, x: i32, y: i32, w: u32, h: u32, arc_width: u32, arc_height: u32, color: Color, clip: Clip);
@@ -56,12 +123,23 @@ pub trait Canvas: Send {
     fn fill_arc(&mut self, x: i32, y: i32, w: u32, h: u32, start_angle: u32, arc_angle: u32, color: Color, clip: Clip);
     fn fill_round_rect(&mut self, x: i32, y: i32, w: u32, h: u32, arc_width: u32, arc_height: u32, color: Color, clip: Clip);
     fn put_pixel(&mut self, x: i32, y: i32, color: Color);
+    /// Draws a pixel combined with existing one using current composite operation.
+    fn draw_pixel(&mut self, x: i32, y: i32, color: Color);
+    fn set_composite_op(&mut self, op: CompositeOp);
 }
 
 pub trait PixelType: Send {
-    type DataType: Copy + Pod + Num + Send;
+    type DataType: Copy + Pod + Num + BitXor<Output = Self::DataType> + Send;
     fn from_color(color: Color) -> Self::DataType;
     fn to_color(raw: Self::DataType) -> Color;
+
+    fn composite(dst: Self::DataType, color: Color, op: CompositeOp) -> Self::DataType {
+        match op {
+            // xor raw values so that drawing twice restores original pixel
+            CompositeOp::Xor => dst ^ Self::from_color(Color { a: 0, ..color }),
+            _ => Self::from_color(op.composite(color, Self::to_color(dst))),
+        }
+    }
 }
 
 pub struct Rgb332Pixel;
@@ -70,11 +148,11 @@ impl PixelType for Rgb332Pixel {
     type DataType = u8;
 
     fn from_color(color: Color) -> Self::DataType {
-        let r = (color.r * 7 + 127) / 255;
-        let g = (color.g * 7 + 127) / 255;
-        let b = (color.b * 3 + 127) / 255;
+        let r = (color.r as u16 * 7 + 127) / 255;
+        let g = (color.g as u16 * 7 + 127) / 255;
+        let b = (color.b as u16 * 3 + 127) / 255;
 
-        (r << 5) | (g << 2) | b
+        ((r << 5) | (g << 2) | b) as u8
     }
 
     fn to_color(raw: Self::DataType) -> Color {
@@ -258,6 +336,15 @@ where
             self.data[((y as u32) * self.width + (x as u32)) as usize] = raw;
         }
     }
+
+    fn composite_pixel(&mut self, x: i32, y: i32, color: Color, op: CompositeOp) {
+        if x < 0 || y < 0 || (x as u32) >= self.width || (y as u32) >= self.height {
+            return;
+        }
+
+        let index = ((y as u32) * self.width + (x as u32)) as usize;
+        self.data[index] = T::composite(self.data[index], color, op);
+    }
 }
 
 pub struct ImageBufferCanvas<T>
@@ -265,6 +352,7 @@ where
     T: ImageBuffer + Image,
 {
     image_buffer: T,
+    composite_op: CompositeOp,
 }
 
 impl<T> ImageBufferCanvas<T>
@@ -272,7 +360,10 @@ where
     T: ImageBuffer + Image,
 {
     pub fn new(image_buffer: T) -> Self {
-        Self { image_buffer }
+        Self {
+            image_buffer,
+            composite_op: CompositeOp::default(),
+        }
     }
 
     pub fn into_inner(self) -> T {
@@ -290,17 +381,34 @@ where
             );
             return;
         }
-        let bg = self.image_buffer.get_pixel(x, y);
-        let factor = color.a as f32 / 255.0;
 
-        let computed_color = Color {
-            a: 0xff,
-            r: (color.r as f32 * factor + bg.r as f32 * (1.0 - factor)) as u8,
-            g: (color.g as f32 * factor + bg.g as f32 * (1.0 - factor)) as u8,
-            b: (color.b as f32 * factor + bg.b as f32 * (1.0 - factor)) as u8,
-        };
+        self.image_buffer.composite_pixel(x, y, color, self.composite_op);
+    }
 
-        self.put_pixel(x, y, computed_color);
+    // opaque source-over writes shape pixels directly, other composite operations blend with existing ones
+    fn paint_pixel(&mut self, x: i32, y: i32, color: Color) {
+        if matches!(self.composite_op, CompositeOp::SourceOver { alpha: 0xff }) {
+            self.image_buffer.put_pixel(x, y, color);
+        } else {
+            self.image_buffer.composite_pixel(x, y, color, self.composite_op);
+        }
+    }
+
+    // pixels are expected to be rasterized within `visible` clip
+    fn paint_pixels(&mut self, pixels: &[(i32, i32)], color: Color) {
+        for &(x, y) in pixels {
+            self.paint_pixel(x, y, color);
+        }
+    }
+
+    // part of clip inside image buffer
+    fn visible(&self, clip: Clip) -> Clip {
+        clip.intersect(&Clip {
+            x: 0,
+            y: 0,
+            width: self.image_buffer.width(),
+            height: self.image_buffer.height(),
+        })
     }
 }
 
@@ -314,120 +422,64 @@ where
     }
 
     fn draw(&mut self, dx: i32, dy: i32, w: u32, h: u32, src: &dyn Image, sx: i32, sy: i32, clip: Clip) {
-        for y in 0..(h as i32) {
-            for x in 0..(w as i32) {
-                if sx + x < 0 || sy + y < 0 || sx + x >= src.width() as i32 || sy + y >= src.height() as i32 {
-                    continue;
-                }
-                if dx + x < 0 || dy + y < 0 || dx + x >= self.image_buffer.width() as i32 || dy + y >= self.image_buffer.height() as i32 {
-                    continue;
-                }
-                if dx + x < clip.x || dx + x >= clip.x + (clip.width as i32) || dy + y < clip.y || dy + y >= clip.y + (clip.height as i32) {
-                    continue;
-                }
+        let (shift_x, shift_y) = (sx as i64 - dx as i64, sy as i64 - dy as i64);
 
-                // TODO blend multiple pixels at once for performance
-                self.blend_pixel(dx + x, dy + y, src.get_pixel(sx + x, sy + y));
+        for (x, y) in raster::fill_rect(dx, dy, w, h, self.visible(clip)) {
+            let (src_x, src_y) = (x as i64 + shift_x, y as i64 + shift_y);
+            if src_x < 0 || src_y < 0 || src_x >= src.width() as i64 || src_y >= src.height() as i64 {
+                continue;
             }
+
+            // TODO blend multiple pixels at once for performance
+            self.blend_pixel(x, y, src.get_pixel(src_x as _, src_y as _));
         }
     }
 
     fn draw_line(&mut self, x1: i32, y1: i32, x2: i32, y2: i32, color: Color) {
-        let x2 = if x1 == x2 { x2 + 1 } else { x2 };
-        let y2 = if y1 == y2 { y2 + 1 } else { y2 };
-
-        // bresenham's line drawing
-        let dx = (x2 - x1).abs();
-        let dy = (y2 - y1).abs();
-        let sx = if x1 < x2 { 1 } else { -1 };
-        let sy = if y1 < y2 { 1 } else { -1 };
-        let mut err = dx - dy;
-
-        let mut x = x1;
-        let mut y = y1;
-
-        while x != x2 && y != y2 {
-            self.blend_pixel(x as _, y as _, color);
-
-            let e2 = 2 * err;
-            if e2 > -dy {
-                err -= dy;
-                x += sx;
-            }
-            if e2 < dx {
-                err += dx;
-                y += sy;
-            }
-        }
-    }
+        let clip = Clip {
+            x: 0,
+            y: 0,
+            width: self.image_buffer.width(),
+            height: self.image_buffer.height(),
+        };
 
-    fn draw_text(&mut self, string: &str, x: i32, y: i32, text_alignment: TextAlignment) {
-        let font = FONT.as_scaled(FONT.pt_to_px_scale(10.0).unwrap());
+        let pixels = raster::line(x1, y1, x2, y2, clip);
+        self.paint_pixels(&pixels, color);
+    }
 
-        let total_width = string.chars().map(|c| font.h_advance(font.scaled_glyph(c).id)).sum::<f32>();
+    fn draw_text(&mut self, string: &str, x: i32, y: i32, font: &Font, color: Color, text_alignment: TextAlignment, clip: Clip) {
+        let total_width = font.string_width(string) as i32;
         let x = match text_alignment {
             TextAlignment::Left => x,
-            TextAlignment::Center => x - (total_width / 2.0) as i32,
-            TextAlignment::Right => x - total_width as i32,
+            TextAlignment::Center => x.saturating_sub(total_width / 2),
+            TextAlignment::Right => x.saturating_sub(total_width),
         };
 
-        let mut position = 0.0;
-        for c in string.chars() {
-            if c.is_control() {
-                continue;
+        let clip = self.visible(clip);
+        font.render(string, x, y, |x, y, coverage| {
+            if !clip.contains(x, y) {
+                return;
             }
 
-            let glyph = font.scaled_glyph(c);
-            let h_advance = font.h_advance(glyph.id);
-
-            if let Some(outlined_glyph) = font.outline_glyph(glyph) {
-                outlined_glyph.draw(|glyph_x: u32, glyph_y, c| {
-                    self.blend_pixel(
-                        x + (glyph_x as f32 + position) as i32,
-                        y + glyph_y as i32,
-                        Color {
-                            a: (c * 255.0) as u8,
-                            r: 0,
-                            g: 0,
-                            b: 0,
-                        },
-                    )
-                });
-            }
-
-            position += h_advance;
-        }
+            self.blend_pixel(
+                x,
+                y,
+                Color {
+                    a: (coverage.clamp(0.0, 1.0) * color.a as f32) as u8,
+                    ..color
+                },
+            )
+        });
     }
 
     fn draw_rect(&mut self, x: i32, y: i32, w: u32, h: u32, color: Color, clip: Clip) {
-        // TODO use put_pixels
-        for x in x..x + (w as i32) {
-            if x < 0 || x >= self.image_buffer.width() as i32 {
-                continue;
-            }
-            if x < clip.x || x >= clip.x + clip.width as i32 {
-                continue;
-            }
-
-            self.put_pixel(x, y, color);
-            self.put_pixel(x, y + (h as i32) - 1, color);
-        }
-        for y in y..y + (h as i32) {
-            if y < 0 || y >= self.image_buffer.height() as i32 {
-                continue;
-            }
-            if y < clip.y || y >= clip.y + clip.height as i32 {
-                continue;
-            }
-
-            self.put_pixel(x, y, color);
-            self.put_pixel(x + (w as i32) - 1, y, color);
-        }
+        let pixels = raster::rect(x, y, w, h, self.visible(clip));
+        self.paint_pixels(&pixels, color);
     }
 
-    fn draw_arc(&mut self, x: i32, y: i32, w: u32, h: u32, _start_angle: u32, _arc_angle: u32, color: Color, clip: Clip) {
-        // TODO unimplemented
-        self.draw_rect(x, y, w, h, color, clip);
+    fn draw_arc(&mut self, x: i32, y: i32, w: u32, h: u32, start_angle: u32, arc_angle: u32, color: Color, clip: Clip) {
+        let pixels = raster::arc(x, y, w, h, start_angle as _, arc_angle as _, self.visible(clip));
+        self.paint_pixels(&pixels, color);
     }
 
     fn draw_round_rect(&mut self, x: i32, y: i32, w: u32, h: u32, _arc_width: u32, _arc_height: u32, color: Color, clip: Clip) {
@@ -437,22 +489,14 @@ where
 
     fn fill_rect(&mut self, x: i32, y: i32, w: u32, h: u32, color: Color, clip: Clip) {
         // TODO use put_pixels
-        for y in y..y + (h as i32) {
-            for x in x..x + (w as i32) {
-                if x >= self.image_buffer.width() as i32 || y >= self.image_buffer.height() as i32 {
-                    continue;
-                }
-                if x < clip.x || x >= clip.x + clip.width as i32 || y < clip.y || y >= clip.y + clip.height as i32 {
-                    continue;
-                }
-                self.put_pixel(x, y, color);
-            }
+        for (x, y) in raster::fill_rect(x, y, w, h, self.visible(clip)) {
+            self.paint_pixel(x, y, color);
         }
     }
 
-    fn fill_arc(&mut self, x: i32, y: i32, w: u32, h: u32, _start_angle: u32, _arc_angle: u32, color: Color, clip: Clip) {
-        // TODO unimplemented
-        self.fill_rect(x, y, w, h, color, clip);
+    fn fill_arc(&mut self, x: i32, y: i32, w: u32, h: u32, start_angle: u32, arc_angle: u32, color: Color, clip: Clip) {
+        let pixels = raster::fill_arc(x, y, w, h, start_angle as _, arc_angle as _, self.visible(clip));
+        self.paint_pixels(&pixels, color);
     }
 
     fn fill_round_rect(&mut self, x: i32, y: i32, w: u32, h: u32, _arc_width: u32, _arc_height: u32, color: Color, clip: Clip) {
@@ -463,8 +507,17 @@ where
     fn put_pixel(&mut self, x: i32, y: i32, color: Color) {
         self.image_buffer.put_pixel(x, y, color)
     }
+
+    fn draw_pixel(&mut self, x: i32, y: i32, color: Color) {
+        self.image_buffer.composite_pixel(x, y, color, self.composite_op)
+    }
+
+    fn set_composite_op(&mut self, op: CompositeOp) {
+        self.composite_op = op;
+    }
 }
 
+#[derive(Clone, Copy)]
 pub struct Clip {
     pub x: i32,
     pub y: i32,
@@ -473,48 +526,85 @@ pub struct Clip {
 }
 
 impl Clip {
+    /// Common area of two clips, empty if they don't overlap.
     pub fn intersect(&self, other: &Clip) -> Clip {
-        let x = self.x.max(other.x);
-        let y = self.y.max(other.y);
-        let width = (self.x + (self.width as i32)).min(other.x + (other.width as i32)) - x;
-        let height = (self.y + (self.height as i32)).min(other.y + (other.height as i32)) - y;
+        let (left, top) = (self.x.max(other.x), self.y.max(other.y));
+        let (right, bottom) = (self.right().min(other.right()), self.bottom().min(other.bottom()));
 
         Clip {
-            x,
-            y,
-            width: width as _,
-            height: height as _,
+            x: left,
+            y: top,
+            width: (right - left as i64).clamp(0, u32::MAX as i64) as _,
+            height: (bottom - top as i64).clamp(0, u32::MAX as i64) as _,
         }
     }
+
+    /// Clip moved by (`dx`, `dy`), saturating at the bounds of `i32`.
+    pub fn translate(&self, dx: i32, dy: i32) -> Clip {
+        Clip {
+            x: self.x.saturating_add(dx),
+            y: self.y.saturating_add(dy),
+            ..*self
+        }
+    }
+
+    pub fn contains(&self, x: i32, y: i32) -> bool {
+        x >= self.x && y >= self.y && (x as i64) < self.right() && (y as i64) < self.bottom()
+    }
+
+    // exclusive right and bottom edges, which may not fit in i32
+    fn right(&self) -> i64 {
+        self.x as i64 + self.width as i64
+    }
+
+    fn bottom(&self) -> i64 {
+        self.y as i64 + self.height as i64
+    }
 }
 
 pub fn decode_image(data: &[u8]) -> Result<Box<dyn Image>> {
     use std::io::Cursor;
 
-    if data[0] == b'L' && data[1] == b'B' && data[2] == b'M' && data[3] == b'P' {
+    if data.starts_with(b"LBMP") {
         return decode_lbmp(data);
     }
+    if data.starts_with(b"GIF8") {
+        return decode_gif(data);
+    }
+    // wbmp has no magic, but its header never collides with other formats' magics
+    if is_wbmp(data) {
+        return decode_wbmp(data);
+    }
 
     let image = ImageReader::new(Cursor::new(&data))
         .with_guessed_format()
         .map_err(|x| WieError::FatalError(x.to_string()))?
         .decode()
         .map_err(|x| WieError::FatalError(x.to_string()))?;
-    let rgba = image.into_rgba8();
 
-    let data = rgba.pixels().flat_map(|x| [x.0[2], x.0[1], x.0[0], x.0[3]]).collect::<Vec<_>>();
+    // palette formats expand transparent entries to zero alpha here
+    Ok(Box::new(rgba_to_image(image.into_rgba8())) as Box<_>)
+}
+
+/// Converts `image` into `depth` bits per pixel, as shown on a handset with lower color depth.
+pub fn quantize_image(image: &dyn Image, depth: u32) -> Box<dyn Image> {
+    fn convert<T: PixelType + 'static>(image: &dyn Image) -> Box<dyn Image> {
+        let raw = image.colors().into_iter().map(T::from_color).collect();
+
+        Box::new(VecImageBuffer::<T>::from_raw(image.width(), image.height(), raw))
+    }
 
-    Ok(Box::new(VecImageBuffer::<ArgbPixel>::from_raw(
-        rgba.width(),
-        rgba.height(),
-        pod_collect_to_vec(&data),
-    )) as Box<_>)
+    match depth {
+        ..=8 => convert::<Rgb332Pixel>(image),
+        9..=16 => convert::<Rgb565Pixel>(image),
+        _ => convert::<Rgb8Pixel>(image),
+    }
 }
 
-pub fn string_width(string: &str, pt_size: f32) -> f32 {
-    let font = FONT.as_scaled(FONT.pt_to_px_scale(pt_size).unwrap());
+fn rgba_to_image(rgba: RgbaImage) -> VecImageBuffer<ArgbPixel> {
+    let data = rgba.pixels().flat_map(|x| [x.0[2], x.0[1], x.0[0], x.0[3]]).collect::<Vec<_>>();
 
-    string.chars().map(|c| font.h_advance(font.scaled_glyph(c).id)).sum::<f32>()
+    VecImageBuffer::<ArgbPixel>::from_raw(rgba.width(), rgba.height(), pod_collect_to_vec(&data))
 }
 
 #[cfg(test)]
@@ -523,7 +613,7 @@ mod tests {
 
     use crate::canvas::{Clip, Image, ImageBufferCanvas};
 
-    use super::{ArgbPixel, Canvas, Color, VecImageBuffer};
+    use super::{decode_image, quantize_image, ArgbPixel, Canvas, Color, CompositeOp, Rgb565Pixel, VecImageBuffer};
 
     #[test]
     fn test_canvas() -> Result<()> {
@@ -551,4 +641,107 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_shapes() {
+        let mut canvas = ImageBufferCanvas::new(VecImageBuffer::<ArgbPixel>::new(4, 4));
+        let clip = Clip {
+            x: 0,
+            y: 0,
+            width: 4,
+            height: 4,
+        };
+        let red = Color {
+            a: 0xff,
+            r: 0xff,
+            g: 0,
+            b: 0,
+        };
+
+        // shapes overwrite with default composite operation
+        canvas.fill_rect(0, 0, 4, 4, Color { a: 0x80, ..red }, clip);
+        let color = canvas.image().get_pixel(1, 1);
+        assert_eq!((color.a, color.r, color.g), (0x80, 0xff, 0));
+
+        canvas.fill_rect(0, 0, 4, 4, Color { a: 0, r: 0, g: 0, b: 0 }, clip);
+        canvas.draw_line(0, 0, 3, 3, red);
+        canvas.draw_line(3, 0, 3, 2, red);
+        canvas.draw_line(-2, 3, 10, 3, red);
+        for y in 0..4 {
+            for x in 0..4 {
+                let drawn = x == y || (x == 3 && y <= 2) || y == 3;
+                assert_eq!(canvas.image().get_pixel(x, y).a, if drawn { 0xff } else { 0 }, "{}, {}", x, y);
+            }
+        }
+    }
+
+    #[test]
+    fn test_composite_op() {
+        let mut canvas = ImageBufferCanvas::new(VecImageBuffer::<Rgb565Pixel>::new(2, 1));
+        let white = Color {
+            a: 0xff,
+            r: 0xff,
+            g: 0xff,
+            b: 0xff,
+        };
+
+        canvas.set_composite_op(CompositeOp::SourceOver { alpha: 0x80 });
+        canvas.draw_pixel(0, 0, white);
+        let color = canvas.image().get_pixel(0, 0);
+        assert!((0x70..0x90).contains(&color.r) && (0x70..0x90).contains(&color.g));
+
+        canvas.set_composite_op(CompositeOp::Xor);
+        canvas.draw_pixel(1, 0, white);
+        assert_eq!(canvas.image().get_pixel(1, 0).r, 0xff);
+        canvas.draw_pixel(1, 0, white);
+        assert_eq!(canvas.image().get_pixel(1, 0).r, 0);
+    }
+
+    #[test]
+    fn test_decode_animated_gif() -> Result<()> {
+        use image::{
+            codecs::gif::{GifEncoder, Repeat},
+            Delay, Frame, Rgba, RgbaImage,
+        };
+
+        let mut data = Vec::new();
+        {
+            let mut encoder = GifEncoder::new(&mut data);
+            encoder.set_repeat(Repeat::Infinite).unwrap();
+
+            let frames = [Rgba([0xff, 0, 0, 0xff]), Rgba([0, 0, 0, 0])].map(|color| {
+                let mut buffer = RgbaImage::from_pixel(2, 2, color);
+                buffer.put_pixel(1, 1, Rgba([0, 0, 0xff, 0xff]));
+
+                Frame::from_parts(buffer, 0, 0, Delay::from_numer_denom_ms(100, 1))
+            });
+            encoder.encode_frames(frames).unwrap();
+        }
+
+        let image = decode_image(&data)?;
+        assert_eq!((image.width(), image.height()), (2, 2));
+        assert_eq!(image.loop_count(), 0);
+        assert_eq!(image.frames().len(), 2);
+        assert_eq!(image.frames()[0].delay, 100);
+
+        let first = image.get_pixel(0, 0);
+        assert_eq!((first.a, first.r), (0xff, 0xff));
+        assert_eq!(image.frames()[1].image.get_pixel(1, 1).b, 0xff);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_quantize_image() {
+        let image = VecImageBuffer::<ArgbPixel>::from_raw(1, 1, vec![0xff123456]);
+
+        let quantized = quantize_image(&image, 8);
+        assert_eq!(quantized.bytes_per_pixel(), 1);
+        let color = quantized.get_pixel(0, 0);
+        assert_eq!((color.r, color.g, color.b), (0, 36, 85));
+
+        let quantized = quantize_image(&image, 16);
+        let color = quantized.get_pixel(0, 0);
+        assert_eq!((color.r, color.g, color.b), (0x10, 0x35, 0x52));
+    }
 }
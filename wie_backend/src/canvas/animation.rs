@@ -0,0 +1,86 @@
+use std::io::Cursor;
+
+use image::{codecs::gif::GifDecoder, AnimationDecoder};
+
+use wie_util::{Result, WieError};
+
+use crate::canvas::{rgba_to_image, Color, Image, ImageFrame};
+
+pub struct AnimatedImage {
+    frames: Vec<ImageFrame>,
+    loop_count: u32,
+}
+
+impl Image for AnimatedImage {
+    fn width(&self) -> u32 {
+        self.frames[0].image.width()
+    }
+
+    fn height(&self) -> u32 {
+        self.frames[0].image.height()
+    }
+
+    fn bytes_per_pixel(&self) -> u32 {
+        self.frames[0].image.bytes_per_pixel()
+    }
+
+    fn get_pixel(&self, x: i32, y: i32) -> Color {
+        self.frames[0].image.get_pixel(x, y)
+    }
+
+    fn raw(&self) -> &[u8] {
+        self.frames[0].image.raw()
+    }
+
+    fn colors(&self) -> Vec<Color> {
+        self.frames[0].image.colors()
+    }
+
+    fn frames(&self) -> &[ImageFrame] {
+        &self.frames
+    }
+
+    fn loop_count(&self) -> u32 {
+        self.loop_count
+    }
+}
+
+pub fn decode_gif(data: &[u8]) -> Result<Box<dyn Image>> {
+    let decoder = GifDecoder::new(Cursor::new(data)).map_err(|x| WieError::FatalError(x.to_string()))?;
+
+    // frames are composited onto full logical screen, with transparent palette entry as zero alpha
+    let mut frames = decoder
+        .into_frames()
+        .map(|frame| {
+            let frame = frame.map_err(|x| WieError::FatalError(x.to_string()))?;
+            let (numerator, denominator) = frame.delay().numer_denom_ms();
+
+            Ok(ImageFrame {
+                delay: numerator / denominator.max(1),
+                image: Box::new(rgba_to_image(frame.into_buffer())),
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    if frames.len() <= 1 {
+        return frames
+            .pop()
+            .map(|x| x.image)
+            .ok_or_else(|| WieError::FatalError("GIF has no frames".into()));
+    }
+
+    Ok(Box::new(AnimatedImage {
+        frames,
+        loop_count: gif_loop_count(data),
+    }))
+}
+
+// netscape application extension holds repeat count, animation without it plays once
+fn gif_loop_count(data: &[u8]) -> u32 {
+    const NETSCAPE_EXTENSION: &[u8] = b"\x21\xff\x0bNETSCAPE2.0\x03\x01";
+
+    data.windows(NETSCAPE_EXTENSION.len() + 2)
+        .find(|x| x.starts_with(NETSCAPE_EXTENSION))
+        .map(|x| u16::from_le_bytes([x[NETSCAPE_EXTENSION.len()], x[NETSCAPE_EXTENSION.len() + 1]]) as u32)
+        .unwrap_or(1)
+}
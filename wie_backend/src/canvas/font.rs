@@ -0,0 +1,426 @@
+use alloc::{collections::BTreeMap, sync::Arc};
+
+use ab_glyph::{Font as _, FontRef, PxScale, ScaleFont};
+
+use wie_util::{Result, WieError};
+
+lazy_static::lazy_static! {
+    static ref OUTLINE_FONT: FontRef<'static> = FontRef::try_from_slice(include_bytes!("../../../fonts/neodgm.ttf")).unwrap();
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub enum FontFace {
+    System,
+    Monospace,
+    Proportional,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub enum FontSize {
+    Small,
+    Medium,
+    Large,
+}
+
+bitflags::bitflags! {
+    #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+    pub struct FontStyle: u32 {
+        const PLAIN = 0;
+        const BOLD = 1;
+        const ITALIC = 2;
+        const UNDERLINED = 4;
+    }
+}
+
+/// Pixel heights of each font size, as reported by the handset.
+#[derive(Clone, Copy, Debug)]
+pub struct DeviceFontMetrics {
+    pub small: u32,
+    pub medium: u32,
+    pub large: u32,
+}
+
+impl DeviceFontMetrics {
+    pub fn height(&self, size: FontSize) -> u32 {
+        match size {
+            FontSize::Small => self.small,
+            FontSize::Medium => self.medium,
+            FontSize::Large => self.large,
+        }
+    }
+}
+
+impl Default for DeviceFontMetrics {
+    fn default() -> Self {
+        // most korean handsets ship 12/16/16 pixel hangul fonts
+        Self {
+            small: 12,
+            medium: 16,
+            large: 16,
+        }
+    }
+}
+
+/// Fonts of the emulated handset, creating [`Font`]s with device metrics and registered bitmap fonts.
+#[derive(Clone, Default)]
+pub struct FontRegistry {
+    metrics: DeviceFontMetrics,
+    bitmap_fonts: BTreeMap<FontSize, Arc<BdfFont>>,
+}
+
+impl FontRegistry {
+    /// `metrics` are the pixel heights used for outline fonts.
+    pub fn new(metrics: DeviceFontMetrics) -> Self {
+        Self {
+            metrics,
+            bitmap_fonts: BTreeMap::new(),
+        }
+    }
+
+    /// Registers a bitmap font used for `size` instead of the built-in outline font.
+    pub fn register_bitmap_font(&mut self, size: FontSize, font: BdfFont) {
+        self.bitmap_fonts.insert(size, Arc::new(font));
+    }
+
+    pub fn font(&self, face: FontFace, size: FontSize, style: FontStyle) -> Font {
+        let bitmap = self.bitmap_fonts.get(&size).cloned();
+        let height = match &bitmap {
+            Some(x) => x.ascent + x.descent,
+            None => self.metrics.height(size),
+        };
+
+        Font {
+            face,
+            size,
+            style,
+            height,
+            bitmap,
+        }
+    }
+
+    /// Medium plain system font, used when application doesn't specify one.
+    pub fn default_font(&self) -> Font {
+        self.font(FontFace::System, FontSize::Medium, FontStyle::PLAIN)
+    }
+}
+
+#[derive(Clone)]
+pub struct Font {
+    face: FontFace,
+    size: FontSize,
+    style: FontStyle,
+    height: u32,
+    bitmap: Option<Arc<BdfFont>>,
+}
+
+impl Font {
+    pub fn face(&self) -> FontFace {
+        self.face
+    }
+
+    pub fn size(&self) -> FontSize {
+        self.size
+    }
+
+    pub fn style(&self) -> FontStyle {
+        self.style
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn ascent(&self) -> u32 {
+        match &self.bitmap {
+            Some(x) => x.ascent,
+            None => {
+                let font = OUTLINE_FONT.as_scaled(PxScale::from(self.height as f32));
+                (font.ascent().round() as u32).min(self.height)
+            }
+        }
+    }
+
+    pub fn descent(&self) -> u32 {
+        self.height - self.ascent()
+    }
+
+    pub fn char_width(&self, c: char) -> u32 {
+        if c.is_control() {
+            return 0;
+        }
+
+        let advance = match self.bitmap.as_ref().and_then(|x| x.glyphs.get(&(c as u32))) {
+            Some(glyph) => glyph.advance,
+            None => {
+                let font = OUTLINE_FONT.as_scaled(PxScale::from(self.height as f32));
+                font.h_advance(font.glyph_id(c)).round() as u32
+            }
+        };
+
+        if self.style.contains(FontStyle::BOLD) {
+            advance + 1
+        } else {
+            advance
+        }
+    }
+
+    pub fn string_width(&self, string: &str) -> u32 {
+        string.chars().map(|c| self.char_width(c)).sum()
+    }
+
+    /// Rasterizes `string` with its top-left corner at (`x`, `y`), calling `put` with each pixel and its coverage.
+    pub fn render<F>(&self, string: &str, x: i32, y: i32, mut put: F)
+    where
+        F: FnMut(i32, i32, f32),
+    {
+        let baseline = y + self.ascent() as i32;
+        let bold = self.style.contains(FontStyle::BOLD);
+        let italic = self.style.contains(FontStyle::ITALIC);
+
+        let mut put_styled = |px: i32, py: i32, coverage: f32| {
+            let px = if italic { px + (baseline - py) / 4 } else { px };
+
+            put(px, py, coverage);
+            if bold {
+                put(px + 1, py, coverage);
+            }
+        };
+
+        let mut position = x;
+        for c in string.chars() {
+            if c.is_control() {
+                continue;
+            }
+
+            match self.bitmap.as_ref().and_then(|x| x.glyphs.get(&(c as u32))) {
+                Some(glyph) => {
+                    let top = baseline - glyph.y_offset - glyph.height as i32;
+                    let left = position + glyph.x_offset;
+
+                    for row in 0..glyph.height {
+                        for col in 0..glyph.width {
+                            if glyph.pixel(col, row) {
+                                put_styled(left + col as i32, top + row as i32, 1.0);
+                            }
+                        }
+                    }
+                }
+                None => {
+                    let font = OUTLINE_FONT.as_scaled(PxScale::from(self.height as f32));
+                    let glyph = font.scaled_glyph(c);
+
+                    if let Some(outlined_glyph) = font.outline_glyph(glyph) {
+                        let bounds = outlined_glyph.px_bounds();
+
+                        outlined_glyph.draw(|glyph_x, glyph_y, coverage| {
+                            put_styled(
+                                position + bounds.min.x as i32 + glyph_x as i32,
+                                baseline + bounds.min.y as i32 + glyph_y as i32,
+                                coverage,
+                            )
+                        });
+                    }
+                }
+            }
+
+            position += self.char_width(c) as i32;
+        }
+
+        if self.style.contains(FontStyle::UNDERLINED) {
+            let underline_y = if self.descent() > 1 { baseline + 1 } else { baseline };
+
+            for px in x..position {
+                put(px, underline_y, 1.0);
+            }
+        }
+    }
+}
+
+struct BdfGlyph {
+    advance: u32,
+    width: u32,
+    height: u32,
+    x_offset: i32,
+    y_offset: i32,
+    bitmap: Vec<u8>,
+}
+
+impl BdfGlyph {
+    fn pixel(&self, x: u32, y: u32) -> bool {
+        let stride = self.width.div_ceil(8);
+        let byte = self.bitmap[(y * stride + x / 8) as usize];
+
+        byte & (0x80 >> (x % 8)) != 0
+    }
+}
+
+/// Bitmap font in Glyph Bitmap Distribution Format, with glyphs indexed by unicode code point.
+pub struct BdfFont {
+    ascent: u32,
+    descent: u32,
+    glyphs: BTreeMap<u32, BdfGlyph>,
+}
+
+impl BdfFont {
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        let data = core::str::from_utf8(data).map_err(|x| WieError::FatalError(x.to_string()))?;
+
+        let parse_int = |x: Option<&str>| -> Result<i32> {
+            x.and_then(|x| x.parse().ok())
+                .ok_or_else(|| WieError::FatalError("Invalid BDF number".into()))
+        };
+
+        let mut ascent = None;
+        let mut descent = None;
+        let mut bounding_box = (0, 0, 0, 0);
+        let mut glyphs = BTreeMap::new();
+
+        let mut lines = data.lines();
+        while let Some(line) = lines.next() {
+            let mut tokens = line.split_whitespace();
+            match tokens.next() {
+                Some("FONTBOUNDINGBOX") => {
+                    bounding_box = (
+                        parse_int(tokens.next())?,
+                        parse_int(tokens.next())?,
+                        parse_int(tokens.next())?,
+                        parse_int(tokens.next())?,
+                    );
+                }
+                Some("FONT_ASCENT") => ascent = Some(parse_int(tokens.next())?),
+                Some("FONT_DESCENT") => descent = Some(parse_int(tokens.next())?),
+                Some("STARTCHAR") => {
+                    let mut encoding = -1;
+                    let mut advance = bounding_box.0;
+                    let (mut width, mut height, mut x_offset, mut y_offset) = bounding_box;
+                    let mut bitmap = Vec::new();
+
+                    for line in lines.by_ref() {
+                        let mut tokens = line.split_whitespace();
+                        match tokens.next() {
+                            Some("ENCODING") => encoding = parse_int(tokens.next())?,
+                            Some("DWIDTH") => advance = parse_int(tokens.next())?,
+                            Some("BBX") => {
+                                width = parse_int(tokens.next())?;
+                                height = parse_int(tokens.next())?;
+                                x_offset = parse_int(tokens.next())?;
+                                y_offset = parse_int(tokens.next())?;
+                            }
+                            Some("ENDCHAR") => break,
+                            Some("BITMAP") => {}
+                            Some(row) if row.bytes().all(|x| x.is_ascii_hexdigit()) => {
+                                let row = (0..row.len() / 2).map(|i| u8::from_str_radix(&row[i * 2..i * 2 + 2], 16).unwrap());
+                                bitmap.extend(row.take((width as u32).div_ceil(8) as usize));
+                            }
+                            _ => {}
+                        }
+                    }
+
+                    if encoding < 0 || bitmap.len() != ((width as u32).div_ceil(8) * height as u32) as usize {
+                        continue;
+                    }
+
+                    glyphs.insert(
+                        encoding as u32,
+                        BdfGlyph {
+                            advance: advance.max(0) as _,
+                            width: width as _,
+                            height: height as _,
+                            x_offset,
+                            y_offset,
+                            bitmap,
+                        },
+                    );
+                }
+                _ => {}
+            }
+        }
+
+        let ascent = ascent.unwrap_or(bounding_box.1 + bounding_box.3);
+        let descent = descent.unwrap_or(-bounding_box.3);
+
+        Ok(Self {
+            ascent: ascent.max(0) as _,
+            descent: descent.max(0) as _,
+            glyphs,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use wie_util::Result;
+
+    use super::{BdfFont, DeviceFontMetrics, FontFace, FontRegistry, FontSize, FontStyle};
+
+    const TEST_BDF: &str = "STARTFONT 2.1
+FONTBOUNDINGBOX 4 6 0 -1
+STARTPROPERTIES 2
+FONT_ASCENT 5
+FONT_DESCENT 1
+ENDPROPERTIES
+CHARS 1
+STARTCHAR A
+ENCODING 65
+SWIDTH 500 0
+DWIDTH 5 0
+BBX 4 6 0 -1
+BITMAP
+60
+90
+F0
+90
+90
+00
+ENDCHAR
+ENDFONT
+";
+
+    #[test]
+    fn test_bdf() -> Result<()> {
+        let font = BdfFont::parse(TEST_BDF.as_bytes())?;
+
+        assert_eq!(font.ascent, 5);
+        assert_eq!(font.descent, 1);
+
+        let glyph = font.glyphs.get(&65).unwrap();
+        assert_eq!(glyph.advance, 5);
+        assert!(!glyph.pixel(0, 0));
+        assert!(glyph.pixel(1, 0));
+        assert!(glyph.pixel(3, 2));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_metrics() {
+        let registry = FontRegistry::default();
+        let plain = registry.font(FontFace::System, FontSize::Medium, FontStyle::PLAIN);
+        let bold = registry.font(FontFace::System, FontSize::Medium, FontStyle::BOLD);
+
+        assert_eq!(plain.height(), plain.ascent() + plain.descent());
+        assert_eq!(plain.string_width("가나"), plain.char_width('가') + plain.char_width('나'));
+        assert_eq!(bold.string_width("가나"), plain.string_width("가나") + 2);
+
+        let mut right = 0;
+        plain.render("가", 0, 0, |x, _, _| right = right.max(x));
+        assert!(right < plain.string_width("가") as i32);
+    }
+
+    #[test]
+    fn test_registry() -> Result<()> {
+        let mut registry = FontRegistry::new(DeviceFontMetrics {
+            small: 10,
+            medium: 14,
+            large: 18,
+        });
+        registry.register_bitmap_font(FontSize::Large, BdfFont::parse(TEST_BDF.as_bytes())?);
+
+        // each registry keeps its own metrics
+        assert_eq!(registry.font(FontFace::System, FontSize::Medium, FontStyle::PLAIN).height(), 14);
+        assert_eq!(FontRegistry::default().default_font().height(), 16);
+        assert_eq!(registry.font(FontFace::System, FontSize::Large, FontStyle::PLAIN).height(), 6);
+
+        Ok(())
+    }
+}
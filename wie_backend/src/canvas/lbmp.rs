@@ -1,10 +1,18 @@
-use bytemuck::{cast_vec, from_bytes, Pod, Zeroable};
+use bytemuck::{bytes_of, pod_read_unaligned, Pod, Zeroable};
 
 use wie_util::{Result, WieError};
 
-use crate::canvas::{Image, Rgb332Pixel, Rgb565Pixel, VecImageBuffer};
+use crate::canvas::{ArgbPixel, Color, Image, PixelType, Rgb332Pixel, Rgb565Pixel, VecImageBuffer};
 
 // lcd bitmap file format for skvm
+// type is bits per pixel. 1 to 4 bits are grayscale levels packed msb first with 0 as white, 8 bits rgb332 and 16 bits rgb565.
+// if mask is nonzero, 1 bit opacity plane follows pixel data. rows of packed data are padded to bytes.
+// palettized files of 1 to 8 bits have a table of `1 << type` colors before pixel data, which is counted in size field.
+// palette entries are 0x00rrggbb little endian, and pixels are indices into it.
+
+const DESCRIPTOR: &[u8; 4] = b"LBMP";
+const HEADER_SIZE: usize = 24;
+const PALETTE_ENTRY_SIZE: usize = 4;
 
 #[repr(C)]
 #[derive(Clone, Copy, Pod, Zeroable)]
@@ -18,23 +26,247 @@ struct LbmpHeader {
 }
 
 pub fn decode_lbmp(data: &[u8]) -> Result<Box<dyn Image>> {
-    let header: &LbmpHeader = from_bytes(&data[0..24]);
-    let data = &data[24..];
+    let header: LbmpHeader = pod_read_unaligned(
+        data.get(0..HEADER_SIZE)
+            .ok_or_else(|| WieError::FatalError("Truncated LBMP header".into()))?,
+    );
+    let (width, height, bpp) = (header.width as usize, header.height as usize, header.r#type as usize);
 
-    if header.r#type == 2 || header.r#type == 3 {
-        // unsupported grayscale
-        return Err(WieError::Unimplemented(format!("Unsupported grayscale type {}", header.r#type)));
+    if !matches!(bpp, 1..=4 | 8 | 16) {
+        return Err(WieError::Unimplemented(format!("Unsupported type {}", header.r#type)));
     }
 
-    Ok(if header.r#type == 8 {
-        Box::new(VecImageBuffer::<Rgb332Pixel>::from_raw(header.width, header.height, data.to_vec()))
-    } else if header.r#type == 16 {
-        Box::new(VecImageBuffer::<Rgb565Pixel>::from_raw(
+    let size = row_bytes(width, bpp) * height;
+    let mut data = &data[HEADER_SIZE..];
+
+    let palette_size = if bpp <= 8 { (1 << bpp) * PALETTE_ENTRY_SIZE } else { 0 };
+    let palette = if palette_size != 0 && header.size as usize == palette_size + size {
+        let palette = data
+            .get(..palette_size)
+            .ok_or_else(|| WieError::FatalError("Truncated LBMP palette".into()))?;
+        data = &data[palette_size..];
+
+        Some(palette)
+    } else {
+        None
+    };
+
+    let pixels = data.get(..size).ok_or_else(|| WieError::FatalError("Truncated LBMP data".into()))?;
+
+    let mask = if header.mask != 0 {
+        // size field is not reliable on some files, so mask offset is computed from dimensions
+        let mask_size = row_bytes(width, 1) * height;
+        Some(
+            data.get(size..size + mask_size)
+                .ok_or_else(|| WieError::FatalError("Truncated LBMP mask".into()))?,
+        )
+    } else {
+        None
+    };
+
+    Ok(match (bpp, mask, palette) {
+        (8, None, None) => Box::new(VecImageBuffer::<Rgb332Pixel>::from_raw(header.width, header.height, pixels.to_vec())),
+        (16, None, _) => Box::new(VecImageBuffer::<Rgb565Pixel>::from_raw(
             header.width,
             header.height,
-            cast_vec(data.to_vec()),
-        ))
-    } else {
-        return Err(WieError::Unimplemented(format!("Unsupported type {}", header.r#type)));
+            pixels.chunks_exact(2).map(|x| u16::from_le_bytes([x[0], x[1]])).collect(),
+        )),
+        _ => {
+            let raw = (0..height)
+                .flat_map(|y| (0..width).map(move |x| (x, y)))
+                .map(|(x, y)| {
+                    let color = match (bpp, palette) {
+                        (_, Some(palette)) => palette_color(palette, read_bits(pixels, y * row_bytes(width, bpp), x, bpp)),
+                        (8, None) => Rgb332Pixel::to_color(pixels[y * width + x]),
+                        (16, None) => Rgb565Pixel::to_color(u16::from_le_bytes([pixels[(y * width + x) * 2], pixels[(y * width + x) * 2 + 1]])),
+                        _ => gray_color(read_bits(pixels, y * row_bytes(width, bpp), x, bpp), bpp),
+                    };
+
+                    let opaque = match mask {
+                        Some(mask) => read_bits(mask, y * row_bytes(width, 1), x, 1) != 0,
+                        None => true,
+                    };
+
+                    ArgbPixel::from_color(Color {
+                        a: if opaque { 0xff } else { 0 },
+                        ..color
+                    })
+                })
+                .collect();
+
+            Box::new(VecImageBuffer::<ArgbPixel>::from_raw(header.width, header.height, raw))
+        }
     })
 }
+
+/// Encodes `image` into LBMP of `bpp` bits per pixel. Opacity mask is added if `image` has transparent pixels.
+pub fn encode_lbmp(image: &dyn Image, bpp: u32) -> Result<Vec<u8>> {
+    if !matches!(bpp, 1..=4 | 8 | 16) {
+        return Err(WieError::Unimplemented(format!("Unsupported type {}", bpp)));
+    }
+
+    let (width, height) = (image.width() as usize, image.height() as usize);
+    let colors = image.colors();
+
+    let stride = row_bytes(width, bpp as _);
+    let mut pixels = vec![0u8; stride * height];
+    for (i, color) in colors.iter().enumerate() {
+        let (x, y) = (i % width, i / width);
+
+        match bpp {
+            8 => pixels[i] = Rgb332Pixel::from_color(*color),
+            16 => pixels[i * 2..i * 2 + 2].copy_from_slice(&Rgb565Pixel::from_color(*color).to_le_bytes()),
+            _ => write_bits(&mut pixels, y * stride, x, bpp as _, gray_level(*color, bpp as _)),
+        }
+    }
+
+    let mask = colors.iter().any(|x| x.a < 0x80).then(|| {
+        let stride = row_bytes(width, 1);
+        let mut mask = vec![0u8; stride * height];
+        for (i, color) in colors.iter().enumerate() {
+            write_bits(&mut mask, (i / width) * stride, i % width, 1, (color.a >= 0x80) as u8);
+        }
+
+        mask
+    });
+
+    let header = LbmpHeader {
+        descriptor: u32::from_ne_bytes(*DESCRIPTOR),
+        r#type: bpp,
+        width: width as _,
+        height: height as _,
+        size: pixels.len() as _,
+        mask: mask.is_some() as _,
+    };
+
+    let mut result = bytes_of(&header).to_vec();
+    result.extend(pixels);
+    if let Some(mask) = mask {
+        result.extend(mask);
+    }
+
+    Ok(result)
+}
+
+fn row_bytes(width: usize, bpp: usize) -> usize {
+    (width * bpp).div_ceil(8)
+}
+
+// reads `bpp` bits of `x`th pixel from row starting at `offset`
+fn read_bits(data: &[u8], offset: usize, x: usize, bpp: usize) -> u8 {
+    let bit = x * bpp;
+    let value = ((data[offset + bit / 8] as u16) << 8) | *data.get(offset + bit / 8 + 1).unwrap_or(&0) as u16;
+
+    ((value >> (16 - bpp - bit % 8)) & ((1 << bpp) - 1)) as u8
+}
+
+fn write_bits(data: &mut [u8], offset: usize, x: usize, bpp: usize, value: u8) {
+    for i in 0..bpp {
+        let bit = x * bpp + i;
+        if value & (1 << (bpp - i - 1)) != 0 {
+            data[offset + bit / 8] |= 0x80 >> (bit % 8);
+        }
+    }
+}
+
+fn palette_color(palette: &[u8], index: u8) -> Color {
+    let offset = index as usize * PALETTE_ENTRY_SIZE;
+
+    Color {
+        a: 0xff,
+        r: palette[offset + 2],
+        g: palette[offset + 1],
+        b: palette[offset],
+    }
+}
+
+fn gray_color(level: u8, bpp: usize) -> Color {
+    let max = (1u32 << bpp) - 1;
+    let value = (255 - level as u32 * 255 / max) as u8;
+
+    Color {
+        a: 0xff,
+        r: value,
+        g: value,
+        b: value,
+    }
+}
+
+fn gray_level(color: Color, bpp: usize) -> u8 {
+    let max = (1u32 << bpp) - 1;
+    let luma = (color.r as u32 * 299 + color.g as u32 * 587 + color.b as u32 * 114) / 1000;
+
+    (max - (luma * max + 127) / 255) as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::canvas::{ArgbPixel, Color, PixelType, VecImageBuffer};
+
+    use super::{decode_lbmp, encode_lbmp};
+
+    #[test]
+    fn test_lbmp_grayscale() {
+        // 3x2, 4-level grayscale with mask
+        let mut data = b"LBMP".to_vec();
+        for x in [2u32, 3, 2, 2, 1] {
+            data.extend(x.to_le_bytes());
+        }
+        data.extend([0b00_01_10_00, 0b11_00_00_00]);
+        data.extend([0b1100_0000, 0b1010_0000]);
+
+        let image = decode_lbmp(&data).unwrap();
+        assert_eq!((image.width(), image.height()), (3, 2));
+        assert_eq!(image.get_pixel(0, 0).r, 0xff);
+        assert_eq!(image.get_pixel(1, 0).r, 0xaa);
+        assert_eq!(image.get_pixel(2, 0).r, 0x55);
+        assert_eq!(image.get_pixel(0, 1).r, 0);
+        assert_eq!(image.get_pixel(1, 0).a, 0xff);
+        assert_eq!(image.get_pixel(2, 0).a, 0);
+        assert_eq!(image.get_pixel(1, 1).a, 0);
+
+        assert!(decode_lbmp(&data[..27]).is_err());
+    }
+
+    #[test]
+    fn test_lbmp_palette() {
+        // 3x1, 2 bit palettized without mask
+        let mut data = b"LBMP".to_vec();
+        for x in [2u32, 3, 1, 4 * 4 + 1, 0] {
+            data.extend(x.to_le_bytes());
+        }
+        for color in [0x000000u32, 0xff0000, 0x00ff00, 0x0000ff] {
+            data.extend(color.to_le_bytes());
+        }
+        data.push(0b11_01_10_00);
+
+        let image = decode_lbmp(&data).unwrap();
+        let pixels = (0..3).map(|x| image.get_pixel(x, 0)).map(|x| (x.r, x.g, x.b, x.a)).collect::<Vec<_>>();
+        assert_eq!(pixels, [(0, 0, 0xff, 0xff), (0xff, 0, 0, 0xff), (0, 0xff, 0, 0xff)]);
+    }
+
+    #[test]
+    fn test_lbmp_round_trip() {
+        let colors = [0xff000000, 0xffffffff, 0x00000000, 0xff808080u32];
+        let image = VecImageBuffer::<ArgbPixel>::from_raw(2, 2, colors.to_vec());
+
+        for bpp in [1, 2, 4, 8, 16] {
+            let decoded = decode_lbmp(&encode_lbmp(&image, bpp).unwrap()).unwrap();
+
+            assert_eq!((decoded.width(), decoded.height()), (2, 2));
+            assert_eq!(decoded.get_pixel(0, 0).r, 0);
+            assert!(decoded.get_pixel(1, 0).r >= 0xf0);
+            assert_eq!(decoded.get_pixel(0, 1).a, 0);
+            assert_eq!(decoded.get_pixel(1, 1).a, 0xff);
+        }
+
+        let gray = decode_lbmp(&encode_lbmp(&image, 4).unwrap()).unwrap();
+        assert_eq!(
+            ArgbPixel::from_color(Color {
+                a: 0xff,
+                ..gray.get_pixel(1, 1)
+            }),
+            0xff888888
+        );
+    }
+}
@@ -0,0 +1,271 @@
+// rasterizers returning covered pixel coordinates, for callers which apply their own per-pixel operation.
+// only pixels inside `clip` are returned, so work is bounded by clip size even for huge shapes.
+
+use super::Clip;
+
+/// Pixels of a line from (`x1`, `y1`) to (`x2`, `y2`), both ends inclusive.
+pub fn line(x1: i32, y1: i32, x2: i32, y2: i32, clip: Clip) -> Vec<(i32, i32)> {
+    let (dx, dy) = (x2 as i64 - x1 as i64, y2 as i64 - y1 as i64);
+
+    // one pixel per step along the major axis, skipping steps outside of clip
+    let x_major = dx.abs() >= dy.abs();
+    let mut result = if x_major {
+        (x1.min(x2).max(clip.x) as i64..=(x1.max(x2) as i64).min(clip.right() - 1))
+            .map(|x| (x as i32, (y1 as i64 + round_div((x - x1 as i64) * dy, dx)) as i32))
+            .filter(|&(x, y)| clip.contains(x, y))
+            .collect::<Vec<_>>()
+    } else {
+        (y1.min(y2).max(clip.y) as i64..=(y1.max(y2) as i64).min(clip.bottom() - 1))
+            .map(|y| ((x1 as i64 + round_div((y - y1 as i64) * dx, dy)) as i32, y as i32))
+            .filter(|&(x, y)| clip.contains(x, y))
+            .collect::<Vec<_>>()
+    };
+
+    // pixels are ordered from the start point
+    if (x_major && x1 > x2) || (!x_major && y1 > y2) {
+        result.reverse();
+    }
+
+    result
+}
+
+/// Pixels of a `w`x`h` rectangle outline with its top-left corner at (`x`, `y`).
+pub fn rect(x: i32, y: i32, w: u32, h: u32, clip: Clip) -> Vec<(i32, i32)> {
+    if w == 0 || h == 0 {
+        return Vec::new();
+    }
+
+    let x2 = (x as i64 + w as i64 - 1).min(i32::MAX as i64) as i32;
+    let y2 = (y as i64 + h as i64 - 1).min(i32::MAX as i64) as i32;
+
+    polygon(&[(x, y), (x2, y), (x2, y2), (x, y2)], clip)
+}
+
+/// Pixels of a filled `w`x`h` rectangle with its top-left corner at (`x`, `y`).
+pub fn fill_rect(x: i32, y: i32, w: u32, h: u32, clip: Clip) -> Vec<(i32, i32)> {
+    let visible = clip.intersect(&Clip { x, y, width: w, height: h });
+
+    pixels(&visible).collect()
+}
+
+/// Pixels of a closed polygon outline.
+pub fn polygon(points: &[(i32, i32)], clip: Clip) -> Vec<(i32, i32)> {
+    let mut result = points
+        .iter()
+        .zip(points.iter().cycle().skip(1))
+        .flat_map(|(&(x1, y1), &(x2, y2))| line(x1, y1, x2, y2, clip))
+        .collect::<Vec<_>>();
+
+    // vertices are shared by two edges
+    result.sort_unstable();
+    result.dedup();
+
+    result
+}
+
+/// Pixels inside a polygon, using even-odd rule on pixel centers.
+pub fn fill_polygon(points: &[(i32, i32)], clip: Clip) -> Vec<(i32, i32)> {
+    let (Some(min_y), Some(max_y)) = (points.iter().map(|x| x.1).min(), points.iter().map(|x| x.1).max()) else {
+        return Vec::new();
+    };
+
+    let mut result = Vec::new();
+    let mut crossings = Vec::new();
+    for y in min_y.max(clip.y) as i64..=(max_y as i64).min(clip.bottom() - 1) {
+        let scan_y = y as f64 + 0.5;
+
+        crossings.clear();
+        for (&(x1, y1), &(x2, y2)) in points.iter().zip(points.iter().cycle().skip(1)) {
+            let (y1, y2) = (y1 as f64, y2 as f64);
+            if (y1 > scan_y) != (y2 > scan_y) {
+                crossings.push(x1 as f64 + (scan_y - y1) * (x2 as f64 - x1 as f64) / (y2 - y1));
+            }
+        }
+        crossings.sort_by(|a, b| a.total_cmp(b));
+
+        for span in crossings.chunks_exact(2) {
+            let start = ((span[0] - 0.5).ceil() as i64).max(clip.x as i64);
+            let end = ((span[1] - 0.5).ceil() as i64).min(clip.right());
+
+            result.extend((start..end).map(|x| (x as i32, y as i32)));
+        }
+    }
+
+    result
+}
+
+/// Pixels of an elliptical arc outline inscribed in the given rectangle.
+/// Angles are in degrees, counter-clockwise from 3 o'clock, as in MIDP `Graphics.drawArc`.
+pub fn arc(x: i32, y: i32, w: u32, h: u32, start_angle: i32, arc_angle: i32, clip: Clip) -> Vec<(i32, i32)> {
+    let ellipse = Ellipse::new(x, y, w, h);
+
+    ellipse
+        .pixels(clip)
+        .filter(|&(px, py)| ellipse.is_edge(px, py) && ellipse.is_in_arc(px, py, start_angle, arc_angle))
+        .collect()
+}
+
+/// Pixels of a filled elliptical pie inscribed in the given rectangle.
+pub fn fill_arc(x: i32, y: i32, w: u32, h: u32, start_angle: i32, arc_angle: i32, clip: Clip) -> Vec<(i32, i32)> {
+    let ellipse = Ellipse::new(x, y, w, h);
+
+    ellipse
+        .pixels(clip)
+        .filter(|&(px, py)| ellipse.contains(px, py) && ellipse.is_in_arc(px, py, start_angle, arc_angle))
+        .collect()
+}
+
+// row-major pixels of a clip
+fn pixels(clip: &Clip) -> impl Iterator<Item = (i32, i32)> {
+    let (x, right) = (clip.x as i64, clip.right());
+
+    (clip.y as i64..clip.bottom()).flat_map(move |y| (x..right).map(move |x| (x as i32, y as i32)))
+}
+
+// division rounded half away from zero
+fn round_div(numerator: i64, denominator: i64) -> i64 {
+    if denominator == 0 {
+        return 0;
+    }
+
+    let (numerator, denominator) = if denominator < 0 {
+        (-numerator, -denominator)
+    } else {
+        (numerator, denominator)
+    };
+    if numerator >= 0 {
+        (numerator + denominator / 2) / denominator
+    } else {
+        -((-numerator + denominator / 2) / denominator)
+    }
+}
+
+struct Ellipse {
+    x: i32,
+    y: i32,
+    w: u32,
+    h: u32,
+    center_x: f32,
+    center_y: f32,
+    radius_x: f32,
+    radius_y: f32,
+}
+
+impl Ellipse {
+    fn new(x: i32, y: i32, w: u32, h: u32) -> Self {
+        Self {
+            x,
+            y,
+            w,
+            h,
+            center_x: x as f32 + w as f32 / 2.0,
+            center_y: y as f32 + h as f32 / 2.0,
+            radius_x: w as f32 / 2.0,
+            radius_y: h as f32 / 2.0,
+        }
+    }
+
+    // bounding box pixels inside clip
+    fn pixels(&self, clip: Clip) -> impl Iterator<Item = (i32, i32)> {
+        let bounds = Clip {
+            x: self.x,
+            y: self.y,
+            width: self.w,
+            height: self.h,
+        };
+
+        pixels(&clip.intersect(&bounds))
+    }
+
+    fn normalize(&self, px: i32, py: i32) -> (f32, f32) {
+        (
+            (px as f32 + 0.5 - self.center_x) / self.radius_x,
+            (py as f32 + 0.5 - self.center_y) / self.radius_y,
+        )
+    }
+
+    fn contains(&self, px: i32, py: i32) -> bool {
+        let (nx, ny) = self.normalize(px, py);
+
+        nx * nx + ny * ny <= 1.0
+    }
+
+    fn is_edge(&self, px: i32, py: i32) -> bool {
+        self.contains(px, py) && [(-1, 0), (1, 0), (0, -1), (0, 1)].iter().any(|(dx, dy)| !self.contains(px + dx, py + dy))
+    }
+
+    fn is_in_arc(&self, px: i32, py: i32, start_angle: i32, arc_angle: i32) -> bool {
+        if arc_angle.abs() >= 360 {
+            return true;
+        }
+
+        let (start, length) = if arc_angle < 0 {
+            (start_angle + arc_angle, -arc_angle)
+        } else {
+            (start_angle, arc_angle)
+        };
+
+        // screen y grows downwards
+        let (nx, ny) = self.normalize(px, py);
+        let angle = (-ny).atan2(nx).to_degrees();
+
+        (angle - start as f32).rem_euclid(360.0) <= length as f32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{arc, fill_arc, fill_polygon, fill_rect, line, rect, Clip};
+
+    const ALL: Clip = Clip {
+        x: i32::MIN,
+        y: i32::MIN,
+        width: u32::MAX,
+        height: u32::MAX,
+    };
+
+    #[test]
+    fn test_line() {
+        assert_eq!(line(0, 0, 3, 0, ALL), [(0, 0), (1, 0), (2, 0), (3, 0)]);
+        assert_eq!(line(2, 2, 0, 0, ALL), [(2, 2), (1, 1), (0, 0)]);
+        assert_eq!(line(1, 1, 1, 1, ALL), [(1, 1)]);
+    }
+
+    #[test]
+    fn test_rect() {
+        assert_eq!(rect(0, 0, 3, 3, ALL).len(), 8);
+        assert_eq!(rect(0, 0, 1, 1, ALL), [(0, 0)]);
+        assert_eq!(fill_polygon(&[(0, 0), (4, 0), (4, 4), (0, 4)], ALL).len(), 16);
+    }
+
+    #[test]
+    fn test_arc() {
+        let full = fill_arc(0, 0, 10, 10, 0, 360, ALL);
+        let quarter = fill_arc(0, 0, 10, 10, 0, 90, ALL);
+
+        assert!(full.contains(&(5, 5)) && !full.contains(&(0, 0)));
+        assert!(quarter.iter().all(|&(x, y)| x >= 5 && y < 5));
+        assert_eq!(fill_arc(0, 0, 10, 10, 90, -90, ALL), quarter);
+
+        assert!(arc(0, 0, 10, 10, 0, 360, ALL).iter().all(|x| full.contains(x)));
+        assert!(!arc(0, 0, 10, 10, 0, 360, ALL).contains(&(5, 5)));
+    }
+
+    #[test]
+    fn test_clip() {
+        let clip = Clip {
+            x: 0,
+            y: 0,
+            width: 4,
+            height: 4,
+        };
+
+        assert_eq!(
+            fill_rect(-2, 2, u32::MAX, u32::MAX, clip),
+            [(0, 2), (1, 2), (2, 2), (3, 2), (0, 3), (1, 3), (2, 3), (3, 3)]
+        );
+        assert_eq!(line(i32::MIN, 1, i32::MAX, 1, clip), [(0, 1), (1, 1), (2, 1), (3, 1)]);
+        assert_eq!(rect(-1, -1, u32::MAX, u32::MAX, clip).len(), 0);
+        assert_eq!(fill_arc(i32::MIN, i32::MIN, u32::MAX, u32::MAX, 0, 360, clip).len(), 16);
+    }
+}
@@ -0,0 +1,89 @@
+use wie_util::{Result, WieError};
+
+use crate::canvas::{ArgbPixel, Color, Image, PixelType, VecImageBuffer};
+
+// wireless bitmap, only type 0 (monochrome, no extension headers) is used in practice
+
+pub fn is_wbmp(data: &[u8]) -> bool {
+    parse_header(data).is_some_and(|(width, height, offset)| width != 0 && height != 0 && data.len() >= offset + row_bytes(width) * height)
+}
+
+pub fn decode_wbmp(data: &[u8]) -> Result<Box<dyn Image>> {
+    let (width, height, offset) = parse_header(data).ok_or_else(|| WieError::FatalError("Invalid WBMP header".into()))?;
+
+    let stride = row_bytes(width);
+    let pixels = data
+        .get(offset..offset + stride * height)
+        .ok_or_else(|| WieError::FatalError("Truncated WBMP data".into()))?;
+
+    let raw = (0..height)
+        .flat_map(|y| (0..width).map(move |x| (x, y)))
+        .map(|(x, y)| {
+            // 1 is white, 0 is black
+            let value = if pixels[y * stride + x / 8] & (0x80 >> (x % 8)) != 0 { 0xff } else { 0 };
+
+            ArgbPixel::from_color(Color {
+                a: 0xff,
+                r: value,
+                g: value,
+                b: value,
+            })
+        })
+        .collect();
+
+    Ok(Box::new(VecImageBuffer::<ArgbPixel>::from_raw(width as _, height as _, raw)))
+}
+
+fn row_bytes(width: usize) -> usize {
+    width.div_ceil(8)
+}
+
+// returns width, height and offset of pixel data
+fn parse_header(data: &[u8]) -> Option<(usize, usize, usize)> {
+    let (r#type, offset) = read_multibyte_int(data, 0)?;
+    let fix_header = *data.get(offset)?;
+    if r#type != 0 || fix_header != 0 {
+        return None;
+    }
+
+    let (width, offset) = read_multibyte_int(data, offset + 1)?;
+    let (height, offset) = read_multibyte_int(data, offset)?;
+
+    Some((width as _, height as _, offset))
+}
+
+// big endian, 7 bits per byte with continuation flag on msb
+fn read_multibyte_int(data: &[u8], offset: usize) -> Option<(u32, usize)> {
+    let mut result = 0u32;
+
+    for (i, &byte) in data.get(offset..)?.iter().take(4).enumerate() {
+        result = (result << 7) | (byte & 0x7f) as u32;
+        if byte & 0x80 == 0 {
+            return Some((result, offset + i + 1));
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_wbmp, is_wbmp};
+
+    #[test]
+    fn test_wbmp() {
+        // 10x2, first row starts with white pixel, second row ends with white pixel
+        let data = [0x00, 0x00, 0x0a, 0x02, 0x80, 0x00, 0x00, 0x40];
+
+        assert!(is_wbmp(&data));
+        assert!(!is_wbmp(&data[..7]));
+        assert!(!is_wbmp(b"BM\x00\x00"));
+
+        let image = decode_wbmp(&data).unwrap();
+        assert_eq!((image.width(), image.height()), (10, 2));
+        assert_eq!(image.get_pixel(0, 0).r, 0xff);
+        assert_eq!(image.get_pixel(1, 0).r, 0);
+        assert_eq!(image.get_pixel(9, 1).r, 0xff);
+        assert_eq!(image.get_pixel(8, 1).r, 0);
+    }
+}
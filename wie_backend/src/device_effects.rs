@@ -0,0 +1,26 @@
+/// Handset effect requested by application.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DeviceEffect {
+    /// Vibrates for `duration` milliseconds at `intensity` percent.
+    /// `pattern` is alternating on and off milliseconds repeated while vibrating, zero off time being continuous.
+    Vibrate {
+        pattern: Vec<u64>,
+        duration: u64,
+        intensity: u8,
+    },
+    StopVibration,
+    /// Turns backlight on or off. Backlight turned on with `timeout` goes off after that many milliseconds.
+    Backlight {
+        on: bool,
+        timeout: Option<u64>,
+    },
+    Led {
+        index: u8,
+        on: bool,
+    },
+}
+
+/// Receiver of device effects, which may be rendered (e.g. by shaking the window) or recorded.
+pub trait DeviceEffects: Send {
+    fn apply(&self, effect: DeviceEffect);
+}
@@ -0,0 +1,30 @@
+use wie_util::Charset;
+
+use crate::{canvas::FontRegistry, system::HangulLayout};
+
+/// Characteristics of the emulated handset, applied when the emulator is loaded.
+#[derive(Clone)]
+pub struct DeviceProfile {
+    /// Built-in fonts, with pixel heights of each size and optional bitmap fonts.
+    pub fonts: FontRegistry,
+    /// Charset of native strings and java default encoding.
+    pub charset: Charset,
+    /// Number of vibration strength levels, reported to application as handset property.
+    pub vibration_level_count: i32,
+    /// Hangul keypad layout of text input.
+    pub hangul_layout: HangulLayout,
+    /// Whether handset has touch screen. Pointer events are dropped if not.
+    pub pointer_events: bool,
+}
+
+impl Default for DeviceProfile {
+    fn default() -> Self {
+        Self {
+            fonts: FontRegistry::default(),
+            charset: Charset::default(),
+            vibration_level_count: 5,
+            hangul_layout: HangulLayout::Chunjiin,
+            pointer_events: false,
+        }
+    }
+}
@@ -4,7 +4,10 @@ use core::{
     pin::Pin,
     task::{Context, Poll, RawWaker, RawWakerVTable, Waker},
 };
-use std::{collections::HashMap, sync::Mutex};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Mutex,
+};
 
 use wie_util::{Result, WieError};
 
@@ -16,6 +19,8 @@ pub struct ExecutorInner {
     current_task_id: Option<usize>,
     tasks: HashMap<usize, Task>,
     sleeping_tasks: HashMap<usize, Instant>,
+    aborted_tasks: HashSet<usize>,
+    timers: HashMap<u64, usize>,
     last_task_id: usize,
 }
 
@@ -67,6 +72,8 @@ impl Executor {
             current_task_id: None,
             tasks: HashMap::new(),
             sleeping_tasks: HashMap::new(),
+            aborted_tasks: HashSet::new(),
+            timers: HashMap::new(),
             last_task_id: 0,
         }));
 
@@ -128,6 +135,39 @@ impl Executor {
         Ok(())
     }
 
+    /// Drops task `task_id` without polling it again. Aborting current task takes effect once it yields.
+    pub fn abort(&mut self, task_id: usize) {
+        let mut inner = self.inner.lock().unwrap();
+
+        inner.tasks.remove(&task_id);
+        inner.sleeping_tasks.remove(&task_id);
+        inner.aborted_tasks.insert(task_id);
+    }
+
+    /// Records `task_id` as the running timer for `key`, aborting the timer previously running for it.
+    pub fn set_timer(&mut self, key: u64, task_id: usize) {
+        let previous = self.inner.lock().unwrap().timers.insert(key, task_id);
+        if let Some(previous) = previous {
+            self.abort(previous);
+        }
+    }
+
+    /// Aborts the timer running for `key`, if any.
+    pub fn abort_timer(&mut self, key: u64) {
+        let task_id = self.inner.lock().unwrap().timers.remove(&key);
+        if let Some(task_id) = task_id {
+            self.abort(task_id);
+        }
+    }
+
+    /// Forgets the timer for `key` if it is the current task, so the timer can be set again from its own callback.
+    pub fn release_timer(&mut self, key: u64) {
+        let mut inner = self.inner.lock().unwrap();
+        if inner.current_task_id.is_some() && inner.timers.get(&key) == inner.current_task_id.as_ref() {
+            inner.timers.remove(&key);
+        }
+    }
+
     pub fn current_task_id(&self) -> u64 {
         self.inner.lock().unwrap().current_task_id.unwrap() as _
     }
@@ -138,6 +178,11 @@ impl Executor {
         let mut sleeping_tasks = self.inner.lock().unwrap().sleeping_tasks.drain().collect::<HashMap<_, _>>();
 
         for (task_id, mut task) in tasks.into_iter() {
+            // tasks polled earlier in this step may abort the rest
+            if self.inner.lock().unwrap().aborted_tasks.contains(&task_id) {
+                continue;
+            }
+
             let item = sleeping_tasks.get(&task_id);
             if let Some(item) = item {
                 if *item <= now {
@@ -154,6 +199,7 @@ impl Executor {
 
             match task.as_mut().poll(&mut context) {
                 Poll::Ready(x) => {
+                    self.inner.lock().unwrap().timers.retain(|_, x| *x != task_id);
                     x?;
                 }
                 Poll::Pending => {
@@ -164,8 +210,14 @@ impl Executor {
             self.inner.lock().unwrap().current_task_id = None;
         }
 
-        self.inner.lock().unwrap().sleeping_tasks.extend(sleeping_tasks);
-        self.inner.lock().unwrap().tasks.extend(next_tasks);
+        let mut inner = self.inner.lock().unwrap();
+        let aborted_tasks = core::mem::take(&mut inner.aborted_tasks);
+
+        inner.sleeping_tasks.extend(sleeping_tasks);
+        inner.tasks.extend(next_tasks);
+
+        inner.sleeping_tasks.retain(|x, _| !aborted_tasks.contains(x));
+        inner.tasks.retain(|x, _| !aborted_tasks.contains(x));
 
         Ok(())
     }
@@ -192,3 +244,80 @@ impl Executor {
         unsafe { Waker::from_raw(noop_raw_waker()) }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use alloc::sync::Arc;
+    use core::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+
+    use crate::{task::SleepFuture, time::Instant};
+
+    use super::Executor;
+
+    #[test]
+    fn test_abort() {
+        let mut executor = Executor::new();
+        let fired = Arc::new(AtomicU32::new(0));
+
+        let spawn_timer = |executor: &mut Executor, wakeup: u64| {
+            let mut executor_clone = executor.clone();
+            let fired = fired.clone();
+
+            executor.spawn(move || async move {
+                SleepFuture::new(Instant::from_epoch_millis(wakeup), &mut executor_clone).await;
+                fired.fetch_add(1, Ordering::SeqCst);
+            })
+        };
+
+        let first = spawn_timer(&mut executor, 10);
+        spawn_timer(&mut executor, 20);
+
+        executor.tick(|| Instant::from_epoch_millis(0)).unwrap();
+        executor.abort(first);
+
+        let clock = AtomicU64::new(30);
+        executor
+            .tick(|| Instant::from_epoch_millis(clock.fetch_add(1, Ordering::SeqCst)))
+            .unwrap();
+
+        assert_eq!(fired.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_timer() {
+        let mut executor = Executor::new();
+        let fired = Arc::new(AtomicU32::new(0));
+
+        let spawn_timer = |executor: &mut Executor, key: u64, wakeup: u64| {
+            let mut executor_clone = executor.clone();
+            let fired = fired.clone();
+
+            let task_id = executor.spawn(move || async move {
+                SleepFuture::new(Instant::from_epoch_millis(wakeup), &mut executor_clone).await;
+                fired.fetch_add(1, Ordering::SeqCst);
+            });
+            executor.set_timer(key, task_id);
+        };
+
+        // setting the same timer again replaces the running one
+        spawn_timer(&mut executor, 1, 10);
+        spawn_timer(&mut executor, 1, 20);
+        spawn_timer(&mut executor, 2, 20);
+
+        executor.tick(|| Instant::from_epoch_millis(0)).unwrap();
+
+        spawn_timer(&mut executor, 3, 20);
+        executor.abort_timer(3);
+
+        let clock = AtomicU64::new(30);
+        executor
+            .tick(|| Instant::from_epoch_millis(clock.fetch_add(1, Ordering::SeqCst)))
+            .unwrap();
+
+        assert_eq!(fired.load(Ordering::SeqCst), 2);
+
+        // finished timers are forgotten, so aborting them is a no-op
+        executor.abort_timer(1);
+        assert!(executor.inner.lock().unwrap().timers.is_empty());
+    }
+}
@@ -0,0 +1,11 @@
+/// Files of an application kept across runs. Paths are relative and separated with '/'.
+/// Only files are kept, directories are implied by file paths.
+pub trait FileStorage: Send {
+    fn files(&self) -> Vec<(String, Vec<u8>)>;
+    fn write(&mut self, path: &str, data: &[u8]) -> bool;
+    fn remove(&mut self, path: &str) -> bool;
+}
+
+pub trait FileStorageRepository {
+    fn open(&self, app_id: &str) -> Box<dyn FileStorage>;
+}
@@ -3,9 +3,14 @@ extern crate alloc;
 mod audio_sink;
 pub mod canvas;
 mod database;
+mod device_effects;
+mod device_profile;
 mod executor;
+mod file_storage;
+mod mixer;
 mod platform;
 mod screen;
+mod synth;
 mod system;
 mod task;
 mod time;
@@ -13,20 +18,29 @@ mod time;
 pub use self::{
     audio_sink::AudioSink,
     database::{Database, DatabaseRepository, RecordId},
+    device_effects::{DeviceEffect, DeviceEffects},
+    device_profile::DeviceProfile,
     executor::{AsyncCallable, AsyncCallableResult},
+    file_storage::{FileStorage, FileStorageRepository},
+    mixer::{Mixer, MixerChannelId},
     platform::Platform,
     screen::Screen,
-    system::{Event, KeyCode, System},
+    synth::Synthesizer,
+    system::{
+        AudioError, AudioEvent, AudioHandle, Event, HangulLayout, InputMethod, InputMode, KeyCode, PlaybackState, SharedBuffer, SharedBuffers, System,
+    },
     time::Instant,
 };
 
 use alloc::collections::BTreeMap;
 
-use wie_util::Result;
+use wie_util::{Charset, Result};
 
 pub trait Emulator {
     fn handle_event(&mut self, event: Event);
     fn tick(&mut self) -> Result<()>;
+    /// Called when frontend stops the emulator while application is still running.
+    fn close(&mut self);
 }
 
 pub fn extract_zip(zip: &[u8]) -> Result<BTreeMap<String, Vec<u8>>> {
@@ -45,7 +59,10 @@ pub fn extract_zip(zip: &[u8]) -> Result<BTreeMap<String, Vec<u8>>> {
             let mut data = Vec::new();
             file.read_to_end(&mut data).unwrap();
 
-            Some(Ok((file.name().to_string(), data)))
+            // entry names may be in euc-kr without the utf-8 flag set
+            let name = Charset::detect(file.name_raw()).decode(file.name_raw());
+
+            Some(Ok((name, data)))
         })
         .collect::<Result<_>>()
 }
@@ -0,0 +1,193 @@
+use alloc::collections::BTreeMap;
+
+use crate::synth::Synthesizer;
+
+pub type MixerChannelId = u32;
+
+const MAX_CHANNELS: usize = 16;
+
+struct MixerChannel {
+    channels: usize,
+    step: f64,
+    position: f64,
+    data: Vec<i16>,
+    volume: f32,
+    paused: bool,
+}
+
+impl MixerChannel {
+    fn frames(&self) -> usize {
+        self.data.len() / self.channels
+    }
+
+    fn is_finished(&self) -> bool {
+        self.position as usize >= self.frames()
+    }
+
+    // linear interpolation between neighboring frames
+    fn sample(&self, channel: usize) -> f32 {
+        let index = self.position as usize;
+        let fraction = (self.position - index as f64) as f32;
+
+        let current = self.data[index * self.channels + channel] as f32;
+        let next = if index + 1 < self.frames() {
+            self.data[(index + 1) * self.channels + channel] as f32
+        } else {
+            current
+        };
+
+        (current + (next - current) * fraction) / 32768.0
+    }
+}
+
+/// Mixes pcm clips on independent channels and synthesized midi into interleaved stereo pcm at fixed output rate.
+pub struct Mixer {
+    sample_rate: u32,
+    synthesizer: Synthesizer,
+    channels: BTreeMap<MixerChannelId, MixerChannel>,
+    last_channel_id: MixerChannelId,
+}
+
+impl Mixer {
+    pub fn new(sample_rate: u32) -> Self {
+        Self {
+            sample_rate,
+            synthesizer: Synthesizer::new(sample_rate),
+            channels: BTreeMap::new(),
+            last_channel_id: 0,
+        }
+    }
+
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    pub fn synthesizer(&mut self) -> &mut Synthesizer {
+        &mut self.synthesizer
+    }
+
+    /// Starts playing interleaved pcm `data` on a new channel, with `volume` in percent.
+    pub fn play(&mut self, channels: u8, sampling_rate: u32, data: &[i16], volume: u8) -> MixerChannelId {
+        self.last_channel_id += 1;
+        let id = self.last_channel_id;
+
+        if channels == 0 || sampling_rate == 0 || data.len() < channels as usize {
+            return id;
+        }
+
+        if self.channels.len() >= MAX_CHANNELS {
+            // ids are increasing, so first one is the oldest
+            self.channels.pop_first();
+        }
+
+        self.channels.insert(
+            id,
+            MixerChannel {
+                channels: channels as _,
+                step: sampling_rate as f64 / self.sample_rate as f64,
+                position: 0.0,
+                data: data.to_vec(),
+                volume: volume.min(100) as f32 / 100.0,
+                paused: false,
+            },
+        );
+
+        id
+    }
+
+    pub fn stop(&mut self, id: MixerChannelId) {
+        self.channels.remove(&id);
+    }
+
+    pub fn pause(&mut self, id: MixerChannelId) {
+        if let Some(x) = self.channels.get_mut(&id) {
+            x.paused = true;
+        }
+    }
+
+    pub fn resume(&mut self, id: MixerChannelId) {
+        if let Some(x) = self.channels.get_mut(&id) {
+            x.paused = false;
+        }
+    }
+
+    pub fn set_volume(&mut self, id: MixerChannelId, volume: u8) {
+        if let Some(x) = self.channels.get_mut(&id) {
+            x.volume = volume.min(100) as f32 / 100.0;
+        }
+    }
+
+    /// Whether channel has remaining audio to play, including paused ones.
+    pub fn is_playing(&self, id: MixerChannelId) -> bool {
+        self.channels.contains_key(&id)
+    }
+
+    /// Renders next interleaved stereo samples into `buffer`.
+    pub fn render(&mut self, buffer: &mut [i16]) {
+        let mut mix = vec![0.0f32; buffer.len()];
+
+        self.synthesizer.render(&mut mix);
+
+        for channel in self.channels.values_mut().filter(|x| !x.paused) {
+            let right = if channel.channels > 1 { 1 } else { 0 };
+
+            for frame in mix.chunks_exact_mut(2) {
+                if channel.is_finished() {
+                    break;
+                }
+
+                frame[0] += channel.sample(0) * channel.volume;
+                frame[1] += channel.sample(right) * channel.volume;
+
+                channel.position += channel.step;
+            }
+        }
+        self.channels.retain(|_, x| !x.is_finished());
+
+        for (output, sample) in buffer.iter_mut().zip(mix) {
+            *output = (sample.clamp(-1.0, 1.0) * i16::MAX as f32) as i16;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Mixer;
+
+    #[test]
+    fn test_resampling() {
+        let mut mixer = Mixer::new(8000);
+
+        // mono 4khz clip is played at half step on 8khz output
+        let id = mixer.play(1, 4000, &[16384, 16384], 100);
+
+        let mut buffer = [0; 10];
+        mixer.render(&mut buffer);
+
+        assert_eq!(&buffer[..8], [16383; 8]);
+        assert_eq!(&buffer[8..], [0, 0]);
+        assert!(!mixer.is_playing(id));
+    }
+
+    #[test]
+    fn test_channels() {
+        let mut mixer = Mixer::new(8000);
+
+        let music = mixer.play(2, 8000, &[8192; 40], 100);
+        let effect = mixer.play(1, 8000, &[8192; 20], 50);
+
+        let mut buffer = [0; 4];
+        mixer.render(&mut buffer);
+        assert_eq!(buffer[0], 12287);
+
+        mixer.pause(music);
+        mixer.render(&mut buffer);
+        assert_eq!(buffer[0], 4095);
+
+        mixer.resume(music);
+        mixer.stop(effect);
+        mixer.render(&mut buffer);
+        assert_eq!(buffer[0], 8191);
+        assert!(mixer.is_playing(music) && !mixer.is_playing(effect));
+    }
+}
@@ -1,10 +1,18 @@
-use crate::{audio_sink::AudioSink, database::DatabaseRepository, screen::Screen, time::Instant};
+use crate::{
+    audio_sink::AudioSink, database::DatabaseRepository, device_effects::DeviceEffects, device_profile::DeviceProfile,
+    file_storage::FileStorageRepository, screen::Screen, time::Instant,
+};
 
 pub trait Platform: Send {
     fn screen(&mut self) -> &mut dyn Screen;
     fn now(&self) -> Instant;
     fn database_repository(&self) -> &dyn DatabaseRepository;
+    fn file_storage_repository(&self) -> &dyn FileStorageRepository;
     fn audio_sink(&self) -> Box<dyn AudioSink>;
+    fn device_effects(&self) -> &dyn DeviceEffects;
+    fn device_profile(&self) -> DeviceProfile;
     fn write_stdout(&self, buf: &[u8]);
     fn write_stderr(&self, buf: &[u8]);
+    /// Called once when application exits by itself. Frontend should stop running the emulator.
+    fn exit(&mut self);
 }
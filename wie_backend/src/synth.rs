@@ -0,0 +1,317 @@
+mod patch;
+
+use core::f32::consts::{FRAC_PI_2, TAU};
+
+use self::patch::{drum_patch, melodic_patch, Patch};
+
+// ma-3 class chips have 32 voice polyphony
+const MAX_VOICES: usize = 32;
+const DRUM_CHANNEL: u8 = 9;
+const MASTER_GAIN: f32 = 0.25;
+const SILENCE: f32 = 1.0 / 32768.0;
+
+#[derive(Clone, Copy)]
+struct ChannelState {
+    program: u8,
+    volume: u8,
+    expression: u8,
+    pan: u8,
+    sustain: bool,
+}
+
+impl Default for ChannelState {
+    fn default() -> Self {
+        Self {
+            program: 0,
+            volume: 100,
+            expression: 127,
+            pan: 64,
+            sustain: false,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Eq, PartialEq)]
+enum Stage {
+    Attack,
+    Decay,
+    Release,
+    Done,
+}
+
+struct Voice {
+    channel: u8,
+    note: u8,
+    gain: f32,
+    patch: Patch,
+    frequency: f32,
+    carrier_phase: f32,
+    modulator_phase: f32,
+    last_modulator: f32,
+    modulator_level: f32,
+    pitch_level: f32,
+    level: f32,
+    stage: Stage,
+    held: bool,
+    serial: u64,
+}
+
+impl Voice {
+    fn release(&mut self) {
+        if self.stage != Stage::Done {
+            self.stage = Stage::Release;
+        }
+    }
+
+    fn render(&mut self, sample_rate: f32, noise: f32) -> f32 {
+        let patch = &self.patch;
+
+        match self.stage {
+            Stage::Attack => {
+                self.level += 1.0 / (patch.attack * sample_rate).max(1.0);
+                if self.level >= 1.0 {
+                    self.level = 1.0;
+                    self.stage = Stage::Decay;
+                }
+            }
+            Stage::Decay => self.level = patch.sustain + (self.level - patch.sustain) * decay_coefficient(patch.decay, sample_rate),
+            Stage::Release => {
+                self.level *= decay_coefficient(patch.release, sample_rate);
+                if self.level < SILENCE {
+                    self.stage = Stage::Done;
+                }
+            }
+            Stage::Done => return 0.0,
+        }
+        if self.stage == Stage::Decay && patch.sustain == 0.0 && self.level < SILENCE {
+            self.stage = Stage::Done;
+        }
+
+        let frequency = self.frequency * (1.0 + patch.pitch_sweep * self.pitch_level);
+        self.pitch_level *= decay_coefficient(0.02, sample_rate);
+
+        let modulator = (TAU * self.modulator_phase + patch.feedback * self.last_modulator).sin();
+        self.last_modulator = modulator;
+
+        let carrier = (TAU * self.carrier_phase + modulator * patch.modulation_index * self.modulator_level).sin();
+        if patch.modulator_decay > 0.0 {
+            self.modulator_level *= decay_coefficient(patch.modulator_decay, sample_rate);
+        }
+
+        self.carrier_phase = (self.carrier_phase + frequency / sample_rate).fract();
+        self.modulator_phase = (self.modulator_phase + frequency * patch.modulator_ratio / sample_rate).fract();
+
+        (carrier * (1.0 - patch.noise) + noise * patch.noise) * self.level * self.gain
+    }
+}
+
+fn decay_coefficient(time: f32, sample_rate: f32) -> f32 {
+    (-1.0 / (time * sample_rate).max(1.0)).exp()
+}
+
+/// Software general midi synthesizer, rendering midi events to stereo pcm with two operator fm voices.
+pub struct Synthesizer {
+    sample_rate: u32,
+    channels: [ChannelState; 16],
+    voices: Vec<Voice>,
+    last_serial: u64,
+    noise_state: u32,
+}
+
+impl Synthesizer {
+    pub fn new(sample_rate: u32) -> Self {
+        Self {
+            sample_rate,
+            channels: [ChannelState::default(); 16],
+            voices: Vec::with_capacity(MAX_VOICES),
+            last_serial: 0,
+            noise_state: 0x1234_5678,
+        }
+    }
+
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    pub fn note_on(&mut self, channel: u8, note: u8, velocity: u8) {
+        let channel = channel & 0x0f;
+        if velocity == 0 {
+            return self.note_off(channel, note);
+        }
+
+        // retriggering a note releases the sounding one
+        self.voices
+            .iter_mut()
+            .filter(|x| x.channel == channel && x.note == note)
+            .for_each(Voice::release);
+
+        if self.voices.len() >= MAX_VOICES {
+            // steal quietest releasing voice, or oldest one
+            let index = self
+                .voices
+                .iter()
+                .enumerate()
+                .filter(|(_, x)| x.stage == Stage::Release)
+                .min_by(|a, b| a.1.level.total_cmp(&b.1.level))
+                .or_else(|| self.voices.iter().enumerate().min_by_key(|(_, x)| x.serial))
+                .map(|(i, _)| i)
+                .unwrap();
+            self.voices.swap_remove(index);
+        }
+
+        let (patch, frequency) = if channel == DRUM_CHANNEL {
+            let patch = drum_patch(note);
+            (patch, patch.frequency.unwrap_or(440.0))
+        } else {
+            let patch = melodic_patch(self.channels[channel as usize].program);
+            (patch, 440.0 * 2f32.powf((note as f32 - 69.0) / 12.0))
+        };
+
+        let velocity = velocity as f32 / 127.0;
+
+        self.last_serial += 1;
+        self.voices.push(Voice {
+            channel,
+            note,
+            gain: velocity * velocity,
+            patch,
+            frequency,
+            carrier_phase: 0.0,
+            modulator_phase: 0.0,
+            last_modulator: 0.0,
+            modulator_level: 1.0,
+            pitch_level: 1.0,
+            level: 0.0,
+            stage: Stage::Attack,
+            held: false,
+            serial: self.last_serial,
+        });
+    }
+
+    pub fn note_off(&mut self, channel: u8, note: u8) {
+        let channel = channel & 0x0f;
+        let sustain = self.channels[channel as usize].sustain;
+
+        for voice in self
+            .voices
+            .iter_mut()
+            .filter(|x| x.channel == channel && x.note == note && x.stage != Stage::Release)
+        {
+            if sustain {
+                voice.held = true;
+            } else {
+                voice.release();
+            }
+        }
+    }
+
+    pub fn program_change(&mut self, channel: u8, program: u8) {
+        self.channels[(channel & 0x0f) as usize].program = program & 0x7f;
+    }
+
+    pub fn control_change(&mut self, channel: u8, control: u8, value: u8) {
+        let channel = channel & 0x0f;
+        let state = &mut self.channels[channel as usize];
+
+        match control {
+            7 => state.volume = value,
+            10 => state.pan = value,
+            11 => state.expression = value,
+            64 => {
+                state.sustain = value >= 64;
+                if !state.sustain {
+                    self.voices.iter_mut().filter(|x| x.channel == channel && x.held).for_each(Voice::release);
+                }
+            }
+            // all sound off
+            120 => self.voices.retain(|x| x.channel != channel),
+            // reset all controllers
+            121 => {
+                *state = ChannelState {
+                    program: state.program,
+                    ..Default::default()
+                }
+            }
+            // all notes off
+            123 => self.voices.iter_mut().filter(|x| x.channel == channel).for_each(Voice::release),
+            _ => tracing::debug!("Unhandled midi control change {} {}", control, value),
+        }
+    }
+
+    /// Mixes synthesized audio into interleaved stereo `buffer`.
+    pub fn render(&mut self, buffer: &mut [f32]) {
+        let sample_rate = self.sample_rate as f32;
+
+        for voice in &mut self.voices {
+            let channel = &self.channels[voice.channel as usize];
+
+            let volume = channel.volume as f32 / 127.0 * channel.expression as f32 / 127.0;
+            let pan = channel.pan as f32 / 127.0 * FRAC_PI_2;
+            let (left, right) = (pan.cos() * volume * MASTER_GAIN, pan.sin() * volume * MASTER_GAIN);
+
+            for frame in buffer.chunks_exact_mut(2) {
+                if voice.stage == Stage::Done {
+                    break;
+                }
+
+                // xorshift32
+                self.noise_state ^= self.noise_state << 13;
+                self.noise_state ^= self.noise_state >> 17;
+                self.noise_state ^= self.noise_state << 5;
+                let noise = self.noise_state as f32 / u32::MAX as f32 * 2.0 - 1.0;
+
+                let sample = voice.render(sample_rate, noise);
+                frame[0] += sample * left;
+                frame[1] += sample * right;
+            }
+        }
+
+        self.voices.retain(|x| x.stage != Stage::Done);
+    }
+
+    pub fn active_voices(&self) -> usize {
+        self.voices.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Synthesizer;
+
+    #[test]
+    fn test_note_lifecycle() {
+        let mut synthesizer = Synthesizer::new(8000);
+        let mut buffer = vec![0.0; 800];
+
+        synthesizer.note_on(0, 69, 100);
+        synthesizer.render(&mut buffer);
+        assert!(buffer.iter().any(|x| x.abs() > 0.01));
+
+        synthesizer.note_off(0, 69);
+        for _ in 0..100 {
+            buffer.fill(0.0);
+            synthesizer.render(&mut buffer);
+        }
+        assert_eq!(synthesizer.active_voices(), 0);
+        assert!(buffer.iter().all(|x| x.abs() < 0.001));
+    }
+
+    #[test]
+    fn test_sustain_pedal() {
+        let mut synthesizer = Synthesizer::new(8000);
+        let mut buffer = vec![0.0; 8000];
+
+        synthesizer.program_change(0, 16); // organ
+        synthesizer.control_change(0, 64, 127);
+        synthesizer.note_on(0, 60, 100);
+        synthesizer.note_off(0, 60);
+        synthesizer.render(&mut buffer);
+        assert_eq!(synthesizer.active_voices(), 1);
+
+        synthesizer.control_change(0, 64, 0);
+        for _ in 0..4 {
+            synthesizer.render(&mut buffer);
+        }
+        assert_eq!(synthesizer.active_voices(), 0);
+    }
+}
@@ -0,0 +1,97 @@
+// two operator fm patches, roughly approximating yamaha ma-series general midi voices
+
+#[derive(Clone, Copy)]
+pub struct Patch {
+    /// Modulator frequency relative to carrier.
+    pub modulator_ratio: f32,
+    /// Peak modulation depth in radians.
+    pub modulation_index: f32,
+    /// Time constant of modulation depth decay in seconds, 0 to keep it constant.
+    pub modulator_decay: f32,
+    pub feedback: f32,
+    pub attack: f32,
+    pub decay: f32,
+    pub sustain: f32,
+    pub release: f32,
+    /// Ratio of white noise mixed into carrier output.
+    pub noise: f32,
+    /// Initial pitch offset in octaves, decaying quickly. Used for drums.
+    pub pitch_sweep: f32,
+    /// Frequency overriding note pitch. Used for drums.
+    pub frequency: Option<f32>,
+}
+
+impl Patch {
+    const fn new(modulator_ratio: f32, modulation_index: f32, modulator_decay: f32, attack: f32, decay: f32, sustain: f32, release: f32) -> Self {
+        Self {
+            modulator_ratio,
+            modulation_index,
+            modulator_decay,
+            feedback: 0.0,
+            attack,
+            decay,
+            sustain,
+            release,
+            noise: 0.0,
+            pitch_sweep: 0.0,
+            frequency: None,
+        }
+    }
+
+    const fn with_feedback(self, feedback: f32) -> Self {
+        Self { feedback, ..self }
+    }
+
+    const fn with_noise(self, noise: f32) -> Self {
+        Self { noise, ..self }
+    }
+
+    const fn drum(frequency: f32, noise: f32, pitch_sweep: f32, decay: f32) -> Self {
+        Self {
+            frequency: Some(frequency),
+            noise,
+            pitch_sweep,
+            ..Self::new(1.0, 1.0, 0.05, 0.001, decay, 0.0, decay)
+        }
+    }
+}
+
+// one patch per general midi instrument family of 8 programs
+const MELODIC_PATCHES: [Patch; 16] = [
+    Patch::new(1.0, 1.5, 0.5, 0.002, 1.0, 0.2, 0.3),                    // piano
+    Patch::new(3.5, 2.0, 0.3, 0.001, 0.6, 0.0, 0.3),                    // chromatic percussion
+    Patch::new(2.0, 0.8, 0.0, 0.01, 0.1, 0.9, 0.08),                    // organ
+    Patch::new(1.0, 2.0, 0.2, 0.002, 0.8, 0.1, 0.2),                    // guitar
+    Patch::new(1.0, 1.5, 0.3, 0.002, 0.5, 0.4, 0.1),                    // bass
+    Patch::new(1.0, 1.2, 0.0, 0.15, 0.3, 0.8, 0.3),                     // strings
+    Patch::new(1.0, 1.0, 0.0, 0.2, 0.3, 0.8, 0.4),                      // ensemble
+    Patch::new(1.0, 2.5, 0.0, 0.05, 0.2, 0.7, 0.15),                    // brass
+    Patch::new(3.0, 1.5, 0.0, 0.03, 0.2, 0.8, 0.1),                     // reed
+    Patch::new(1.0, 0.4, 0.0, 0.05, 0.2, 0.8, 0.15),                    // pipe
+    Patch::new(1.0, 3.0, 0.0, 0.005, 0.2, 0.8, 0.1).with_feedback(0.5), // synth lead
+    Patch::new(2.0, 1.0, 0.0, 0.3, 0.5, 0.8, 0.6),                      // synth pad
+    Patch::new(1.41, 2.0, 0.0, 0.1, 0.5, 0.6, 0.5),                     // synth effects
+    Patch::new(3.0, 1.8, 0.3, 0.002, 0.6, 0.1, 0.2),                    // ethnic
+    Patch::new(1.41, 3.0, 0.1, 0.001, 0.3, 0.0, 0.1),                   // percussive
+    Patch::new(1.7, 4.0, 0.0, 0.01, 0.5, 0.3, 0.3).with_noise(0.5),     // sound effects
+];
+
+pub fn melodic_patch(program: u8) -> Patch {
+    MELODIC_PATCHES[(program as usize / 8) % MELODIC_PATCHES.len()]
+}
+
+// general midi percussion key map
+pub fn drum_patch(note: u8) -> Patch {
+    match note {
+        35 | 36 => Patch::drum(60.0, 0.0, 2.0, 0.25),                                 // bass drum
+        37 => Patch::drum(400.0, 0.6, 0.0, 0.05),                                     // side stick
+        38 | 40 => Patch::drum(180.0, 0.7, 0.5, 0.2),                                 // snare
+        39 => Patch::drum(300.0, 0.9, 0.0, 0.15),                                     // hand clap
+        41 | 43 | 45 | 47 | 48 | 50 => Patch::drum(note as f32 * 2.5, 0.1, 1.0, 0.3), // toms
+        42 | 44 => Patch::drum(8000.0, 1.0, 0.0, 0.05),                               // closed hi-hat
+        46 => Patch::drum(8000.0, 1.0, 0.0, 0.3),                                     // open hi-hat
+        49 | 52 | 55 | 57 => Patch::drum(6000.0, 0.9, 0.0, 1.0),                      // crash cymbals
+        51 | 53 | 59 => Patch::drum(5000.0, 0.7, 0.0, 0.6),                           // ride cymbals
+        _ => Patch::drum(note as f32 * 8.0, 0.5, 0.0, 0.2),
+    }
+}
@@ -1,46 +1,82 @@
+mod animation;
 mod audio;
+mod device;
 mod event_queue;
 mod file_system;
+mod input_method;
+mod shared_buffer;
 
 use alloc::sync::Arc;
+use core::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Mutex, MutexGuard, RwLock, RwLockWriteGuard};
 
-use wie_util::Result;
+use wie_util::{Charset, Result};
 
 use crate::{
+    canvas::{Font, FontFace, FontRegistry, FontSize, FontStyle},
     executor::{AsyncCallableResult, Executor},
     platform::Platform,
     task::{SleepFuture, YieldFuture},
     AsyncCallable, Instant,
 };
 
-use self::{audio::Audio, event_queue::EventQueue, file_system::Filesystem};
+use self::{audio::Audio, device::Device, event_queue::EventQueue, file_system::Filesystem};
 
-pub use self::event_queue::{Event, KeyCode};
+pub use self::{
+    animation::Animations,
+    audio::{AudioError, AudioEvent, AudioHandle, PlaybackState},
+    event_queue::{Event, KeyCode},
+    input_method::{HangulLayout, InputMethod, InputMode},
+    shared_buffer::{SharedBuffer, SharedBuffers},
+};
+
+const AUDIO_EVENT_POLL_MILLIS: u64 = 10;
 
 #[derive(Clone)]
 pub struct System {
     app_id: String,
+    app_name: Arc<Mutex<Option<String>>>,
     executor: Executor,
     platform: Arc<Mutex<Box<dyn Platform>>>,
     filesystem: Arc<Mutex<Filesystem>>,
     event_queue: Arc<RwLock<EventQueue>>,
     audio: Option<Arc<RwLock<Audio>>>,
+    device: Arc<Mutex<Device>>,
+    input_method: Arc<Mutex<InputMethod>>,
+    shared_buffers: Arc<Mutex<SharedBuffers>>,
+    animations: Arc<Mutex<Animations>>,
+    fonts: Arc<FontRegistry>,
+    charset: Charset,
+    exited: Arc<AtomicBool>,
 }
 
 impl System {
     pub fn new(platform: Box<dyn Platform>, app_id: &str) -> Self {
         let audio_sink = platform.audio_sink();
 
+        let device_profile = platform.device_profile();
+        let storage = platform.file_storage_repository().open(app_id);
+
+        let mut input_method = InputMethod::default();
+        input_method.set_layout(device_profile.hangul_layout);
+
         let platform = Arc::new(Mutex::new(platform));
 
         let mut result = Self {
             app_id: app_id.to_owned(),
+            app_name: Arc::new(Mutex::new(None)),
             executor: Executor::new(),
             platform: platform.clone(),
-            filesystem: Arc::new(Mutex::new(Filesystem::new())),
+            filesystem: Arc::new(Mutex::new(Filesystem::with_storage(storage))),
             event_queue: Arc::new(RwLock::new(EventQueue::new())),
             audio: None,
+            device: Arc::new(Mutex::new(Device::new(platform))),
+            input_method: Arc::new(Mutex::new(input_method)),
+            shared_buffers: Arc::new(Mutex::new(SharedBuffers::default())),
+            animations: Arc::new(Mutex::new(Animations::default())),
+            fonts: Arc::new(device_profile.fonts),
+            charset: device_profile.charset,
+            exited: Arc::new(AtomicBool::new(false)),
         };
 
         // late initialization
@@ -50,6 +86,14 @@ impl System {
     }
 
     pub fn tick(&mut self) -> Result<()> {
+        if self.is_exited() {
+            return Ok(());
+        }
+
+        let now = self.platform().now();
+        self.audio().render(now);
+        self.event_queue().update(now);
+
         let platform = self.platform.clone();
         self.executor.tick(move || {
             let platform = platform.lock().unwrap();
@@ -58,12 +102,45 @@ impl System {
         })
     }
 
-    pub fn spawn<C, R>(&mut self, callable: C)
+    /// Spawns task, returning its id.
+    pub fn spawn<C, R>(&mut self, callable: C) -> u64
     where
         C: AsyncCallable<R> + 'static + Send,
         R: AsyncCallableResult,
     {
-        self.executor.spawn(callable);
+        self.executor.spawn(callable) as _
+    }
+
+    /// Records task `task_id` as the running timer `key`, cancelling the timer's previous task.
+    pub fn set_timer(&mut self, key: u64, task_id: u64) {
+        self.executor.set_timer(key, task_id as _)
+    }
+
+    /// Cancels the task running for timer `key`, if any.
+    pub fn abort_timer(&mut self, key: u64) {
+        self.executor.abort_timer(key)
+    }
+
+    /// Detaches current task from timer `key`, called by the timer before running its callback.
+    pub fn release_timer(&mut self, key: u64) {
+        self.executor.release_timer(key)
+    }
+
+    /// Stops running application and notifies platform, called when application exits by itself.
+    pub fn exit(&mut self) {
+        if !self.exited.swap(true, Ordering::SeqCst) {
+            self.filesystem().flush();
+            self.platform().exit();
+        }
+    }
+
+    /// Saves application state kept by system, called when frontend closes the emulator.
+    pub fn close(&mut self) {
+        self.filesystem().flush();
+    }
+
+    pub fn is_exited(&self) -> bool {
+        self.exited.load(Ordering::SeqCst)
     }
 
     pub fn sleep(&mut self, until: Instant) -> SleepFuture {
@@ -86,6 +163,15 @@ impl System {
         &self.app_id
     }
 
+    /// Application name from descriptor, falling back to app id if not known.
+    pub fn app_name(&self) -> String {
+        self.app_name.lock().unwrap().clone().unwrap_or_else(|| self.app_id.clone())
+    }
+
+    pub fn set_app_name(&mut self, name: &str) {
+        *self.app_name.lock().unwrap() = Some(name.to_owned());
+    }
+
     pub fn platform(&self) -> MutexGuard<'_, Box<dyn Platform>> {
         self.platform.lock().unwrap()
     }
@@ -94,6 +180,71 @@ impl System {
         self.audio.as_ref().unwrap().write().unwrap()
     }
 
+    /// Waits for next event of `audio_handle` after `serial`, updating `serial` to it.
+    /// Returns `None` if `audio_handle` is closed.
+    pub async fn wait_audio_event(&mut self, audio_handle: AudioHandle, serial: &mut u64) -> Option<AudioEvent> {
+        loop {
+            let event = self.audio().event_after(audio_handle, *serial).ok()?;
+            if let Some((event_serial, event)) = event {
+                *serial = event_serial;
+
+                return Some(event);
+            }
+
+            let until = self.platform().now() + AUDIO_EVENT_POLL_MILLIS;
+            self.sleep(until).await;
+        }
+    }
+
+    pub fn device(&self) -> MutexGuard<'_, Device> {
+        self.device.lock().unwrap()
+    }
+
+    /// Handset input method, shared by text components. Only the focused component should use it.
+    pub fn input_method(&self) -> MutexGuard<'_, InputMethod> {
+        self.input_method.lock().unwrap()
+    }
+
+    /// Named buffers shared between programs.
+    pub fn shared_buffers(&self) -> MutexGuard<'_, SharedBuffers> {
+        self.shared_buffers.lock().unwrap()
+    }
+
+    /// Decoded frames of animated images.
+    pub fn animations(&self) -> MutexGuard<'_, Animations> {
+        self.animations.lock().unwrap()
+    }
+
+    /// Charset of native strings.
+    pub fn charset(&self) -> Charset {
+        self.charset
+    }
+
+    /// Handset font of given face, size and style.
+    pub fn font(&self, face: FontFace, size: FontSize, style: FontStyle) -> Font {
+        self.fonts.font(face, size, style)
+    }
+
+    /// Font used when application doesn't specify one.
+    pub fn default_font(&self) -> Font {
+        self.fonts.default_font()
+    }
+
+    /// Queues event from frontend, tracking held keys for repeat.
+    pub fn handle_event(&mut self, event: Event) {
+        let now = self.platform().now();
+
+        let has_pointer_events = self.device().has_pointer_events();
+
+        let mut event_queue = self.event_queue();
+        match event {
+            Event::Keydown(key) => event_queue.key_down(key, now),
+            Event::Keyup(key) => event_queue.key_up(key),
+            Event::PointerDown(..) | Event::PointerMove(..) | Event::PointerUp(..) if !has_pointer_events => {}
+            _ => event_queue.push(event),
+        }
+    }
+
     pub fn event_queue(&self) -> RwLockWriteGuard<'_, EventQueue> {
         self.event_queue.write().unwrap()
     }
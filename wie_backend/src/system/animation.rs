@@ -0,0 +1,28 @@
+use alloc::{boxed::Box, collections::BTreeMap};
+
+use crate::canvas::{Image, ImageFrame};
+
+/// Decoded animated images, so frames are decoded once on creation rather than on each frame advance.
+#[derive(Default)]
+pub struct Animations {
+    /// Keyed by runtime specific image handle.
+    images: BTreeMap<u32, Box<dyn Image>>,
+}
+
+impl Animations {
+    pub fn insert(&mut self, handle: u32, image: Box<dyn Image>) {
+        self.images.insert(handle, image);
+    }
+
+    pub fn frame_count(&self, handle: u32) -> usize {
+        self.images.get(&handle).map(|x| x.frames().len()).unwrap_or(0)
+    }
+
+    pub fn frame(&self, handle: u32, index: usize) -> Option<&ImageFrame> {
+        self.images.get(&handle)?.frames().get(index)
+    }
+
+    pub fn remove(&mut self, handle: u32) -> Option<Box<dyn Image>> {
+        self.images.remove(&handle)
+    }
+}
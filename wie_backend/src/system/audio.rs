@@ -1,97 +1,611 @@
-use alloc::{collections::BTreeMap, sync::Arc};
+mod midi;
+mod tone;
+mod wave;
+
+use alloc::{
+    collections::{BTreeMap, BTreeSet, VecDeque},
+    sync::Arc,
+};
 use core::time::Duration;
+use std::sync::{Mutex, MutexGuard};
 
 use smaf_player::{AudioBackend, SmafPlayer};
 
-use crate::{audio_sink::AudioSink, System};
+use crate::{
+    audio_sink::AudioSink,
+    mixer::{Mixer, MixerChannelId},
+    Instant, System,
+};
+
+use self::{
+    midi::MidiSequence,
+    tone::{is_tone_sequence, parse_tone_sequence, tone},
+    wave::Wave,
+};
+
+// audio rendered ahead of playback, to cover scheduling jitter of tick
+const LATENCY_MILLIS: u64 = 100;
+// longer stalls are not caught up, to avoid bursts of delayed audio
+const MAX_RENDER_MILLIS: u64 = 500;
+// interval of checking pause state in paused playback
+const PAUSE_POLL_MILLIS: u64 = 10;
+// longer waits are split, to follow pause and stop while waiting
+const MAX_SLEEP_MILLIS: u64 = 100;
+const MAX_EVENTS: usize = 16;
+
+pub type AudioHandle = u32;
+#[derive(Debug)]
+pub enum AudioError {
+    InvalidHandle,
+    InvalidAudio,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PlaybackState {
+    Stopped,
+    Playing,
+    Paused,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AudioEvent {
+    Started,
+    Paused,
+    /// Stopped before reaching end of media.
+    Stopped,
+    /// Reached end of media after last loop.
+    EndOfMedia,
+    VolumeChanged,
+}
+
+struct Playback {
+    state: PlaybackState,
+    volume: u8,
+    /// Remaining number of plays, negative to repeat forever.
+    loop_count: i32,
+    /// Media time in milliseconds.
+    position: u64,
+    /// Media time to fast forward to without sound.
+    seek_target: u64,
+    // bumped on stop and restart, so that tasks of previous playback exit early
+    generation: u32,
+    notes: BTreeSet<(u8, u8)>,
+    mixer_channels: Vec<MixerChannelId>,
+    events: VecDeque<(u64, AudioEvent)>,
+    last_event_serial: u64,
+}
+
+impl Playback {
+    fn new() -> Self {
+        Self {
+            state: PlaybackState::Stopped,
+            volume: 100,
+            loop_count: 1,
+            position: 0,
+            seek_target: 0,
+            generation: 0,
+            notes: BTreeSet::new(),
+            mixer_channels: Vec::new(),
+            events: VecDeque::new(),
+            last_event_serial: 0,
+        }
+    }
+
+    fn push_event(&mut self, event: AudioEvent) {
+        self.last_event_serial += 1;
+        self.events.push_back((self.last_event_serial, event));
+        if self.events.len() > MAX_EVENTS {
+            self.events.pop_front();
+        }
+    }
 
-struct AudioBackendImpl {
+    // silences everything this playback has started
+    fn silence(&mut self, mixer: &mut Mixer) {
+        for (channel_id, note) in core::mem::take(&mut self.notes) {
+            mixer.synthesizer().note_off(channel_id, note);
+        }
+        for channel in core::mem::take(&mut self.mixer_channels) {
+            mixer.stop(channel);
+        }
+    }
+}
+
+struct PlaybackBackend {
     system: System,
-    sink: Box<dyn AudioSink>,
+    mixer: Arc<Mutex<Mixer>>,
+    playback: Arc<Mutex<Playback>>,
+    generation: u32,
+}
+
+impl PlaybackBackend {
+    // returns playback if it's current and audible
+    fn audible(&self) -> Option<MutexGuard<'_, Playback>> {
+        let playback = self.playback.lock().unwrap();
+
+        (playback.generation == self.generation && playback.state == PlaybackState::Playing && playback.position >= playback.seek_target)
+            .then_some(playback)
+    }
+
+    // skips to seek target for sources which can start from any position, returns the position
+    fn skip_to_seek_target(&self) -> u64 {
+        let mut playback = self.playback.lock().unwrap();
+        playback.position = playback.position.max(playback.seek_target);
+
+        playback.position
+    }
+
+    // returns whether another loop should be played
+    fn finish_loop(&self) -> bool {
+        let mut playback = self.playback.lock().unwrap();
+        if playback.generation != self.generation {
+            return false;
+        }
+
+        playback.silence(&mut self.mixer.lock().unwrap());
+        playback.position = 0;
+        playback.seek_target = 0;
+
+        if playback.loop_count < 0 || playback.loop_count > 1 {
+            if playback.loop_count > 0 {
+                playback.loop_count -= 1;
+            }
+
+            true
+        } else {
+            playback.state = PlaybackState::Stopped;
+            playback.push_event(AudioEvent::EndOfMedia);
+
+            false
+        }
+    }
 }
 
 #[async_trait::async_trait]
-impl AudioBackend for AudioBackendImpl {
+impl AudioBackend for PlaybackBackend {
     fn play_wave(&self, channel: u8, sampling_rate: u32, wave_data: &[i16]) {
-        self.sink.play_wave(channel, sampling_rate, wave_data);
+        if let Some(mut playback) = self.audible() {
+            let mut mixer = self.mixer.lock().unwrap();
+
+            let id = mixer.play(channel, sampling_rate, wave_data, playback.volume);
+            playback.mixer_channels.retain(|&x| mixer.is_playing(x));
+            playback.mixer_channels.push(id);
+        }
     }
 
     fn midi_note_on(&self, channel_id: u8, note: u8, velocity: u8) {
-        self.sink.midi_note_on(channel_id, note, velocity);
+        if let Some(mut playback) = self.audible() {
+            let velocity = (velocity as u32 * playback.volume as u32 / 100) as u8;
+
+            self.mixer.lock().unwrap().synthesizer().note_on(channel_id, note, velocity);
+            playback.notes.insert((channel_id, note));
+        }
     }
 
-    fn midi_note_off(&self, channel_id: u8, note: u8, velocity: u8) {
-        self.sink.midi_note_off(channel_id, note, velocity);
+    fn midi_note_off(&self, channel_id: u8, note: u8, _velocity: u8) {
+        let mut playback = self.playback.lock().unwrap();
+
+        if playback.notes.remove(&(channel_id, note)) {
+            self.mixer.lock().unwrap().synthesizer().note_off(channel_id, note);
+        }
     }
 
     fn midi_program_change(&self, channel_id: u8, program: u8) {
-        self.sink.midi_program_change(channel_id, program);
+        // channel settings are applied while fast forwarding too
+        if self.playback.lock().unwrap().generation == self.generation {
+            self.mixer.lock().unwrap().synthesizer().program_change(channel_id, program);
+        }
     }
 
     fn midi_control_change(&self, channel_id: u8, control: u8, value: u8) {
-        self.sink.midi_control_change(channel_id, control, value);
+        if self.playback.lock().unwrap().generation == self.generation {
+            self.mixer.lock().unwrap().synthesizer().control_change(channel_id, control, value);
+        }
     }
 
     async fn sleep(&self, duration: Duration) {
-        let now = self.system.platform().now();
-        let end = now + duration.as_millis() as _;
+        let mut remaining = duration.as_millis() as u64;
+
+        while remaining > 0 {
+            let (generation, state, position, seek_target) = {
+                let playback = self.playback.lock().unwrap();
+                (playback.generation, playback.state, playback.position, playback.seek_target)
+            };
+
+            // stopped playback runs to the end without waiting
+            if generation != self.generation || state == PlaybackState::Stopped {
+                return;
+            }
 
-        self.system.clone().sleep(end).await
+            if state == PlaybackState::Paused {
+                let until = self.system.platform().now() + PAUSE_POLL_MILLIS;
+                self.system.clone().sleep(until).await;
+
+                continue;
+            }
+
+            let step = if position < seek_target {
+                // fast forwarding
+                remaining.min(seek_target - position)
+            } else {
+                let step = remaining.min(MAX_SLEEP_MILLIS);
+
+                let until = self.system.platform().now() + step;
+                self.system.clone().sleep(until).await;
+
+                step
+            };
+
+            self.playback.lock().unwrap().position += step;
+            remaining -= step;
+        }
     }
 
     fn now_millis(&self) -> u64 {
-        self.system.platform().now().raw()
+        // media clock, so that fast forwarding is seen as time passing
+        self.playback.lock().unwrap().position
     }
 }
 
-pub type AudioHandle = u32;
-#[derive(Debug)]
-pub enum AudioError {
-    InvalidHandle,
-    InvalidAudio,
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum AudioFormat {
+    Smaf,
+    Midi,
+    Tone,
+    Wave,
+}
+
+impl AudioFormat {
+    fn detect(data: &[u8]) -> Option<Self> {
+        let is_wav = data.starts_with(b"RIFF") && data.get(8..12) == Some(b"WAVE");
+        let is_mp4 = data.get(4..8) == Some(b"ftyp");
+        // mp3 frame or aac adts header
+        let is_mpeg = data.starts_with(b"ID3") || (data.len() >= 2 && data[0] == 0xff && data[1] & 0xe0 == 0xe0);
+
+        if data.starts_with(b"MMMD") {
+            Some(Self::Smaf)
+        } else if data.starts_with(b"MThd") {
+            Some(Self::Midi)
+        } else if is_tone_sequence(data) {
+            Some(Self::Tone)
+        } else if is_wav || is_mp4 || is_mpeg {
+            Some(Self::Wave)
+        } else {
+            None
+        }
+    }
+
+    // mime types, and short names used by wipi clips
+    fn from_content_type(content_type: &str) -> Option<Self> {
+        let content_type = content_type.split(';').next().unwrap().trim().to_ascii_lowercase();
+
+        match content_type.as_str() {
+            "application/vnd.smaf" | "application/x-smaf" | "audio/smaf" | "audio/mmf" | "audio/x-mmf" | "mmf" | "smaf" | "ma2" | "ma3" | "ma5" => {
+                Some(Self::Smaf)
+            }
+            "audio/midi" | "audio/mid" | "audio/x-midi" | "audio/sp-midi" | "mid" | "midi" => Some(Self::Midi),
+            "audio/x-tone-seq" | "tone" => Some(Self::Tone),
+            "audio/wav" | "audio/x-wav" | "audio/mpeg" | "audio/mp3" | "audio/aac" | "audio/mp4" | "audio/x-m4a" | "wav" | "wave" | "mp3" | "aac"
+            | "m4a" => Some(Self::Wave),
+            _ => None,
+        }
+    }
 }
 
+#[derive(Clone)]
 enum AudioFile {
     Smaf(SmafPlayer),
+    Midi(Arc<MidiSequence>),
+    Wave(Arc<Wave>),
+}
+
+impl AudioFile {
+    async fn play(&self, backend: &PlaybackBackend) {
+        match self {
+            Self::Smaf(x) => x.play(backend).await,
+            Self::Midi(x) => x.play(backend).await,
+            Self::Wave(x) => {
+                let position = backend.skip_to_seek_target();
+
+                backend.play_wave(x.channels, x.sampling_rate, x.data_from(position));
+                backend.sleep(Duration::from_millis(x.duration().saturating_sub(position))).await;
+            }
+        }
+    }
+}
+
+struct AudioClip {
+    file: AudioFile,
+    playback: Arc<Mutex<Playback>>,
 }
 
 pub struct Audio {
     system: System,
-    backend: Arc<AudioBackendImpl>,
-    files: BTreeMap<AudioHandle, AudioFile>,
+    sink: Box<dyn AudioSink>,
+    mixer: Arc<Mutex<Mixer>>,
+    clips: BTreeMap<AudioHandle, AudioClip>,
     last_audio_handle: AudioHandle,
+    render_start: Option<Instant>,
+    rendered_frames: u64,
 }
 
 impl Audio {
     pub fn new(sink: Box<dyn AudioSink>, system: System) -> Self {
+        let mixer = Arc::new(Mutex::new(Mixer::new(sink.sample_rate())));
+
         Self {
-            system: system.clone(),
-            backend: Arc::new(AudioBackendImpl { sink, system }),
-            files: BTreeMap::new(),
+            system,
+            sink,
+            mixer,
+            clips: BTreeMap::new(),
             last_audio_handle: 0,
+            render_start: None,
+            rendered_frames: 0,
         }
     }
 
-    pub fn load_smaf(&mut self, data: &[u8]) -> Result<AudioHandle, AudioError> {
-        let audio_handle = self.last_audio_handle;
+    /// Writes mixed audio up to `now` plus output latency to sink.
+    pub fn render(&mut self, now: Instant) {
+        let sample_rate = self.sink.sample_rate() as u64;
 
-        self.last_audio_handle += 1;
-        self.files.insert(audio_handle, AudioFile::Smaf(SmafPlayer::new(data.to_vec())));
+        let render_start = match self.render_start {
+            Some(x) if now - x <= self.rendered_frames * 1000 / sample_rate + MAX_RENDER_MILLIS => x,
+            _ => {
+                // start over on first render or after a long stall
+                self.render_start = Some(now);
+                self.rendered_frames = 0;
+                now
+            }
+        };
+
+        let target_frames = (now - render_start + LATENCY_MILLIS) * sample_rate / 1000;
+        if target_frames <= self.rendered_frames {
+            return;
+        }
+
+        let mut samples = vec![0; ((target_frames - self.rendered_frames) * 2) as usize];
+        self.mixer.lock().unwrap().render(&mut samples);
+        self.sink.write(&samples);
+
+        self.rendered_frames = target_frames;
+    }
+
+    /// Loads audio in format detected from `data`, or from `content_type` if it can't be detected.
+    pub fn load(&mut self, data: &[u8], content_type: Option<&str>) -> Result<AudioHandle, AudioError> {
+        let format = AudioFormat::detect(data)
+            .or_else(|| content_type.and_then(AudioFormat::from_content_type))
+            .ok_or(AudioError::InvalidAudio)?;
+
+        let file = match format {
+            AudioFormat::Smaf => AudioFile::Smaf(SmafPlayer::new(data.to_vec())),
+            AudioFormat::Midi => AudioFile::Midi(Arc::new(MidiSequence::from_smf(data).ok_or(AudioError::InvalidAudio)?)),
+            AudioFormat::Tone => AudioFile::Midi(Arc::new(parse_tone_sequence(data).ok_or(AudioError::InvalidAudio)?)),
+            AudioFormat::Wave => AudioFile::Wave(Arc::new(Wave::decode(data).ok_or(AudioError::InvalidAudio)?)),
+        };
+
+        Ok(self.insert(file))
+    }
+
+    /// Plays single `note` for `duration` milliseconds with `volume` in percent, without a handle.
+    pub fn play_tone(&mut self, note: u8, duration: u64, volume: u8) {
+        let mut playback = Playback::new();
+        playback.state = PlaybackState::Playing;
+
+        let sequence = tone(note, duration, volume);
+        self.spawn_playback(AudioFile::Midi(Arc::new(sequence)), Arc::new(Mutex::new(playback)));
+    }
+
+    /// Releases `audio_handle`, stopping its playback.
+    pub fn close(&mut self, audio_handle: AudioHandle) -> Result<(), AudioError> {
+        self.stop(audio_handle)?;
+        self.clips.remove(&audio_handle);
+
+        Ok(())
+    }
+
+    /// Starts playback from current position, playing `loop_count` times or forever if negative.
+    /// Playing clip is restarted.
+    pub fn play(&mut self, audio_handle: AudioHandle, loop_count: i32) -> Result<(), AudioError> {
+        let clip = self.clips.get(&audio_handle).ok_or(AudioError::InvalidHandle)?;
+
+        {
+            let mut playback = clip.playback.lock().unwrap();
+            let start_position = if playback.state == PlaybackState::Stopped {
+                playback.position.max(playback.seek_target)
+            } else {
+                playback.silence(&mut self.mixer.lock().unwrap());
+                playback.push_event(AudioEvent::Stopped);
+
+                0
+            };
+
+            playback.position = 0;
+            playback.seek_target = start_position;
+            playback.loop_count = if loop_count == 0 { 1 } else { loop_count };
+            playback.state = PlaybackState::Playing;
+            playback.push_event(AudioEvent::Started);
+        }
 
-        Ok(audio_handle)
+        self.start(audio_handle)
     }
 
-    pub fn play(&self, audio_handle: AudioHandle) -> Result<(), AudioError> {
-        match self.files.get(&audio_handle) {
-            Some(AudioFile::Smaf(player)) => {
-                let player_clone = player.clone();
-                let backend = self.backend.clone();
+    pub fn stop(&mut self, audio_handle: AudioHandle) -> Result<(), AudioError> {
+        let clip = self.clips.get(&audio_handle).ok_or(AudioError::InvalidHandle)?;
+        let mut playback = clip.playback.lock().unwrap();
 
-                self.system.clone().spawn(move || async move { player_clone.play(&*backend).await });
+        if playback.state != PlaybackState::Stopped {
+            playback.generation += 1;
+            playback.state = PlaybackState::Stopped;
+            playback.silence(&mut self.mixer.lock().unwrap());
+            playback.push_event(AudioEvent::Stopped);
+        }
+
+        Ok(())
+    }
+
+    pub fn pause(&mut self, audio_handle: AudioHandle) -> Result<(), AudioError> {
+        let clip = self.clips.get(&audio_handle).ok_or(AudioError::InvalidHandle)?;
+        let mut playback = clip.playback.lock().unwrap();
+
+        if playback.state == PlaybackState::Playing {
+            let mut mixer = self.mixer.lock().unwrap();
+
+            for (channel_id, note) in core::mem::take(&mut playback.notes) {
+                mixer.synthesizer().note_off(channel_id, note);
+            }
+            for &channel in &playback.mixer_channels {
+                mixer.pause(channel);
+            }
+
+            playback.state = PlaybackState::Paused;
+            playback.push_event(AudioEvent::Paused);
+        }
+
+        Ok(())
+    }
+
+    pub fn resume(&mut self, audio_handle: AudioHandle) -> Result<(), AudioError> {
+        let clip = self.clips.get(&audio_handle).ok_or(AudioError::InvalidHandle)?;
+        let mut playback = clip.playback.lock().unwrap();
+
+        if playback.state == PlaybackState::Paused {
+            let mut mixer = self.mixer.lock().unwrap();
+            for &channel in &playback.mixer_channels {
+                mixer.resume(channel);
             }
-            None => return Err(AudioError::InvalidHandle),
+
+            playback.state = PlaybackState::Playing;
+            playback.push_event(AudioEvent::Started);
+        }
+
+        Ok(())
+    }
+
+    pub fn state(&self, audio_handle: AudioHandle) -> Result<PlaybackState, AudioError> {
+        Ok(self.playback(audio_handle)?.state)
+    }
+
+    /// Volume in percent.
+    pub fn volume(&self, audio_handle: AudioHandle) -> Result<u8, AudioError> {
+        Ok(self.playback(audio_handle)?.volume)
+    }
+
+    pub fn set_volume(&mut self, audio_handle: AudioHandle, volume: u8) -> Result<(), AudioError> {
+        let clip = self.clips.get(&audio_handle).ok_or(AudioError::InvalidHandle)?;
+        let mut playback = clip.playback.lock().unwrap();
+
+        playback.volume = volume.min(100);
+
+        let mut mixer = self.mixer.lock().unwrap();
+        for &channel in &playback.mixer_channels {
+            mixer.set_volume(channel, playback.volume);
+        }
+        playback.push_event(AudioEvent::VolumeChanged);
+
+        Ok(())
+    }
+
+    /// Length of media in milliseconds, if known.
+    pub fn duration(&self, audio_handle: AudioHandle) -> Result<Option<u64>, AudioError> {
+        let clip = self.clips.get(&audio_handle).ok_or(AudioError::InvalidHandle)?;
+
+        Ok(match &clip.file {
+            AudioFile::Smaf(_) => None,
+            AudioFile::Midi(x) => Some(x.duration()),
+            AudioFile::Wave(x) => Some(x.duration()),
+        })
+    }
+
+    /// Media time in milliseconds.
+    pub fn position(&self, audio_handle: AudioHandle) -> Result<u64, AudioError> {
+        Ok(self.playback(audio_handle)?.position)
+    }
+
+    /// Moves media time to `position` milliseconds. Playing clip continues from there.
+    pub fn set_position(&mut self, audio_handle: AudioHandle, position: u64) -> Result<(), AudioError> {
+        let clip = self.clips.get(&audio_handle).ok_or(AudioError::InvalidHandle)?;
+
+        let restart = {
+            let mut playback = clip.playback.lock().unwrap();
+
+            // media can only be played forward, so we replay from the start up to position without sound
+            playback.generation += 1;
+            playback.silence(&mut self.mixer.lock().unwrap());
+            playback.position = 0;
+            playback.seek_target = position;
+
+            playback.state != PlaybackState::Stopped
+        };
+
+        if restart {
+            self.start(audio_handle)?;
         }
 
         Ok(())
     }
+
+    /// Serial of last event of `audio_handle`, to wait for events after it.
+    pub fn last_event_serial(&self, audio_handle: AudioHandle) -> Result<u64, AudioError> {
+        Ok(self.playback(audio_handle)?.last_event_serial)
+    }
+
+    /// First event after `serial`, with its serial.
+    pub fn event_after(&self, audio_handle: AudioHandle, serial: u64) -> Result<Option<(u64, AudioEvent)>, AudioError> {
+        Ok(self.playback(audio_handle)?.events.iter().find(|x| x.0 > serial).copied())
+    }
+
+    fn insert(&mut self, file: AudioFile) -> AudioHandle {
+        // handle 0 is left unused, as callers use it for no audio
+        self.last_audio_handle += 1;
+
+        self.clips.insert(
+            self.last_audio_handle,
+            AudioClip {
+                file,
+                playback: Arc::new(Mutex::new(Playback::new())),
+            },
+        );
+
+        self.last_audio_handle
+    }
+
+    fn playback(&self, audio_handle: AudioHandle) -> Result<MutexGuard<'_, Playback>, AudioError> {
+        let clip = self.clips.get(&audio_handle).ok_or(AudioError::InvalidHandle)?;
+
+        Ok(clip.playback.lock().unwrap())
+    }
+
+    fn start(&mut self, audio_handle: AudioHandle) -> Result<(), AudioError> {
+        let clip = self.clips.get(&audio_handle).ok_or(AudioError::InvalidHandle)?;
+
+        self.spawn_playback(clip.file.clone(), clip.playback.clone());
+
+        Ok(())
+    }
+
+    // spawns task playing new generation of playback
+    fn spawn_playback(&self, file: AudioFile, playback: Arc<Mutex<Playback>>) {
+        let generation = {
+            let mut playback = playback.lock().unwrap();
+            playback.generation += 1;
+
+            playback.generation
+        };
+
+        let backend = PlaybackBackend {
+            system: self.system.clone(),
+            mixer: self.mixer.clone(),
+            playback,
+            generation,
+        };
+
+        self.system.clone().spawn(move || async move {
+            loop {
+                file.play(&backend).await;
+
+                if !backend.finish_loop() {
+                    break;
+                }
+            }
+        });
+    }
 }
@@ -0,0 +1,254 @@
+use core::time::Duration;
+
+use smaf_player::AudioBackend;
+
+// 120 bpm, midi default
+const DEFAULT_TEMPO: u64 = 500000;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MidiEvent {
+    NoteOn { channel: u8, note: u8, velocity: u8 },
+    NoteOff { channel: u8, note: u8 },
+    ProgramChange { channel: u8, program: u8 },
+    ControlChange { channel: u8, control: u8, value: u8 },
+}
+
+enum TrackEvent {
+    Midi(MidiEvent),
+    Tempo(u64),
+    End,
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn bytes(&mut self, length: usize) -> Option<&'a [u8]> {
+        let result = self.data.get(self.offset..self.offset + length)?;
+        self.offset += length;
+
+        Some(result)
+    }
+
+    fn u8(&mut self) -> Option<u8> {
+        Some(self.bytes(1)?[0])
+    }
+
+    fn u16(&mut self) -> Option<u16> {
+        Some(u16::from_be_bytes(self.bytes(2)?.try_into().unwrap()))
+    }
+
+    fn u32(&mut self) -> Option<u32> {
+        Some(u32::from_be_bytes(self.bytes(4)?.try_into().unwrap()))
+    }
+
+    fn variable_length(&mut self) -> Option<u32> {
+        let mut result = 0;
+        for _ in 0..4 {
+            let byte = self.u8()?;
+            result = (result << 7) | (byte & 0x7f) as u32;
+
+            if byte & 0x80 == 0 {
+                return Some(result);
+            }
+        }
+
+        None
+    }
+}
+
+// returns events with absolute ticks
+fn parse_track(data: &[u8]) -> Option<Vec<(u64, TrackEvent)>> {
+    let mut reader = Reader { data, offset: 0 };
+    let mut result = Vec::new();
+    let mut tick = 0u64;
+    let mut running_status = 0;
+
+    while reader.offset < data.len() {
+        tick += reader.variable_length()? as u64;
+
+        let mut status = reader.u8()?;
+        if status < 0x80 {
+            // running status, first byte is data
+            reader.offset -= 1;
+            status = running_status;
+        }
+
+        match status {
+            0x80..=0xef => {
+                running_status = status;
+
+                let channel = status & 0x0f;
+                let param1 = reader.u8()? & 0x7f;
+                let param2 = if matches!(status & 0xf0, 0xc0 | 0xd0) { 0 } else { reader.u8()? & 0x7f };
+
+                let event = match status & 0xf0 {
+                    0x90 if param2 != 0 => MidiEvent::NoteOn {
+                        channel,
+                        note: param1,
+                        velocity: param2,
+                    },
+                    0x80 | 0x90 => MidiEvent::NoteOff { channel, note: param1 },
+                    0xb0 => MidiEvent::ControlChange {
+                        channel,
+                        control: param1,
+                        value: param2,
+                    },
+                    0xc0 => MidiEvent::ProgramChange { channel, program: param1 },
+                    _ => continue,
+                };
+                result.push((tick, TrackEvent::Midi(event)));
+            }
+            0xf0 | 0xf7 => {
+                let length = reader.variable_length()?;
+                reader.bytes(length as _)?;
+            }
+            0xff => {
+                let r#type = reader.u8()?;
+                let length = reader.variable_length()?;
+                let data = reader.bytes(length as _)?;
+
+                match r#type {
+                    0x51 if length == 3 => result.push((tick, TrackEvent::Tempo(u32::from_be_bytes([0, data[0], data[1], data[2]]) as u64))),
+                    0x2f => break,
+                    _ => {}
+                }
+            }
+            _ => return None,
+        }
+    }
+
+    // end of track may be later than last event
+    result.push((tick, TrackEvent::End));
+
+    Some(result)
+}
+
+/// Midi events with their time in milliseconds, played through `AudioBackend`.
+pub struct MidiSequence {
+    events: Vec<(u64, MidiEvent)>,
+    duration: u64,
+}
+
+impl MidiSequence {
+    pub fn new(events: Vec<(u64, MidiEvent)>, duration: u64) -> Self {
+        Self { events, duration }
+    }
+
+    /// Parses standard midi file, merging all tracks.
+    pub fn from_smf(data: &[u8]) -> Option<Self> {
+        let mut reader = Reader { data, offset: 0 };
+        if reader.bytes(4)? != b"MThd" {
+            return None;
+        }
+        let header_length = reader.u32()? as usize;
+        let mut header = Reader {
+            data: reader.bytes(header_length)?,
+            offset: 0,
+        };
+        let (_format, track_count, division) = (header.u16()?, header.u16()?, header.u16()?);
+
+        // (tick, track, event), with track index to keep order of simultaneous events stable
+        let mut track_events = Vec::new();
+        for i in 0..track_count as usize {
+            let (id, length) = (reader.bytes(4)?, reader.u32()? as usize);
+            let chunk = reader.bytes(length)?;
+            if id != b"MTrk" {
+                continue;
+            }
+
+            track_events.extend(parse_track(chunk)?.into_iter().map(|(tick, event)| (tick, i, event)));
+        }
+        track_events.sort_by_key(|x| (x.0, x.1));
+
+        let mut events = Vec::new();
+        let mut tempo = DEFAULT_TEMPO;
+        let (mut last_tick, mut last_micros) = (0u64, 0u64);
+
+        for (tick, _, event) in track_events {
+            let micros = if division & 0x8000 == 0 {
+                last_micros + (tick - last_tick) * tempo / division.max(1) as u64
+            } else {
+                // smpte timing, negative frames per second and ticks per frame
+                let (fps, subframes) = (-((division >> 8) as i8) as u64, (division & 0xff) as u64);
+
+                tick * 1000000 / (fps * subframes).max(1)
+            };
+            (last_tick, last_micros) = (tick, micros);
+
+            match event {
+                TrackEvent::Midi(x) => events.push((micros / 1000, x)),
+                TrackEvent::Tempo(x) => tempo = x,
+                TrackEvent::End => {}
+            }
+        }
+
+        Some(Self::new(events, last_micros / 1000))
+    }
+
+    pub fn duration(&self) -> u64 {
+        self.duration
+    }
+
+    pub async fn play(&self, backend: &dyn AudioBackend) {
+        let mut now = 0;
+
+        for &(time, event) in &self.events {
+            if time > now {
+                backend.sleep(Duration::from_millis(time - now)).await;
+                now = time;
+            }
+
+            match event {
+                MidiEvent::NoteOn { channel, note, velocity } => backend.midi_note_on(channel, note, velocity),
+                MidiEvent::NoteOff { channel, note } => backend.midi_note_off(channel, note, 0),
+                MidiEvent::ProgramChange { channel, program } => backend.midi_program_change(channel, program),
+                MidiEvent::ControlChange { channel, control, value } => backend.midi_control_change(channel, control, value),
+            }
+        }
+
+        if self.duration > now {
+            backend.sleep(Duration::from_millis(self.duration - now)).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MidiEvent, MidiSequence};
+
+    #[test]
+    fn test_smf() {
+        #[rustfmt::skip]
+        let data = [
+            b'M', b'T', b'h', b'd', 0, 0, 0, 6, 0, 0, 0, 1, 0, 96,
+            b'M', b'T', b'r', b'k', 0, 0, 0, 21,
+            0x00, 0xff, 0x51, 0x03, 0x0f, 0x42, 0x40, // tempo 1000000us per beat
+            0x00, 0xc0, 0x05, // program change
+            0x00, 0x90, 0x3c, 0x64, // note on
+            0x60, 0x3c, 0x00, // running status note on with zero velocity
+            0x30, 0xff, 0x2f, 0x00, // end of track
+        ];
+
+        let sequence = MidiSequence::from_smf(&data).unwrap();
+
+        assert_eq!(sequence.duration(), 1500);
+        assert_eq!(
+            sequence.events,
+            [
+                (0, MidiEvent::ProgramChange { channel: 0, program: 5 }),
+                (
+                    0,
+                    MidiEvent::NoteOn {
+                        channel: 0,
+                        note: 0x3c,
+                        velocity: 0x64
+                    }
+                ),
+                (1000, MidiEvent::NoteOff { channel: 0, note: 0x3c }),
+            ]
+        );
+    }
+}
@@ -0,0 +1,204 @@
+use super::midi::{MidiEvent, MidiSequence};
+
+// MIDP ToneControl sequence tokens
+const VERSION: i8 = -2;
+const TEMPO: i8 = -3;
+const RESOLUTION: i8 = -4;
+const BLOCK_START: i8 = -5;
+const BLOCK_END: i8 = -6;
+const PLAY_BLOCK: i8 = -7;
+const SET_VOLUME: i8 = -8;
+const REPEAT: i8 = -9;
+const SILENCE: i8 = -1;
+
+const DEFAULT_TEMPO: u64 = 30; // 120 bpm
+const DEFAULT_RESOLUTION: u64 = 64;
+const MAX_BLOCK_DEPTH: usize = 8;
+
+// square lead, the closest to a buzzer tone
+const TONE_PROGRAM: u8 = 80;
+// synthesizer is shared by all playbacks, so tones use channels music rarely uses.
+// Manager.playTone and tone sequences get separate channels, so one doesn't cut notes of the other
+const SEQUENCE_CHANNEL: u8 = 15;
+const TONE_CHANNEL: u8 = 14;
+
+/// Whether `data` starts with tone sequence header.
+pub fn is_tone_sequence(data: &[u8]) -> bool {
+    data.len() >= 2 && data[0] as i8 == VERSION && data[1] == 1
+}
+
+struct ToneParser<'a> {
+    data: &'a [i8],
+    blocks: [Option<usize>; 128],
+    tempo: u64,
+    resolution: u64,
+    volume: u8,
+    now: u64,
+    events: Vec<(u64, MidiEvent)>,
+}
+
+impl ToneParser<'_> {
+    fn duration(&self, duration: i8) -> u64 {
+        // tempo is in units of 4 bpm, duration in units of 1/resolution of a whole note, which is 4 beats
+        duration.max(0) as u64 * 60000 / (self.resolution * self.tempo)
+    }
+
+    fn note(&mut self, note: i8, duration: i8) {
+        let duration = self.duration(duration);
+
+        if note >= 0 {
+            let velocity = (self.volume as u32 * 127 / 100) as u8;
+            self.events.push((
+                self.now,
+                MidiEvent::NoteOn {
+                    channel: SEQUENCE_CHANNEL,
+                    note: note as u8,
+                    velocity,
+                },
+            ));
+            self.events.push((
+                self.now + duration,
+                MidiEvent::NoteOff {
+                    channel: SEQUENCE_CHANNEL,
+                    note: note as u8,
+                },
+            ));
+        }
+
+        self.now += duration;
+    }
+
+    // parses sequence events from `offset` until block end or end of data
+    fn parse_events(&mut self, mut offset: usize, depth: usize) -> Option<usize> {
+        while offset < self.data.len() {
+            let token = self.data[offset];
+            let arg = *self.data.get(offset + 1)?;
+            offset += 2;
+
+            match token {
+                BLOCK_START => {
+                    // block definitions are skipped until played
+                    let block = arg as usize;
+                    *self.blocks.get_mut(block)? = Some(offset);
+
+                    while !(self.data.get(offset)? == &BLOCK_END && *self.data.get(offset + 1)? as usize == block) {
+                        offset += 2;
+                    }
+                    offset += 2;
+                }
+                BLOCK_END => return Some(offset),
+                PLAY_BLOCK => {
+                    let block_offset = (*self.blocks.get(arg as usize)?)?;
+                    if depth >= MAX_BLOCK_DEPTH {
+                        return None;
+                    }
+
+                    self.parse_events(block_offset, depth + 1)?;
+                }
+                SET_VOLUME => self.volume = arg.clamp(0, 100) as u8,
+                REPEAT => {
+                    let (note, duration) = (*self.data.get(offset)?, *self.data.get(offset + 1)?);
+                    offset += 2;
+
+                    for _ in 0..arg.max(0) {
+                        self.note(note, duration);
+                    }
+                }
+                SILENCE => self.note(-1, arg),
+                0.. => self.note(token, arg),
+                _ => return None,
+            }
+        }
+
+        Some(offset)
+    }
+}
+
+/// Single note of `duration` milliseconds, as MIDP `Manager.playTone`.
+pub fn tone(note: u8, duration: u64, volume: u8) -> MidiSequence {
+    let velocity = (volume.min(100) as u32 * 127 / 100) as u8;
+
+    let events = vec![
+        (
+            0,
+            MidiEvent::ProgramChange {
+                channel: TONE_CHANNEL,
+                program: TONE_PROGRAM,
+            },
+        ),
+        (
+            0,
+            MidiEvent::NoteOn {
+                channel: TONE_CHANNEL,
+                note,
+                velocity,
+            },
+        ),
+        (duration, MidiEvent::NoteOff { channel: TONE_CHANNEL, note }),
+    ];
+
+    MidiSequence::new(events, duration)
+}
+
+/// Converts MIDP `ToneControl` tone sequence into midi events.
+pub fn parse_tone_sequence(data: &[u8]) -> Option<MidiSequence> {
+    if !is_tone_sequence(data) {
+        return None;
+    }
+
+    let data = bytemuck::cast_slice::<u8, i8>(data);
+    let mut parser = ToneParser {
+        data,
+        blocks: [None; 128],
+        tempo: DEFAULT_TEMPO,
+        resolution: DEFAULT_RESOLUTION,
+        volume: 100,
+        now: 0,
+        events: vec![(
+            0,
+            MidiEvent::ProgramChange {
+                channel: SEQUENCE_CHANNEL,
+                program: TONE_PROGRAM,
+            },
+        )],
+    };
+
+    let mut offset = 2;
+    while offset + 1 < data.len() {
+        match data[offset] {
+            TEMPO => parser.tempo = data[offset + 1].max(1) as u64,
+            RESOLUTION => parser.resolution = data[offset + 1].max(1) as u64,
+            _ => break,
+        }
+        offset += 2;
+    }
+
+    parser.parse_events(offset, 0)?;
+
+    let duration = parser.now;
+    parser.events.sort_by_key(|x| x.0);
+
+    Some(MidiSequence::new(parser.events, duration))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_tone_sequence;
+
+    #[test]
+    fn test_tone_sequence() {
+        #[rustfmt::skip]
+        let data = [
+            0xfe, 1, // version
+            0xfd, 15, // tempo 60 bpm
+            0xfb, 0, 60, 16, 0xff, 16, 0xfa, 0, // block 0, quarter note and quarter rest
+            0xf9, 0, // play block 0
+            0xf7, 2, 62, 32, // repeat half note twice
+        ];
+
+        let sequence = parse_tone_sequence(&data).unwrap();
+
+        assert_eq!(sequence.duration(), 1000 + 1000 + 2000 * 2);
+        assert!(parse_tone_sequence(&[0xfe, 1, 0xf9, 3]).is_none());
+    }
+}
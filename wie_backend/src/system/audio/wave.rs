@@ -0,0 +1,117 @@
+use std::io::Cursor;
+
+use symphonia::core::{
+    audio::SampleBuffer, codecs::DecoderOptions, errors::Error, formats::FormatOptions, io::MediaSourceStream, meta::MetadataOptions, probe::Hint,
+};
+
+/// Decoded interleaved pcm.
+pub struct Wave {
+    pub channels: u8,
+    pub sampling_rate: u32,
+    pub data: Vec<i16>,
+}
+
+impl Wave {
+    /// Decodes wav (pcm, adpcm), mp3 or aac into pcm.
+    pub fn decode(data: &[u8]) -> Option<Self> {
+        let stream = MediaSourceStream::new(Box::new(Cursor::new(data.to_vec())), Default::default());
+        let probed = symphonia::default::get_probe()
+            .format(&Hint::new(), stream, &FormatOptions::default(), &MetadataOptions::default())
+            .ok()?;
+        let mut format = probed.format;
+
+        let track = format.default_track()?;
+        let track_id = track.id;
+        let mut decoder = symphonia::default::get_codecs()
+            .make(&track.codec_params, &DecoderOptions::default())
+            .ok()?;
+
+        let mut channels = track.codec_params.channels.map(|x| x.count()).unwrap_or(0);
+        let mut sampling_rate = track.codec_params.sample_rate.unwrap_or(0);
+        let mut result = Vec::new();
+        loop {
+            let packet = match format.next_packet() {
+                Ok(x) => x,
+                Err(Error::IoError(_)) => break, // end of stream
+                Err(err) => {
+                    tracing::warn!("Audio demux error: {}", err);
+                    break;
+                }
+            };
+            if packet.track_id() != track_id {
+                continue;
+            }
+
+            let decoded = match decoder.decode(&packet) {
+                Ok(x) => x,
+                // corrupted packets are skipped
+                Err(Error::DecodeError(_)) => continue,
+                Err(err) => {
+                    tracing::warn!("Audio decode error: {}", err);
+                    break;
+                }
+            };
+
+            let spec = *decoded.spec();
+            (channels, sampling_rate) = (spec.channels.count(), spec.rate);
+
+            let mut buffer = SampleBuffer::<i16>::new(decoded.capacity() as _, spec);
+            buffer.copy_interleaved_ref(decoded);
+            result.extend_from_slice(buffer.samples());
+        }
+
+        if channels == 0 || sampling_rate == 0 || result.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            channels: channels as _,
+            sampling_rate,
+            data: result,
+        })
+    }
+
+    /// Length in milliseconds.
+    pub fn duration(&self) -> u64 {
+        (self.data.len() / self.channels as usize) as u64 * 1000 / self.sampling_rate as u64
+    }
+
+    /// Samples starting from `position` milliseconds.
+    pub fn data_from(&self, position: u64) -> &[i16] {
+        let frame = (position * self.sampling_rate as u64 / 1000) as usize;
+
+        self.data.get(frame * self.channels as usize..).unwrap_or(&[])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Wave;
+
+    #[test]
+    fn test_wav() {
+        let samples = (0..800).map(|x| (x * 40) as i16).collect::<Vec<_>>();
+
+        let mut data = Vec::new();
+        data.extend_from_slice(b"RIFF");
+        data.extend_from_slice(&(36 + samples.len() as u32 * 2).to_le_bytes());
+        data.extend_from_slice(b"WAVEfmt ");
+        data.extend_from_slice(&16u32.to_le_bytes());
+        data.extend_from_slice(&1u16.to_le_bytes()); // pcm
+        data.extend_from_slice(&1u16.to_le_bytes()); // mono
+        data.extend_from_slice(&8000u32.to_le_bytes());
+        data.extend_from_slice(&16000u32.to_le_bytes());
+        data.extend_from_slice(&2u16.to_le_bytes());
+        data.extend_from_slice(&16u16.to_le_bytes());
+        data.extend_from_slice(b"data");
+        data.extend_from_slice(&(samples.len() as u32 * 2).to_le_bytes());
+        data.extend(samples.iter().flat_map(|x| x.to_le_bytes()));
+
+        let wave = Wave::decode(&data).unwrap();
+
+        assert_eq!((wave.channels, wave.sampling_rate), (1, 8000));
+        assert_eq!(wave.data, samples);
+        assert_eq!(wave.duration(), 100);
+        assert_eq!(wave.data_from(50).len(), 400);
+    }
+}
@@ -0,0 +1,116 @@
+use alloc::{sync::Arc, vec, vec::Vec};
+use std::sync::Mutex;
+
+use crate::{device_effects::DeviceEffect, platform::Platform};
+
+const LED_COUNT: usize = 1;
+// handset vibrators are on/off motors, so weaker levels are pulsed within this period
+const VIBRATION_PULSE_MILLIS: u64 = 100;
+
+/// Vibrator, backlight and led state, forwarded to platform `DeviceEffects`.
+pub struct Device {
+    platform: Arc<Mutex<Box<dyn Platform>>>,
+    vibration_level_count: i32,
+    pointer_events: bool,
+    backlight: bool,
+    leds: [bool; LED_COUNT],
+    color_depth: Option<u32>,
+}
+
+impl Device {
+    pub fn new(platform: Arc<Mutex<Box<dyn Platform>>>) -> Self {
+        let profile = platform.lock().unwrap().device_profile();
+
+        Self {
+            platform,
+            vibration_level_count: profile.vibration_level_count.max(1),
+            pointer_events: profile.pointer_events,
+            backlight: true,
+            leds: [false; LED_COUNT],
+            color_depth: None,
+        }
+    }
+
+    /// Whether handset has touch screen, from device profile.
+    pub fn has_pointer_events(&self) -> bool {
+        self.pointer_events
+    }
+
+    /// Number of vibration levels, reported to application as handset property.
+    pub fn vibration_level_count(&self) -> i32 {
+        self.vibration_level_count
+    }
+
+    /// Vibrates for `duration` milliseconds at `level`, from 1 to `vibration_level_count`. Level 0 stops vibration.
+    pub fn vibrate(&mut self, level: i32, duration: u64) {
+        let effect = if level <= 0 || duration == 0 {
+            DeviceEffect::StopVibration
+        } else {
+            let level = level.min(self.vibration_level_count);
+
+            DeviceEffect::Vibrate {
+                pattern: self.vibration_pattern(level),
+                duration,
+                intensity: (level * 100 / self.vibration_level_count) as _,
+            }
+        };
+
+        self.apply(effect);
+    }
+
+    pub fn stop_vibration(&mut self) {
+        self.apply(DeviceEffect::StopVibration);
+    }
+
+    pub fn backlight(&self) -> bool {
+        self.backlight
+    }
+
+    pub fn set_backlight(&mut self, on: bool, timeout: Option<u64>) {
+        // timeout is left to platform, backlight is reported as last requested
+        self.backlight = on;
+
+        self.apply(DeviceEffect::Backlight { on, timeout });
+    }
+
+    pub fn led_count(&self) -> usize {
+        LED_COUNT
+    }
+
+    pub fn led(&self, index: usize) -> Option<bool> {
+        self.leds.get(index).copied()
+    }
+
+    pub fn set_led(&mut self, index: usize, on: bool) -> bool {
+        let Some(led) = self.leds.get_mut(index) else {
+            return false;
+        };
+        *led = on;
+
+        self.apply(DeviceEffect::Led { index: index as _, on });
+
+        true
+    }
+
+    /// Bits per pixel screen output is quantized to, `None` for full color.
+    pub fn color_depth(&self) -> Option<u32> {
+        self.color_depth
+    }
+
+    pub fn set_color_depth(&mut self, color_depth: Option<u32>) {
+        self.color_depth = color_depth;
+    }
+
+    // on time grows with level, top level is continuous
+    fn vibration_pattern(&self, level: i32) -> Vec<u64> {
+        let on = VIBRATION_PULSE_MILLIS * level as u64 / self.vibration_level_count as u64;
+
+        vec![on, VIBRATION_PULSE_MILLIS - on]
+    }
+
+    fn apply(&self, effect: DeviceEffect) {
+        tracing::debug!("Device effect {:?}", effect);
+
+        self.platform.lock().unwrap().device_effects().apply(effect);
+    }
+}
@@ -1,6 +1,9 @@
 use alloc::collections::VecDeque;
 
+use crate::time::Instant;
+
 #[allow(clippy::upper_case_acronyms)]
+#[allow(non_camel_case_types)]
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 pub enum KeyCode {
     UP,
@@ -22,12 +25,25 @@ pub enum KeyCode {
     HASH,
     STAR,
     CLEAR,
+
+    LEFT_SOFT,
+    RIGHT_SOFT,
+    SEND,
+    END,
+    VOLUME_UP,
+    VOLUME_DOWN,
+    CAMERA,
+
+    GAME_A,
+    GAME_B,
+    GAME_C,
+    GAME_D,
 }
 
 impl KeyCode {
-    // TODO we can use libraries like strum
-    pub fn parse(string: &str) -> KeyCode {
-        match string {
+    /// Parses key name, as used in key mapping configurations.
+    pub fn parse(string: &str) -> Option<KeyCode> {
+        Some(match string {
             "UP" => KeyCode::UP,
             "DOWN" => KeyCode::DOWN,
             "LEFT" => KeyCode::LEFT,
@@ -46,8 +62,19 @@ impl KeyCode {
             "#" => KeyCode::HASH,
             "*" => KeyCode::STAR,
             "CLR" => KeyCode::CLEAR,
-            _ => unimplemented!("Unknown key: {}", string),
-        }
+            "LSK" => KeyCode::LEFT_SOFT,
+            "RSK" => KeyCode::RIGHT_SOFT,
+            "SEND" => KeyCode::SEND,
+            "END" => KeyCode::END,
+            "VOLUP" => KeyCode::VOLUME_UP,
+            "VOLDOWN" => KeyCode::VOLUME_DOWN,
+            "CAMERA" => KeyCode::CAMERA,
+            "A" => KeyCode::GAME_A,
+            "B" => KeyCode::GAME_B,
+            "C" => KeyCode::GAME_C,
+            "D" => KeyCode::GAME_D,
+            _ => return None,
+        })
     }
 }
 
@@ -57,16 +84,51 @@ pub enum Event {
     Keydown(KeyCode),
     Keyup(KeyCode),
     Keyrepeat(KeyCode),
+    /// Key held down for `LONG_PRESS_MILLIS`, sent once per press.
+    Keylongpress(KeyCode),
+    /// Pointer events, with x and y in screen pixels.
+    PointerDown(i32, i32),
+    PointerMove(i32, i32),
+    PointerUp(i32, i32),
+    /// Text typed on host keyboard or committed by host input method, inserted into focused text field as is.
+    TextInput(String),
+}
+
+const DEFAULT_KEY_REPEAT_DELAY: u64 = 300;
+const DEFAULT_KEY_REPEAT_INTERVAL: u64 = 100;
+const LONG_PRESS_MILLIS: u64 = 1000;
+
+struct PressedKey {
+    key: KeyCode,
+    pressed_at: Instant,
+    next_repeat: Instant,
+    long_pressed: bool,
 }
 
-#[derive(Default)]
 pub struct EventQueue {
     events: VecDeque<Event>,
+    pressed_keys: Vec<PressedKey>,
+    // keys pressed since last `take_key_states`, so short presses between polls aren't missed
+    latched_keys: Vec<KeyCode>,
+    key_repeat_delay: u64,
+    key_repeat_interval: u64,
+}
+
+impl Default for EventQueue {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl EventQueue {
     pub fn new() -> Self {
-        Self { events: VecDeque::new() }
+        Self {
+            events: VecDeque::new(),
+            pressed_keys: Vec::new(),
+            latched_keys: Vec::new(),
+            key_repeat_delay: DEFAULT_KEY_REPEAT_DELAY,
+            key_repeat_interval: DEFAULT_KEY_REPEAT_INTERVAL,
+        }
     }
 
     pub fn push(&mut self, event: Event) {
@@ -76,4 +138,147 @@ impl EventQueue {
     pub fn pop(&mut self) -> Option<Event> {
         self.events.pop_front()
     }
+
+    /// Queues raw key press. Repeat and long press events are generated by `update` while the key is held.
+    pub fn key_down(&mut self, key: KeyCode, now: Instant) {
+        if self.is_key_pressed(key) {
+            return;
+        }
+
+        self.pressed_keys.push(PressedKey {
+            key,
+            pressed_at: now,
+            next_repeat: now + self.key_repeat_delay,
+            long_pressed: false,
+        });
+        if !self.latched_keys.contains(&key) {
+            self.latched_keys.push(key);
+        }
+        self.push(Event::Keydown(key));
+    }
+
+    pub fn key_up(&mut self, key: KeyCode) {
+        if !self.is_key_pressed(key) {
+            return;
+        }
+
+        self.pressed_keys.retain(|x| x.key != key);
+        self.push(Event::Keyup(key));
+    }
+
+    pub fn is_key_pressed(&self, key: KeyCode) -> bool {
+        self.pressed_keys.iter().any(|x| x.key == key)
+    }
+
+    /// Keys currently held down, in the order they were pressed.
+    pub fn pressed_keys(&self) -> impl Iterator<Item = KeyCode> + '_ {
+        self.pressed_keys.iter().map(|x| x.key)
+    }
+
+    /// Keys held down or pressed at least once since last call, as MIDP `GameCanvas.getKeyStates`.
+    pub fn take_key_states(&mut self) -> Vec<KeyCode> {
+        let mut result = core::mem::take(&mut self.latched_keys);
+        for key in self.pressed_keys() {
+            if !result.contains(&key) {
+                result.push(key);
+            }
+        }
+
+        result
+    }
+
+    /// Sets milliseconds before first key repeat and between repeats. Zero `interval` disables key repeat.
+    pub fn set_key_repeat(&mut self, delay: u64, interval: u64) {
+        self.key_repeat_delay = delay;
+        self.key_repeat_interval = interval;
+    }
+
+    /// Generates repeat and long press events for held keys.
+    pub fn update(&mut self, now: Instant) {
+        for pressed in &mut self.pressed_keys {
+            if self.key_repeat_interval != 0 && now >= pressed.next_repeat {
+                // repeats aren't piled up if application is slow to handle them
+                let pending = self.events.iter().any(|x| matches!(x, Event::Keyrepeat(key) if *key == pressed.key));
+                if !pending {
+                    self.events.push_back(Event::Keyrepeat(pressed.key));
+                }
+
+                pressed.next_repeat = now + self.key_repeat_interval;
+            }
+
+            if !pressed.long_pressed && now >= pressed.pressed_at + LONG_PRESS_MILLIS {
+                self.events.push_back(Event::Keylongpress(pressed.key));
+                pressed.long_pressed = true;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Instant;
+
+    use super::{Event, EventQueue, KeyCode};
+
+    fn drain(queue: &mut EventQueue) -> Vec<String> {
+        core::iter::from_fn(|| queue.pop()).map(|x| format!("{:?}", x)).collect()
+    }
+
+    #[test]
+    fn test_key_repeat() {
+        let mut queue = EventQueue::new();
+        queue.set_key_repeat(200, 100);
+
+        queue.key_down(KeyCode::UP, Instant::from_epoch_millis(0));
+        queue.key_down(KeyCode::UP, Instant::from_epoch_millis(10));
+        queue.key_down(KeyCode::OK, Instant::from_epoch_millis(50));
+        assert_eq!(queue.pressed_keys().collect::<Vec<_>>(), [KeyCode::UP, KeyCode::OK]);
+
+        queue.update(Instant::from_epoch_millis(100));
+        assert_eq!(drain(&mut queue), ["Keydown(UP)", "Keydown(OK)"]);
+
+        queue.update(Instant::from_epoch_millis(200));
+        assert_eq!(drain(&mut queue), ["Keyrepeat(UP)"]);
+        queue.update(Instant::from_epoch_millis(300));
+        assert_eq!(drain(&mut queue), ["Keyrepeat(UP)", "Keyrepeat(OK)"]);
+
+        // undrained repeats are coalesced
+        queue.update(Instant::from_epoch_millis(400));
+        queue.update(Instant::from_epoch_millis(500));
+        assert_eq!(drain(&mut queue), ["Keyrepeat(UP)", "Keyrepeat(OK)"]);
+
+        queue.key_up(KeyCode::OK);
+        queue.update(Instant::from_epoch_millis(1000));
+        assert_eq!(drain(&mut queue), ["Keyup(OK)", "Keyrepeat(UP)", "Keylongpress(UP)"]);
+        queue.update(Instant::from_epoch_millis(1100));
+        assert_eq!(drain(&mut queue), ["Keyrepeat(UP)"]);
+        assert!(!queue.is_key_pressed(KeyCode::OK));
+
+        queue.push(Event::Redraw);
+        queue.key_up(KeyCode::UP);
+        queue.key_up(KeyCode::UP);
+        assert_eq!(drain(&mut queue), ["Redraw", "Keyup(UP)"]);
+    }
+
+    #[test]
+    fn test_key_states() {
+        let mut queue = EventQueue::new();
+
+        queue.key_down(KeyCode::UP, Instant::from_epoch_millis(0));
+        queue.key_down(KeyCode::OK, Instant::from_epoch_millis(0));
+        queue.key_up(KeyCode::OK);
+        assert_eq!(queue.take_key_states(), [KeyCode::UP, KeyCode::OK]);
+
+        // released key is reported once, held key until released
+        assert_eq!(queue.take_key_states(), [KeyCode::UP]);
+        queue.key_up(KeyCode::UP);
+        assert_eq!(queue.take_key_states(), []);
+    }
+
+    #[test]
+    fn test_key_code_parse() {
+        assert_eq!(KeyCode::parse("LSK"), Some(KeyCode::LEFT_SOFT));
+        assert_eq!(KeyCode::parse("#"), Some(KeyCode::HASH));
+        assert_eq!(KeyCode::parse("POWER"), None);
+    }
 }
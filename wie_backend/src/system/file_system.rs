@@ -1,21 +1,42 @@
-use alloc::string::String;
+use alloc::{boxed::Box, string::String};
 use core::cmp::min;
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use crate::file_storage::FileStorage;
 
 #[derive(Default)]
 pub struct Filesystem {
     virtual_files: HashMap<String, Vec<u8>>,
+    directories: HashSet<String>,
+    storage: Option<Box<dyn FileStorage>>,
+    // files written, truncated, removed or renamed since last flush
+    changed: BTreeSet<String>,
 }
 
 impl Filesystem {
     pub fn new() -> Self {
         Self {
             virtual_files: HashMap::new(),
+            directories: HashSet::new(),
+            storage: None,
+            changed: BTreeSet::new(),
         }
     }
 
+    /// Filesystem starting with files kept in `storage`, where changes are saved on `flush`.
+    pub fn with_storage(storage: Box<dyn FileStorage>) -> Self {
+        let mut result = Self::new();
+        for (path, data) in storage.files() {
+            result.virtual_files.insert(path, data);
+        }
+        result.storage = Some(storage);
+
+        result
+    }
+
+    /// Adds a file of application package. Files restored from storage are kept, as they're newer.
     pub fn add(&mut self, path: &str, data: Vec<u8>) {
-        self.virtual_files.insert(path.to_string(), data);
+        self.virtual_files.entry(path.to_string()).or_insert(data);
     }
 
     pub fn exists(&self, path: &str) -> bool {
@@ -38,8 +59,8 @@ impl Filesystem {
         let normalized_path = Self::normalize_path(path);
 
         if let Some(data) = self.virtual_files.get(normalized_path) {
-            let data_size = data.len();
-            let size_to_read = min(count, data_size - offset);
+            let offset = min(offset, data.len());
+            let size_to_read = min(count, data.len() - offset);
 
             buf[..size_to_read].copy_from_slice(&data[offset..offset + size_to_read]);
 
@@ -52,11 +73,13 @@ impl Filesystem {
     pub fn write(&mut self, path: &str, offset: usize, data: &[u8]) -> usize {
         let normalized_path = Self::normalize_path(path);
 
-        let file = self.virtual_files.get_mut(normalized_path).unwrap();
+        // file could be removed while it's open
+        let file = self.virtual_files.entry(normalized_path.to_string()).or_default();
         if file.len() < offset + data.len() {
             file.resize(offset + data.len(), 0);
         }
         file[offset..offset + data.len()].copy_from_slice(data);
+        self.changed.insert(normalized_path.to_string());
 
         data.len()
     }
@@ -66,14 +89,234 @@ impl Filesystem {
 
         if let Some(data) = self.virtual_files.get_mut(normalized_path) {
             data.resize(len, 0);
+            self.changed.insert(normalized_path.to_string());
+        }
+    }
+
+    /// Removes a file or an empty directory. Returns false if nothing was removed.
+    pub fn remove(&mut self, path: &str) -> bool {
+        let normalized_path = Self::normalize_path(path);
+
+        if self.virtual_files.remove(normalized_path).is_some() {
+            self.changed.insert(normalized_path.to_string());
+
+            return true;
+        }
+
+        let directory = normalized_path.trim_end_matches('/');
+        if self.directories.contains(directory) && self.list(directory).is_empty() {
+            self.directories.remove(directory);
+
+            return true;
+        }
+
+        false
+    }
+
+    /// Renames a file or a directory with its contents. Fails if `from` doesn't exist or `to` already exists.
+    pub fn rename(&mut self, from: &str, to: &str) -> bool {
+        let from = Self::normalize_path(from).trim_end_matches('/');
+        let to = Self::normalize_path(to).trim_end_matches('/');
+
+        if from.is_empty() || to.is_empty() || self.exists(to) || self.is_directory(to) {
+            return false;
+        }
+
+        if let Some(data) = self.virtual_files.remove(from) {
+            self.virtual_files.insert(to.to_string(), data);
+            self.changed.extend([from.to_string(), to.to_string()]);
+
+            return true;
+        }
+
+        if !self.is_directory(from) {
+            return false;
+        }
+
+        let renamed_path = |path: &str| path.strip_prefix(from).filter(|x| x.starts_with('/')).map(|x| format!("{}{}", to, x));
+
+        let files = self
+            .virtual_files
+            .keys()
+            .filter_map(|x| Some((x.clone(), renamed_path(x)?)))
+            .collect::<Vec<_>>();
+        for (old, new) in files {
+            let data = self.virtual_files.remove(&old).unwrap();
+            self.virtual_files.insert(new.clone(), data);
+            self.changed.extend([old, new]);
+        }
+
+        self.directories = self
+            .directories
+            .drain()
+            .map(|x| if x == from { to.to_string() } else { renamed_path(&x).unwrap_or(x) })
+            .collect();
+
+        true
+    }
+
+    /// Creates a directory. Returns false if something already exists at `path`.
+    pub fn create_directory(&mut self, path: &str) -> bool {
+        let directory = Self::normalize_path(path).trim_end_matches('/');
+
+        if directory.is_empty() || self.exists(directory) || self.is_directory(directory) {
+            return false;
+        }
+
+        self.directories.insert(directory.to_string());
+
+        true
+    }
+
+    /// Directories are either created explicitly or implied by paths of files in them.
+    pub fn is_directory(&self, path: &str) -> bool {
+        let directory = Self::normalize_path(path).trim_end_matches('/');
+        if directory.is_empty() {
+            return true;
         }
+
+        let prefix = format!("{}/", directory);
+
+        self.directories.contains(directory)
+            || self.directories.iter().any(|x| x.starts_with(&prefix))
+            || self.virtual_files.keys().any(|x| x.starts_with(&prefix))
+    }
+
+    /// Names of direct children of `path`, sorted. Directory names end with '/'.
+    pub fn list(&self, path: &str) -> Vec<String> {
+        let directory = Self::normalize_path(path).trim_end_matches('/');
+        let prefix = if directory.is_empty() {
+            String::new()
+        } else {
+            format!("{}/", directory)
+        };
+
+        let files = self.virtual_files.keys().map(|x| (x, false));
+        let directories = self.directories.iter().map(|x| (x, true));
+
+        files
+            .chain(directories)
+            .filter_map(|(x, is_directory)| {
+                let name = x.strip_prefix(&prefix)?;
+                Some(match name.split_once('/') {
+                    Some((child, _)) => format!("{}/", child),
+                    None if is_directory => format!("{}/", name),
+                    None => name.to_string(),
+                })
+            })
+            .filter(|x| x != "/")
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect()
     }
 
     pub fn files(&self) -> impl Iterator<Item = (&str, &[u8])> {
         self.virtual_files.iter().map(|(k, v)| (k.as_str(), v.as_slice()))
     }
 
+    /// Saves files changed since last flush to storage. Only files are saved, empty directories are not kept.
+    pub fn flush(&mut self) {
+        let Some(storage) = &mut self.storage else {
+            return;
+        };
+
+        for path in core::mem::take(&mut self.changed) {
+            let result = match self.virtual_files.get(&path) {
+                Some(data) => storage.write(&path, data),
+                None => storage.remove(&path),
+            };
+            if !result {
+                tracing::warn!("Failed to save {}", path);
+            }
+        }
+    }
+
     fn normalize_path(path: &str) -> &str {
         path.trim_start_matches('/')
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use alloc::{boxed::Box, string::String, sync::Arc};
+    use std::{collections::BTreeMap, sync::Mutex};
+
+    use crate::file_storage::FileStorage;
+
+    use super::Filesystem;
+
+    #[derive(Clone, Default)]
+    struct MemoryStorage(Arc<Mutex<BTreeMap<String, Vec<u8>>>>);
+
+    impl FileStorage for MemoryStorage {
+        fn files(&self) -> Vec<(String, Vec<u8>)> {
+            self.0.lock().unwrap().iter().map(|(k, v)| (k.clone(), v.clone())).collect()
+        }
+
+        fn write(&mut self, path: &str, data: &[u8]) -> bool {
+            self.0.lock().unwrap().insert(path.into(), data.into());
+
+            true
+        }
+
+        fn remove(&mut self, path: &str) -> bool {
+            self.0.lock().unwrap().remove(path);
+
+            true
+        }
+    }
+
+    #[test]
+    fn test_filesystem() {
+        let mut filesystem = Filesystem::new();
+        filesystem.add("a.txt", b"test".to_vec());
+        filesystem.add("data/b.bin", vec![1, 2, 3]);
+        assert!(filesystem.create_directory("/save"));
+        assert!(!filesystem.create_directory("data"));
+
+        assert_eq!(filesystem.list("/"), ["a.txt", "data/", "save/"]);
+        assert_eq!(filesystem.list("data"), ["b.bin"]);
+        assert!(filesystem.is_directory("/data/"));
+        assert!(!filesystem.is_directory("a.txt"));
+
+        let mut buf = [0; 4];
+        assert_eq!(filesystem.read("/a.txt", 10, 4, &mut buf), Some(0));
+
+        assert!(filesystem.rename("a.txt", "save/a.txt"));
+        assert!(!filesystem.rename("a.txt", "b.txt"));
+        assert!(!filesystem.rename("data/b.bin", "save/a.txt"));
+        assert_eq!(filesystem.list("save"), ["a.txt"]);
+
+        assert!(filesystem.rename("save", "save2"));
+        assert_eq!(filesystem.size("save2/a.txt"), Some(4));
+
+        assert!(!filesystem.remove("save2"));
+        assert!(filesystem.remove("/save2/a.txt"));
+        assert!(filesystem.remove("save2"));
+        assert!(!filesystem.remove("save2"));
+        assert_eq!(filesystem.list(""), ["data/"]);
+    }
+
+    #[test]
+    fn test_filesystem_storage() {
+        let storage = MemoryStorage::default();
+        storage.0.lock().unwrap().insert("save/a.dat".into(), b"saved".to_vec());
+
+        let mut filesystem = Filesystem::with_storage(Box::new(storage.clone()));
+        filesystem.add("save/a.dat", b"package".to_vec());
+        filesystem.add("res/b.dat", b"package".to_vec());
+        assert_eq!(filesystem.size("save/a.dat"), Some(5));
+
+        filesystem.write("save/c.dat", 0, b"new");
+        assert!(filesystem.rename("save", "save2"));
+        filesystem.write("tmp.dat", 0, b"tmp");
+        assert!(filesystem.remove("tmp.dat"));
+        assert!(storage.0.lock().unwrap().contains_key("save/a.dat"));
+
+        filesystem.flush();
+        assert_eq!(
+            storage.0.lock().unwrap().keys().map(|x| x.as_str()).collect::<Vec<_>>(),
+            ["save2/a.dat", "save2/c.dat"]
+        );
+    }
+}
@@ -0,0 +1,775 @@
+use alloc::{string::String, vec::Vec};
+
+use crate::{system::KeyCode, time::Instant};
+
+// same key within this duration cycles the last character
+const MULTI_TAP_TIMEOUT: u64 = 1000;
+
+const LATIN_LETTERS: [&str; 10] = [" 0", ".,?!'-@1", "abc2", "def3", "ghi4", "jkl5", "mno6", "pqrs7", "tuv8", "wxyz9"];
+
+// chunjiin layout. number 1 to 3 are vowel strokes, others cycle consonants
+const STROKE_I: char = 'ㅣ';
+const STROKE_A: char = 'ㆍ';
+const STROKE_EU: char = 'ㅡ';
+const CHUNJIIN_CONSONANTS: [&str; 10] = ["ㅇㅁ", "", "", "", "ㄱㅋㄲ", "ㄴㄹ", "ㄷㅌㄸ", "ㅂㅍㅃ", "ㅅㅎㅆ", "ㅈㅊㅉ"];
+
+// narageul layout. star adds a stroke to the last letter, and hash doubles the last consonant
+const NARAGEUL_KEYS: [&str; 10] = ["ㅡ", "ㄱ", "ㄴ", "ㅏㅓ", "ㄹ", "ㅁ", "ㅗㅜ", "ㅅ", "ㅇ", "ㅣ"];
+const NARAGEUL_STROKES: [(char, char); 21] = [
+    ('ㄱ', 'ㅋ'),
+    ('ㅋ', 'ㄱ'),
+    ('ㄴ', 'ㄷ'),
+    ('ㄷ', 'ㅌ'),
+    ('ㅌ', 'ㄴ'),
+    ('ㅁ', 'ㅂ'),
+    ('ㅂ', 'ㅍ'),
+    ('ㅍ', 'ㅁ'),
+    ('ㅅ', 'ㅈ'),
+    ('ㅈ', 'ㅊ'),
+    ('ㅊ', 'ㅅ'),
+    ('ㅇ', 'ㅎ'),
+    ('ㅎ', 'ㅇ'),
+    ('ㅏ', 'ㅑ'),
+    ('ㅑ', 'ㅏ'),
+    ('ㅓ', 'ㅕ'),
+    ('ㅕ', 'ㅓ'),
+    ('ㅗ', 'ㅛ'),
+    ('ㅛ', 'ㅗ'),
+    ('ㅜ', 'ㅠ'),
+    ('ㅠ', 'ㅜ'),
+];
+const NARAGEUL_DOUBLES: [(char, char); 10] = [
+    ('ㄱ', 'ㄲ'),
+    ('ㄲ', 'ㄱ'),
+    ('ㄷ', 'ㄸ'),
+    ('ㄸ', 'ㄷ'),
+    ('ㅂ', 'ㅃ'),
+    ('ㅃ', 'ㅂ'),
+    ('ㅅ', 'ㅆ'),
+    ('ㅆ', 'ㅅ'),
+    ('ㅈ', 'ㅉ'),
+    ('ㅉ', 'ㅈ'),
+];
+
+// sky layout. all keys cycle letters, star and hash are 11th and 12th
+const SKY_KEYS: [&str; 12] = [
+    "ㅇㅁ",
+    "ㄱㅋㄲ",
+    "ㅣㅡ",
+    "ㅏㅑ",
+    "ㄷㅌㄸ",
+    "ㄴㄹ",
+    "ㅓㅕ",
+    "ㅂㅍㅃ",
+    "ㅅㅎㅆ",
+    "ㅗㅛ",
+    "ㅈㅊㅉ",
+    "ㅜㅠ",
+];
+const STAR_INDEX: usize = 10;
+const HASH_INDEX: usize = 11;
+
+const INITIALS: &str = "ㄱㄲㄴㄷㄸㄹㅁㅂㅃㅅㅆㅇㅈㅉㅊㅋㅌㅍㅎ";
+const MEDIALS: &str = "ㅏㅐㅑㅒㅓㅔㅕㅖㅗㅘㅙㅚㅛㅜㅝㅞㅟㅠㅡㅢㅣ";
+const FINALS: &str = "ㄱㄲㄳㄴㄵㄶㄷㄹㄺㄻㄼㄽㄾㄿㅀㅁㅂㅄㅅㅆㅇㅈㅊㅋㅌㅍㅎ";
+
+const COMPOUND_FINALS: [(char, char, char); 11] = [
+    ('ㄱ', 'ㅅ', 'ㄳ'),
+    ('ㄴ', 'ㅈ', 'ㄵ'),
+    ('ㄴ', 'ㅎ', 'ㄶ'),
+    ('ㄹ', 'ㄱ', 'ㄺ'),
+    ('ㄹ', 'ㅁ', 'ㄻ'),
+    ('ㄹ', 'ㅂ', 'ㄼ'),
+    ('ㄹ', 'ㅅ', 'ㄽ'),
+    ('ㄹ', 'ㅌ', 'ㄾ'),
+    ('ㄹ', 'ㅍ', 'ㄿ'),
+    ('ㄹ', 'ㅎ', 'ㅀ'),
+    ('ㅂ', 'ㅅ', 'ㅄ'),
+];
+
+// chunjiin vowel stroke sequences, shown as the stroke itself until a vowel is complete
+const CHUNJIIN_VOWELS: [(&str, char); 23] = [
+    ("ㆍ", 'ㆍ'),
+    ("ㆍㆍ", 'ᆢ'),
+    ("ㅣ", 'ㅣ'),
+    ("ㅣㆍ", 'ㅏ'),
+    ("ㅣㆍㆍ", 'ㅑ'),
+    ("ㅣㆍㅣ", 'ㅐ'),
+    ("ㅣㆍㆍㅣ", 'ㅒ'),
+    ("ㆍㅣ", 'ㅓ'),
+    ("ㆍㆍㅣ", 'ㅕ'),
+    ("ㆍㅣㅣ", 'ㅔ'),
+    ("ㆍㆍㅣㅣ", 'ㅖ'),
+    ("ㆍㅡ", 'ㅗ'),
+    ("ㆍㆍㅡ", 'ㅛ'),
+    ("ㆍㅡㅣ", 'ㅚ'),
+    ("ㆍㅡㅣㆍ", 'ㅘ'),
+    ("ㆍㅡㅣㆍㅣ", 'ㅙ'),
+    ("ㅡ", 'ㅡ'),
+    ("ㅡㆍ", 'ㅜ'),
+    ("ㅡㆍㆍ", 'ㅠ'),
+    ("ㅡㆍㅣ", 'ㅟ'),
+    ("ㅡㆍㆍㅣ", 'ㅝ'),
+    ("ㅡㆍㆍㅣㅣ", 'ㅞ'),
+    ("ㅡㅣ", 'ㅢ'),
+];
+
+// vowels combined from basic vowels, used by narageul and sky
+const JAMO_VOWELS: [(&str, char); 21] = [
+    ("ㅏ", 'ㅏ'),
+    ("ㅑ", 'ㅑ'),
+    ("ㅓ", 'ㅓ'),
+    ("ㅕ", 'ㅕ'),
+    ("ㅗ", 'ㅗ'),
+    ("ㅛ", 'ㅛ'),
+    ("ㅜ", 'ㅜ'),
+    ("ㅠ", 'ㅠ'),
+    ("ㅡ", 'ㅡ'),
+    ("ㅣ", 'ㅣ'),
+    ("ㅏㅣ", 'ㅐ'),
+    ("ㅑㅣ", 'ㅒ'),
+    ("ㅓㅣ", 'ㅔ'),
+    ("ㅕㅣ", 'ㅖ'),
+    ("ㅗㅏ", 'ㅘ'),
+    ("ㅗㅏㅣ", 'ㅙ'),
+    ("ㅗㅣ", 'ㅚ'),
+    ("ㅜㅓ", 'ㅝ'),
+    ("ㅜㅓㅣ", 'ㅞ'),
+    ("ㅜㅣ", 'ㅟ'),
+    ("ㅡㅣ", 'ㅢ'),
+];
+
+type VowelTable = [(&'static str, char)];
+
+/// Hangul keypad layout, which differs between handset makers.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum HangulLayout {
+    /// Keys 1, 2 and 3 are `ㅣ`, `ㆍ` and `ㅡ` strokes combined into vowels, and other number keys cycle consonants.
+    #[default]
+    Chunjiin,
+    /// Each number key has one basic letter. Star adds a stroke to the last letter and hash doubles the last consonant.
+    Narageul,
+    /// All twelve keys cycle letters, vowels are combined from basic vowels.
+    Sky,
+}
+
+impl HangulLayout {
+    pub fn from_name(name: &str) -> Option<Self> {
+        Some(match name.to_ascii_lowercase().as_str() {
+            "chunjiin" => Self::Chunjiin,
+            "narageul" => Self::Narageul,
+            "sky" => Self::Sky,
+            _ => return None,
+        })
+    }
+
+    // layouts using star for letters switch mode with left soft key
+    fn mode_key(self) -> KeyCode {
+        match self {
+            Self::Chunjiin => KeyCode::STAR,
+            Self::Narageul | Self::Sky => KeyCode::LEFT_SOFT,
+        }
+    }
+
+    fn vowels(self) -> &'static VowelTable {
+        match self {
+            Self::Chunjiin => &CHUNJIIN_VOWELS,
+            Self::Narageul | Self::Sky => &JAMO_VOWELS,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum InputMode {
+    Hangul,
+    UpperCase,
+    LowerCase,
+    Number,
+}
+
+impl InputMode {
+    /// Mode selected by star key after this one.
+    pub fn next(self) -> Self {
+        match self {
+            Self::Hangul => Self::UpperCase,
+            Self::UpperCase => Self::LowerCase,
+            Self::LowerCase => Self::Number,
+            Self::Number => Self::Hangul,
+        }
+    }
+
+    /// Short indicator drawn by text components.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Hangul => "한",
+            Self::UpperCase => "AB",
+            Self::LowerCase => "ab",
+            Self::Number => "123",
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum HangulInput {
+    Consonant(char),
+    /// Chunjiin stroke, or a basic vowel on other layouts.
+    Stroke(char),
+}
+
+#[derive(Default)]
+struct Syllable {
+    initial: Option<char>,
+    strokes: String,
+    finals: Vec<char>,
+    input_count: usize,
+}
+
+impl Syllable {
+    fn vowel(&self, vowels: &VowelTable) -> Option<char> {
+        vowels.iter().find(|(strokes, _)| *strokes == self.strokes).map(|(_, vowel)| *vowel)
+    }
+
+    // complete vowel, not a lone stroke in progress
+    fn medial(&self, vowels: &VowelTable) -> Option<char> {
+        self.vowel(vowels).filter(|x| MEDIALS.contains(*x))
+    }
+
+    fn render(&self, output: &mut String, vowels: &VowelTable) {
+        let final_ = match self.finals[..] {
+            [] => None,
+            [x] => Some(x),
+            [x, y] => compound_final(x, y),
+            _ => unreachable!(),
+        };
+
+        if let (Some(initial), Some(medial)) = (self.initial, self.medial(vowels)) {
+            let initial = INITIALS.chars().position(|x| x == initial).unwrap() as u32;
+            let medial = MEDIALS.chars().position(|x| x == medial).unwrap() as u32;
+            let final_ = final_.map(|x| FINALS.chars().position(|y| y == x).unwrap() as u32 + 1).unwrap_or(0);
+
+            output.push(char::from_u32(0xac00 + (initial * 21 + medial) * 28 + final_).unwrap());
+            return;
+        }
+
+        output.extend(self.initial);
+        output.extend(self.vowel(vowels));
+    }
+}
+
+/// Handset text input method, turning number pad keys into text.
+///
+/// Hangul follows the selected [`HangulLayout`], chunjiin by default. Latin letters are entered with multi-tap.
+/// Star switches input mode, or left soft key on layouts using star for letters.
+/// Characters are kept in composition buffer until they can't be changed by following keys.
+// TODO: MIDP TextBox and TextField, and SKVM text input don't use this yet
+pub struct InputMethod {
+    layout: HangulLayout,
+    mode: InputMode,
+    hangul: Vec<HangulInput>,
+    latin: Option<char>,
+    last_key: Option<(KeyCode, Instant)>,
+    committed: String,
+    owner: Option<u64>,
+}
+
+impl InputMethod {
+    pub fn new(mode: InputMode) -> Self {
+        Self {
+            layout: HangulLayout::default(),
+            mode,
+            hangul: Vec::new(),
+            latin: None,
+            last_key: None,
+            committed: String::new(),
+            owner: None,
+        }
+    }
+
+    pub fn layout(&self) -> HangulLayout {
+        self.layout
+    }
+
+    pub fn set_layout(&mut self, layout: HangulLayout) {
+        self.commit();
+        self.layout = layout;
+    }
+
+    pub fn mode(&self) -> InputMode {
+        self.mode
+    }
+
+    pub fn set_mode(&mut self, mode: InputMode) {
+        self.commit();
+        self.mode = mode;
+    }
+
+    /// Discards all state except layout, starting input for a new text field.
+    pub fn reset(&mut self, mode: InputMode) {
+        let layout = self.layout;
+
+        *self = Self::new(mode);
+        self.layout = layout;
+    }
+
+    /// Starts input for text field identified by `owner`, keeping state if it's already the one being typed into.
+    /// For text fields without focus tracking.
+    pub fn attach(&mut self, owner: u64, mode: InputMode) {
+        if self.owner != Some(owner) {
+            self.reset(mode);
+            self.owner = Some(owner);
+        }
+    }
+
+    pub fn is_attached(&self, owner: u64) -> bool {
+        self.owner == Some(owner)
+    }
+
+    /// Returns true if any text field is attached, so that text [`insert`](Self::insert)ed has somewhere to go.
+    pub fn has_owner(&self) -> bool {
+        self.owner.is_some()
+    }
+
+    /// Drops state of text field identified by `owner`, if it's the one being typed into.
+    pub fn detach(&mut self, owner: u64) {
+        if self.owner == Some(owner) {
+            self.reset(self.mode);
+        }
+    }
+
+    /// Returns true if the key is handled by input method.
+    pub fn key(&mut self, key: KeyCode, now: Instant) -> bool {
+        let last_key = self.last_key.take();
+
+        if key == self.layout.mode_key() {
+            self.set_mode(self.mode.next());
+
+            return true;
+        }
+
+        let is_tap = matches!(last_key, Some((last_key, time)) if last_key == key && now >= time && now - time < MULTI_TAP_TIMEOUT);
+
+        let handled = match (self.mode, Self::digit(key)) {
+            (InputMode::Hangul, _) => self.hangul_key(key, is_tap),
+            (InputMode::UpperCase | InputMode::LowerCase, Some(digit)) => {
+                self.latin_key(digit, is_tap);
+                true
+            }
+            (InputMode::Number, Some(digit)) => {
+                self.commit();
+                self.committed.push(char::from_digit(digit as _, 10).unwrap());
+                true
+            }
+            _ => false,
+        };
+        if handled {
+            self.last_key = Some((key, now));
+
+            return true;
+        }
+
+        if key == KeyCode::HASH {
+            self.commit();
+            self.committed.push(if self.mode == InputMode::Number { '#' } else { ' ' });
+
+            return true;
+        }
+
+        false
+    }
+
+    /// Removes last character or stroke being composed. Returns false if there's nothing to remove.
+    pub fn backspace(&mut self) -> bool {
+        self.last_key = None;
+
+        if self.latin.take().is_some() {
+            return true;
+        }
+
+        if self.hangul.pop().is_some() {
+            self.flush_hangul();
+            return true;
+        }
+
+        false
+    }
+
+    /// Moves composition buffer to committed text.
+    pub fn commit(&mut self) {
+        let composition = self.composition();
+        self.committed.push_str(&composition);
+
+        self.hangul.clear();
+        self.latin = None;
+        self.last_key = None;
+    }
+
+    /// Commits composition and appends `text` as is, for text not typed with handset keys.
+    pub fn insert(&mut self, text: &str) {
+        self.commit();
+        self.committed.push_str(text);
+    }
+
+    /// Text being composed, which is not yet committed.
+    pub fn composition(&self) -> String {
+        let vowels = self.layout.vowels();
+
+        let mut result = String::new();
+        for syllable in Self::compose(&self.hangul, vowels) {
+            syllable.render(&mut result, vowels);
+        }
+        result.extend(self.latin);
+
+        result
+    }
+
+    /// Takes text committed since last call.
+    pub fn take_committed(&mut self) -> String {
+        core::mem::take(&mut self.committed)
+    }
+
+    fn hangul_key(&mut self, key: KeyCode, is_tap: bool) -> bool {
+        let index = match key {
+            KeyCode::STAR => STAR_INDEX,
+            KeyCode::HASH => HASH_INDEX,
+            _ => match Self::digit(key) {
+                Some(digit) => digit,
+                None => return false,
+            },
+        };
+
+        match (self.layout, index) {
+            (HangulLayout::Chunjiin, STAR_INDEX | HASH_INDEX) => return false,
+            (HangulLayout::Chunjiin, 1) => self.push_hangul(HangulInput::Stroke(STROKE_I)),
+            (HangulLayout::Chunjiin, 2) => self.push_hangul(HangulInput::Stroke(STROKE_A)),
+            (HangulLayout::Chunjiin, 3) => self.push_hangul(HangulInput::Stroke(STROKE_EU)),
+            (HangulLayout::Chunjiin, _) => self.cycle_hangul(CHUNJIIN_CONSONANTS[index], is_tap),
+            // star does nothing without a letter to change, while hash falls back to space
+            (HangulLayout::Narageul, STAR_INDEX) => {
+                self.replace_last_hangul(&NARAGEUL_STROKES);
+            }
+            (HangulLayout::Narageul, HASH_INDEX) => return self.replace_last_hangul(&NARAGEUL_DOUBLES),
+            (HangulLayout::Narageul, _) => self.cycle_hangul(NARAGEUL_KEYS[index], is_tap),
+            (HangulLayout::Sky, _) => self.cycle_hangul(SKY_KEYS[index], is_tap),
+        }
+
+        true
+    }
+
+    // tapping the same key again replaces last letter with the next one of the key
+    fn cycle_hangul(&mut self, letters: &str, is_tap: bool) {
+        let last = match self.hangul.last() {
+            Some(HangulInput::Consonant(x) | HangulInput::Stroke(x)) if is_tap && letters.contains(*x) => Some(*x),
+            _ => None,
+        };
+
+        let next = if let Some(last) = last {
+            let index = letters.chars().position(|x| x == last).unwrap();
+
+            self.hangul.pop();
+            letters.chars().cycle().nth(index + 1).unwrap()
+        } else {
+            letters.chars().next().unwrap()
+        };
+
+        self.push_hangul(Self::hangul_input(next));
+    }
+
+    fn replace_last_hangul(&mut self, replacements: &[(char, char)]) -> bool {
+        let Some(HangulInput::Consonant(last) | HangulInput::Stroke(last)) = self.hangul.last().copied() else {
+            return false;
+        };
+        let Some((_, replacement)) = replacements.iter().find(|(x, _)| *x == last) else {
+            return false;
+        };
+
+        self.hangul.pop();
+        self.push_hangul(Self::hangul_input(*replacement));
+
+        true
+    }
+
+    fn push_hangul(&mut self, input: HangulInput) {
+        self.hangul.push(input);
+        self.flush_hangul();
+    }
+
+    fn hangul_input(letter: char) -> HangulInput {
+        if MEDIALS.contains(letter) {
+            HangulInput::Stroke(letter)
+        } else {
+            HangulInput::Consonant(letter)
+        }
+    }
+
+    fn latin_key(&mut self, digit: usize, is_tap: bool) {
+        let letters = if self.mode == InputMode::UpperCase {
+            LATIN_LETTERS[digit].to_uppercase()
+        } else {
+            LATIN_LETTERS[digit].into()
+        };
+
+        let next = match self.latin {
+            Some(last) if is_tap => {
+                let index = letters.chars().position(|x| x == last).unwrap_or(0);
+
+                letters.chars().cycle().nth(index + 1).unwrap()
+            }
+            _ => {
+                self.commit();
+
+                letters.chars().next().unwrap()
+            }
+        };
+
+        self.latin = Some(next);
+    }
+
+    // syllables before the last two can't change anymore
+    fn flush_hangul(&mut self) {
+        let vowels = self.layout.vowels();
+
+        let syllables = Self::compose(&self.hangul, vowels);
+        if syllables.len() <= 2 {
+            return;
+        }
+
+        let mut flushed = 0;
+        for syllable in &syllables[..syllables.len() - 2] {
+            syllable.render(&mut self.committed, vowels);
+            flushed += syllable.input_count;
+        }
+
+        self.hangul.drain(..flushed);
+    }
+
+    fn compose(inputs: &[HangulInput], vowels: &VowelTable) -> Vec<Syllable> {
+        let mut syllables: Vec<Syllable> = Vec::new();
+
+        for &input in inputs {
+            let current = syllables.last_mut();
+
+            let new_syllable = match (input, current) {
+                (HangulInput::Consonant(x), Some(current)) if Self::accepts_consonant(current, x, vowels) => {
+                    if current.initial.is_none() {
+                        current.initial = Some(x);
+                    } else {
+                        current.finals.push(x);
+                    }
+                    current.input_count += 1;
+
+                    None
+                }
+                (HangulInput::Consonant(x), _) => Some(Syllable {
+                    initial: Some(x),
+                    input_count: 1,
+                    ..Default::default()
+                }),
+                // last final consonant moves to the next syllable
+                (HangulInput::Stroke(x), Some(current)) if !current.finals.is_empty() => {
+                    let initial = current.finals.pop();
+                    current.input_count -= 1;
+
+                    Some(Syllable {
+                        initial,
+                        strokes: x.into(),
+                        input_count: 2,
+                        ..Default::default()
+                    })
+                }
+                (HangulInput::Stroke(x), Some(current)) if Self::accepts_stroke(current, x, vowels) => {
+                    current.strokes.push(x);
+                    current.input_count += 1;
+
+                    None
+                }
+                (HangulInput::Stroke(x), _) => Some(Syllable {
+                    strokes: x.into(),
+                    input_count: 1,
+                    ..Default::default()
+                }),
+            };
+
+            syllables.extend(new_syllable);
+        }
+
+        syllables
+    }
+
+    fn accepts_consonant(syllable: &Syllable, consonant: char, vowels: &VowelTable) -> bool {
+        if syllable.strokes.is_empty() {
+            return syllable.initial.is_none();
+        }
+        if syllable.initial.is_none() || syllable.medial(vowels).is_none() {
+            return false;
+        }
+
+        match syllable.finals[..] {
+            [] => FINALS.contains(consonant),
+            [x] => compound_final(x, consonant).is_some(),
+            _ => false,
+        }
+    }
+
+    fn accepts_stroke(syllable: &Syllable, stroke: char, vowels: &VowelTable) -> bool {
+        let mut strokes = syllable.strokes.clone();
+        strokes.push(stroke);
+
+        vowels.iter().any(|(x, _)| *x == strokes)
+    }
+
+    fn digit(key: KeyCode) -> Option<usize> {
+        Some(match key {
+            KeyCode::NUM0 => 0,
+            KeyCode::NUM1 => 1,
+            KeyCode::NUM2 => 2,
+            KeyCode::NUM3 => 3,
+            KeyCode::NUM4 => 4,
+            KeyCode::NUM5 => 5,
+            KeyCode::NUM6 => 6,
+            KeyCode::NUM7 => 7,
+            KeyCode::NUM8 => 8,
+            KeyCode::NUM9 => 9,
+            _ => return None,
+        })
+    }
+}
+
+impl Default for InputMethod {
+    fn default() -> Self {
+        Self::new(InputMode::Hangul)
+    }
+}
+
+fn compound_final(first: char, second: char) -> Option<char> {
+    COMPOUND_FINALS
+        .iter()
+        .find(|(x, y, _)| *x == first && *y == second)
+        .map(|(_, _, compound)| *compound)
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::String;
+
+    use crate::{system::KeyCode, time::Instant};
+
+    use super::{HangulLayout, InputMethod, InputMode};
+
+    fn type_keys(input_method: &mut InputMethod, keys: &str) -> String {
+        let mut now = Instant::from_epoch_millis(0);
+        for key in keys.chars() {
+            // space waits for multi-tap timeout
+            if key == ' ' {
+                now = now + 2000;
+                continue;
+            }
+
+            if key == '<' {
+                input_method.backspace();
+                continue;
+            }
+
+            let key = KeyCode::parse(key.encode_utf8(&mut [0; 4])).unwrap();
+            input_method.key(key, now);
+            now = now + 100;
+        }
+
+        let mut result = input_method.take_committed();
+        result.push_str(&input_method.composition());
+
+        result
+    }
+
+    #[test]
+    fn test_hangul() {
+        // ㅎ ㅏ ㄴ ㄱ ㅡ ㄹ, consonants cycle by tapping same key
+        let mut input_method = InputMethod::new(InputMode::Hangul);
+        assert_eq!(type_keys(&mut input_method, "88125 4355"), "한글");
+
+        // ㅇ ㅏ ㄴ ㄴ ㅕ ㅇ, waiting starts new consonant
+        let mut input_method = InputMethod::new(InputMode::Hangul);
+        assert_eq!(type_keys(&mut input_method, "0125 52210"), "안녕");
+
+        // final consonant moves to next syllable, and older syllables are committed
+        let mut input_method = InputMethod::new(InputMode::Hangul);
+        assert_eq!(type_keys(&mut input_method, "412558"), "갌");
+        assert_eq!(type_keys(&mut input_method, "141"), "갈시기");
+        assert_eq!(input_method.composition(), "시기");
+
+        // backspace removes a stroke at a time
+        let mut input_method = InputMethod::new(InputMode::Hangul);
+        assert_eq!(type_keys(&mut input_method, "4231<"), "고");
+    }
+
+    #[test]
+    fn test_narageul() {
+        let mut input_method = InputMethod::new(InputMode::Hangul);
+        input_method.set_layout(HangulLayout::Narageul);
+
+        // ㅇ, star makes ㅎ, ㅏ ㄴ ㄱ ㅡ ㄹ
+        assert_eq!(type_keys(&mut input_method, "8*32104"), "한글");
+
+        // hash doubles consonant, star adds stroke to vowel, tapping switches ㅏ and ㅓ
+        input_method.reset(InputMode::Hangul);
+        assert_eq!(type_keys(&mut input_method, "1#3 83* 833"), "까야어");
+
+        // hash without a consonant to double is space, and star doesn't switch mode
+        input_method.reset(InputMode::Hangul);
+        assert_eq!(type_keys(&mut input_method, "83#*"), "아 ");
+        assert_eq!(input_method.mode(), InputMode::Hangul);
+        input_method.key(KeyCode::LEFT_SOFT, Instant::from_epoch_millis(0));
+        assert_eq!(input_method.mode(), InputMode::UpperCase);
+        assert_eq!(input_method.layout(), HangulLayout::Narageul);
+    }
+
+    #[test]
+    fn test_sky() {
+        let mut input_method = InputMethod::new(InputMode::Hangul);
+        input_method.set_layout(HangulLayout::Sky);
+
+        // ㅅ ㅎ ㅏ ㄴ ㄱ ㅣ ㅡ ㄴ ㄹ, tapping cycles vowels as well
+        assert_eq!(type_keys(&mut input_method, "883512255"), "한글");
+
+        // star and hash are letters, compound vowels are typed in parts
+        input_method.reset(InputMode::Hangul);
+        assert_eq!(type_keys(&mut input_method, "*3 0# 093 022 2"), "자우와의");
+        assert_eq!(input_method.mode(), InputMode::Hangul);
+    }
+
+    #[test]
+    fn test_latin() {
+        let mut input_method = InputMethod::new(InputMode::LowerCase);
+        assert_eq!(type_keys(&mut input_method, "44 444 0 555666"), "hi lo");
+
+        // star switches mode, committing composition
+        assert_eq!(type_keys(&mut input_method, "*"), "o");
+        assert_eq!(input_method.mode(), InputMode::Number);
+        assert_eq!(type_keys(&mut input_method, "010#"), "010#");
+
+        let mut input_method = InputMethod::new(InputMode::UpperCase);
+        assert_eq!(type_keys(&mut input_method, "22"), "B");
+
+        // inserted text goes after composition
+        type_keys(&mut input_method, "33");
+        input_method.insert("xyz");
+        assert_eq!(input_method.take_committed(), "Exyz");
+        assert_eq!(input_method.composition(), "");
+
+        // composition survives attaching same owner, but not another one
+        input_method.attach(1, InputMode::LowerCase);
+        type_keys(&mut input_method, "2");
+        input_method.attach(1, InputMode::LowerCase);
+        assert_eq!(input_method.composition(), "a");
+        input_method.attach(2, InputMode::Number);
+        assert_eq!(input_method.composition(), "");
+        assert!(!input_method.is_attached(1));
+
+        // detaching other owner keeps pending text, detaching attached one drops it
+        input_method.insert("1");
+        input_method.detach(1);
+        assert!(input_method.has_owner());
+        input_method.detach(2);
+        assert!(!input_method.has_owner());
+        assert_eq!(input_method.take_committed(), "");
+    }
+}
@@ -0,0 +1,77 @@
+use alloc::{collections::BTreeMap, string::String};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SharedBuffer {
+    /// Runtime specific memory handle.
+    pub handle: u32,
+    pub size: u32,
+}
+
+/// Named buffers shared between programs. Buffer memory is owned by the runtime, only handles are tracked here.
+#[derive(Default)]
+pub struct SharedBuffers {
+    buffers: BTreeMap<String, SharedBuffer>,
+}
+
+impl SharedBuffers {
+    /// Registers buffer under `name`. Returns `false` if the name is already taken.
+    pub fn create(&mut self, name: &str, buffer: SharedBuffer) -> bool {
+        if self.buffers.contains_key(name) {
+            return false;
+        }
+
+        self.buffers.insert(name.into(), buffer);
+
+        true
+    }
+
+    pub fn get(&self, name: &str) -> Option<SharedBuffer> {
+        self.buffers.get(name).copied()
+    }
+
+    pub fn find(&self, handle: u32) -> Option<SharedBuffer> {
+        self.buffers.values().find(|x| x.handle == handle).copied()
+    }
+
+    /// Replaces buffer registered with `handle`, keeping its name.
+    pub fn update(&mut self, handle: u32, buffer: SharedBuffer) -> bool {
+        match self.buffers.values_mut().find(|x| x.handle == handle) {
+            Some(x) => {
+                *x = buffer;
+
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn destroy(&mut self, handle: u32) -> Option<SharedBuffer> {
+        let name = self.buffers.iter().find(|(_, x)| x.handle == handle).map(|(name, _)| name.clone())?;
+
+        self.buffers.remove(&name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SharedBuffer, SharedBuffers};
+
+    #[test]
+    fn test_shared_buffers() {
+        let mut buffers = SharedBuffers::default();
+
+        let buffer = SharedBuffer { handle: 0x100, size: 16 };
+        assert!(buffers.create("score", buffer));
+        assert!(!buffers.create("score", SharedBuffer { handle: 0x200, size: 4 }));
+        assert_eq!(buffers.get("score"), Some(buffer));
+
+        let resized = SharedBuffer { handle: 0x300, size: 32 };
+        assert!(buffers.update(0x100, resized));
+        assert_eq!(buffers.find(0x100), None);
+        assert_eq!(buffers.get("score"), Some(resized));
+
+        assert_eq!(buffers.destroy(0x300), Some(resized));
+        assert_eq!(buffers.get("score"), None);
+        assert_eq!(buffers.destroy(0x300), None);
+    }
+}
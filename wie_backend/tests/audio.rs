@@ -0,0 +1,66 @@
+use test_utils::TestPlatform;
+use wie_backend::{AudioError, AudioEvent, PlaybackState, System};
+
+#[test]
+fn test_playback_state() {
+    let system = System::new(Box::new(TestPlatform::new()), "test");
+    let mut audio = system.audio();
+
+    let handle = audio.load(&[], Some("application/vnd.smaf")).unwrap();
+    assert_ne!(handle, 0);
+    assert_eq!(audio.state(handle).unwrap(), PlaybackState::Stopped);
+
+    audio.play(handle, 1).unwrap();
+    assert_eq!(audio.state(handle).unwrap(), PlaybackState::Playing);
+
+    audio.pause(handle).unwrap();
+    assert_eq!(audio.state(handle).unwrap(), PlaybackState::Paused);
+
+    audio.resume(handle).unwrap();
+    audio.set_volume(handle, 150).unwrap();
+    assert_eq!(audio.volume(handle).unwrap(), 100);
+
+    audio.stop(handle).unwrap();
+    assert_eq!(audio.state(handle).unwrap(), PlaybackState::Stopped);
+
+    let events = (0..5).map(|x| audio.event_after(handle, x).unwrap().unwrap().1).collect::<Vec<_>>();
+    assert_eq!(
+        events,
+        [
+            AudioEvent::Started,
+            AudioEvent::Paused,
+            AudioEvent::Started,
+            AudioEvent::VolumeChanged,
+            AudioEvent::Stopped
+        ]
+    );
+
+    audio.close(handle).unwrap();
+    assert!(matches!(audio.state(handle), Err(AudioError::InvalidHandle)));
+}
+
+#[test]
+fn test_end_of_media() {
+    let mut system = System::new(Box::new(TestPlatform::new()), "test");
+
+    let handle = system.audio().load(&[], Some("application/vnd.smaf")).unwrap();
+    system.audio().play(handle, 2).unwrap();
+    let serial = system.audio().last_event_serial(handle).unwrap();
+
+    system.tick().unwrap();
+
+    assert_eq!(system.audio().state(handle).unwrap(), PlaybackState::Stopped);
+    assert_eq!(system.audio().event_after(handle, serial).unwrap().unwrap().1, AudioEvent::EndOfMedia);
+}
+
+#[test]
+fn test_load_format() {
+    let system = System::new(Box::new(TestPlatform::new()), "test");
+    let mut audio = system.audio();
+
+    // tone sequence is detected from data regardless of content type
+    assert!(audio.load(&[0xfe, 1, 60, 16], Some("audio/midi")).is_ok());
+    assert!(audio.load(&[0; 4], Some("MMF")).is_ok());
+    assert!(matches!(audio.load(&[0; 4], None), Err(AudioError::InvalidAudio)));
+    assert!(matches!(audio.load(b"MThd", None), Err(AudioError::InvalidAudio)));
+}
@@ -0,0 +1,34 @@
+use test_utils::TestPlatform;
+use wie_backend::{DeviceEffect, System};
+
+#[test]
+fn test_device_effects() {
+    let platform = TestPlatform::new();
+    let effects = platform.effects();
+
+    let system = System::new(Box::new(platform), "test");
+    let level_count = system.device().vibration_level_count();
+
+    system.device().vibrate(level_count, 300);
+    system.device().vibrate(1, 200);
+    system.device().vibrate(0, 200);
+    system.device().set_backlight(false, None);
+
+    assert_eq!(
+        *effects.lock(),
+        vec![
+            DeviceEffect::Vibrate {
+                pattern: vec![100, 0],
+                duration: 300,
+                intensity: 100
+            },
+            DeviceEffect::Vibrate {
+                pattern: vec![20, 80],
+                duration: 200,
+                intensity: 20
+            },
+            DeviceEffect::StopVibration,
+            DeviceEffect::Backlight { on: false, timeout: None },
+        ]
+    );
+}
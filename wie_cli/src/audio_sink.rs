@@ -1,18 +1,24 @@
-use std::sync::{mpsc::Sender, Mutex};
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
 
-use midir::MidiOutputConnection;
+use rodio::Source;
+
+const SAMPLE_RATE: u32 = 44100;
+// bound of queued samples, for when output is slower than emulator clock
+const MAX_QUEUED_SAMPLES: usize = SAMPLE_RATE as usize * 2;
+
+type SampleQueue = Arc<Mutex<VecDeque<i16>>>;
 
 pub struct AudioSink {
-    midi_out: Option<Mutex<MidiOutputConnection>>,
-    audio_tx: Sender<(u8, u32, Vec<i16>)>,
+    queue: SampleQueue,
 }
 
 impl AudioSink {
-    pub fn new(midi_out: Option<MidiOutputConnection>, audio_tx: Sender<(u8, u32, Vec<i16>)>) -> Self {
-        Self {
-            midi_out: midi_out.map(Mutex::new),
-            audio_tx,
-        }
+    pub fn new(queue: SampleQueue) -> Self {
+        Self { queue }
     }
 }
 
@@ -23,31 +29,54 @@ unsafe impl Sync for AudioSink {}
 unsafe impl Send for AudioSink {}
 
 impl wie_backend::AudioSink for AudioSink {
-    fn play_wave(&self, channel: u8, sampling_rate: u32, wave_data: &[i16]) {
-        self.audio_tx.send((channel, sampling_rate, wave_data.to_vec())).unwrap();
+    fn sample_rate(&self) -> u32 {
+        SAMPLE_RATE
     }
 
-    fn midi_note_on(&self, channel_id: u8, note: u8, velocity: u8) {
-        if let Some(x) = self.midi_out.as_ref() {
-            x.lock().unwrap().send(&[0x90 | channel_id, note, velocity]).unwrap();
+    fn write(&self, samples: &[i16]) {
+        let mut queue = self.queue.lock().unwrap();
+
+        queue.extend(samples);
+        if queue.len() > MAX_QUEUED_SAMPLES {
+            let overflow = (queue.len() - MAX_QUEUED_SAMPLES).next_multiple_of(2);
+            queue.drain(..overflow);
         }
     }
+}
 
-    fn midi_note_off(&self, channel_id: u8, note: u8, velocity: u8) {
-        if let Some(x) = self.midi_out.as_ref() {
-            x.lock().unwrap().send(&[0x80 | channel_id, note, velocity]).unwrap();
-        }
+/// Endless stereo rodio source playing samples written to `AudioSink`, with silence on underrun.
+pub struct QueueSource {
+    queue: SampleQueue,
+}
+
+impl QueueSource {
+    pub fn new(queue: SampleQueue) -> Self {
+        Self { queue }
     }
+}
 
-    fn midi_control_change(&self, channel_id: u8, control: u8, value: u8) {
-        if let Some(x) = self.midi_out.as_ref() {
-            x.lock().unwrap().send(&[0xB0 | channel_id, control, value]).unwrap()
-        }
+impl Iterator for QueueSource {
+    type Item = i16;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.queue.lock().unwrap().pop_front().unwrap_or(0))
     }
+}
 
-    fn midi_program_change(&self, channel_id: u8, program: u8) {
-        if let Some(x) = self.midi_out.as_ref() {
-            x.lock().unwrap().send(&[0xC0 | channel_id, program]).unwrap()
-        }
+impl Source for QueueSource {
+    fn current_frame_len(&self) -> Option<usize> {
+        None
+    }
+
+    fn channels(&self) -> u16 {
+        2
+    }
+
+    fn sample_rate(&self) -> u32 {
+        SAMPLE_RATE
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        None
     }
 }
@@ -0,0 +1,97 @@
+use std::{fs, path::PathBuf};
+
+use directories::ProjectDirs;
+
+pub struct FileStorageRepository {
+    base_path: PathBuf,
+}
+
+impl FileStorageRepository {
+    pub fn new() -> Self {
+        let base_dir = ProjectDirs::from("net", "dlunch", "wie").unwrap();
+
+        let base_path = base_dir.data_dir().join("files");
+
+        Self { base_path }
+    }
+}
+
+impl wie_backend::FileStorageRepository for FileStorageRepository {
+    fn open(&self, app_id: &str) -> Box<dyn wie_backend::FileStorage> {
+        let path = self.base_path.join(app_id);
+
+        Box::new(FileStorage::new(path).unwrap())
+    }
+}
+
+pub struct FileStorage {
+    base_path: PathBuf,
+}
+
+impl FileStorage {
+    pub fn new(base_path: PathBuf) -> anyhow::Result<Self> {
+        tracing::trace!("Opening file storage at {:?}", base_path);
+
+        fs::create_dir_all(&base_path)?;
+
+        Ok(Self { base_path })
+    }
+
+    fn collect_files(&self, relative_path: &str, files: &mut Vec<(String, Vec<u8>)>) {
+        let Ok(entries) = fs::read_dir(self.base_path.join(relative_path)) else {
+            return;
+        };
+
+        for entry in entries.flatten() {
+            let Some(name) = entry.file_name().to_str().map(|x| x.to_owned()) else {
+                continue;
+            };
+            let path = if relative_path.is_empty() {
+                name
+            } else {
+                format!("{}/{}", relative_path, name)
+            };
+
+            if entry.path().is_dir() {
+                self.collect_files(&path, files);
+            } else if let Ok(data) = fs::read(entry.path()) {
+                files.push((path, data));
+            }
+        }
+    }
+}
+
+impl wie_backend::FileStorage for FileStorage {
+    fn files(&self) -> Vec<(String, Vec<u8>)> {
+        let mut files = Vec::new();
+        self.collect_files("", &mut files);
+
+        files
+    }
+
+    fn write(&mut self, path: &str, data: &[u8]) -> bool {
+        let path = self.base_path.join(path);
+
+        tracing::trace!("Write file {:?}", &path);
+
+        if let Some(parent) = path.parent() {
+            if fs::create_dir_all(parent).is_err() {
+                return false;
+            }
+        }
+
+        fs::write(path, data).is_ok()
+    }
+
+    fn remove(&mut self, path: &str) -> bool {
+        let path = self.base_path.join(path);
+
+        tracing::trace!("Remove file {:?}", &path);
+
+        // file created and removed before being saved is never written
+        match fs::remove_file(path) {
+            Ok(()) => true,
+            Err(e) => e.kind() == std::io::ErrorKind::NotFound,
+        }
+    }
+}
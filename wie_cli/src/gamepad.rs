@@ -0,0 +1,178 @@
+use std::collections::HashMap;
+
+use gilrs::{Button, EventType, GamepadId, Gilrs};
+
+use wie_backend::{Event, KeyCode};
+
+/// Default gamepad bindings. Each line maps gilrs button name to handset key name.
+const DEFAULT_GAMEPAD_MAP: &str = "
+DPadUp = UP
+DPadDown = DOWN
+DPadLeft = LEFT
+DPadRight = RIGHT
+South = OK
+East = CLR
+West = *
+North = #
+LeftTrigger = LSK
+RightTrigger = RSK
+Start = SEND
+";
+
+macro_rules! gilrs_buttons {
+    ($($name:ident),* $(,)?) => {
+        fn parse_button(name: &str) -> Option<Button> {
+            match name {
+                $(stringify!($name) => Some(Button::$name),)*
+                _ => None,
+            }
+        }
+    };
+}
+
+gilrs_buttons!(
+    South,
+    East,
+    North,
+    West,
+    C,
+    Z,
+    LeftTrigger,
+    LeftTrigger2,
+    RightTrigger,
+    RightTrigger2,
+    Select,
+    Start,
+    Mode,
+    LeftThumb,
+    RightThumb,
+    DPadUp,
+    DPadDown,
+    DPadLeft,
+    DPadRight,
+);
+
+/// Mapping of gamepad buttons to handset keys.
+pub struct GamepadMap {
+    buttons: HashMap<Button, KeyCode>,
+}
+
+impl Default for GamepadMap {
+    fn default() -> Self {
+        let mut result = Self { buttons: HashMap::new() };
+        result.load(DEFAULT_GAMEPAD_MAP, "").unwrap();
+
+        result
+    }
+}
+
+impl GamepadMap {
+    /// Adds `button = handset key` lines from `config` over current mapping. Lines starting with `#` are ignored.
+    ///
+    /// Lines after a `[name]` header only apply if `name` equals `game`, so a single file can hold per-game overrides.
+    pub fn load(&mut self, config: &str, game: &str) -> anyhow::Result<()> {
+        let mut in_section = true;
+
+        for line in config.lines().map(|x| x.trim()) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            if let Some(section) = line.strip_prefix('[').and_then(|x| x.strip_suffix(']')) {
+                in_section = section.trim() == game;
+                continue;
+            }
+
+            let Some((button, handset_key)) = line.split_once('=') else {
+                anyhow::bail!("Invalid gamepad map line: {}", line);
+            };
+
+            let button = parse_button(button.trim()).ok_or_else(|| anyhow::anyhow!("Unknown gamepad button: {}", button.trim()))?;
+            let handset_key = KeyCode::parse(handset_key.trim()).ok_or_else(|| anyhow::anyhow!("Unknown handset key: {}", handset_key.trim()))?;
+
+            if in_section {
+                self.buttons.insert(button, handset_key);
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn get(&self, button: Button) -> Option<KeyCode> {
+        self.buttons.get(&button).copied()
+    }
+}
+
+/// Gamepad input, translated into handset key events.
+pub struct Gamepad {
+    gilrs: Option<Gilrs>,
+    map: GamepadMap,
+    /// Keys held by each gamepad, released on disconnect.
+    pressed: HashMap<(GamepadId, Button), KeyCode>,
+}
+
+impl Gamepad {
+    pub fn new(map: GamepadMap) -> Self {
+        // gamepad is optional, continue without it if the backend is not available
+        let gilrs = match Gilrs::new() {
+            Ok(x) => Some(x),
+            Err(x) => {
+                tracing::warn!("Gamepad is not available: {}", x);
+
+                None
+            }
+        };
+
+        if let Some(gilrs) = &gilrs {
+            for (_, gamepad) in gilrs.gamepads() {
+                tracing::info!("Gamepad connected: {}", gamepad.name());
+            }
+        }
+
+        Self {
+            gilrs,
+            map,
+            pressed: HashMap::new(),
+        }
+    }
+
+    /// Passes pending gamepad input to `handler`. Gamepads can be connected or disconnected at any time.
+    pub fn poll<F>(&mut self, mut handler: F)
+    where
+        F: FnMut(Event),
+    {
+        let Some(gilrs) = &mut self.gilrs else {
+            return;
+        };
+
+        while let Some(event) = gilrs.next_event() {
+            match event.event {
+                EventType::ButtonPressed(button, _) => {
+                    if let Some(key) = self.map.get(button) {
+                        if self.pressed.insert((event.id, button), key).is_none() {
+                            handler(Event::Keydown(key));
+                        }
+                    }
+                }
+                EventType::ButtonReleased(button, _) => {
+                    if let Some(key) = self.pressed.remove(&(event.id, button)) {
+                        handler(Event::Keyup(key));
+                    }
+                }
+                EventType::Connected => {
+                    tracing::info!("Gamepad connected: {}", gilrs.gamepad(event.id).name());
+                }
+                EventType::Disconnected => {
+                    tracing::info!("Gamepad disconnected: {}", gilrs.gamepad(event.id).name());
+
+                    let released = self.pressed.keys().filter(|(id, _)| *id == event.id).copied().collect::<Vec<_>>();
+                    for x in released {
+                        let key = self.pressed.remove(&x).unwrap();
+                        handler(Event::Keyup(key));
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+}
@@ -0,0 +1,191 @@
+use std::collections::HashMap;
+
+use winit::keyboard::{KeyCode as WinitKeyCode, PhysicalKey};
+
+use wie_backend::KeyCode;
+
+/// Default key map. Each line maps winit key name to handset key name.
+const DEFAULT_KEY_MAP: &str = "
+Digit1 = 1
+Digit2 = 2
+Digit3 = 3
+KeyQ = 4
+KeyW = 5
+KeyE = 6
+KeyA = 7
+KeyS = 8
+KeyD = 9
+KeyZ = *
+KeyX = 0
+KeyC = #
+Space = OK
+ArrowUp = UP
+ArrowDown = DOWN
+ArrowLeft = LEFT
+ArrowRight = RIGHT
+Backspace = CLR
+F1 = LSK
+F2 = RSK
+Enter = SEND
+Escape = END
+PageUp = VOLUP
+PageDown = VOLDOWN
+F3 = CAMERA
+KeyR = A
+KeyT = B
+KeyF = C
+KeyG = D
+";
+
+macro_rules! winit_key_codes {
+    ($($name:ident),* $(,)?) => {
+        fn parse_winit_key_code(name: &str) -> Option<WinitKeyCode> {
+            match name {
+                $(stringify!($name) => Some(WinitKeyCode::$name),)*
+                _ => None,
+            }
+        }
+    };
+}
+
+winit_key_codes!(
+    KeyA,
+    KeyB,
+    KeyC,
+    KeyD,
+    KeyE,
+    KeyF,
+    KeyG,
+    KeyH,
+    KeyI,
+    KeyJ,
+    KeyK,
+    KeyL,
+    KeyM,
+    KeyN,
+    KeyO,
+    KeyP,
+    KeyQ,
+    KeyR,
+    KeyS,
+    KeyT,
+    KeyU,
+    KeyV,
+    KeyW,
+    KeyX,
+    KeyY,
+    KeyZ,
+    Digit0,
+    Digit1,
+    Digit2,
+    Digit3,
+    Digit4,
+    Digit5,
+    Digit6,
+    Digit7,
+    Digit8,
+    Digit9,
+    Numpad0,
+    Numpad1,
+    Numpad2,
+    Numpad3,
+    Numpad4,
+    Numpad5,
+    Numpad6,
+    Numpad7,
+    Numpad8,
+    Numpad9,
+    NumpadAdd,
+    NumpadSubtract,
+    NumpadMultiply,
+    NumpadDivide,
+    NumpadDecimal,
+    NumpadEnter,
+    ArrowUp,
+    ArrowDown,
+    ArrowLeft,
+    ArrowRight,
+    Space,
+    Enter,
+    Backspace,
+    Escape,
+    Tab,
+    Minus,
+    Equal,
+    BracketLeft,
+    BracketRight,
+    Backslash,
+    Semicolon,
+    Quote,
+    Backquote,
+    Comma,
+    Period,
+    Slash,
+    ShiftLeft,
+    ShiftRight,
+    ControlLeft,
+    ControlRight,
+    AltLeft,
+    AltRight,
+    Insert,
+    Delete,
+    Home,
+    End,
+    PageUp,
+    PageDown,
+    F1,
+    F2,
+    F3,
+    F4,
+    F5,
+    F6,
+    F7,
+    F8,
+    F9,
+    F10,
+    F11,
+    F12,
+);
+
+/// Mapping of keyboard keys to handset keys.
+pub struct KeyMap {
+    keys: HashMap<WinitKeyCode, KeyCode>,
+}
+
+impl Default for KeyMap {
+    fn default() -> Self {
+        let mut result = Self { keys: HashMap::new() };
+        result.load(DEFAULT_KEY_MAP).unwrap();
+
+        result
+    }
+}
+
+impl KeyMap {
+    /// Adds `key = handset key` lines from `config` over current mapping. Lines starting with `#` are ignored.
+    pub fn load(&mut self, config: &str) -> anyhow::Result<()> {
+        for line in config.lines().map(|x| x.trim()) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let Some((key, handset_key)) = line.split_once('=') else {
+                anyhow::bail!("Invalid key map line: {}", line);
+            };
+
+            let key = parse_winit_key_code(key.trim()).ok_or_else(|| anyhow::anyhow!("Unknown key: {}", key.trim()))?;
+            let handset_key = KeyCode::parse(handset_key.trim()).ok_or_else(|| anyhow::anyhow!("Unknown handset key: {}", handset_key.trim()))?;
+
+            self.keys.insert(key, handset_key);
+        }
+
+        Ok(())
+    }
+
+    pub fn get(&self, key: PhysicalKey) -> Option<KeyCode> {
+        match key {
+            PhysicalKey::Code(x) => self.keys.get(&x).copied(),
+            _ => None,
+        }
+    }
+}
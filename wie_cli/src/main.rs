@@ -2,84 +2,90 @@ extern crate alloc;
 
 mod audio_sink;
 mod database;
+mod file_storage;
+mod gamepad;
+mod key_map;
 mod window;
 
 use core::str;
 use std::{
-    collections::{hash_map::Entry, HashMap},
-    error::Error,
+    collections::VecDeque,
     fs,
     io::stderr,
-    sync::mpsc::{channel, Receiver, Sender},
+    path::Path,
+    sync::{Arc, Mutex},
     thread,
     time::{SystemTime, UNIX_EPOCH},
 };
 
 use clap::Parser;
-use midir::MidiOutput;
-use rodio::{buffer::SamplesBuffer, OutputStream, Sink};
-use winit::keyboard::{KeyCode as WinitKeyCode, PhysicalKey};
+use rodio::{OutputStream, Source};
 
-use wie_backend::{extract_zip, Emulator, Event, Instant, KeyCode, Platform, Screen};
+use wie_backend::{
+    canvas::{BdfFont, DeviceFontMetrics, FontRegistry, FontSize},
+    extract_zip, DeviceEffects, DeviceProfile, Emulator, Event, HangulLayout, Instant, Platform, Screen,
+};
 use wie_j2me::J2MEEmulator;
 use wie_ktf::KtfEmulator;
 use wie_lgt::LgtEmulator;
 use wie_skt::SktEmulator;
+use wie_util::Charset;
 
 use self::{
-    audio_sink::AudioSink,
+    audio_sink::{AudioSink, QueueSource},
     database::DatabaseRepository,
-    window::{WindowCallbackEvent, WindowImpl},
+    file_storage::FileStorageRepository,
+    gamepad::{Gamepad, GamepadMap},
+    key_map::KeyMap,
+    window::{WindowCallbackEvent, WindowHandle, WindowImpl},
 };
 
 struct WieCliPlatform {
-    audio_thread_tx: Sender<(u8, u32, Vec<i16>)>,
+    audio_queue: Arc<Mutex<VecDeque<i16>>>,
     database_repository: DatabaseRepository,
-    window: Box<dyn Screen>,
+    file_storage_repository: FileStorageRepository,
+    window: WindowHandle,
+    device_effects: Box<dyn DeviceEffects>,
+    device_profile: DeviceProfile,
 }
 
 impl WieCliPlatform {
-    fn new(window: Box<dyn Screen>) -> Self {
-        let (tx, rx) = channel();
-        thread::spawn(|| Self::audio_thread(rx));
+    fn new(window: WindowHandle, device_effects: Box<dyn DeviceEffects>, device_profile: DeviceProfile) -> Self {
+        let audio_queue = Arc::new(Mutex::new(VecDeque::new()));
+
+        let audio_queue_clone = audio_queue.clone();
+        thread::spawn(move || Self::audio_thread(audio_queue_clone));
 
         Self {
-            audio_thread_tx: tx,
+            audio_queue,
             database_repository: DatabaseRepository::new(),
+            file_storage_repository: FileStorageRepository::new(),
             window,
+            device_effects,
+            device_profile,
         }
     }
 
-    fn audio_thread(rx: Receiver<(u8, u32, Vec<i16>)>) {
-        let default_output = OutputStream::try_default();
-        if default_output.is_err() {
-            // do nothing if we can't open output
-            loop {
-                rx.recv().unwrap();
-            }
-        }
+    fn audio_thread(audio_queue: Arc<Mutex<VecDeque<i16>>>) {
+        // do nothing if we can't open output
+        let Ok((_output_stream, stream_handle)) = OutputStream::try_default() else {
+            return;
+        };
 
-        let (_output_stream, stream_handle) = default_output.unwrap();
-        let sink = Sink::try_new(&stream_handle).unwrap();
+        if stream_handle.play_raw(QueueSource::new(audio_queue).convert_samples()).is_err() {
+            return;
+        }
 
+        // output stream should be kept alive
         loop {
-            let result = rx.recv();
-            if result.is_err() {
-                break;
-            }
-            let (channel, sampling_rate, wave_data) = result.unwrap();
-
-            let buffer = SamplesBuffer::new(channel as _, sampling_rate as _, wave_data);
-
-            // TODO we should be able to play multiple audio at once
-            sink.append(buffer);
+            thread::park();
         }
     }
 }
 
 impl Platform for WieCliPlatform {
     fn screen(&mut self) -> &mut dyn Screen {
-        self.window.as_mut()
+        &mut self.window
     }
 
     fn now(&self) -> Instant {
@@ -93,17 +99,20 @@ impl Platform for WieCliPlatform {
         &self.database_repository
     }
 
+    fn file_storage_repository(&self) -> &dyn wie_backend::FileStorageRepository {
+        &self.file_storage_repository
+    }
+
     fn audio_sink(&self) -> Box<dyn wie_backend::AudioSink> {
-        let midi_out = (|| {
-            let midi_out = MidiOutput::new("wie_cli")?;
-            let midi_ports = midi_out.ports();
-            let out_port = midi_ports.last().ok_or_else(|| anyhow::anyhow!("No MIDI output port"))?;
+        Box::new(AudioSink::new(self.audio_queue.clone()))
+    }
 
-            Ok::<_, Box<dyn Error>>(midi_out.connect(out_port, "wie_cli")?)
-        })()
-        .ok();
+    fn device_effects(&self) -> &dyn DeviceEffects {
+        self.device_effects.as_ref()
+    }
 
-        Box::new(AudioSink::new(midi_out, self.audio_thread_tx.clone()))
+    fn device_profile(&self) -> DeviceProfile {
+        self.device_profile.clone()
     }
 
     fn write_stdout(&self, buf: &[u8]) {
@@ -117,11 +126,47 @@ impl Platform for WieCliPlatform {
 
         tracing::info!("stderr: {}", str)
     }
+
+    fn exit(&mut self) {
+        self.window.exit()
+    }
 }
 
 #[derive(Parser)]
 struct Args {
     filename: String,
+    /// BDF font to use for small text
+    #[arg(long)]
+    font_small: Option<String>,
+    /// BDF font to use for medium text
+    #[arg(long)]
+    font_medium: Option<String>,
+    /// BDF font to use for large text
+    #[arg(long)]
+    font_large: Option<String>,
+    /// Pixel heights of small, medium and large built-in fonts, e.g. `12,16,16`. Ignored for sizes with a BDF font
+    #[arg(long, value_delimiter = ',', num_args = 3)]
+    font_heights: Option<Vec<u32>>,
+    /// Handset charset of native strings, e.g. `EUC-KR`, `Johab` or `UTF-8`
+    #[arg(long)]
+    charset: Option<String>,
+    /// Number of vibration strength levels reported to applications
+    #[arg(long)]
+    vibration_levels: Option<i32>,
+    /// Hangul keypad layout of text input, `chunjiin`, `narageul` or `sky`
+    #[arg(long)]
+    hangul_layout: Option<String>,
+    /// Key map file, with `key = handset key` lines overriding default mapping.
+    /// F12 switches between handset keys and typing text into text fields
+    #[arg(long)]
+    key_map: Option<String>,
+    /// Gamepad map file, with `button = handset key` lines overriding default mapping.
+    /// Lines under a `[name]` header only apply to the game file named `name`, without extension
+    #[arg(long)]
+    gamepad_map: Option<String>,
+    /// Emulate a handset with touch screen, sending mouse input as pointer events
+    #[arg(long)]
+    pointer: bool,
 }
 
 fn main() -> anyhow::Result<()> {
@@ -130,12 +175,60 @@ fn main() -> anyhow::Result<()> {
         .with_env_filter(tracing_subscriber::EnvFilter::from_default_env())
         .init();
 
-    start(&Args::parse().filename)
+    let args = Args::parse();
+
+    let font_metrics = match &args.font_heights {
+        Some(heights) => DeviceFontMetrics {
+            small: heights[0],
+            medium: heights[1],
+            large: heights[2],
+        },
+        None => DeviceFontMetrics::default(),
+    };
+    let mut fonts = FontRegistry::new(font_metrics);
+    for (size, path) in [
+        (FontSize::Small, &args.font_small),
+        (FontSize::Medium, &args.font_medium),
+        (FontSize::Large, &args.font_large),
+    ] {
+        if let Some(path) = path {
+            let font = BdfFont::parse(&fs::read(path)?)?;
+            fonts.register_bitmap_font(size, font);
+        }
+    }
+
+    let mut key_map = KeyMap::default();
+    if let Some(path) = &args.key_map {
+        key_map.load(&fs::read_to_string(path)?)?;
+    }
+
+    let mut gamepad_map = GamepadMap::default();
+    if let Some(path) = &args.gamepad_map {
+        let game = Path::new(&args.filename).file_stem().and_then(|x| x.to_str()).unwrap_or_default();
+        gamepad_map.load(&fs::read_to_string(path)?, game)?;
+    }
+
+    let mut device_profile = DeviceProfile {
+        fonts,
+        pointer_events: args.pointer,
+        ..Default::default()
+    };
+    if let Some(name) = &args.charset {
+        device_profile.charset = Charset::from_name(name).ok_or_else(|| anyhow::anyhow!("Unknown charset {}", name))?;
+    }
+    if let Some(levels) = args.vibration_levels {
+        device_profile.vibration_level_count = levels;
+    }
+    if let Some(name) = &args.hangul_layout {
+        device_profile.hangul_layout = HangulLayout::from_name(name).ok_or_else(|| anyhow::anyhow!("Unknown hangul layout {}", name))?;
+    }
+
+    start(&args.filename, key_map, gamepad_map, device_profile)
 }
 
-pub fn start(filename: &str) -> anyhow::Result<()> {
+pub fn start(filename: &str, key_map: KeyMap, gamepad_map: GamepadMap, device_profile: DeviceProfile) -> anyhow::Result<()> {
     let window = WindowImpl::new(240, 320).unwrap(); // TODO hardcoded size
-    let platform = Box::new(WieCliPlatform::new(Box::new(window.handle())));
+    let platform = Box::new(WieCliPlatform::new(window.handle(), Box::new(window.handle()), device_profile));
 
     let buf = fs::read(filename)?;
     let mut emulator: Box<dyn Emulator> = if filename.ends_with("zip") {
@@ -173,71 +266,33 @@ pub fn start(filename: &str) -> anyhow::Result<()> {
         anyhow::bail!("Unknown file format");
     };
 
-    let mut key_events = HashMap::new();
+    let mut gamepad = Gamepad::new(gamepad_map);
+
     window.run(move |event| {
         match event {
             WindowCallbackEvent::Update => {
-                let now = SystemTime::now();
-
-                for entry in key_events.iter_mut() {
-                    let (keycode, time) = entry;
-
-                    // TODO const
-                    if now.duration_since(*time).unwrap().as_millis() > 100 {
-                        emulator.handle_event(Event::Keyrepeat(*keycode));
-                        *time = now;
-                    }
-                }
-
+                gamepad.poll(|event| emulator.handle_event(event));
                 emulator.tick()?
             }
             WindowCallbackEvent::Redraw => emulator.handle_event(Event::Redraw),
+            // key repeat is generated by the emulator, so only raw key state is sent
             WindowCallbackEvent::Keydown(x) => {
-                if let Some(keycode) = convert_key(x) {
-                    let entry = key_events.entry(keycode);
-                    if let Entry::Vacant(entry) = entry {
-                        emulator.handle_event(Event::Keydown(keycode));
-
-                        let now = SystemTime::now();
-
-                        entry.insert(now);
-                    }
+                if let Some(keycode) = key_map.get(x) {
+                    emulator.handle_event(Event::Keydown(keycode));
                 }
             }
             WindowCallbackEvent::Keyup(x) => {
-                if let Some(keycode) = convert_key(x) {
-                    if key_events.contains_key(&keycode) {
-                        key_events.remove(&keycode);
-                        emulator.handle_event(Event::Keyup(keycode));
-                    }
+                if let Some(keycode) = key_map.get(x) {
+                    emulator.handle_event(Event::Keyup(keycode));
                 }
             }
+            WindowCallbackEvent::PointerDown(x, y) => emulator.handle_event(Event::PointerDown(x, y)),
+            WindowCallbackEvent::PointerMove(x, y) => emulator.handle_event(Event::PointerMove(x, y)),
+            WindowCallbackEvent::PointerUp(x, y) => emulator.handle_event(Event::PointerUp(x, y)),
+            WindowCallbackEvent::TextInput(text) => emulator.handle_event(Event::TextInput(text)),
+            WindowCallbackEvent::Close => emulator.close(),
         }
 
         Ok(())
     })
 }
-
-fn convert_key(key: PhysicalKey) -> Option<KeyCode> {
-    match key {
-        PhysicalKey::Code(WinitKeyCode::Digit1) => Some(KeyCode::NUM1),
-        PhysicalKey::Code(WinitKeyCode::Digit2) => Some(KeyCode::NUM2),
-        PhysicalKey::Code(WinitKeyCode::Digit3) => Some(KeyCode::NUM3),
-        PhysicalKey::Code(WinitKeyCode::KeyQ) => Some(KeyCode::NUM4),
-        PhysicalKey::Code(WinitKeyCode::KeyW) => Some(KeyCode::NUM5),
-        PhysicalKey::Code(WinitKeyCode::KeyE) => Some(KeyCode::NUM6),
-        PhysicalKey::Code(WinitKeyCode::KeyA) => Some(KeyCode::NUM7),
-        PhysicalKey::Code(WinitKeyCode::KeyS) => Some(KeyCode::NUM8),
-        PhysicalKey::Code(WinitKeyCode::KeyD) => Some(KeyCode::NUM9),
-        PhysicalKey::Code(WinitKeyCode::KeyZ) => Some(KeyCode::STAR),
-        PhysicalKey::Code(WinitKeyCode::KeyX) => Some(KeyCode::NUM0),
-        PhysicalKey::Code(WinitKeyCode::KeyC) => Some(KeyCode::HASH),
-        PhysicalKey::Code(WinitKeyCode::Space) => Some(KeyCode::OK),
-        PhysicalKey::Code(WinitKeyCode::ArrowUp) => Some(KeyCode::UP),
-        PhysicalKey::Code(WinitKeyCode::ArrowDown) => Some(KeyCode::DOWN),
-        PhysicalKey::Code(WinitKeyCode::ArrowLeft) => Some(KeyCode::LEFT),
-        PhysicalKey::Code(WinitKeyCode::ArrowRight) => Some(KeyCode::RIGHT),
-        PhysicalKey::Code(WinitKeyCode::Backspace) => Some(KeyCode::CLEAR),
-        _ => None,
-    }
-}
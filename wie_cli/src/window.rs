@@ -1,25 +1,36 @@
 use alloc::sync::Arc;
 use core::{fmt::Debug, fmt::Formatter, num::NonZeroU32};
-use std::fmt;
+use std::{fmt, time::Instant};
 
 use fast_image_resize::ResizeAlg;
 use fast_image_resize::{PixelType, ResizeOptions, SrcCropping};
 use softbuffer::{Context, Surface};
 use winit::{
     application::ApplicationHandler,
-    dpi::{LogicalSize, PhysicalSize},
-    event::{ElementState, KeyEvent, StartCause, WindowEvent},
+    dpi::{LogicalSize, PhysicalPosition, PhysicalSize},
+    event::{ElementState, Ime, KeyEvent, MouseButton, StartCause, WindowEvent},
     event_loop::{ActiveEventLoop, ControlFlow, EventLoop, EventLoopProxy},
-    keyboard::PhysicalKey,
+    keyboard::{Key, KeyCode, NamedKey, PhysicalKey},
     window::{Window as WinitWindow, WindowId},
 };
 
-use wie_backend::{canvas::Image, Screen};
+use wie_backend::{canvas::Image, DeviceEffect, DeviceEffects, Screen};
+
+const SHAKE_OFFSET: i32 = 4;
+const SHAKE_INTERVAL_MILLIS: u128 = 30;
+
+// switches between handset keys and typing text on host keyboard
+const TEXT_INPUT_TOGGLE_KEY: KeyCode = KeyCode::F12;
 
 #[derive(Debug)]
 pub enum WindowInternalEvent {
     RequestRedraw,
     Paint(Vec<u32>),
+    /// Shakes the window for given milliseconds, stopping if zero.
+    Vibrate(u64),
+    Backlight(bool),
+    /// Application exited, closes the window.
+    Exit,
 }
 
 pub enum WindowCallbackEvent {
@@ -27,6 +38,14 @@ pub enum WindowCallbackEvent {
     Redraw,
     Keydown(PhysicalKey),
     Keyup(PhysicalKey),
+    /// Pointer events, in content screen coordinates.
+    PointerDown(i32, i32),
+    PointerMove(i32, i32),
+    PointerUp(i32, i32),
+    /// Text typed on host keyboard while text input is enabled.
+    TextInput(String),
+    /// Window is being closed by user.
+    Close,
 }
 
 pub struct WindowHandle {
@@ -41,6 +60,10 @@ impl WindowHandle {
 
         Ok(())
     }
+
+    pub fn exit(&self) {
+        self.send_event(WindowInternalEvent::Exit).unwrap()
+    }
 }
 
 impl Screen for WindowHandle {
@@ -67,6 +90,19 @@ impl Screen for WindowHandle {
     }
 }
 
+impl DeviceEffects for WindowHandle {
+    fn apply(&self, effect: DeviceEffect) {
+        let event = match effect {
+            DeviceEffect::Vibrate { duration, .. } => WindowInternalEvent::Vibrate(duration),
+            DeviceEffect::StopVibration => WindowInternalEvent::Vibrate(0),
+            DeviceEffect::Backlight { on, .. } => WindowInternalEvent::Backlight(on),
+            DeviceEffect::Led { .. } => return,
+        };
+
+        self.send_event(event).unwrap()
+    }
+}
+
 pub struct WindowImpl {
     width: u32,
     height: u32,
@@ -109,6 +145,14 @@ impl WindowImpl {
             surface: None,
             callback: Box::new(callback),
             last_frame: None,
+            backlight: true,
+            shake_until: None,
+            shake_origin: None,
+            shake_offset: 0,
+            cursor_position: None,
+            pointer_pressed: false,
+            text_input: false,
+            ime_composing: false,
         };
 
         Ok(self.event_loop.run_app(&mut handler)?)
@@ -229,6 +273,25 @@ where
     window_size: PhysicalSize<u32>,
     /// Last content screen image data.
     last_frame: Option<Vec<u32>>,
+    /// Whether the backlight is on. Screen is dimmed if not.
+    backlight: bool,
+
+    /// Time to stop shaking the window on vibration.
+    shake_until: Option<Instant>,
+    /// Window position before shaking.
+    shake_origin: Option<PhysicalPosition<i32>>,
+    /// Current horizontal offset from `shake_origin`.
+    shake_offset: i32,
+
+    /// Last cursor position in content screen coordinates, `None` if outside the content.
+    cursor_position: Option<(i32, i32)>,
+    /// Whether the left mouse button is pressed on the content.
+    pointer_pressed: bool,
+
+    /// Whether keys producing text are sent as text instead of mapped handset keys.
+    text_input: bool,
+    /// Whether host input method is composing text, which is sent when committed.
+    ime_composing: bool,
 
     window: Option<Arc<WinitWindow>>,
     context: Option<Context<Arc<WinitWindow>>>,
@@ -249,6 +312,18 @@ where
         }
     }
 
+    /// Converts window position into content screen coordinates.
+    fn to_content_position(&self, position: PhysicalPosition<f64>) -> Option<(i32, i32)> {
+        let scale = self.scaler.scale();
+        let (x, y) = ((position.x / scale).floor(), (position.y / scale).floor());
+
+        if x < 0.0 || y < 0.0 || x >= self.content_size.width as f64 || y >= self.content_size.height as f64 {
+            return None;
+        }
+
+        Some((x as i32, y as i32))
+    }
+
     /// Sets the native/user scale factor.
     /// After calling this you'll need to call [`Self::on_resize`] to update the surface accordingly.
     fn update_scale_factor(&mut self, native: Option<f64>, user: Option<f64>) {
@@ -334,9 +409,96 @@ where
             );
             return None;
         }
+        if !self.backlight {
+            win_buf.iter_mut().for_each(|x| *x = (*x >> 2) & 0x3f3f3f);
+        }
         win_buf.present().unwrap();
         Some(())
     }
+
+    fn on_key(&mut self, event: KeyEvent, event_loop: &ActiveEventLoop) {
+        if event.repeat {
+            return;
+        }
+
+        if event.physical_key == PhysicalKey::Code(TEXT_INPUT_TOGGLE_KEY) {
+            if event.state == ElementState::Pressed {
+                self.toggle_text_input();
+            }
+            return;
+        }
+
+        // printable keys are typed as text, others like arrows and backspace stay mapped
+        let is_text_key = matches!(event.logical_key, Key::Character(_) | Key::Named(NamedKey::Space));
+        if self.text_input && is_text_key {
+            if let (ElementState::Pressed, false, Some(text)) = (event.state, self.ime_composing, event.text) {
+                self.callback(WindowCallbackEvent::TextInput(text.to_string()), event_loop);
+            }
+            return;
+        }
+
+        match event.state {
+            ElementState::Pressed => {
+                self.callback(WindowCallbackEvent::Keydown(event.physical_key), event_loop);
+            }
+            ElementState::Released => {
+                self.callback(WindowCallbackEvent::Keyup(event.physical_key), event_loop);
+            }
+        }
+    }
+
+    fn toggle_text_input(&mut self) {
+        self.text_input = !self.text_input;
+        self.ime_composing = false;
+
+        let Some(window) = self.window.as_ref() else {
+            return;
+        };
+        window.set_ime_allowed(self.text_input);
+        window.set_title(if self.text_input { "WIE (text input)" } else { "WIE" });
+    }
+
+    /// Starts shaking the window, or stops it if `duration` is zero.
+    fn vibrate(&mut self, duration: u64) {
+        let Some(window) = self.window.as_ref() else {
+            return;
+        };
+        if self.shake_origin.is_none() {
+            // some platforms don't allow positioning windows
+            let Ok(position) = window.outer_position() else {
+                return;
+            };
+            self.shake_origin = Some(position);
+        }
+
+        self.shake_until = Some(Instant::now() + core::time::Duration::from_millis(duration));
+    }
+
+    /// Moves the shaking window back and forth, restoring its position after the vibration.
+    fn update_shake(&mut self) {
+        let (Some(until), Some(origin), Some(window)) = (self.shake_until, self.shake_origin, self.window.as_ref()) else {
+            return;
+        };
+
+        let now = Instant::now();
+        let offset = if now < until {
+            if (until - now).as_millis() / SHAKE_INTERVAL_MILLIS % 2 == 0 {
+                SHAKE_OFFSET
+            } else {
+                -SHAKE_OFFSET
+            }
+        } else {
+            self.shake_until = None;
+            self.shake_origin = None;
+
+            0
+        };
+
+        if offset != self.shake_offset {
+            window.set_outer_position(PhysicalPosition::new(origin.x + offset, origin.y));
+            self.shake_offset = offset;
+        }
+    }
 }
 
 impl<C> ApplicationHandler<WindowInternalEvent> for ApplicationHandlerImpl<C>
@@ -344,6 +506,8 @@ where
     C: FnMut(WindowCallbackEvent) -> wie_util::Result<()> + 'static,
 {
     fn new_events(&mut self, event_loop: &ActiveEventLoop, _cause: StartCause) {
+        self.update_shake();
+
         self.callback(WindowCallbackEvent::Update, event_loop)
     }
 
@@ -366,7 +530,7 @@ where
         self.on_resize();
     }
 
-    fn user_event(&mut self, _event_loop: &ActiveEventLoop, event: WindowInternalEvent) {
+    fn user_event(&mut self, event_loop: &ActiveEventLoop, event: WindowInternalEvent) {
         match event {
             WindowInternalEvent::RequestRedraw => {
                 self.window.as_ref().unwrap().request_redraw();
@@ -375,27 +539,69 @@ where
                 self.last_frame = Some(data);
                 self.paint_last_frame();
             }
+            WindowInternalEvent::Vibrate(duration) => {
+                self.vibrate(duration);
+            }
+            WindowInternalEvent::Backlight(on) => {
+                self.backlight = on;
+                self.paint_last_frame();
+            }
+            WindowInternalEvent::Exit => {
+                tracing::info!("Application exited");
+
+                event_loop.exit();
+            }
         }
     }
 
     fn window_event(&mut self, event_loop: &ActiveEventLoop, _id: WindowId, event: WindowEvent) {
         match event {
-            WindowEvent::CloseRequested => event_loop.exit(),
-            WindowEvent::KeyboardInput {
-                event:
-                    KeyEvent {
-                        physical_key,
-                        state,
-                        repeat: false,
-                        ..
-                    },
+            WindowEvent::CloseRequested => {
+                self.callback(WindowCallbackEvent::Close, event_loop);
+
+                event_loop.exit()
+            }
+            WindowEvent::KeyboardInput { event, .. } => self.on_key(event, event_loop),
+            WindowEvent::Ime(ime) => match ime {
+                Ime::Preedit(text, _) => self.ime_composing = !text.is_empty(),
+                Ime::Commit(text) => {
+                    self.ime_composing = false;
+                    self.callback(WindowCallbackEvent::TextInput(text), event_loop);
+                }
+                Ime::Enabled | Ime::Disabled => self.ime_composing = false,
+            },
+            WindowEvent::CursorMoved { position, .. } => {
+                let position = self.to_content_position(position);
+
+                if !self.pointer_pressed {
+                    self.cursor_position = position;
+                } else if let Some((x, y)) = position {
+                    // while dragging, last position on the content is kept
+                    self.cursor_position = position;
+                    self.callback(WindowCallbackEvent::PointerMove(x, y), event_loop);
+                }
+            }
+            WindowEvent::CursorLeft { .. } => {
+                if !self.pointer_pressed {
+                    self.cursor_position = None;
+                }
+            }
+            WindowEvent::MouseInput {
+                state,
+                button: MouseButton::Left,
                 ..
             } => match state {
                 ElementState::Pressed => {
-                    self.callback(WindowCallbackEvent::Keydown(physical_key), event_loop);
+                    if let Some((x, y)) = self.cursor_position {
+                        self.pointer_pressed = true;
+                        self.callback(WindowCallbackEvent::PointerDown(x, y), event_loop);
+                    }
                 }
                 ElementState::Released => {
-                    self.callback(WindowCallbackEvent::Keyup(physical_key), event_loop);
+                    if let (true, Some((x, y))) = (self.pointer_pressed, self.cursor_position) {
+                        self.pointer_pressed = false;
+                        self.callback(WindowCallbackEvent::PointerUp(x, y), event_loop);
+                    }
                 }
             },
             WindowEvent::RedrawRequested => {
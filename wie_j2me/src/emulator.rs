@@ -80,12 +80,16 @@ impl J2MEEmulator {
 
 impl Emulator for J2MEEmulator {
     fn handle_event(&mut self, event: Event) {
-        self.system.event_queue().push(event)
+        self.system.handle_event(event)
     }
 
     fn tick(&mut self) -> Result<()> {
         self.system.tick()
     }
+
+    fn close(&mut self) {
+        self.system.close()
+    }
 }
 
 struct J2MEDescriptor {
@@ -11,7 +11,7 @@ use java_runtime::{Runtime, RT_RUSTJAR};
 use jvm::{runtime::JavaLangString, JavaError, Jvm};
 
 use wie_backend::System;
-use wie_util::{Result, WieError};
+use wie_util::{Charset, Result, WieError};
 
 pub use context::{WieJavaClassProto, WieJvmContext};
 pub use jvm_implementation::{JvmImplementation, RustJavaJvmImplementation};
@@ -40,11 +40,21 @@ impl JvmSupport {
             format!("{}:{}", RT_RUSTJAR, WIE_RUSTJAR,)
         };
 
-        let properties = [("file.encoding", "EUC-KR"), ("java.class.path", &class_path)]
-            .iter()
-            .chain(properties.iter())
-            .copied()
-            .collect();
+        // johab is only handled natively, java strings stay in EUC-KR
+        let encoding = match system.charset() {
+            Charset::Johab => Charset::Ksc5601,
+            x => x,
+        }
+        .name();
+        let properties = [
+            ("file.encoding", encoding),
+            ("microedition.encoding", encoding),
+            ("java.class.path", &class_path),
+        ]
+        .iter()
+        .chain(properties.iter())
+        .copied()
+        .collect();
         let jvm = Jvm::new(
             java_runtime::get_bootstrap_class_loader(Box::new(runtime.clone())),
             move || runtime.current_task_id(),
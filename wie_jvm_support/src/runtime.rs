@@ -169,14 +169,20 @@ where
         Ok(Box::new(FileImpl::new(self.system.clone(), path, write)?))
     }
 
-    async fn unlink(&self, _path: &str) -> Result<(), IOError> {
-        Err(IOError::Unsupported)
+    async fn unlink(&self, path: &str) -> Result<(), IOError> {
+        tracing::debug!("unlink({:?})", path);
+
+        if self.system.filesystem().remove(path) {
+            Ok(())
+        } else {
+            Err(IOError::NotFound)
+        }
     }
 
     async fn metadata(&self, path: &str) -> IOResult<FileStat> {
         let filesystem = self.system.filesystem();
 
-        if path.is_empty() || path.ends_with("/") {
+        if path.is_empty() || path.ends_with("/") || filesystem.is_directory(path) {
             return Ok(FileStat {
                 size: 0,
                 r#type: FileType::Directory,
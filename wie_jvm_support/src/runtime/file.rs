@@ -1,4 +1,4 @@
-use alloc::{boxed::Box, string::String, sync::Arc, vec::Vec};
+use alloc::{boxed::Box, string::String, sync::Arc};
 use core::sync::atomic::{AtomicU64, Ordering};
 
 use wie_backend::System;
@@ -19,7 +19,7 @@ impl FileImpl {
 
             if write {
                 if !filesystem.exists(path) {
-                    filesystem.add(path, Vec::new());
+                    filesystem.write(path, 0, &[]);
                 }
             } else if !filesystem.exists(path) {
                 return Err(IOError::NotFound);
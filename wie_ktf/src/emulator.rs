@@ -5,7 +5,7 @@ use jvm::{runtime::JavaLangString, ClassInstance, Result as JvmResult};
 use wie_backend::{extract_zip, Emulator, Event, Platform, System};
 use wie_core_arm::{Allocator, ArmCore};
 use wie_jvm_support::JvmSupport;
-use wie_util::{Result, WieError};
+use wie_util::{Charset, Result, WieError};
 
 use crate::runtime::KtfJvmSupport;
 
@@ -19,19 +19,19 @@ pub struct KtfEmulator {
 impl KtfEmulator {
     pub fn from_archive(platform: Box<dyn Platform>, files: BTreeMap<String, Vec<u8>>) -> Result<Self> {
         let adf = files.get("__adf__").unwrap();
-        let adf = KtfAdf::parse(adf);
+        let adf = KtfAdf::parse(adf, platform.device_profile().charset);
 
-        tracing::info!("Loading app {}, mclass {}", adf.aid, adf.mclass);
+        tracing::info!("Loading app {} ({}), mclass {}", adf.name, adf.aid, adf.mclass);
 
         let jar_filename = format!("{}.jar", adf.aid);
 
-        Self::load(platform, &jar_filename, &adf.aid, Some(adf.mclass), &files)
+        Self::load(platform, &jar_filename, &adf.aid, Some(&adf.name), Some(adf.mclass), &files)
     }
 
     pub fn from_jar(platform: Box<dyn Platform>, jar_filename: &str, jar: Vec<u8>, id: &str, main_class_name: Option<String>) -> Result<Self> {
         let files = [(jar_filename.to_owned(), jar)].into_iter().collect();
 
-        Self::load(platform, jar_filename, id, main_class_name, &files)
+        Self::load(platform, jar_filename, id, None, main_class_name, &files)
     }
 
     pub fn loadable_archive(files: &BTreeMap<String, Vec<u8>>) -> bool {
@@ -54,11 +54,15 @@ impl KtfEmulator {
         platform: Box<dyn Platform>,
         jar_filename: &str,
         id: &str,
+        name: Option<&str>,
         main_class_name: Option<String>,
         files: &BTreeMap<String, Vec<u8>>,
     ) -> Result<Self> {
         let mut core = ArmCore::new()?;
         let mut system = System::new(platform, id);
+        if let Some(name) = name {
+            system.set_app_name(name);
+        }
 
         for (path, data) in files {
             let path = path.trim_start_matches("P/");
@@ -115,7 +119,7 @@ impl KtfEmulator {
 
 impl Emulator for KtfEmulator {
     fn handle_event(&mut self, event: Event) {
-        self.system.event_queue().push(event)
+        self.system.handle_event(event)
     }
 
     fn tick(&mut self) -> Result<()> {
@@ -127,29 +131,38 @@ impl Emulator for KtfEmulator {
             }
         })
     }
+
+    fn close(&mut self) {
+        self.system.close()
+    }
 }
 
 struct KtfAdf {
     aid: String,
     mclass: String,
+    name: String,
 }
 
 impl KtfAdf {
-    pub fn parse(data: &[u8]) -> Self {
+    pub fn parse(data: &[u8], charset: Charset) -> Self {
         let mut aid = String::new();
         let mut mclass = String::new();
+        let mut name = String::new();
 
         let mut lines = data.split(|x| *x == b'\n');
 
         for line in &mut lines {
+            let line = line.strip_suffix(b"\r").unwrap_or(line);
+
             if line.starts_with(b"AID:") {
-                aid = String::from_utf8_lossy(&line[4..]).into();
+                aid = charset.decode(&line[4..]);
             } else if line.starts_with(b"MClass:") {
-                mclass = String::from_utf8_lossy(&line[7..]).into();
+                mclass = charset.decode(&line[7..]);
+            } else if line.starts_with(b"Name:") {
+                name = charset.decode(&line[5..]);
             }
-            // TODO load name, it's in euc-kr..
         }
 
-        Self { aid, mclass }
+        Self { aid, mclass, name }
     }
 }
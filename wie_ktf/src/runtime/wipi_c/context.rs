@@ -111,7 +111,7 @@ impl WIPICContext for KtfWIPICContext {
         self.core.run_function(address, args).await
     }
 
-    fn spawn(&mut self, callback: WIPICMethodBody) -> Result<()> {
+    fn spawn(&mut self, callback: WIPICMethodBody) -> Result<u64> {
         struct SpawnProxy {
             context: KtfWIPICContext,
             callback: WIPICMethodBody,
@@ -128,12 +128,10 @@ impl WIPICContext for KtfWIPICContext {
             }
         }
 
-        self.system.spawn(SpawnProxy {
+        Ok(self.system.spawn(SpawnProxy {
             context: self.clone(),
             callback,
-        });
-
-        Ok(())
+        }))
     }
 
     async fn get_resource_size(&self, name: &str) -> Result<Option<usize>> {
@@ -20,23 +20,23 @@ where
         kernel::printk.into_body(),
         kernel::sprintk.into_body(),
         gen_stub(2, "MC_knlGetExecNames"),
-        gen_stub(3, "MC_knlExecute"),
+        kernel::execute.into_body(),
         gen_stub(4, "MC_knlMExecute"),
-        gen_stub(5, "MC_knlLoad"),
+        kernel::load.into_body(),
         gen_stub(6, "MC_knlMLoad"),
-        gen_stub(7, "MC_knlExit"),
-        gen_stub(8, "MC_knlProgramStop"),
+        kernel::exit.into_body(),
+        kernel::program_stop.into_body(),
         kernel::get_cur_program_id.into_body(),
-        gen_stub(10, "MC_knlGetParentProgramID"),
+        kernel::get_parent_program_id.into_body(),
         gen_stub(11, "MC_knlGetAppManagerID"),
         gen_stub(12, "MC_knlGetProgramInfo"),
         gen_stub(13, "MC_knlGetAccessLevel"),
-        gen_stub(14, "MC_knlGetProgramName"),
-        gen_stub(15, "MC_knlCreateSharedBuf"),
-        gen_stub(16, "MC_knlDestroySharedBuf"),
-        gen_stub(17, "MC_knlGetSharedBuf"),
-        gen_stub(18, "MC_knlGetSharedBufSize"),
-        gen_stub(19, "MC_knlResizeSharedBuf"),
+        kernel::get_program_name.into_body(),
+        kernel::create_shared_buf.into_body(),
+        kernel::destroy_shared_buf.into_body(),
+        kernel::get_shared_buf.into_body(),
+        kernel::get_shared_buf_size.into_body(),
+        kernel::resize_shared_buf.into_body(),
         kernel::alloc.into_body(),
         kernel::calloc.into_body(),
         kernel::free.into_body(),
@@ -87,26 +87,26 @@ where
 pub fn get_graphics_method_table() -> Vec<WIPICMethodBody> {
     vec![
         graphics::get_image_property.into_body(),
-        gen_stub(1, "MC_grpGetImageFrameBuffer"),
+        graphics::get_image_framebuffer.into_body(),
         graphics::get_screen_framebuffer.into_body(),
         graphics::destroy_offscreen_framebuffer.into_body(),
         graphics::create_offscreen_framebuffer.into_body(),
         graphics::init_context.into_body(),
         graphics::set_context.into_body(),
-        gen_stub(7, "MC_grpGetContext"),
+        graphics::get_context.into_body(),
         graphics::put_pixel.into_body(),
-        gen_stub(9, "MC_grpDrawLine"),
-        gen_stub(10, "MC_grpDrawRect"),
+        graphics::draw_line.into_body(),
+        graphics::draw_rect.into_body(),
         graphics::fill_rect.into_body(),
         graphics::copy_frame_buffer.into_body(),
         graphics::draw_image.into_body(),
         graphics::copy_area.into_body(),
-        gen_stub(15, "MC_grpDrawArc"),
-        gen_stub(16, "MC_grpFillArc"),
+        graphics::draw_arc.into_body(),
+        graphics::fill_arc.into_body(),
         graphics::draw_string.into_body(),
-        gen_stub(18, "MC_grpDrawUnicodeString"),
-        gen_stub(19, "MC_grpGetRGBPixels"),
-        gen_stub(20, "MC_grpSetRGBPixels"),
+        graphics::draw_unicode_string.into_body(),
+        graphics::get_rgb_pixels.into_body(),
+        graphics::set_rgb_pixels.into_body(),
         graphics::flush.into_body(),
         graphics::get_pixel_from_rgb.into_body(),
         graphics::get_rgb_from_pixel.into_body(),
@@ -114,13 +114,13 @@ pub fn get_graphics_method_table() -> Vec<WIPICMethodBody> {
         graphics::repaint.into_body(),
         graphics::get_font.into_body(),
         graphics::get_font_height.into_body(),
-        gen_stub(28, "MC_grpGetFontAscent"),
-        gen_stub(29, "MC_grpGetFontDescent"),
+        graphics::get_font_ascent.into_body(),
+        graphics::get_font_descent.into_body(),
         graphics::get_string_width.into_body(),
-        gen_stub(31, "MC_grpGetUnicodeStringWidth"),
+        graphics::get_unicode_string_width.into_body(),
         graphics::create_image.into_body(),
-        gen_stub(33, "MC_grpDestroyImage"),
-        gen_stub(34, "MC_grpDecodeNextImage"),
+        graphics::destroy_image.into_body(),
+        graphics::decode_next_image.into_body(),
         gen_stub(35, "MC_grpEncodeImage"),
         gen_stub(36, "MC_grpPostEvent"),
         gen_stub(37, "MC_imHandleInput"),
@@ -128,8 +128,8 @@ pub fn get_graphics_method_table() -> Vec<WIPICMethodBody> {
         gen_stub(39, "MC_imGetCurrentMode"),
         gen_stub(40, "MC_imGetSupportModeCount"),
         gen_stub(41, "MC_imGetSupportedModes"),
-        gen_stub(42, "MC_grpFillPolygon"),
-        gen_stub(43, "MC_grpDrawPolygon"),
+        graphics::fill_polygon.into_body(),
+        graphics::draw_polygon.into_body(),
         gen_stub(44, "OEMC_grpShowAnnunciator"),
         gen_stub(45, "OEMC_grpGetAnnunciatorInfo"),
         gen_stub(46, "OEMC_grpSetAnnunciatorIcon"),
@@ -184,9 +184,9 @@ pub fn get_util_method_table() -> Vec<WIPICMethodBody> {
 pub fn get_misc_method_table() -> Vec<WIPICMethodBody> {
     vec![
         misc::back_light.into_body(),
-        gen_stub(1, "MC_miscSetLed"),
-        gen_stub(2, "MC_miscGetLed"),
-        gen_stub(3, "MC_miscGetLedCount"),
+        misc::set_led.into_body(),
+        misc::get_led.into_body(),
+        misc::get_led_count.into_body(),
         gen_stub(4, "OEMC_miscGetCompassData"),
     ]
 }
@@ -216,50 +216,50 @@ pub fn get_database_method_table() -> Vec<WIPICMethodBody> {
 pub fn get_uic_method_table() -> Vec<WIPICMethodBody> {
     vec![
         uic::create_application_context.into_body(),
-        gen_stub(1, "MC_uicGetClass"),
-        gen_stub(2, "MC_uicCreate"),
-        gen_stub(3, "MC_uicDestroy"),
-        gen_stub(4, "MC_uicRepaint"),
-        gen_stub(5, "MC_uicPaint"),
-        gen_stub(6, "MC_uicGetClassName"),
-        gen_stub(7, "MC_uicIsInstance"),
-        gen_stub(8, "MC_uicHandleEvent"),
-        gen_stub(9, "MC_uicConfigure"),
-        gen_stub(10, "MC_uicGetGeometry"),
-        gen_stub(11, "MC_uicSetEnable"),
-        gen_stub(12, "MC_uicSetCallback"),
-        gen_stub(13, "MC_uicSetEventHandler"),
-        gen_stub(14, "MC_uicSetFont"),
-        gen_stub(15, "MC_uicGetFont"),
-        gen_stub(16, "MC_uicSetFgColor"),
-        gen_stub(17, "MC_uicSetBgColor"),
-        gen_stub(18, "MC_uicSetLabel"),
-        gen_stub(19, "MC_uicGetLabel"),
-        gen_stub(20, "MC_uicSetLabelAlignment"),
+        uic::get_class.into_body(),
+        uic::create.into_body(),
+        uic::destroy.into_body(),
+        uic::repaint.into_body(),
+        uic::paint.into_body(),
+        uic::get_class_name.into_body(),
+        uic::is_instance.into_body(),
+        uic::handle_event.into_body(),
+        uic::configure.into_body(),
+        uic::get_geometry.into_body(),
+        uic::set_enable.into_body(),
+        uic::set_callback.into_body(),
+        uic::set_event_handler.into_body(),
+        uic::set_font.into_body(),
+        uic::get_font.into_body(),
+        uic::set_fg_color.into_body(),
+        uic::set_bg_color.into_body(),
+        uic::set_label.into_body(),
+        uic::get_label.into_body(),
+        uic::set_label_alignment.into_body(),
         gen_stub(21, "MC_uicSetTimeMask"),
         gen_stub(22, "MC_uicSetTime"),
         gen_stub(23, "MC_uicSetTimeLong"),
         gen_stub(24, "MC_uicGetTime"),
-        gen_stub(25, "MC_uicAddMenuItem"),
-        gen_stub(26, "MC_uicGetMenuItem"),
-        gen_stub(27, "MC_uicRemoveMenuItem"),
-        gen_stub(28, "MC_uicSetActiveMenuItem"),
-        gen_stub(29, "MC_uicGetActiveMenuItem"),
-        gen_stub(30, "MC_uicInsertText"),
-        gen_stub(31, "MC_uicDeleteText"),
-        gen_stub(32, "MC_uicGetMaxTextSize"),
-        gen_stub(33, "MC_uicSetMaxTextSize"),
-        gen_stub(34, "MC_uicGetTextSize"),
-        gen_stub(35, "MC_uicGetText"),
-        gen_stub(36, "MC_uicAddListItem"),
-        gen_stub(37, "MC_uicGetListItem"),
-        gen_stub(38, "MC_uicRemoveListItem"),
-        gen_stub(39, "MC_uicSetActiveListItem"),
-        gen_stub(40, "MC_uicGetActiveListItem"),
-        gen_stub(41, "OEMC_uicGetCursorPosition"),
-        gen_stub(42, "OEMC_uicSetCursorPosition"),
-        gen_stub(43, "OEMC_uicSetLineGap"),
-        gen_stub(44, "OEMC_uicGetLineGap"),
+        uic::add_menu_item.into_body(),
+        uic::get_menu_item.into_body(),
+        uic::remove_menu_item.into_body(),
+        uic::set_active_menu_item.into_body(),
+        uic::get_active_menu_item.into_body(),
+        uic::insert_text.into_body(),
+        uic::delete_text.into_body(),
+        uic::get_max_text_size.into_body(),
+        uic::set_max_text_size.into_body(),
+        uic::get_text_size.into_body(),
+        uic::get_text.into_body(),
+        uic::add_list_item.into_body(),
+        uic::get_list_item.into_body(),
+        uic::remove_list_item.into_body(),
+        uic::set_active_list_item.into_body(),
+        uic::get_active_list_item.into_body(),
+        uic::get_cursor_position.into_body(),
+        uic::set_cursor_position.into_body(),
+        uic::set_line_gap.into_body(),
+        uic::get_line_gap.into_body(),
     ]
 }
 
@@ -5,7 +5,7 @@ use jvm::runtime::{JavaIoInputStream, JavaLangClassLoader};
 use wie_backend::{extract_zip, Emulator, Event, Platform, System};
 use wie_core_arm::{Allocator, ArmCore};
 use wie_jvm_support::{JvmSupport, RustJavaJvmImplementation};
-use wie_util::{Result, WieError};
+use wie_util::{Charset, Result, WieError};
 
 use crate::runtime::init::load_native;
 
@@ -17,19 +17,26 @@ pub struct LgtEmulator {
 impl LgtEmulator {
     pub fn from_archive(platform: Box<dyn Platform>, files: BTreeMap<String, Vec<u8>>) -> Result<Self> {
         let app_info = files.get("app_info").unwrap();
-        let app_info = LgtAppInfo::parse(app_info);
+        let app_info = LgtAppInfo::parse(app_info, platform.device_profile().charset);
 
-        tracing::info!("Loading app {}, mclass {}", app_info.aid, app_info.mclass);
+        tracing::info!("Loading app {} ({}), mclass {}", app_info.name, app_info.aid, app_info.mclass);
 
         let jar_filename = format!("{}.jar", app_info.aid);
 
-        Self::load(platform, &jar_filename, &app_info.aid, Some(app_info.mclass), &files)
+        Self::load(
+            platform,
+            &jar_filename,
+            &app_info.aid,
+            Some(&app_info.name),
+            Some(app_info.mclass),
+            &files,
+        )
     }
 
     pub fn from_jar(platform: Box<dyn Platform>, jar_filename: &str, jar: Vec<u8>, id: &str, main_class_name: Option<String>) -> Result<Self> {
         let files = [(jar_filename.to_owned(), jar)].into_iter().collect();
 
-        Self::load(platform, jar_filename, id, main_class_name, &files)
+        Self::load(platform, jar_filename, id, None, main_class_name, &files)
     }
 
     pub fn loadable_archive(files: &BTreeMap<String, Vec<u8>>) -> bool {
@@ -46,11 +53,15 @@ impl LgtEmulator {
         platform: Box<dyn Platform>,
         jar_filename: &str,
         id: &str,
+        name: Option<&str>,
         main_class_name: Option<String>,
         files: &BTreeMap<String, Vec<u8>>,
     ) -> Result<Self> {
         let mut core = ArmCore::new()?;
         let mut system = System::new(platform, id);
+        if let Some(name) = name {
+            system.set_app_name(name);
+        }
 
         for (filename, data) in files {
             system.filesystem().add(filename, data.clone())
@@ -91,7 +102,7 @@ impl LgtEmulator {
 
 impl Emulator for LgtEmulator {
     fn handle_event(&mut self, event: Event) {
-        self.system.event_queue().push(event)
+        self.system.handle_event(event)
     }
 
     fn tick(&mut self) -> Result<()> {
@@ -103,30 +114,39 @@ impl Emulator for LgtEmulator {
             }
         })
     }
+
+    fn close(&mut self) {
+        self.system.close()
+    }
 }
 
 // almost similar to KtfAdf.. can we merge these?
 struct LgtAppInfo {
     aid: String,
     mclass: String,
+    name: String,
 }
 
 impl LgtAppInfo {
-    pub fn parse(data: &[u8]) -> Self {
+    pub fn parse(data: &[u8], charset: Charset) -> Self {
         let mut aid = String::new();
         let mut mclass = String::new();
+        let mut name = String::new();
 
         let mut lines = data.split(|x| *x == b'\n');
 
         for line in &mut lines {
+            let line = line.strip_suffix(b"\r").unwrap_or(line);
+
             if line.starts_with(b"AID:") {
-                aid = String::from_utf8_lossy(&line[4..]).into();
+                aid = charset.decode(&line[4..]);
             } else if line.starts_with(b"MClass:") {
-                mclass = String::from_utf8_lossy(&line[7..]).into();
+                mclass = charset.decode(&line[7..]);
+            } else if line.starts_with(b"Name:") {
+                name = charset.decode(&line[5..]);
             }
-            // TODO load name, it's in euc-kr..
         }
 
-        Self { aid, mclass }
+        Self { aid, mclass, name }
     }
 }
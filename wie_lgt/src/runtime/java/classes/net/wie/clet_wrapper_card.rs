@@ -4,6 +4,9 @@ use java_class_proto::{JavaClassProto, JavaFieldProto, JavaMethodProto};
 use jvm::{ClassInstanceRef, JavaError, Jvm, Result as JvmResult};
 
 use wie_util::WieError;
+use wie_wipi_c::event::{
+    MV_KEY_PRESS_EVENT, MV_KEY_RELEASE_EVENT, MV_KEY_REPEAT_EVENT, MV_POINTER_MOVE_EVENT, MV_POINTER_PRESS_EVENT, MV_POINTER_RELEASE_EVENT,
+};
 
 use super::CletWrapperContext;
 
@@ -20,6 +23,7 @@ impl CletWrapperCard {
                 JavaMethodProto::new("<init>", "(II)V", Self::init, Default::default()),
                 JavaMethodProto::new("paint", "(Lorg/kwis/msp/lcdui/Graphics;)V", Self::paint, Default::default()),
                 JavaMethodProto::new("keyNotify", "(II)Z", Self::key_notify, Default::default()),
+                JavaMethodProto::new("pointerNotify", "(III)Z", Self::pointer_notify, Default::default()),
             ],
             fields: vec![
                 JavaFieldProto::new("paintClet", "I", Default::default()),
@@ -57,8 +61,15 @@ impl CletWrapperCard {
     async fn key_notify(jvm: &Jvm, context: &mut CletWrapperContext, this: ClassInstanceRef<Self>, r#type: i32, key: i32) -> JvmResult<bool> {
         tracing::debug!("net.wie.CletWrapperCard::keyNotify({:?}, {}, {})", &this, r#type, key);
 
+        // org.kwis.msp.lcdui.Card key event types
+        let r#type = match r#type {
+            1 => MV_KEY_PRESS_EVENT,
+            2 => MV_KEY_RELEASE_EVENT,
+            3 => MV_KEY_REPEAT_EVENT,
+            _ => return Ok(true),
+        };
+
         let handle_input: i32 = jvm.get_field(&this, "handleCletEvent", "I").await?;
-        let r#type = r#type + 501; // TODO constants
         let _: () = context
             .core
             .run_function(handle_input as _, &[r#type as _, key as _, 0 as _])
@@ -70,4 +81,35 @@ impl CletWrapperCard {
 
         Ok(true)
     }
+
+    async fn pointer_notify(
+        jvm: &Jvm,
+        context: &mut CletWrapperContext,
+        this: ClassInstanceRef<Self>,
+        r#type: i32,
+        x: i32,
+        y: i32,
+    ) -> JvmResult<bool> {
+        tracing::debug!("net.wie.CletWrapperCard::pointerNotify({:?}, {}, {}, {})", &this, r#type, x, y);
+
+        // org.kwis.msp.lcdui.Card pointer event types
+        let r#type = match r#type {
+            1 => MV_POINTER_PRESS_EVENT,
+            2 => MV_POINTER_RELEASE_EVENT,
+            3 => MV_POINTER_MOVE_EVENT,
+            _ => return Ok(true),
+        };
+
+        let handle_input: i32 = jvm.get_field(&this, "handleCletEvent", "I").await?;
+        let _: () = context
+            .core
+            .run_function(handle_input as _, &[r#type as _, x as _, y as _])
+            .await
+            .map_err(|x| match x {
+                WieError::FatalError(x) => JavaError::FatalError(x),
+                _ => JavaError::FatalError(format!("{}", x)),
+            })?;
+
+        Ok(true)
+    }
 }
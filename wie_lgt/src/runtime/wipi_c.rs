@@ -44,23 +44,38 @@ pub fn get_wipi_c_method(core: &mut ArmCore, system: &mut System, jvm: &Jvm, fun
         0x81 => kernel::get_resource.into_body(),
         0x97 => unk2.into_body(),
         0xc8 => graphics::get_image_property.into_body(),
+        0xc9 => graphics::get_image_framebuffer.into_body(),
         0xca => graphics::get_screen_framebuffer.into_body(),
         0xcb => graphics::destroy_offscreen_framebuffer.into_body(),
         0xcc => graphics::create_offscreen_framebuffer.into_body(),
         0xcd => graphics::init_context.into_body(),
         0xce => graphics::set_context.into_body(),
+        0xcf => graphics::get_context.into_body(),
         0xd0 => graphics::put_pixel.into_body(),
+        0xd1 => graphics::draw_line.into_body(),
+        0xd2 => graphics::draw_rect.into_body(),
         0xd3 => graphics::fill_rect.into_body(),
+        0xd4 => graphics::copy_frame_buffer.into_body(),
         0xd5 => graphics::draw_image.into_body(),
         0xda => graphics::draw_string.into_body(),
+        0xdb => graphics::draw_unicode_string.into_body(),
+        0xdc => graphics::get_rgb_pixels.into_body(),
+        0xdd => graphics::set_rgb_pixels.into_body(),
         0xde => graphics::flush.into_body(),
         0xdf => graphics::get_pixel_from_rgb.into_body(),
         0xe0 => graphics::get_rgb_from_pixel.into_body(),
         0xe1 => graphics::get_display_info.into_body(),
         0xe3 => graphics::get_font.into_body(),
         0xe4 => graphics::get_font_height.into_body(),
+        0xe5 => graphics::get_font_ascent.into_body(),
+        0xe6 => graphics::get_font_descent.into_body(),
+        0xe7 => graphics::get_string_width.into_body(),
+        0xe8 => graphics::get_unicode_string_width.into_body(),
         0xe9 => graphics::create_image.into_body(),
-        0xeb => unk0.into_body(),
+        0xea => graphics::destroy_image.into_body(),
+        0xeb => graphics::decode_next_image.into_body(),
+        0xf3 => graphics::fill_polygon.into_body(),
+        0xf4 => graphics::draw_polygon.into_body(),
         0x12c => unk3.into_body(),
         0x12d => unk4.into_body(),
         0x190 => database::open_database.into_body(),
@@ -80,6 +95,8 @@ pub fn get_wipi_c_method(core: &mut ArmCore, system: &mut System, jvm: &Jvm, fun
         0x4c6 => media::clip_free_player.into_body(),
         0x4d1 => media::set_mute_state.into_body(),
         0x4ba => media::play.into_body(),
+        0x4bb => media::pause.into_body(),
+        0x4bc => media::resume.into_body(),
         0x4bd => media::stop.into_body(),
         0x578 => misc::back_light.into_body(),
         _ => return Err(WieError::FatalError(format!("Unknown lgt wipi import: {:#x}", function_index))),
@@ -149,14 +166,6 @@ async fn clet_register(core: &mut ArmCore, jvm: &mut Jvm, function_table: u32, a
     Ok(())
 }
 
-async fn unk0(_context: &mut dyn WIPICContext, a0: u32, a1: u32, a2: u32, a3: u32) -> Result<u32> {
-    tracing::warn!("stub unk0({:#x}, {:#x}, {:#x}, {:#x})", a0, a1, a2, a3);
-
-    // graphics
-
-    Ok(0)
-}
-
 async fn unk1(_context: &mut dyn WIPICContext, a0: u32, a1: u32, a2: u32, a3: u32) -> Result<u32> {
     tracing::warn!("stub unk1({:#x}, {:#x}, {:#x}, {:#x})", a0, a1, a2, a3);
 
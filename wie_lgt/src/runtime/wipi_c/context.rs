@@ -109,7 +109,7 @@ impl WIPICContext for LgtWIPICContext {
         self.core.run_function(address, args).await
     }
 
-    fn spawn(&mut self, callback: WIPICMethodBody) -> Result<()> {
+    fn spawn(&mut self, callback: WIPICMethodBody) -> Result<u64> {
         struct SpawnProxy {
             context: LgtWIPICContext,
             callback: WIPICMethodBody,
@@ -126,12 +126,10 @@ impl WIPICContext for LgtWIPICContext {
             }
         }
 
-        self.system.spawn(SpawnProxy {
+        Ok(self.system.spawn(SpawnProxy {
             context: self.clone(),
             callback,
-        });
-
-        Ok(())
+        }))
     }
 
     async fn get_resource_size(&self, name: &str) -> Result<Option<usize>> {
@@ -2,7 +2,12 @@ mod canvas;
 mod display;
 mod displayable;
 mod font;
+pub mod game;
 mod graphics;
 mod image;
+mod screen;
+mod text_box;
 
-pub use {canvas::Canvas, display::Display, displayable::Displayable, font::Font, graphics::Graphics, image::Image};
+pub use {
+    canvas::Canvas, display::Display, displayable::Displayable, font::Font, graphics::Graphics, image::Image, screen::Screen, text_box::TextBox,
+};
@@ -5,6 +5,8 @@ use jvm::{ClassInstanceRef, Jvm, Result as JvmResult};
 
 use wie_jvm_support::{WieJavaClassProto, WieJvmContext};
 
+use crate::classes::net::wie::MIDPKeyCode;
+
 // class javax.microedition.lcdui.Canvas
 pub struct Canvas;
 
@@ -21,9 +23,15 @@ impl Canvas {
                 JavaMethodProto::new("serviceRepaints", "()V", Self::service_repaints, Default::default()),
                 JavaMethodProto::new_abstract("paint", "(Ljavax/microedition/lcdui/Graphics;)V", Default::default()),
                 JavaMethodProto::new("getGameAction", "(I)I", Self::get_game_action, Default::default()),
+                JavaMethodProto::new("hasRepeatEvents", "()Z", Self::has_repeat_events, Default::default()),
                 JavaMethodProto::new("keyPressed", "(I)V", Self::key_pressed, Default::default()),
                 JavaMethodProto::new("keyRepeated", "(I)V", Self::key_repeated, Default::default()),
                 JavaMethodProto::new("keyReleased", "(I)V", Self::key_released, Default::default()),
+                JavaMethodProto::new("hasPointerEvents", "()Z", Self::has_pointer_events, Default::default()),
+                JavaMethodProto::new("hasPointerMotionEvents", "()Z", Self::has_pointer_motion_events, Default::default()),
+                JavaMethodProto::new("pointerPressed", "(II)V", Self::pointer_pressed, Default::default()),
+                JavaMethodProto::new("pointerReleased", "(II)V", Self::pointer_released, Default::default()),
+                JavaMethodProto::new("pointerDragged", "(II)V", Self::pointer_dragged, Default::default()),
             ],
             fields: vec![],
         }
@@ -79,16 +87,13 @@ impl Canvas {
     async fn get_game_action(_: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>, key: i32) -> JvmResult<i32> {
         tracing::debug!("javax.microedition.lcdui.Canvas::getGameAction({:?}, {})", &this, key);
 
-        let action = match key {
-            -1 => 1, // UP
-            -2 => 6, // DOWN
-            -3 => 2, // LEFT
-            -4 => 5, // RIGHT
-            -5 => 8, // FIRE,
-            _ => key,
-        };
+        Ok(Self::game_action(key).map(|x| x as _).unwrap_or(0))
+    }
+
+    async fn has_repeat_events(_: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<bool> {
+        tracing::debug!("javax.microedition.lcdui.Canvas::hasRepeatEvents({:?})", &this);
 
-        Ok(action)
+        Ok(true)
     }
 
     async fn key_pressed(_: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>, key: i32) -> JvmResult<()> {
@@ -108,4 +113,50 @@ impl Canvas {
 
         Ok(())
     }
+
+    async fn has_pointer_events(_: &Jvm, context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<bool> {
+        tracing::debug!("javax.microedition.lcdui.Canvas::hasPointerEvents({:?})", &this);
+
+        Ok(context.system().device().has_pointer_events())
+    }
+
+    async fn has_pointer_motion_events(_: &Jvm, context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<bool> {
+        tracing::debug!("javax.microedition.lcdui.Canvas::hasPointerMotionEvents({:?})", &this);
+
+        Ok(context.system().device().has_pointer_events())
+    }
+
+    async fn pointer_pressed(_: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>, x: i32, y: i32) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.lcdui.Canvas::pointerPressed({:?}, {}, {})", &this, x, y);
+
+        Ok(())
+    }
+
+    async fn pointer_released(_: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>, x: i32, y: i32) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.lcdui.Canvas::pointerReleased({:?}, {}, {})", &this, x, y);
+
+        Ok(())
+    }
+
+    async fn pointer_dragged(_: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>, x: i32, y: i32) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.lcdui.Canvas::pointerDragged({:?}, {}, {})", &this, x, y);
+
+        Ok(())
+    }
+
+    /// Game action of `key`. Game actions have the same values as MIDPKeyCode of the same names.
+    pub fn game_action(key: i32) -> Option<MIDPKeyCode> {
+        Some(match key {
+            -1 | 1 | 50 => MIDPKeyCode::UP,
+            -2 | 6 | 56 => MIDPKeyCode::DOWN,
+            -3 | 2 | 52 => MIDPKeyCode::LEFT,
+            -4 | 5 | 54 => MIDPKeyCode::RIGHT,
+            -5 | 8 | 53 => MIDPKeyCode::FIRE,
+            9 | 49 => MIDPKeyCode::GAME_A,
+            10 | 51 => MIDPKeyCode::GAME_B,
+            11 | 55 => MIDPKeyCode::GAME_C,
+            12 | 57 => MIDPKeyCode::GAME_D,
+            _ => return None,
+        })
+    }
 }
@@ -2,9 +2,10 @@ use alloc::vec;
 
 use java_class_proto::{JavaFieldProto, JavaMethodProto};
 use java_constants::MethodAccessFlags;
-use java_runtime::classes::java::lang::Runnable;
+use java_runtime::classes::java::lang::{Runnable, String};
 use jvm::{ClassInstanceRef, Jvm, Result as JvmResult};
 
+use wie_backend::canvas::quantize_image;
 use wie_jvm_support::{WieJavaClassProto, WieJvmContext};
 
 use crate::classes::{
@@ -12,7 +13,7 @@ use crate::classes::{
         lcdui::{Displayable, Graphics, Image},
         midlet::MIDlet,
     },
-    net::wie::KeyboardEventType,
+    net::wie::{KeyboardEventType, PointerEventType},
 };
 
 // class javax.microedition.lcdui.Display
@@ -44,6 +45,8 @@ impl Display {
                 // wie private methods...
                 JavaMethodProto::new("handlePaintEvent", "()V", Self::handle_paint_event, Default::default()),
                 JavaMethodProto::new("handleKeyEvent", "(II)V", Self::handle_key_event, Default::default()),
+                JavaMethodProto::new("handlePointerEvent", "(III)V", Self::handle_pointer_event, Default::default()),
+                JavaMethodProto::new("handleTextEvent", "(Ljava/lang/String;)V", Self::handle_text_event, Default::default()),
             ],
             fields: vec![
                 JavaFieldProto::new("currentDisplayable", "Ljavax/microedition/lcdui/Displayable;", Default::default()),
@@ -125,7 +128,7 @@ impl Display {
 
     async fn set_current(
         jvm: &Jvm,
-        _context: &mut WieJvmContext,
+        context: &mut WieJvmContext,
         mut this: ClassInstanceRef<Self>,
         displayable: ClassInstanceRef<Displayable>,
     ) -> JvmResult<()> {
@@ -134,6 +137,9 @@ impl Display {
         jvm.put_field(&mut this, "currentDisplayable", "Ljavax/microedition/lcdui/Displayable;", displayable)
             .await?;
 
+        // screens are drawn by emulator, so they need a paint event to show up
+        context.system().platform().screen().request_redraw().unwrap();
+
         Ok(())
     }
 
@@ -167,11 +173,66 @@ impl Display {
                 KeyboardEventType::KeyRepeated => jvm.invoke_virtual(&current_displayable, "keyRepeated", "(I)V", (code,)).await?,
                 _ => unimplemented!(),
             }
+        } else if !current_displayable.is_null() && jvm.is_instance(&**current_displayable, "javax/microedition/lcdui/Screen") {
+            let _: () = jvm.invoke_virtual(&current_displayable, "keyEvent", "(II)V", (event_type, code)).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn handle_pointer_event(
+        jvm: &Jvm,
+        _context: &mut WieJvmContext,
+        this: ClassInstanceRef<Self>,
+        event_type: i32,
+        x: i32,
+        y: i32,
+    ) -> JvmResult<()> {
+        tracing::debug!(
+            "javax.microedition.lcdui.Display::handlePointerEvent({:?}, {:?}, {}, {})",
+            &this,
+            event_type,
+            x,
+            y
+        );
+
+        let current_displayable: ClassInstanceRef<Displayable> = jvm
+            .get_field(&this, "currentDisplayable", "Ljavax/microedition/lcdui/Displayable;")
+            .await?;
+
+        if !current_displayable.is_null() && jvm.is_instance(&**current_displayable, "javax/microedition/lcdui/Canvas") {
+            let method = match PointerEventType::from_raw(event_type) {
+                PointerEventType::PointerPressed => "pointerPressed",
+                PointerEventType::PointerReleased => "pointerReleased",
+                PointerEventType::PointerDragged => "pointerDragged",
+            };
+
+            let _: () = jvm.invoke_virtual(&current_displayable, method, "(II)V", (x, y)).await?;
         }
 
         Ok(())
     }
 
+    async fn handle_text_event(
+        jvm: &Jvm,
+        _context: &mut WieJvmContext,
+        this: ClassInstanceRef<Self>,
+        text: ClassInstanceRef<String>,
+    ) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.lcdui.Display::handleTextEvent({:?}, {:?})", &this, &text);
+
+        let current_displayable: ClassInstanceRef<Displayable> = jvm
+            .get_field(&this, "currentDisplayable", "Ljavax/microedition/lcdui/Displayable;")
+            .await?;
+
+        if current_displayable.is_null() {
+            return Ok(());
+        }
+
+        jvm.invoke_virtual(&current_displayable, "textInput", "(Ljava/lang/String;)V", (text,))
+            .await
+    }
+
     async fn handle_paint_event(jvm: &Jvm, context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<()> {
         tracing::debug!("javax.microedition.lcdui.Display::handlePaintEvent({:?})", &this);
 
@@ -190,15 +251,30 @@ impl Display {
                     (screen_graphics,),
                 )
                 .await?;
+        } else if !current_displayable.is_null() && jvm.is_instance(&**current_displayable, "javax/microedition/lcdui/Screen") {
+            let screen_graphics: ClassInstanceRef<Graphics> = jvm.get_field(&this, "screenGraphics", "Ljavax/microedition/lcdui/Graphics;").await?;
+
+            let _: () = jvm
+                .invoke_virtual(
+                    &current_displayable,
+                    "paintScreen",
+                    "(Ljavax/microedition/lcdui/Graphics;)V",
+                    (screen_graphics,),
+                )
+                .await?;
         }
 
         let screen_image: ClassInstanceRef<Image> = jvm.get_field(&this, "screenImage", "Ljavax/microedition/lcdui/Image;").await?;
         let image = Image::image(jvm, &screen_image).await?;
 
+        let color_depth = context.system().device().color_depth();
         let mut platform = context.system().platform();
         let screen = platform.screen();
 
-        screen.paint(&*image);
+        match color_depth {
+            Some(depth) => screen.paint(&*quantize_image(&*image, depth)),
+            None => screen.paint(&*image),
+        }
 
         Ok(())
     }
@@ -206,4 +282,8 @@ impl Display {
     pub async fn screen_graphics(jvm: &Jvm, this: &ClassInstanceRef<Self>) -> JvmResult<ClassInstanceRef<Graphics>> {
         jvm.get_field(this, "screenGraphics", "Ljavax/microedition/lcdui/Graphics;").await
     }
+
+    pub async fn screen_image(jvm: &Jvm, this: &ClassInstanceRef<Self>) -> JvmResult<ClassInstanceRef<Image>> {
+        jvm.get_field(this, "screenImage", "Ljavax/microedition/lcdui/Image;").await
+    }
 }
@@ -1,6 +1,7 @@
 use alloc::{format, vec};
 
 use java_class_proto::{JavaFieldProto, JavaMethodProto};
+use java_runtime::classes::java::lang::String;
 use jvm::{ClassInstanceRef, Jvm, Result as JvmResult};
 
 use wie_jvm_support::{WieJavaClassProto, WieJvmContext};
@@ -27,6 +28,7 @@ impl Displayable {
                     Self::set_display,
                     Default::default(),
                 ),
+                JavaMethodProto::new("textInput", "(Ljava/lang/String;)V", Self::text_input, Default::default()),
             ],
             fields: vec![JavaFieldProto::new(
                 "currentDisplay",
@@ -60,6 +62,13 @@ impl Displayable {
         Ok(())
     }
 
+    // text typed on host keyboard, ignored unless overridden by a displayable with a text field like TextBox
+    async fn text_input(_: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>, text: ClassInstanceRef<String>) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.lcdui.Displayable::textInput({:?}, {:?})", &this, &text);
+
+        Ok(())
+    }
+
     async fn get_width(jvm: &Jvm, context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
         tracing::debug!("javax.microedition.lcdui.Canvas::getWidth({:?})", &this);
 
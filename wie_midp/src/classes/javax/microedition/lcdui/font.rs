@@ -5,9 +5,20 @@ use java_constants::{FieldAccessFlags, MethodAccessFlags};
 use java_runtime::classes::java::lang::String;
 use jvm::{runtime::JavaLangString, Array, ClassInstanceRef, JavaChar, Jvm, Result as JvmResult};
 
-use wie_backend::canvas;
+use wie_backend::canvas::{Font as BackendFont, FontFace, FontSize, FontStyle};
 use wie_jvm_support::{WieJavaClassProto, WieJvmContext};
 
+const FACE_SYSTEM: i32 = 0;
+const FACE_MONOSPACE: i32 = 32;
+const FACE_PROPORTIONAL: i32 = 64;
+const STYLE_PLAIN: i32 = 0;
+const STYLE_BOLD: i32 = 1;
+const STYLE_ITALIC: i32 = 2;
+const STYLE_UNDERLINED: i32 = 4;
+const SIZE_SMALL: i32 = 8;
+const SIZE_MEDIUM: i32 = 0;
+const SIZE_LARGE: i32 = 16;
+
 // class javax.microedition.lcdui.Font
 pub struct Font;
 
@@ -19,8 +30,16 @@ impl Font {
             interfaces: vec![],
             methods: vec![
                 JavaMethodProto::new("<clinit>", "()V", Self::cl_init, MethodAccessFlags::STATIC),
-                JavaMethodProto::new("<init>", "()V", Self::init, Default::default()),
+                JavaMethodProto::new("<init>", "(III)V", Self::init, Default::default()),
+                JavaMethodProto::new("getFace", "()I", Self::get_face, Default::default()),
+                JavaMethodProto::new("getStyle", "()I", Self::get_style, Default::default()),
+                JavaMethodProto::new("getSize", "()I", Self::get_size, Default::default()),
+                JavaMethodProto::new("isPlain", "()Z", Self::is_plain, Default::default()),
+                JavaMethodProto::new("isBold", "()Z", Self::is_bold, Default::default()),
+                JavaMethodProto::new("isItalic", "()Z", Self::is_italic, Default::default()),
+                JavaMethodProto::new("isUnderlined", "()Z", Self::is_underlined, Default::default()),
                 JavaMethodProto::new("getHeight", "()I", Self::get_height, Default::default()),
+                JavaMethodProto::new("getBaselinePosition", "()I", Self::get_baseline_position, Default::default()),
                 JavaMethodProto::new("stringWidth", "(Ljava/lang/String;)I", Self::string_width, Default::default()),
                 JavaMethodProto::new("substringWidth", "(Ljava/lang/String;II)I", Self::substring_width, Default::default()),
                 JavaMethodProto::new("charWidth", "(C)I", Self::char_width, Default::default()),
@@ -39,108 +58,272 @@ impl Font {
                 ),
             ],
             fields: vec![
-                JavaFieldProto::new("FACE_MONOSPACE", "I", FieldAccessFlags::STATIC),
                 JavaFieldProto::new("FACE_SYSTEM", "I", FieldAccessFlags::STATIC),
+                JavaFieldProto::new("FACE_MONOSPACE", "I", FieldAccessFlags::STATIC),
+                JavaFieldProto::new("FACE_PROPORTIONAL", "I", FieldAccessFlags::STATIC),
                 JavaFieldProto::new("STYLE_PLAIN", "I", FieldAccessFlags::STATIC),
+                JavaFieldProto::new("STYLE_BOLD", "I", FieldAccessFlags::STATIC),
+                JavaFieldProto::new("STYLE_ITALIC", "I", FieldAccessFlags::STATIC),
+                JavaFieldProto::new("STYLE_UNDERLINED", "I", FieldAccessFlags::STATIC),
                 JavaFieldProto::new("SIZE_SMALL", "I", FieldAccessFlags::STATIC),
+                JavaFieldProto::new("SIZE_MEDIUM", "I", FieldAccessFlags::STATIC),
+                JavaFieldProto::new("SIZE_LARGE", "I", FieldAccessFlags::STATIC),
+                JavaFieldProto::new("face", "I", Default::default()),
+                JavaFieldProto::new("style", "I", Default::default()),
+                JavaFieldProto::new("size", "I", Default::default()),
             ],
         }
     }
 
     async fn cl_init(jvm: &Jvm, _: &mut WieJvmContext) -> JvmResult<()> {
-        tracing::debug!("javax.microedition.msp.lcdui.Font::<clinit>");
+        tracing::debug!("javax.microedition.lcdui.Font::<clinit>");
 
-        jvm.put_static_field("javax/microedition/lcdui/Font", "FACE_MONOSPACE", "I", 32).await?;
-        jvm.put_static_field("javax/microedition/lcdui/Font", "FACE_SYSTEM", "I", 0).await?;
-        jvm.put_static_field("javax/microedition/lcdui/Font", "STYLE_PLAIN", "I", 0).await?;
-        jvm.put_static_field("javax/microedition/lcdui/Font", "SIZE_SMALL", "I", 8).await?;
+        jvm.put_static_field("javax/microedition/lcdui/Font", "FACE_SYSTEM", "I", FACE_SYSTEM)
+            .await?;
+        jvm.put_static_field("javax/microedition/lcdui/Font", "FACE_MONOSPACE", "I", FACE_MONOSPACE)
+            .await?;
+        jvm.put_static_field("javax/microedition/lcdui/Font", "FACE_PROPORTIONAL", "I", FACE_PROPORTIONAL)
+            .await?;
+        jvm.put_static_field("javax/microedition/lcdui/Font", "STYLE_PLAIN", "I", STYLE_PLAIN)
+            .await?;
+        jvm.put_static_field("javax/microedition/lcdui/Font", "STYLE_BOLD", "I", STYLE_BOLD)
+            .await?;
+        jvm.put_static_field("javax/microedition/lcdui/Font", "STYLE_ITALIC", "I", STYLE_ITALIC)
+            .await?;
+        jvm.put_static_field("javax/microedition/lcdui/Font", "STYLE_UNDERLINED", "I", STYLE_UNDERLINED)
+            .await?;
+        jvm.put_static_field("javax/microedition/lcdui/Font", "SIZE_SMALL", "I", SIZE_SMALL)
+            .await?;
+        jvm.put_static_field("javax/microedition/lcdui/Font", "SIZE_MEDIUM", "I", SIZE_MEDIUM)
+            .await?;
+        jvm.put_static_field("javax/microedition/lcdui/Font", "SIZE_LARGE", "I", SIZE_LARGE)
+            .await?;
 
         Ok(())
     }
 
-    async fn init(_: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Font>) -> JvmResult<()> {
-        tracing::warn!("stub javax.microedition.msp.lcdui.Font::<init>({:?})", &this);
+    async fn init(jvm: &Jvm, _: &mut WieJvmContext, mut this: ClassInstanceRef<Font>, face: i32, style: i32, size: i32) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.lcdui.Font::<init>({:?}, {}, {}, {})", &this, face, style, size);
+
+        jvm.put_field(&mut this, "face", "I", face).await?;
+        jvm.put_field(&mut this, "style", "I", style).await?;
+        jvm.put_field(&mut this, "size", "I", size).await?;
 
         Ok(())
     }
 
-    async fn get_height(_: &Jvm, _: &mut WieJvmContext) -> JvmResult<i32> {
-        tracing::warn!("stub javax.microedition.msp.lcdui.Font::getHeight");
+    async fn get_face(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Font>) -> JvmResult<i32> {
+        tracing::debug!("javax.microedition.lcdui.Font::getFace({:?})", &this);
+
+        jvm.get_field(&this, "face", "I").await
+    }
+
+    async fn get_style(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Font>) -> JvmResult<i32> {
+        tracing::debug!("javax.microedition.lcdui.Font::getStyle({:?})", &this);
+
+        jvm.get_field(&this, "style", "I").await
+    }
+
+    async fn get_size(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Font>) -> JvmResult<i32> {
+        tracing::debug!("javax.microedition.lcdui.Font::getSize({:?})", &this);
+
+        jvm.get_field(&this, "size", "I").await
+    }
+
+    async fn is_plain(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Font>) -> JvmResult<bool> {
+        tracing::debug!("javax.microedition.lcdui.Font::isPlain({:?})", &this);
+
+        let style: i32 = jvm.get_field(&this, "style", "I").await?;
+
+        Ok(style == STYLE_PLAIN)
+    }
+
+    async fn is_bold(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Font>) -> JvmResult<bool> {
+        tracing::debug!("javax.microedition.lcdui.Font::isBold({:?})", &this);
+
+        let style: i32 = jvm.get_field(&this, "style", "I").await?;
+
+        Ok(style & STYLE_BOLD != 0)
+    }
+
+    async fn is_italic(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Font>) -> JvmResult<bool> {
+        tracing::debug!("javax.microedition.lcdui.Font::isItalic({:?})", &this);
+
+        let style: i32 = jvm.get_field(&this, "style", "I").await?;
+
+        Ok(style & STYLE_ITALIC != 0)
+    }
+
+    async fn is_underlined(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Font>) -> JvmResult<bool> {
+        tracing::debug!("javax.microedition.lcdui.Font::isUnderlined({:?})", &this);
+
+        let style: i32 = jvm.get_field(&this, "style", "I").await?;
+
+        Ok(style & STYLE_UNDERLINED != 0)
+    }
+
+    async fn get_height(jvm: &Jvm, context: &mut WieJvmContext, this: ClassInstanceRef<Font>) -> JvmResult<i32> {
+        tracing::debug!("javax.microedition.lcdui.Font::getHeight({:?})", &this);
+
+        let font = Self::font(jvm, context, &this).await?;
+
+        Ok(font.height() as _)
+    }
+
+    async fn get_baseline_position(jvm: &Jvm, context: &mut WieJvmContext, this: ClassInstanceRef<Font>) -> JvmResult<i32> {
+        tracing::debug!("javax.microedition.lcdui.Font::getBaselinePosition({:?})", &this);
+
+        let font = Self::font(jvm, context, &this).await?;
 
-        Ok(12) // TODO: hardcoded
+        Ok(font.ascent() as _)
     }
 
     async fn get_default_font(jvm: &Jvm, _: &mut WieJvmContext) -> JvmResult<ClassInstanceRef<Self>> {
-        tracing::warn!("stub javax.microedition.msp.lcdui.Font::getDefaultFont");
+        tracing::debug!("javax.microedition.lcdui.Font::getDefaultFont");
 
-        let instance = jvm.new_class("javax/microedition/lcdui/Font", "()V", []).await?;
+        let instance = jvm
+            .new_class("javax/microedition/lcdui/Font", "(III)V", (FACE_SYSTEM, STYLE_PLAIN, SIZE_MEDIUM))
+            .await?;
 
         Ok(instance.into())
     }
 
     async fn get_font(jvm: &Jvm, _: &mut WieJvmContext, face: i32, style: i32, size: i32) -> JvmResult<ClassInstanceRef<Font>> {
-        tracing::warn!("stub javax.microedition.msp.lcdui.Font::getFont({:?}, {:?}, {:?})", face, style, size);
+        tracing::debug!("javax.microedition.lcdui.Font::getFont({:?}, {:?}, {:?})", face, style, size);
 
-        let instance = jvm.new_class("javax/microedition/lcdui/Font", "()V", []).await?;
+        if ![FACE_SYSTEM, FACE_MONOSPACE, FACE_PROPORTIONAL].contains(&face)
+            || ![SIZE_SMALL, SIZE_MEDIUM, SIZE_LARGE].contains(&size)
+            || style & !(STYLE_BOLD | STYLE_ITALIC | STYLE_UNDERLINED) != 0
+        {
+            return Err(jvm.exception("java/lang/IllegalArgumentException", "invalid font").await);
+        }
+
+        let instance = jvm.new_class("javax/microedition/lcdui/Font", "(III)V", (face, style, size)).await?;
 
         Ok(instance.into())
     }
 
-    async fn string_width(jvm: &Jvm, _: &mut WieJvmContext, _: ClassInstanceRef<Self>, string: ClassInstanceRef<String>) -> JvmResult<i32> {
-        tracing::warn!("stub javax.microedition.msp.lcdui.Font::stringWidth({:?})", &string);
+    async fn string_width(jvm: &Jvm, context: &mut WieJvmContext, this: ClassInstanceRef<Self>, string: ClassInstanceRef<String>) -> JvmResult<i32> {
+        tracing::debug!("javax.microedition.lcdui.Font::stringWidth({:?}, {:?})", &this, &string);
 
+        let font = Self::font(jvm, context, &this).await?;
         let string = JavaLangString::to_rust_string(jvm, &string).await?;
 
-        Ok(canvas::string_width(&string, 10.0) as _)
+        Ok(font.string_width(&string) as _)
     }
 
     async fn substring_width(
         jvm: &Jvm,
-        _: &mut WieJvmContext,
-        _: ClassInstanceRef<Self>,
+        context: &mut WieJvmContext,
+        this: ClassInstanceRef<Self>,
         string: ClassInstanceRef<String>,
         offset: i32,
         len: i32,
     ) -> JvmResult<i32> {
-        tracing::warn!(
-            "stub javax.microedition.msp.lcdui.Font::substringWidth({:?}, {:?}, {:?})",
+        tracing::debug!(
+            "javax.microedition.lcdui.Font::substringWidth({:?}, {:?}, {:?}, {:?})",
+            &this,
             &string,
             offset,
             len
         );
 
+        let font = Self::font(jvm, context, &this).await?;
         let string = JavaLangString::to_rust_string(jvm, &string).await?;
         let substring = string.chars().skip(offset as usize).take(len as usize).collect::<RustString>();
 
-        Ok(canvas::string_width(&substring, 10.0) as _)
+        Ok(font.string_width(&substring) as _)
     }
 
-    async fn char_width(_: &Jvm, _: &mut WieJvmContext, _: ClassInstanceRef<Self>, char: JavaChar) -> JvmResult<i32> {
-        tracing::warn!("stub javax.microedition.msp.lcdui.Font::charWidth({:?})", char);
+    async fn char_width(jvm: &Jvm, context: &mut WieJvmContext, this: ClassInstanceRef<Self>, char: JavaChar) -> JvmResult<i32> {
+        tracing::debug!("javax.microedition.lcdui.Font::charWidth({:?}, {:?})", &this, char);
 
-        let string = RustString::from_utf16(&[char]).unwrap();
+        let font = Self::font(jvm, context, &this).await?;
+        let string = RustString::from_utf16_lossy(&[char]);
 
-        Ok(canvas::string_width(&string, 10.0) as _)
+        Ok(font.string_width(&string) as _)
     }
 
     async fn chars_width(
         jvm: &Jvm,
-        _: &mut WieJvmContext,
-        _: ClassInstanceRef<Self>,
+        context: &mut WieJvmContext,
+        this: ClassInstanceRef<Self>,
         chars: ClassInstanceRef<Array<JavaChar>>,
         offset: i32,
         len: i32,
     ) -> JvmResult<i32> {
-        tracing::warn!(
-            "stub javax.microedition.msp.lcdui.Font::charsWidth({:?}, {:?}, {:?})",
+        tracing::debug!(
+            "javax.microedition.lcdui.Font::charsWidth({:?}, {:?}, {:?}, {:?})",
+            &this,
             &chars,
             offset,
             len
         );
 
+        let font = Self::font(jvm, context, &this).await?;
         let chars = jvm.load_array(&chars, offset as _, len as _).await?;
-        let string = RustString::from_utf16(&chars).unwrap();
+        let string = RustString::from_utf16_lossy(&chars);
+
+        Ok(font.string_width(&string) as _)
+    }
+
+    pub async fn font(jvm: &Jvm, context: &mut WieJvmContext, this: &ClassInstanceRef<Self>) -> JvmResult<BackendFont> {
+        if this.is_null() {
+            return Ok(context.system().default_font());
+        }
+
+        let face: i32 = jvm.get_field(this, "face", "I").await?;
+        let style: i32 = jvm.get_field(this, "style", "I").await?;
+        let size: i32 = jvm.get_field(this, "size", "I").await?;
+
+        let face = match face {
+            FACE_MONOSPACE => FontFace::Monospace,
+            FACE_PROPORTIONAL => FontFace::Proportional,
+            _ => FontFace::System,
+        };
+        let size = match size {
+            SIZE_SMALL => FontSize::Small,
+            SIZE_LARGE => FontSize::Large,
+            _ => FontSize::Medium,
+        };
+
+        Ok(context.system().font(face, size, FontStyle::from_bits_truncate(style as _)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::boxed::Box;
+
+    use jvm::{runtime::JavaLangString, ClassInstanceRef};
+
+    use test_utils::run_jvm_test;
+    use wie_util::Result;
+
+    use crate::{classes::javax::microedition::lcdui::Font, get_protos};
+
+    #[test]
+    fn test_font_metrics() -> Result<()> {
+        run_jvm_test(Box::new([get_protos().into()]), |jvm| async move {
+            let font: ClassInstanceRef<Font> = jvm
+                .invoke_static(
+                    "javax/microedition/lcdui/Font",
+                    "getFont",
+                    "(III)Ljavax/microedition/lcdui/Font;",
+                    (0, 1, 8),
+                )
+                .await?;
+
+            let height: i32 = jvm.invoke_virtual(&font, "getHeight", "()I", ()).await?;
+            let baseline: i32 = jvm.invoke_virtual(&font, "getBaselinePosition", "()I", ()).await?;
+            assert!(baseline <= height);
+
+            let string = JavaLangString::from_rust_string(&jvm, "가나").await?;
+            let string_width: i32 = jvm.invoke_virtual(&font, "stringWidth", "(Ljava/lang/String;)I", (string,)).await?;
+            let char_width: i32 = jvm.invoke_virtual(&font, "charWidth", "(C)I", ('가' as u16,)).await?;
+            assert_eq!(string_width, char_width * 2);
 
-        Ok(canvas::string_width(&string, 10.0) as _)
+            Ok(())
+        })
     }
 }
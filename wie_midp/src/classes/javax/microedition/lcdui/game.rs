@@ -0,0 +1,3 @@
+mod game_canvas;
+
+pub use self::game_canvas::GameCanvas;
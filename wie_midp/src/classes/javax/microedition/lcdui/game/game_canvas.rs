@@ -0,0 +1,49 @@
+use alloc::vec;
+
+use java_class_proto::JavaMethodProto;
+use jvm::{ClassInstanceRef, Jvm, Result as JvmResult};
+
+use wie_jvm_support::{WieJavaClassProto, WieJvmContext};
+
+use crate::classes::{javax::microedition::lcdui::Canvas, net::wie::MIDPKeyCode};
+
+// class javax.microedition.lcdui.game.GameCanvas
+pub struct GameCanvas;
+
+impl GameCanvas {
+    pub fn as_proto() -> WieJavaClassProto {
+        WieJavaClassProto {
+            name: "javax/microedition/lcdui/game/GameCanvas",
+            parent_class: Some("javax/microedition/lcdui/Canvas"),
+            interfaces: vec![],
+            methods: vec![
+                JavaMethodProto::new("<init>", "(Z)V", Self::init, Default::default()),
+                JavaMethodProto::new("getKeyStates", "()I", Self::get_key_states, Default::default()),
+            ],
+            fields: vec![],
+        }
+    }
+
+    async fn init(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>, suppress_key_events: bool) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.lcdui.game.GameCanvas::<init>({:?}, {})", &this, suppress_key_events);
+
+        // key events are delivered regardless, as canvas key methods are no-ops unless overridden
+        let _: () = jvm.invoke_special(&this, "javax/microedition/lcdui/Canvas", "<init>", "()V", ()).await?;
+
+        Ok(())
+    }
+
+    async fn get_key_states(_: &Jvm, context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("javax.microedition.lcdui.game.GameCanvas::getKeyStates({:?})", &this);
+
+        let keys = context.system().event_queue().take_key_states();
+
+        // bit of each game action is `1 << action`, e.g. UP_PRESSED is 1 << UP
+        let states = keys
+            .into_iter()
+            .filter_map(|x| Canvas::game_action(MIDPKeyCode::from_key_code(x) as _))
+            .fold(0, |states, action| states | (1 << action as i32));
+
+        Ok(states)
+    }
+}
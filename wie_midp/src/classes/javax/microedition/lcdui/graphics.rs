@@ -1,4 +1,4 @@
-use alloc::{string::String as RustString, vec, vec::Vec};
+use alloc::{boxed::Box, string::String as RustString, vec, vec::Vec};
 
 use bytemuck::cast_vec;
 
@@ -7,7 +7,7 @@ use jvm::{runtime::JavaLangString, Array, ClassInstanceRef, JavaChar, JavaValue,
 use java_class_proto::{JavaFieldProto, JavaMethodProto, TypeConverter};
 use java_runtime::classes::java::lang::String;
 
-use wie_backend::canvas::{Clip, PixelType, Rgb8Pixel, TextAlignment, VecImageBuffer};
+use wie_backend::canvas::{Canvas, Clip, CompositeOp, Image as BackendImage, PixelType, Rgb8Pixel, TextAlignment, VecImageBuffer};
 use wie_jvm_support::{WieJavaClassProto, WieJvmContext};
 
 use crate::classes::javax::microedition::lcdui::{Font, Image};
@@ -105,6 +105,9 @@ impl Graphics {
                 JavaFieldProto::new("translateX", "I", Default::default()),
                 JavaFieldProto::new("translateY", "I", Default::default()),
                 JavaFieldProto::new("color", "I", Default::default()),
+                JavaFieldProto::new("font", "Ljavax/microedition/lcdui/Font;", Default::default()),
+                JavaFieldProto::new("alpha", "I", Default::default()),
+                JavaFieldProto::new("xorMode", "I", Default::default()),
             ],
         }
     }
@@ -121,6 +124,7 @@ impl Graphics {
         jvm.put_field(&mut this, "height", "I", height).await?;
         jvm.put_field(&mut this, "clipWidth", "I", width).await?;
         jvm.put_field(&mut this, "clipHeight", "I", height).await?;
+        jvm.put_field(&mut this, "alpha", "I", 0xff).await?;
 
         Ok(())
     }
@@ -138,16 +142,27 @@ impl Graphics {
         jvm.put_field(&mut this, "translateX", "I", 0).await?;
         jvm.put_field(&mut this, "translateY", "I", 0).await?;
         jvm.put_field(&mut this, "color", "I", 0).await?;
+        jvm.put_field(&mut this, "alpha", "I", 0xff).await?;
+        jvm.put_field(&mut this, "xorMode", "I", 0).await?;
+
+        let font: ClassInstanceRef<Font> = jvm
+            .invoke_static("javax/microedition/lcdui/Font", "getDefaultFont", "()Ljavax/microedition/lcdui/Font;", ())
+            .await?;
+        jvm.put_field(&mut this, "font", "Ljavax/microedition/lcdui/Font;", font).await?;
 
         Ok(())
     }
 
     async fn get_font(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Graphics>) -> JvmResult<ClassInstanceRef<Font>> {
-        tracing::warn!("stub javax.microedition.lcdui.Graphics::getFont({:?})", &this);
+        tracing::debug!("javax.microedition.lcdui.Graphics::getFont({:?})", &this);
 
-        let instance = jvm.new_class("javax/microedition/lcdui/Font", "()V", []).await?;
+        let font: ClassInstanceRef<Font> = jvm.get_field(&this, "font", "Ljavax/microedition/lcdui/Font;").await?;
+        if !font.is_null() {
+            return Ok(font);
+        }
 
-        Ok(instance.into())
+        jvm.invoke_static("javax/microedition/lcdui/Font", "getDefaultFont", "()Ljavax/microedition/lcdui/Font;", ())
+            .await
     }
 
     async fn set_color(jvm: &Jvm, _: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, rgb: i32) -> JvmResult<()> {
@@ -168,8 +183,10 @@ impl Graphics {
         Ok(())
     }
 
-    async fn set_font(_jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Graphics>, font: ClassInstanceRef<Font>) -> JvmResult<()> {
-        tracing::warn!("stub javax.microedition.lcdui.Graphics::setFont({:?}, {:?})", &this, &font);
+    async fn set_font(jvm: &Jvm, _: &mut WieJvmContext, mut this: ClassInstanceRef<Graphics>, font: ClassInstanceRef<Font>) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.lcdui.Graphics::setFont({:?}, {:?})", &this, &font);
+
+        jvm.put_field(&mut this, "font", "Ljavax/microedition/lcdui/Font;", font).await?;
 
         Ok(())
     }
@@ -260,8 +277,7 @@ impl Graphics {
 
         let rgb: i32 = jvm.get_field(&this, "color", "I").await?;
 
-        let image = Self::image(jvm, &mut this).await?;
-        let mut canvas = Image::canvas(jvm, &image).await?;
+        let mut canvas = Self::canvas(jvm, &mut this).await?;
 
         let translate_x: i32 = jvm.get_field(&this, "translateX", "I").await?;
         let translate_y: i32 = jvm.get_field(&this, "translateY", "I").await?;
@@ -306,8 +322,7 @@ impl Graphics {
 
         let rgb: i32 = jvm.get_field(&this, "color", "I").await?;
 
-        let image = Self::image(jvm, &mut this).await?;
-        let mut canvas = Image::canvas(jvm, &image).await?;
+        let mut canvas = Self::canvas(jvm, &mut this).await?;
 
         let translate_x: i32 = jvm.get_field(&this, "translateX", "I").await?;
         let translate_y: i32 = jvm.get_field(&this, "translateY", "I").await?;
@@ -352,8 +367,7 @@ impl Graphics {
 
         let rgb: i32 = jvm.get_field(&this, "color", "I").await?;
 
-        let image = Self::image(jvm, &mut this).await?;
-        let mut canvas = Image::canvas(jvm, &image).await?;
+        let mut canvas = Self::canvas(jvm, &mut this).await?;
 
         let translate_x: i32 = jvm.get_field(&this, "translateX", "I").await?;
         let translate_y: i32 = jvm.get_field(&this, "translateY", "I").await?;
@@ -384,8 +398,7 @@ impl Graphics {
 
         let rgb: i32 = jvm.get_field(&this, "color", "I").await?;
 
-        let image = Self::image(jvm, &mut this).await?;
-        let mut canvas = Image::canvas(jvm, &image).await?;
+        let mut canvas = Self::canvas(jvm, &mut this).await?;
 
         let translate_x: i32 = jvm.get_field(&this, "translateX", "I").await?;
         let translate_y: i32 = jvm.get_field(&this, "translateY", "I").await?;
@@ -406,7 +419,7 @@ impl Graphics {
 
     async fn draw_char(
         jvm: &Jvm,
-        _: &mut WieJvmContext,
+        context: &mut WieJvmContext,
         mut this: ClassInstanceRef<Self>,
         ch: JavaChar,
         x: i32,
@@ -422,22 +435,14 @@ impl Graphics {
             anchor.0
         );
 
-        let image = Self::image(jvm, &mut this).await?;
-        let mut canvas = Image::canvas(jvm, &image).await?;
-
-        let string = RustString::from_utf16(&[ch]).unwrap();
+        let string = RustString::from_utf16_lossy(&[ch]);
 
-        let translate_x: i32 = jvm.get_field(&this, "translateX", "I").await?;
-        let translate_y: i32 = jvm.get_field(&this, "translateY", "I").await?;
-
-        canvas.draw_text(&string, (translate_x + x) as _, (translate_y + y) as _, anchor.into());
-
-        Ok(())
+        Self::draw_text(jvm, context, &mut this, &string, x, y, anchor).await
     }
 
     async fn draw_chars(
         jvm: &Jvm,
-        _: &mut WieJvmContext,
+        context: &mut WieJvmContext,
         mut this: ClassInstanceRef<Self>,
         chars: ClassInstanceRef<Array<JavaChar>>,
         offset: i32,
@@ -456,23 +461,15 @@ impl Graphics {
             y
         );
 
-        let image = Self::image(jvm, &mut this).await?;
-        let mut canvas = Image::canvas(jvm, &image).await?;
-
         let chars = jvm.load_array(&chars, offset as _, length as _).await?;
-        let string = RustString::from_utf16(&chars).unwrap();
+        let string = RustString::from_utf16_lossy(&chars);
 
-        let translate_x: i32 = jvm.get_field(&this, "translateX", "I").await?;
-        let translate_y: i32 = jvm.get_field(&this, "translateY", "I").await?;
-
-        canvas.draw_text(&string, (translate_x + x) as _, (translate_y + y) as _, anchor.into());
-
-        Ok(())
+        Self::draw_text(jvm, context, &mut this, &string, x, y, anchor).await
     }
 
     async fn draw_string(
         jvm: &Jvm,
-        _: &mut WieJvmContext,
+        context: &mut WieJvmContext,
         mut this: ClassInstanceRef<Self>,
         string: ClassInstanceRef<String>,
         x: i32,
@@ -490,19 +487,11 @@ impl Graphics {
 
         let string = JavaLangString::to_rust_string(jvm, &string).await?;
 
-        let image = Self::image(jvm, &mut this).await?;
-        let mut canvas = Image::canvas(jvm, &image).await?;
-
-        let translate_x: i32 = jvm.get_field(&this, "translateX", "I").await?;
-        let translate_y: i32 = jvm.get_field(&this, "translateY", "I").await?;
-
-        canvas.draw_text(&string, (translate_x + x) as _, (translate_y + y) as _, anchor.into());
-
-        Ok(())
+        Self::draw_text(jvm, context, &mut this, &string, x, y, anchor).await
     }
     async fn draw_substring(
         jvm: &Jvm,
-        _: &mut WieJvmContext,
+        context: &mut WieJvmContext,
         mut this: ClassInstanceRef<Self>,
         string: ClassInstanceRef<String>,
         offset: i32,
@@ -524,15 +513,7 @@ impl Graphics {
         let string = JavaLangString::to_rust_string(jvm, &string).await?;
         let substring = string.chars().skip(offset as usize).take(len as usize).collect::<RustString>();
 
-        let image = Self::image(jvm, &mut this).await?;
-        let mut canvas = Image::canvas(jvm, &image).await?;
-
-        let translate_x: i32 = jvm.get_field(&this, "translateX", "I").await?;
-        let translate_y: i32 = jvm.get_field(&this, "translateY", "I").await?;
-
-        canvas.draw_text(&substring, (translate_x + x) as _, (translate_y + y) as _, anchor.into());
-
-        Ok(())
+        Self::draw_text(jvm, context, &mut this, &substring, x, y, anchor).await
     }
 
     async fn draw_line(jvm: &Jvm, _: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, x1: i32, y1: i32, x2: i32, y2: i32) -> JvmResult<()> {
@@ -547,8 +528,7 @@ impl Graphics {
         let x2 = x2 + translate_x;
         let y2 = y2 + translate_y;
 
-        let image = Self::image(jvm, &mut this).await?;
-        let mut canvas = Image::canvas(jvm, &image).await?;
+        let mut canvas = Self::canvas(jvm, &mut this).await?;
 
         canvas.draw_line(x1 as _, y1 as _, x2 as _, y2 as _, Rgb8Pixel::to_color(color as _));
 
@@ -579,8 +559,7 @@ impl Graphics {
 
         let src_image = Image::image(jvm, &img).await?;
 
-        let image = Self::image(jvm, &mut this).await?;
-        let mut canvas = Image::canvas(jvm, &image).await?;
+        let mut canvas = Self::canvas(jvm, &mut this).await?;
 
         let x_delta = if anchor.contains(Anchor::HCENTER) {
             -((src_image.width() / 2) as i32)
@@ -635,8 +614,7 @@ impl Graphics {
 
         let rgb: i32 = jvm.get_field(&this, "color", "I").await?;
 
-        let image = Self::image(jvm, &mut this).await?;
-        let mut canvas = Image::canvas(jvm, &image).await?;
+        let mut canvas = Self::canvas(jvm, &mut this).await?;
 
         let translate_x: i32 = jvm.get_field(&this, "translateX", "I").await?;
         let translate_y: i32 = jvm.get_field(&this, "translateY", "I").await?;
@@ -681,8 +659,7 @@ impl Graphics {
 
         let rgb: i32 = jvm.get_field(&this, "color", "I").await?;
 
-        let image = Self::image(jvm, &mut this).await?;
-        let mut canvas = Image::canvas(jvm, &image).await?;
+        let mut canvas = Self::canvas(jvm, &mut this).await?;
 
         let translate_x: i32 = jvm.get_field(&this, "translateX", "I").await?;
         let translate_y: i32 = jvm.get_field(&this, "translateY", "I").await?;
@@ -801,8 +778,7 @@ impl Graphics {
         let pixel_data: Vec<i32> = jvm.load_array(&rgb_data, offset as _, (width * height) as _).await?;
         let src_image = VecImageBuffer::<Rgb8Pixel>::from_raw(width as _, height as _, cast_vec(pixel_data));
 
-        let image = Self::image(jvm, &mut this).await?;
-        let mut canvas = Image::canvas(jvm, &image).await?;
+        let mut canvas = Self::canvas(jvm, &mut this).await?;
 
         let clip = Self::clip(jvm, &this).await?;
 
@@ -821,6 +797,66 @@ impl Graphics {
         Ok(())
     }
 
+    /// Sets global alpha applied to everything drawn afterwards, used by WIPI `Graphics.setAlpha`.
+    pub async fn set_alpha(jvm: &Jvm, this: &mut ClassInstanceRef<Self>, alpha: i32) -> JvmResult<()> {
+        jvm.put_field(this, "alpha", "I", alpha.clamp(0, 0xff)).await
+    }
+
+    /// Makes drawing xor pixels with existing ones, used by WIPI `Graphics.setXORMode`.
+    pub async fn set_xor_mode(jvm: &Jvm, this: &mut ClassInstanceRef<Self>, xor_mode: bool) -> JvmResult<()> {
+        jvm.put_field(this, "xorMode", "I", xor_mode as i32).await
+    }
+
+    /// Draws `image` at (x, y) with translation, clip and composite mode of this graphics, used by SKVM `Graphics2D.drawImage`.
+    /// Draws `width`x`height` region of `image` at (`sx`, `sy`) to (`x`, `y`), with translation and clip of graphics.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn draw_backend_image(
+        jvm: &Jvm,
+        this: &mut ClassInstanceRef<Self>,
+        x: i32,
+        y: i32,
+        width: u32,
+        height: u32,
+        image: &dyn BackendImage,
+        sx: i32,
+        sy: i32,
+    ) -> JvmResult<()> {
+        let translate_x: i32 = jvm.get_field(this, "translateX", "I").await?;
+        let translate_y: i32 = jvm.get_field(this, "translateY", "I").await?;
+
+        let mut canvas = Self::canvas(jvm, this).await?;
+
+        let clip = Self::clip(jvm, this).await?;
+
+        canvas.draw(
+            translate_x.saturating_add(x),
+            translate_y.saturating_add(y),
+            width,
+            height,
+            image,
+            sx,
+            sy,
+            clip,
+        );
+
+        Ok(())
+    }
+
+    async fn canvas(jvm: &Jvm, this: &mut ClassInstanceRef<Self>) -> JvmResult<Box<dyn Canvas>> {
+        let image = Self::image(jvm, this).await?;
+        let mut canvas = Image::canvas(jvm, &image).await?;
+
+        let alpha: i32 = jvm.get_field(this, "alpha", "I").await?;
+        let xor_mode: i32 = jvm.get_field(this, "xorMode", "I").await?;
+        canvas.set_composite_op(if xor_mode != 0 {
+            CompositeOp::Xor
+        } else {
+            CompositeOp::SourceOver { alpha: alpha as _ }
+        });
+
+        Ok(canvas)
+    }
+
     async fn image(jvm: &Jvm, this: &mut ClassInstanceRef<Graphics>) -> JvmResult<ClassInstanceRef<Image>> {
         let image: ClassInstanceRef<Image> = jvm.get_field(this, "img", "Ljavax/microedition/lcdui/Image;").await?;
 
@@ -845,6 +881,47 @@ impl Graphics {
         }
     }
 
+    async fn draw_text(
+        jvm: &Jvm,
+        context: &mut WieJvmContext,
+        this: &mut ClassInstanceRef<Self>,
+        string: &str,
+        x: i32,
+        y: i32,
+        anchor: Anchor,
+    ) -> JvmResult<()> {
+        let font: ClassInstanceRef<Font> = jvm.get_field(this, "font", "Ljavax/microedition/lcdui/Font;").await?;
+        let font = Font::font(jvm, context, &font).await?;
+
+        let rgb: i32 = jvm.get_field(this, "color", "I").await?;
+        let translate_x: i32 = jvm.get_field(this, "translateX", "I").await?;
+        let translate_y: i32 = jvm.get_field(this, "translateY", "I").await?;
+
+        let y_delta = if anchor.contains(Anchor::BASELINE) {
+            -(font.ascent() as i32)
+        } else if anchor.contains(Anchor::BOTTOM) {
+            -(font.height() as i32)
+        } else {
+            0
+        };
+
+        let mut canvas = Self::canvas(jvm, this).await?;
+
+        let clip = Self::clip(jvm, this).await?;
+
+        canvas.draw_text(
+            string,
+            translate_x + x,
+            translate_y + y + y_delta,
+            &font,
+            Rgb8Pixel::to_color(rgb as _),
+            anchor.into(),
+            clip,
+        );
+
+        Ok(())
+    }
+
     async fn clip(jvm: &Jvm, this: &ClassInstanceRef<Self>) -> JvmResult<Clip> {
         let x: i32 = jvm.get_field(this, "clipX", "I").await?;
         let y: i32 = jvm.get_field(this, "clipY", "I").await?;
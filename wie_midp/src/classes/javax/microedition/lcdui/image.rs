@@ -12,7 +12,7 @@ use jvm::{
 };
 
 use wie_backend::canvas::{
-    decode_image, ArgbPixel, Canvas, Color, Image as BackendImage, ImageBuffer, ImageBufferCanvas, PixelType, Rgb332Pixel, Rgb565Pixel,
+    decode_image, ArgbPixel, Canvas, Color, CompositeOp, Image as BackendImage, ImageBuffer, ImageBufferCanvas, PixelType, Rgb332Pixel, Rgb565Pixel,
 };
 use wie_jvm_support::{WieJavaClassProto, WieJvmContext};
 
@@ -112,23 +112,9 @@ impl Image {
         tracing::debug!("javax.microedition.lcdui.Image::createImage({:?})", &name);
 
         let name = JavaLangString::to_rust_string(jvm, &name).await?;
+        let image = Self::decode_resource(jvm, &name).await?;
 
-        let class_loader = jvm.current_class_loader().await?;
-        let stream = JavaLangClassLoader::get_resource_as_stream(jvm, &class_loader, &name).await?.unwrap();
-
-        let image_data = JavaIoInputStream::read_until_end(jvm, &stream).await?;
-        let image_data_len = image_data.len() as i32;
-
-        let mut image_array = jvm.instantiate_array("B", image_data_len as _).await?;
-        jvm.array_raw_buffer_mut(&mut image_array).await?.write(0, &image_data)?;
-
-        jvm.invoke_static(
-            "javax/microedition/lcdui/Image",
-            "createImage",
-            "([BII)Ljavax/microedition/lcdui/Image;",
-            (image_array, 0, image_data_len),
-        )
-        .await
+        Self::from_backend_image(jvm, &*image).await
     }
 
     async fn create_image_from_data(
@@ -143,19 +129,9 @@ impl Image {
         let mut image_data = vec![0; length as usize];
         jvm.array_raw_buffer(&data).await?.read(offset as _, &mut image_data)?;
 
-        let image = {
-            let result = decode_image(&cast_vec(image_data));
-            if let Ok(image) = result {
-                image
-            } else {
-                tracing::error!("Failed to decode image: {:?}", result.err());
-                let exception = jvm.exception("java/lang/IllegalArgumentException", "Failed to decode image").await;
-
-                return Err(exception);
-            }
-        };
+        let image = Self::decode(jvm, &cast_vec(image_data)).await?;
 
-        Self::create_image_instance(jvm, image.width(), image.height(), image.raw(), image.bytes_per_pixel()).await
+        Self::from_backend_image(jvm, &*image).await
     }
 
     async fn get_graphics(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<ClassInstanceRef<Graphics>> {
@@ -212,6 +188,42 @@ impl Image {
         })
     }
 
+    /// Decodes image data, throwing `IllegalArgumentException` on failure.
+    pub async fn decode(jvm: &Jvm, data: &[u8]) -> JvmResult<Box<dyn BackendImage>> {
+        let result = decode_image(data);
+        if let Ok(image) = result {
+            Ok(image)
+        } else {
+            tracing::error!("Failed to decode image: {:?}", result.err());
+
+            Err(jvm.exception("java/lang/IllegalArgumentException", "Failed to decode image").await)
+        }
+    }
+
+    /// Loads and decodes image resource, throwing `IOException` if it's missing or can't be decoded.
+    pub async fn decode_resource(jvm: &Jvm, name: &str) -> JvmResult<Box<dyn BackendImage>> {
+        let class_loader = jvm.current_class_loader().await?;
+        let Some(stream) = JavaLangClassLoader::get_resource_as_stream(jvm, &class_loader, name).await? else {
+            return Err(jvm.exception("java/io/IOException", "Resource not found").await);
+        };
+
+        let image_data = JavaIoInputStream::read_until_end(jvm, &stream).await?;
+
+        let result = decode_image(&image_data);
+        if let Ok(image) = result {
+            Ok(image)
+        } else {
+            tracing::error!("Failed to decode image {}: {:?}", name, result.err());
+
+            Err(jvm.exception("java/io/IOException", "Failed to decode image").await)
+        }
+    }
+
+    /// Creates immutable image with pixels of `image`, or of its first frame if animated.
+    pub async fn from_backend_image(jvm: &Jvm, image: &dyn BackendImage) -> JvmResult<ClassInstanceRef<Image>> {
+        Self::create_image_instance(jvm, image.width(), image.height(), image.raw(), image.bytes_per_pixel()).await
+    }
+
     async fn create_image_instance(jvm: &Jvm, width: u32, height: u32, data: &[u8], bytes_per_pixel: u32) -> JvmResult<ClassInstanceRef<Image>> {
         let mut data_array = jvm.instantiate_array("B", data.len() as _).await?;
         jvm.array_raw_buffer_mut(&mut data_array).await?.write(0, data)?;
@@ -321,4 +333,18 @@ where
 
         self.raw_buffer.write(offset as _, &raw_bytes).unwrap();
     }
+
+    fn composite_pixel(&mut self, x: i32, y: i32, color: Color, op: CompositeOp) {
+        if x < 0 || y < 0 || x >= self.width || y >= self.height {
+            return;
+        }
+
+        let offset = (((y as u32) * self.width() + (x as u32)) * self.bytes_per_pixel()) as usize;
+
+        let mut buffer = [0; 4];
+        self.raw_buffer.read(offset as _, &mut buffer[..size_of::<T::DataType>()]).unwrap();
+
+        let raw = T::composite(bytemuck::pod_read_unaligned(&buffer[..size_of::<T::DataType>()]), color, op);
+        self.raw_buffer.write(offset as _, bytemuck::bytes_of(&raw)).unwrap();
+    }
 }
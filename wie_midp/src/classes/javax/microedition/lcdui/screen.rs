@@ -0,0 +1,120 @@
+use alloc::vec;
+
+use java_class_proto::{JavaFieldProto, JavaMethodProto};
+use java_runtime::classes::java::lang::String;
+use jvm::{ClassInstanceRef, Jvm, Result as JvmResult};
+
+use wie_jvm_support::{WieJavaClassProto, WieJvmContext};
+
+use crate::classes::javax::microedition::lcdui::{Font, Graphics};
+
+// Graphics anchors
+pub const LEFT: i32 = 4;
+pub const RIGHT: i32 = 8;
+pub const TOP: i32 = 16;
+
+const BACKGROUND_COLOR: i32 = 0xffffff;
+const FOREGROUND_COLOR: i32 = 0x000000;
+
+pub const MARGIN: i32 = 2;
+
+// class javax.microedition.lcdui.Screen
+// drawn by emulator instead of application, with title on top
+pub struct Screen;
+
+impl Screen {
+    pub fn as_proto() -> WieJavaClassProto {
+        WieJavaClassProto {
+            name: "javax/microedition/lcdui/Screen",
+            parent_class: Some("javax/microedition/lcdui/Displayable"),
+            interfaces: vec![],
+            methods: vec![
+                JavaMethodProto::new("<init>", "()V", Self::init, Default::default()),
+                JavaMethodProto::new("getTitle", "()Ljava/lang/String;", Self::get_title, Default::default()),
+                JavaMethodProto::new("setTitle", "(Ljava/lang/String;)V", Self::set_title, Default::default()),
+                // wie private methods...
+                JavaMethodProto::new(
+                    "paintScreen",
+                    "(Ljavax/microedition/lcdui/Graphics;)V",
+                    Self::paint_screen,
+                    Default::default(),
+                ),
+                JavaMethodProto::new("keyEvent", "(II)V", Self::key_event, Default::default()),
+            ],
+            fields: vec![JavaFieldProto::new("title", "Ljava/lang/String;", Default::default())],
+        }
+    }
+
+    async fn init(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.lcdui.Screen::<init>({:?})", &this);
+
+        let _: () = jvm
+            .invoke_special(&this, "javax/microedition/lcdui/Displayable", "<init>", "()V", ())
+            .await?;
+
+        Ok(())
+    }
+
+    async fn get_title(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<ClassInstanceRef<String>> {
+        tracing::debug!("javax.microedition.lcdui.Screen::getTitle({:?})", &this);
+
+        jvm.get_field(&this, "title", "Ljava/lang/String;").await
+    }
+
+    async fn set_title(jvm: &Jvm, context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, title: ClassInstanceRef<String>) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.lcdui.Screen::setTitle({:?}, {:?})", &this, &title);
+
+        jvm.put_field(&mut this, "title", "Ljava/lang/String;", title).await?;
+
+        context.system().platform().screen().request_redraw().unwrap();
+
+        Ok(())
+    }
+
+    async fn paint_screen(
+        jvm: &Jvm,
+        _context: &mut WieJvmContext,
+        this: ClassInstanceRef<Self>,
+        graphics: ClassInstanceRef<Graphics>,
+    ) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.lcdui.Screen::paintScreen({:?}, {:?})", &this, &graphics);
+
+        Self::paint_frame(jvm, &this, &graphics).await?;
+
+        Ok(())
+    }
+
+    // key event from display, ignored unless overridden by a screen handling keys like TextBox
+    async fn key_event(_: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>, event_type: i32, code: i32) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.lcdui.Screen::keyEvent({:?}, {}, {})", &this, event_type, code);
+
+        Ok(())
+    }
+
+    /// Clears the screen and draws title. Returns y coordinate where contents of the screen start.
+    pub async fn paint_frame<T>(jvm: &Jvm, this: &ClassInstanceRef<T>, graphics: &ClassInstanceRef<Graphics>) -> JvmResult<i32> {
+        let width: i32 = jvm.invoke_virtual(this, "getWidth", "()I", ()).await?;
+        let height: i32 = jvm.invoke_virtual(this, "getHeight", "()I", ()).await?;
+        let title: ClassInstanceRef<String> = jvm.get_field(this, "title", "Ljava/lang/String;").await?;
+
+        let _: () = jvm.invoke_virtual(graphics, "setColor", "(I)V", (BACKGROUND_COLOR,)).await?;
+        let _: () = jvm.invoke_virtual(graphics, "fillRect", "(IIII)V", (0, 0, width, height)).await?;
+
+        let _: () = jvm.invoke_virtual(graphics, "setColor", "(I)V", (FOREGROUND_COLOR,)).await?;
+        if title.is_null() {
+            return Ok(MARGIN);
+        }
+
+        let _: () = jvm
+            .invoke_virtual(graphics, "drawString", "(Ljava/lang/String;III)V", (title, MARGIN, MARGIN, LEFT | TOP))
+            .await?;
+
+        // title is separated from contents with a line
+        let font: ClassInstanceRef<Font> = jvm.invoke_virtual(graphics, "getFont", "()Ljavax/microedition/lcdui/Font;", ()).await?;
+        let line_height: i32 = jvm.invoke_virtual(&font, "getHeight", "()I", ()).await?;
+        let bottom = MARGIN * 2 + line_height;
+        let _: () = jvm.invoke_virtual(graphics, "drawLine", "(IIII)V", (0, bottom, width, bottom)).await?;
+
+        Ok(bottom + MARGIN)
+    }
+}
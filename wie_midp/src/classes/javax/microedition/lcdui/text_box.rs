@@ -0,0 +1,586 @@
+use alloc::{string::String as RustString, vec, vec::Vec};
+
+use java_class_proto::{JavaFieldProto, JavaMethodProto};
+use java_constants::FieldAccessFlags;
+use java_runtime::classes::java::lang::String;
+use jvm::{runtime::JavaLangString, ClassInstanceRef, Jvm, Result as JvmResult};
+
+use wie_backend::{canvas::Font as BackendFont, InputMode, KeyCode};
+use wie_jvm_support::{WieJavaClassProto, WieJvmContext};
+
+use crate::classes::{
+    javax::microedition::lcdui::{Font, Graphics, Screen},
+    net::wie::{KeyboardEventType, MIDPKeyCode},
+};
+
+use super::screen::{LEFT, MARGIN, RIGHT, TOP};
+
+// TextField constraints
+const CONSTRAINT_MASK: i32 = 0xffff;
+const NUMERIC: i32 = 2;
+const PHONENUMBER: i32 = 3;
+const DECIMAL: i32 = 5;
+const PASSWORD: i32 = 0x10000;
+
+// input method owner of text box, kept out of 32-bit range used by native text components
+const INPUT_OWNER_BASE: u64 = 1 << 32;
+
+// class javax.microedition.lcdui.TextBox
+// sizes and positions are in UTF-16 code units, as in java strings
+pub struct TextBox;
+
+impl TextBox {
+    pub fn as_proto() -> WieJavaClassProto {
+        WieJavaClassProto {
+            name: "javax/microedition/lcdui/TextBox",
+            parent_class: Some("javax/microedition/lcdui/Screen"),
+            interfaces: vec![],
+            methods: vec![
+                JavaMethodProto::new("<init>", "(Ljava/lang/String;Ljava/lang/String;II)V", Self::init, Default::default()),
+                JavaMethodProto::new("getString", "()Ljava/lang/String;", Self::get_string, Default::default()),
+                JavaMethodProto::new("setString", "(Ljava/lang/String;)V", Self::set_string, Default::default()),
+                JavaMethodProto::new("insert", "(Ljava/lang/String;I)V", Self::insert, Default::default()),
+                JavaMethodProto::new("delete", "(II)V", Self::delete, Default::default()),
+                JavaMethodProto::new("size", "()I", Self::size, Default::default()),
+                JavaMethodProto::new("getMaxSize", "()I", Self::get_max_size, Default::default()),
+                JavaMethodProto::new("setMaxSize", "(I)I", Self::set_max_size, Default::default()),
+                JavaMethodProto::new("getCaretPosition", "()I", Self::get_caret_position, Default::default()),
+                JavaMethodProto::new("getConstraints", "()I", Self::get_constraints, Default::default()),
+                JavaMethodProto::new("setConstraints", "(I)V", Self::set_constraints, Default::default()),
+                // wie private methods...
+                JavaMethodProto::new("textInput", "(Ljava/lang/String;)V", Self::text_input, Default::default()),
+                JavaMethodProto::new("keyEvent", "(II)V", Self::key_event, Default::default()),
+                JavaMethodProto::new(
+                    "paintScreen",
+                    "(Ljavax/microedition/lcdui/Graphics;)V",
+                    Self::paint_screen,
+                    Default::default(),
+                ),
+            ],
+            fields: vec![
+                JavaFieldProto::new("text", "Ljava/lang/String;", Default::default()),
+                JavaFieldProto::new("maxSize", "I", Default::default()),
+                JavaFieldProto::new("constraints", "I", Default::default()),
+                JavaFieldProto::new("caret", "I", Default::default()),
+                JavaFieldProto::new("inputId", "I", Default::default()),
+                JavaFieldProto::new("nextInputId", "I", FieldAccessFlags::STATIC),
+            ],
+        }
+    }
+
+    async fn init(
+        jvm: &Jvm,
+        context: &mut WieJvmContext,
+        mut this: ClassInstanceRef<Self>,
+        title: ClassInstanceRef<String>,
+        text: ClassInstanceRef<String>,
+        max_size: i32,
+        constraints: i32,
+    ) -> JvmResult<()> {
+        tracing::debug!(
+            "javax.microedition.lcdui.TextBox::<init>({:?}, {:?}, {:?}, {}, {})",
+            &this,
+            &title,
+            &text,
+            max_size,
+            constraints
+        );
+
+        if max_size <= 0 {
+            return Err(jvm.exception("java/lang/IllegalArgumentException", "maxSize is zero or less").await);
+        }
+
+        let _: () = jvm.invoke_special(&this, "javax/microedition/lcdui/Screen", "<init>", "()V", ()).await?;
+        let _: () = jvm.invoke_virtual(&this, "setTitle", "(Ljava/lang/String;)V", (title,)).await?;
+
+        let input_id: i32 = jvm.get_static_field("javax/microedition/lcdui/TextBox", "nextInputId", "I").await?;
+        jvm.put_static_field("javax/microedition/lcdui/TextBox", "nextInputId", "I", input_id.wrapping_add(1))
+            .await?;
+
+        jvm.put_field(&mut this, "inputId", "I", input_id).await?;
+        jvm.put_field(&mut this, "maxSize", "I", max_size).await?;
+        jvm.put_field(&mut this, "constraints", "I", constraints).await?;
+
+        let text = Self::to_utf16(jvm, &text).await?;
+        Self::set_text_with_caret_at_end(jvm, context, &mut this, text).await
+    }
+
+    async fn get_string(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<ClassInstanceRef<String>> {
+        tracing::debug!("javax.microedition.lcdui.TextBox::getString({:?})", &this);
+
+        jvm.get_field(&this, "text", "Ljava/lang/String;").await
+    }
+
+    async fn set_string(jvm: &Jvm, context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, text: ClassInstanceRef<String>) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.lcdui.TextBox::setString({:?}, {:?})", &this, &text);
+
+        let text = Self::to_utf16(jvm, &text).await?;
+        Self::set_text_with_caret_at_end(jvm, context, &mut this, text).await
+    }
+
+    async fn insert(
+        jvm: &Jvm,
+        context: &mut WieJvmContext,
+        mut this: ClassInstanceRef<Self>,
+        src: ClassInstanceRef<String>,
+        position: i32,
+    ) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.lcdui.TextBox::insert({:?}, {:?}, {})", &this, &src, position);
+
+        if src.is_null() {
+            return Err(jvm.exception("java/lang/NullPointerException", "src is null").await);
+        }
+
+        let src = Self::to_utf16(jvm, &src).await?;
+        Self::insert_text(jvm, context, &mut this, &src, position).await
+    }
+
+    async fn delete(jvm: &Jvm, context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, offset: i32, length: i32) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.lcdui.TextBox::delete({:?}, {}, {})", &this, offset, length);
+
+        let mut text = Self::text(jvm, &this).await?;
+        if offset < 0 || length < 0 || offset as usize + length as usize > text.len() {
+            return Err(jvm.exception("java/lang/StringIndexOutOfBoundsException", "invalid range").await);
+        }
+
+        text.drain(offset as usize..offset as usize + length as usize);
+        jvm.put_field(&mut this, "caret", "I", offset).await?;
+
+        Self::set_text(jvm, context, &mut this, text).await
+    }
+
+    async fn size(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("javax.microedition.lcdui.TextBox::size({:?})", &this);
+
+        Ok(Self::text(jvm, &this).await?.len() as _)
+    }
+
+    async fn get_max_size(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("javax.microedition.lcdui.TextBox::getMaxSize({:?})", &this);
+
+        jvm.get_field(&this, "maxSize", "I").await
+    }
+
+    async fn set_max_size(jvm: &Jvm, context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, max_size: i32) -> JvmResult<i32> {
+        tracing::debug!("javax.microedition.lcdui.TextBox::setMaxSize({:?}, {})", &this, max_size);
+
+        if max_size <= 0 {
+            return Err(jvm.exception("java/lang/IllegalArgumentException", "maxSize is zero or less").await);
+        }
+
+        // contents are truncated to fit
+        let mut text = Self::text(jvm, &this).await?;
+        text.truncate(max_size as _);
+
+        jvm.put_field(&mut this, "maxSize", "I", max_size).await?;
+        Self::set_text(jvm, context, &mut this, text).await?;
+
+        Ok(max_size)
+    }
+
+    async fn get_caret_position(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("javax.microedition.lcdui.TextBox::getCaretPosition({:?})", &this);
+
+        jvm.get_field(&this, "caret", "I").await
+    }
+
+    async fn get_constraints(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("javax.microedition.lcdui.TextBox::getConstraints({:?})", &this);
+
+        jvm.get_field(&this, "constraints", "I").await
+    }
+
+    async fn set_constraints(jvm: &Jvm, _context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, constraints: i32) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.lcdui.TextBox::setConstraints({:?}, {})", &this, constraints);
+
+        jvm.put_field(&mut this, "constraints", "I", constraints).await
+    }
+
+    // text typed on host keyboard goes in at caret through input method, after what's being composed
+    async fn text_input(jvm: &Jvm, context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, text: ClassInstanceRef<String>) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.lcdui.TextBox::textInput({:?}, {:?})", &this, &text);
+
+        let text = JavaLangString::to_rust_string(jvm, &text).await?;
+
+        Self::attach_input_method(jvm, context, &this).await?;
+        context.system().input_method().insert(&text);
+
+        Self::update_input(jvm, context, &mut this).await
+    }
+
+    // handset keys are turned into text by input method. clear deletes, and left and right move caret
+    async fn key_event(jvm: &Jvm, context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, event_type: i32, code: i32) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.lcdui.TextBox::keyEvent({:?}, {}, {})", &this, event_type, code);
+
+        let repeat = match KeyboardEventType::from_raw(event_type) {
+            KeyboardEventType::KeyPressed => false,
+            KeyboardEventType::KeyRepeated => true,
+            _ => return Ok(()),
+        };
+        let Some(key) = MIDPKeyCode::from_raw(code) else {
+            return Ok(());
+        };
+
+        Self::attach_input_method(jvm, context, &this).await?;
+
+        let mut text = Self::text(jvm, &this).await?;
+        let caret: i32 = jvm.get_field(&this, "caret", "I").await?;
+        let caret = (caret.max(0) as usize).min(text.len());
+        let composing = !context.system().input_method().composition().is_empty();
+
+        match key {
+            MIDPKeyCode::CLEAR => {
+                if !context.system().input_method().backspace() {
+                    if caret == 0 {
+                        return Ok(());
+                    }
+
+                    let start = previous_char(&text, caret);
+                    text.drain(start..caret);
+                    jvm.put_field(&mut this, "caret", "I", start as i32).await?;
+                    Self::set_text(jvm, context, &mut this, text).await?;
+                }
+            }
+            _ if repeat => return Ok(()),
+            MIDPKeyCode::LEFT | MIDPKeyCode::RIGHT if composing => context.system().input_method().commit(),
+            MIDPKeyCode::LEFT => jvm.put_field(&mut this, "caret", "I", previous_char(&text, caret) as i32).await?,
+            MIDPKeyCode::RIGHT => jvm.put_field(&mut this, "caret", "I", next_char(&text, caret) as i32).await?,
+            _ => {
+                let Some(key) = Self::key_code(key) else {
+                    return Ok(());
+                };
+
+                let now = context.system().platform().now();
+                if !context.system().input_method().key(key, now) {
+                    return Ok(());
+                }
+            }
+        }
+
+        Self::update_input(jvm, context, &mut this).await
+    }
+
+    // composition is drawn at caret, and input mode indicator at top right
+    async fn paint_screen(
+        jvm: &Jvm,
+        context: &mut WieJvmContext,
+        this: ClassInstanceRef<Self>,
+        graphics: ClassInstanceRef<Graphics>,
+    ) -> JvmResult<()> {
+        tracing::debug!("javax.microedition.lcdui.TextBox::paintScreen({:?}, {:?})", &this, &graphics);
+
+        let top = Screen::paint_frame(jvm, &this, &graphics).await?;
+
+        let width: i32 = jvm.invoke_virtual(&this, "getWidth", "()I", ()).await?;
+        let height: i32 = jvm.invoke_virtual(&this, "getHeight", "()I", ()).await?;
+        let constraints: i32 = jvm.get_field(&this, "constraints", "I").await?;
+        let text = Self::text(jvm, &this).await?;
+        let caret: i32 = jvm.get_field(&this, "caret", "I").await?;
+        let caret_units = (caret.max(0) as usize).min(text.len());
+
+        let owner = Self::input_owner(jvm, &this).await?;
+        let (composition, mode_label) = {
+            let input_method = context.system().input_method();
+            if input_method.is_attached(owner) {
+                (input_method.composition(), input_method.mode().label())
+            } else {
+                (RustString::new(), "")
+            }
+        };
+
+        let before = RustString::from_utf16_lossy(&text[..caret_units]) + &composition;
+        let caret = before.chars().count();
+        let mut string = before + &RustString::from_utf16_lossy(&text[caret_units..]);
+        if constraints & PASSWORD != 0 {
+            string = string.chars().map(|x| if x == '\n' { x } else { '*' }).collect();
+        }
+
+        let font_ref: ClassInstanceRef<Font> = jvm.invoke_virtual(&graphics, "getFont", "()Ljavax/microedition/lcdui/Font;", ()).await?;
+        let font = Font::font(jvm, context, &font_ref).await?;
+        let line_height = font.height() as i32;
+
+        if !mode_label.is_empty() {
+            let label = JavaLangString::from_rust_string(jvm, mode_label).await?;
+            let _: () = jvm
+                .invoke_virtual(
+                    &graphics,
+                    "drawString",
+                    "(Ljava/lang/String;III)V",
+                    (label, width - MARGIN, top, RIGHT | TOP),
+                )
+                .await?;
+        }
+        let top = top + line_height;
+
+        let lines = wrap_lines(&font, &string, (width - MARGIN * 2).max(0) as _);
+        // caret at wrapped line break is on the next line
+        let caret_line = (0..lines.len())
+            .find(|&i| caret <= lines[i].0 + lines[i].1.chars().count() && lines.get(i + 1).is_none_or(|x| caret < x.0))
+            .unwrap_or(0);
+
+        // scrolled so that caret is visible
+        let visible_lines = ((height - top) / line_height.max(1)).max(1) as usize;
+        let first_line = caret_line.saturating_sub(visible_lines - 1);
+
+        for (i, (start, line)) in lines.iter().enumerate().skip(first_line).take(visible_lines) {
+            let y = top + line_height * (i - first_line) as i32;
+            let line_string = JavaLangString::from_rust_string(jvm, line).await?;
+            let _: () = jvm
+                .invoke_virtual(&graphics, "drawString", "(Ljava/lang/String;III)V", (line_string, MARGIN, y, LEFT | TOP))
+                .await?;
+
+            if i == caret_line {
+                let x = MARGIN + font.string_width(&line.chars().take(caret - start).collect::<RustString>()) as i32;
+                let _: () = jvm
+                    .invoke_virtual(&graphics, "drawLine", "(IIII)V", (x, y, x, y + line_height - 1))
+                    .await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn insert_text(jvm: &Jvm, context: &mut WieJvmContext, this: &mut ClassInstanceRef<Self>, src: &[u16], position: i32) -> JvmResult<()> {
+        let max_size: i32 = jvm.get_field(this, "maxSize", "I").await?;
+        let mut text = Self::text(jvm, this).await?;
+        if text.len() + src.len() > max_size as usize {
+            return Err(jvm.exception("java/lang/IllegalArgumentException", "text exceeds maxSize").await);
+        }
+
+        // out of range position is clamped
+        let position = (position.max(0) as usize).min(text.len());
+        text.splice(position..position, src.iter().copied());
+        jvm.put_field(this, "caret", "I", (position + src.len()) as i32).await?;
+
+        Self::set_text(jvm, context, this, text).await
+    }
+
+    // inserts text committed by input method at caret, dropping what doesn't fit
+    async fn update_input(jvm: &Jvm, context: &mut WieJvmContext, this: &mut ClassInstanceRef<Self>) -> JvmResult<()> {
+        let committed = context.system().input_method().take_committed();
+
+        let max_size: i32 = jvm.get_field(this, "maxSize", "I").await?;
+        let size = Self::text(jvm, this).await?.len();
+
+        let mut committed = committed.encode_utf16().collect::<Vec<_>>();
+        committed.truncate((max_size as usize).saturating_sub(size));
+        if committed.is_empty() {
+            // composition may have changed
+            context.system().platform().screen().request_redraw().unwrap();
+
+            return Ok(());
+        }
+
+        let caret: i32 = jvm.get_field(this, "caret", "I").await?;
+        Self::insert_text(jvm, context, this, &committed, caret).await
+    }
+
+    async fn attach_input_method(jvm: &Jvm, context: &mut WieJvmContext, this: &ClassInstanceRef<Self>) -> JvmResult<()> {
+        let owner = Self::input_owner(jvm, this).await?;
+        let constraints: i32 = jvm.get_field(this, "constraints", "I").await?;
+        let mode = match constraints & CONSTRAINT_MASK {
+            NUMERIC | PHONENUMBER | DECIMAL => InputMode::Number,
+            _ => InputMode::Hangul,
+        };
+
+        context.system().input_method().attach(owner, mode);
+
+        Ok(())
+    }
+
+    async fn input_owner(jvm: &Jvm, this: &ClassInstanceRef<Self>) -> JvmResult<u64> {
+        let input_id: i32 = jvm.get_field(this, "inputId", "I").await?;
+
+        Ok(INPUT_OWNER_BASE | input_id as u32 as u64)
+    }
+
+    async fn text(jvm: &Jvm, this: &ClassInstanceRef<Self>) -> JvmResult<Vec<u16>> {
+        let text = jvm.get_field(this, "text", "Ljava/lang/String;").await?;
+
+        Self::to_utf16(jvm, &text).await
+    }
+
+    async fn set_text_with_caret_at_end(jvm: &Jvm, context: &mut WieJvmContext, this: &mut ClassInstanceRef<Self>, text: Vec<u16>) -> JvmResult<()> {
+        jvm.put_field(this, "caret", "I", text.len() as i32).await?;
+
+        Self::set_text(jvm, context, this, text).await
+    }
+
+    async fn set_text(jvm: &Jvm, context: &mut WieJvmContext, this: &mut ClassInstanceRef<Self>, text: Vec<u16>) -> JvmResult<()> {
+        let max_size: i32 = jvm.get_field(this, "maxSize", "I").await?;
+        if text.len() > max_size as usize {
+            return Err(jvm.exception("java/lang/IllegalArgumentException", "text exceeds maxSize").await);
+        }
+
+        let caret: i32 = jvm.get_field(this, "caret", "I").await?;
+        jvm.put_field(this, "caret", "I", caret.min(text.len() as i32)).await?;
+
+        let text = JavaLangString::from_rust_string(jvm, &RustString::from_utf16_lossy(&text)).await?;
+        jvm.put_field(this, "text", "Ljava/lang/String;", text).await?;
+
+        context.system().platform().screen().request_redraw().unwrap();
+
+        Ok(())
+    }
+
+    // null text is empty
+    async fn to_utf16(jvm: &Jvm, text: &ClassInstanceRef<String>) -> JvmResult<Vec<u16>> {
+        if text.is_null() {
+            return Ok(Vec::new());
+        }
+
+        Ok(JavaLangString::to_rust_string(jvm, text).await?.encode_utf16().collect())
+    }
+
+    fn key_code(key: MIDPKeyCode) -> Option<KeyCode> {
+        Some(match key {
+            MIDPKeyCode::KEY_NUM0 => KeyCode::NUM0,
+            MIDPKeyCode::KEY_NUM1 => KeyCode::NUM1,
+            MIDPKeyCode::KEY_NUM2 => KeyCode::NUM2,
+            MIDPKeyCode::KEY_NUM3 => KeyCode::NUM3,
+            MIDPKeyCode::KEY_NUM4 => KeyCode::NUM4,
+            MIDPKeyCode::KEY_NUM5 => KeyCode::NUM5,
+            MIDPKeyCode::KEY_NUM6 => KeyCode::NUM6,
+            MIDPKeyCode::KEY_NUM7 => KeyCode::NUM7,
+            MIDPKeyCode::KEY_NUM8 => KeyCode::NUM8,
+            MIDPKeyCode::KEY_NUM9 => KeyCode::NUM9,
+            MIDPKeyCode::KEY_STAR => KeyCode::STAR,
+            MIDPKeyCode::KEY_POUND => KeyCode::HASH,
+            MIDPKeyCode::LEFT_SOFT => KeyCode::LEFT_SOFT,
+            _ => return None,
+        })
+    }
+}
+
+// surrogate pairs are moved over and deleted as a whole
+fn is_surrogate_pair(high: u16, low: u16) -> bool {
+    (0xd800..0xdc00).contains(&high) && (0xdc00..0xe000).contains(&low)
+}
+
+fn previous_char(text: &[u16], caret: usize) -> usize {
+    if caret >= 2 && is_surrogate_pair(text[caret - 2], text[caret - 1]) {
+        caret - 2
+    } else {
+        caret.saturating_sub(1)
+    }
+}
+
+fn next_char(text: &[u16], caret: usize) -> usize {
+    if caret + 2 <= text.len() && is_surrogate_pair(text[caret], text[caret + 1]) {
+        caret + 2
+    } else {
+        (caret + 1).min(text.len())
+    }
+}
+
+// lines starting at char index of `text`, broken at newlines and where next char doesn't fit in `width`
+fn wrap_lines(font: &BackendFont, text: &str, width: u32) -> Vec<(usize, RustString)> {
+    let mut lines = vec![(0, RustString::new())];
+    let mut line_width = 0;
+
+    for (i, c) in text.chars().enumerate() {
+        if c == '\n' {
+            lines.push((i + 1, RustString::new()));
+            line_width = 0;
+
+            continue;
+        }
+
+        let char_width = font.char_width(c);
+        if line_width + char_width > width && !lines.last().unwrap().1.is_empty() {
+            lines.push((i, RustString::new()));
+            line_width = 0;
+        }
+
+        lines.last_mut().unwrap().1.push(c);
+        line_width += char_width;
+    }
+
+    lines
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::boxed::Box;
+
+    use jvm::{runtime::JavaLangString, ClassInstanceRef};
+
+    use test_utils::run_jvm_test;
+    use wie_util::Result;
+
+    use crate::{
+        classes::{
+            javax::microedition::lcdui::TextBox,
+            net::wie::{KeyboardEventType, MIDPKeyCode},
+        },
+        get_protos,
+    };
+
+    use super::NUMERIC;
+
+    #[test]
+    fn test_text_input() -> Result<()> {
+        run_jvm_test(Box::new([get_protos().into()]), |jvm| async move {
+            let title = JavaLangString::from_rust_string(&jvm, "title").await?;
+            let text = JavaLangString::from_rust_string(&jvm, "ab").await?;
+            let text_box: ClassInstanceRef<TextBox> = jvm
+                .new_class(
+                    "javax/microedition/lcdui/TextBox",
+                    "(Ljava/lang/String;Ljava/lang/String;II)V",
+                    (title, text, 5, 0),
+                )
+                .await?
+                .into();
+
+            // host text goes in at caret, and what doesn't fit is dropped
+            let input = JavaLangString::from_rust_string(&jvm, "가나다라").await?;
+            let _: () = jvm.invoke_virtual(&text_box, "textInput", "(Ljava/lang/String;)V", (input,)).await?;
+
+            let text = jvm.invoke_virtual(&text_box, "getString", "()Ljava/lang/String;", ()).await?;
+            assert_eq!(JavaLangString::to_rust_string(&jvm, &text).await?, "ab가나다");
+            let caret: i32 = jvm.invoke_virtual(&text_box, "getCaretPosition", "()I", ()).await?;
+            assert_eq!(caret, 5);
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_key_input() -> Result<()> {
+        run_jvm_test(Box::new([get_protos().into()]), |jvm| async move {
+            // sizes are counted in UTF-16 units, so emoji takes two
+            let title = JavaLangString::from_rust_string(&jvm, "title").await?;
+            let text = JavaLangString::from_rust_string(&jvm, "😀").await?;
+            let text_box: ClassInstanceRef<TextBox> = jvm
+                .new_class(
+                    "javax/microedition/lcdui/TextBox",
+                    "(Ljava/lang/String;Ljava/lang/String;II)V",
+                    (title, text, 3, NUMERIC),
+                )
+                .await?
+                .into();
+            let size: i32 = jvm.invoke_virtual(&text_box, "size", "()I", ()).await?;
+            assert_eq!(size, 2);
+
+            let pressed = KeyboardEventType::KeyPressed as i32;
+            for key in [MIDPKeyCode::KEY_NUM5, MIDPKeyCode::KEY_NUM7] {
+                let _: () = jvm.invoke_virtual(&text_box, "keyEvent", "(II)V", (pressed, key as i32)).await?;
+            }
+
+            let text = jvm.invoke_virtual(&text_box, "getString", "()Ljava/lang/String;", ()).await?;
+            assert_eq!(JavaLangString::to_rust_string(&jvm, &text).await?, "😀5");
+
+            // clear removes surrogate pair as a whole
+            for _ in 0..2 {
+                let _: () = jvm
+                    .invoke_virtual(&text_box, "keyEvent", "(II)V", (pressed, MIDPKeyCode::CLEAR as i32))
+                    .await?;
+            }
+
+            let size: i32 = jvm.invoke_virtual(&text_box, "size", "()I", ()).await?;
+            assert_eq!(size, 0);
+
+            Ok(())
+        })
+    }
+}
@@ -1,5 +1,12 @@
+pub mod control;
+mod control_interface;
+mod controllable;
 mod manager;
 mod media_exception;
 mod player;
+mod player_listener;
 
-pub use self::{manager::Manager, media_exception::MediaException, player::Player};
+pub use self::{
+    control_interface::Control, controllable::Controllable, manager::Manager, media_exception::MediaException, player::Player,
+    player_listener::PlayerListener,
+};
@@ -0,0 +1,4 @@
+mod tone_control;
+mod volume_control;
+
+pub use self::{tone_control::ToneControl, volume_control::VolumeControl};
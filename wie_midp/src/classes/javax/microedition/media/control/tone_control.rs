@@ -0,0 +1,20 @@
+use alloc::vec;
+
+use java_class_proto::JavaMethodProto;
+
+use wie_jvm_support::WieJavaClassProto;
+
+// interface javax.microedition.media.control.ToneControl
+pub struct ToneControl;
+
+impl ToneControl {
+    pub fn as_proto() -> WieJavaClassProto {
+        WieJavaClassProto {
+            name: "javax/microedition/media/control/ToneControl",
+            parent_class: None,
+            interfaces: vec!["javax/microedition/media/Control"],
+            methods: vec![JavaMethodProto::new_abstract("setSequence", "([B)V", Default::default())],
+            fields: vec![],
+        }
+    }
+}
@@ -0,0 +1,23 @@
+use alloc::vec;
+
+use java_class_proto::JavaMethodProto;
+
+use wie_jvm_support::WieJavaClassProto;
+
+// interface javax.microedition.media.Controllable
+pub struct Controllable;
+
+impl Controllable {
+    pub fn as_proto() -> WieJavaClassProto {
+        WieJavaClassProto {
+            name: "javax/microedition/media/Controllable",
+            parent_class: None,
+            interfaces: vec![],
+            methods: vec![
+                JavaMethodProto::new_abstract("getControl", "(Ljava/lang/String;)Ljavax/microedition/media/Control;", Default::default()),
+                JavaMethodProto::new_abstract("getControls", "()[Ljavax/microedition/media/Control;", Default::default()),
+            ],
+            fields: vec![],
+        }
+    }
+}
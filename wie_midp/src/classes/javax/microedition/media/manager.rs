@@ -9,6 +9,8 @@ use wie_jvm_support::{WieJavaClassProto, WieJvmContext};
 
 use crate::classes::javax::microedition::media::Player;
 
+const TONE_DEVICE_LOCATOR: &str = "device://tone";
+
 // class javax.microedition.media.Manager
 pub struct Manager;
 
@@ -18,12 +20,21 @@ impl Manager {
             name: "javax/microedition/media/Manager",
             parent_class: Some("java/lang/Object"),
             interfaces: vec![],
-            methods: vec![JavaMethodProto::new(
-                "createPlayer",
-                "(Ljava/io/InputStream;Ljava/lang/String;)Ljavax/microedition/media/Player;",
-                Self::create_player,
-                MethodAccessFlags::STATIC,
-            )],
+            methods: vec![
+                JavaMethodProto::new(
+                    "createPlayer",
+                    "(Ljava/io/InputStream;Ljava/lang/String;)Ljavax/microedition/media/Player;",
+                    Self::create_player,
+                    MethodAccessFlags::STATIC,
+                ),
+                JavaMethodProto::new(
+                    "createPlayer",
+                    "(Ljava/lang/String;)Ljavax/microedition/media/Player;",
+                    Self::create_player_with_locator,
+                    MethodAccessFlags::STATIC,
+                ),
+                JavaMethodProto::new("playTone", "(III)V", Self::play_tone, MethodAccessFlags::STATIC),
+            ],
             fields: vec![],
         }
     }
@@ -36,11 +47,42 @@ impl Manager {
     ) -> Result<ClassInstanceRef<Player>> {
         tracing::debug!("javax.microedition.media.Manager::createPlayer({:?}, {:?})", &stream, &r#type);
 
-        let type_string = JavaLangString::to_rust_string(jvm, &r#type).await?;
-        if type_string == "application/vnd.smaf" {
-            Ok(jvm.new_class("net/wie/SmafPlayer", "(Ljava/io/InputStream;)V", (stream,)).await?.into())
-        } else {
-            Err(jvm.exception("javax/microedition/media/MediaException", "Unsupported media type").await)
+        // content is sniffed, so type is only a hint
+        Ok(jvm
+            .new_class("net/wie/AudioPlayer", "(Ljava/io/InputStream;Ljava/lang/String;)V", (stream, r#type))
+            .await?
+            .into())
+    }
+
+    async fn create_player_with_locator(
+        jvm: &Jvm,
+        _context: &mut WieJvmContext,
+        locator: ClassInstanceRef<String>,
+    ) -> Result<ClassInstanceRef<Player>> {
+        tracing::debug!("javax.microedition.media.Manager::createPlayer({:?})", &locator);
+
+        if locator.is_null() {
+            return Err(jvm.exception("java/lang/IllegalArgumentException", "Locator is null").await);
+        }
+
+        // only tone device is supported, as there's no network or file access
+        let locator = JavaLangString::to_rust_string(jvm, &locator).await?;
+        if locator != TONE_DEVICE_LOCATOR {
+            return Err(jvm.exception("javax/microedition/media/MediaException", "Unsupported locator").await);
+        }
+
+        Ok(jvm.new_class("net/wie/AudioPlayer", "()V", ()).await?.into())
+    }
+
+    async fn play_tone(jvm: &Jvm, context: &mut WieJvmContext, note: i32, duration: i32, volume: i32) -> Result<()> {
+        tracing::debug!("javax.microedition.media.Manager::playTone({}, {}, {})", note, duration, volume);
+
+        if !(0..=127).contains(&note) || duration <= 0 {
+            return Err(jvm.exception("java/lang/IllegalArgumentException", "Invalid note or duration").await);
         }
+
+        context.system().audio().play_tone(note as _, duration as _, volume.clamp(0, 100) as _);
+
+        Ok(())
     }
 }
@@ -12,8 +12,22 @@ impl Player {
         WieJavaClassProto {
             name: "javax/microedition/media/Player",
             parent_class: None,
-            interfaces: vec![],
-            methods: vec![JavaMethodProto::new_abstract("start", "()V", Default::default())],
+            interfaces: vec!["javax/microedition/media/Controllable"],
+            methods: vec![
+                JavaMethodProto::new_abstract("realize", "()V", Default::default()),
+                JavaMethodProto::new_abstract("prefetch", "()V", Default::default()),
+                JavaMethodProto::new_abstract("start", "()V", Default::default()),
+                JavaMethodProto::new_abstract("stop", "()V", Default::default()),
+                JavaMethodProto::new_abstract("deallocate", "()V", Default::default()),
+                JavaMethodProto::new_abstract("close", "()V", Default::default()),
+                JavaMethodProto::new_abstract("getState", "()I", Default::default()),
+                JavaMethodProto::new_abstract("setLoopCount", "(I)V", Default::default()),
+                JavaMethodProto::new_abstract("getDuration", "()J", Default::default()),
+                JavaMethodProto::new_abstract("getMediaTime", "()J", Default::default()),
+                JavaMethodProto::new_abstract("setMediaTime", "(J)J", Default::default()),
+                JavaMethodProto::new_abstract("addPlayerListener", "(Ljavax/microedition/media/PlayerListener;)V", Default::default()),
+                JavaMethodProto::new_abstract("removePlayerListener", "(Ljavax/microedition/media/PlayerListener;)V", Default::default()),
+            ],
             fields: vec![],
         }
     }
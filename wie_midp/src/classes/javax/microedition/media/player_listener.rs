@@ -0,0 +1,24 @@
+use alloc::vec;
+
+use java_class_proto::JavaMethodProto;
+
+use wie_jvm_support::WieJavaClassProto;
+
+// interface javax.microedition.media.PlayerListener
+pub struct PlayerListener;
+
+impl PlayerListener {
+    pub fn as_proto() -> WieJavaClassProto {
+        WieJavaClassProto {
+            name: "javax/microedition/media/PlayerListener",
+            parent_class: None,
+            interfaces: vec![],
+            methods: vec![JavaMethodProto::new_abstract(
+                "playerUpdate",
+                "(Ljavax/microedition/media/Player;Ljava/lang/String;Ljava/lang/Object;)V",
+                Default::default(),
+            )],
+            fields: vec![],
+        }
+    }
+}
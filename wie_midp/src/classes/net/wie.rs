@@ -1,9 +1,9 @@
+mod audio_player;
 mod event_queue;
 mod launcher;
-mod smaf_player;
 
 pub use self::{
-    event_queue::{EventQueue, KeyboardEventType, MIDPKeyCode},
+    audio_player::AudioPlayer,
+    event_queue::{EventQueue, KeyboardEventType, MIDPKeyCode, PointerEventType},
     launcher::Launcher,
-    smaf_player::SmafPlayer,
 };
@@ -0,0 +1,543 @@
+use alloc::{boxed::Box, vec, vec::Vec};
+
+use java_class_proto::{JavaFieldProto, JavaMethodProto, MethodBody};
+use java_runtime::classes::java::{io::InputStream, lang::String};
+use jvm::{
+    runtime::{JavaIoInputStream, JavaLangString},
+    Array, ClassInstanceRef, JavaError, JavaValue, Jvm, Result,
+};
+
+use wie_backend::{AudioEvent, AudioHandle};
+use wie_jvm_support::{WieJavaClassProto, WieJvmContext};
+
+use crate::classes::javax::microedition::media::{Control, Player, PlayerListener};
+
+// javax.microedition.media.Player states
+const CLOSED: i32 = 0;
+const UNREALIZED: i32 = 100;
+const REALIZED: i32 = 200;
+const PREFETCHED: i32 = 300;
+const STARTED: i32 = 400;
+
+const TIME_UNKNOWN: i64 = -1;
+
+// javax.microedition.media.PlayerListener events
+const EVENT_STARTED: &str = "started";
+const EVENT_STOPPED: &str = "stopped";
+const EVENT_END_OF_MEDIA: &str = "endOfMedia";
+const EVENT_CLOSED: &str = "closed";
+const EVENT_VOLUME_CHANGED: &str = "volumeChanged";
+
+// class net.wie.AudioPlayer
+pub struct AudioPlayer;
+
+impl AudioPlayer {
+    pub fn as_proto() -> WieJavaClassProto {
+        WieJavaClassProto {
+            name: "net/wie/AudioPlayer",
+            parent_class: Some("java/lang/Object"),
+            interfaces: vec![
+                "javax/microedition/media/Player",
+                "javax/microedition/media/control/VolumeControl",
+                "javax/microedition/media/control/ToneControl",
+            ],
+            methods: vec![
+                JavaMethodProto::new("<init>", "()V", Self::init, Default::default()),
+                JavaMethodProto::new(
+                    "<init>",
+                    "(Ljava/io/InputStream;Ljava/lang/String;)V",
+                    Self::init_with_stream,
+                    Default::default(),
+                ),
+                JavaMethodProto::new("realize", "()V", Self::realize, Default::default()),
+                JavaMethodProto::new("prefetch", "()V", Self::prefetch, Default::default()),
+                JavaMethodProto::new("start", "()V", Self::start, Default::default()),
+                JavaMethodProto::new("stop", "()V", Self::stop, Default::default()),
+                JavaMethodProto::new("deallocate", "()V", Self::deallocate, Default::default()),
+                JavaMethodProto::new("close", "()V", Self::close, Default::default()),
+                JavaMethodProto::new("getState", "()I", Self::get_state, Default::default()),
+                JavaMethodProto::new("setLoopCount", "(I)V", Self::set_loop_count, Default::default()),
+                JavaMethodProto::new("getDuration", "()J", Self::get_duration, Default::default()),
+                JavaMethodProto::new("getMediaTime", "()J", Self::get_media_time, Default::default()),
+                JavaMethodProto::new("setMediaTime", "(J)J", Self::set_media_time, Default::default()),
+                JavaMethodProto::new(
+                    "addPlayerListener",
+                    "(Ljavax/microedition/media/PlayerListener;)V",
+                    Self::add_player_listener,
+                    Default::default(),
+                ),
+                JavaMethodProto::new(
+                    "removePlayerListener",
+                    "(Ljavax/microedition/media/PlayerListener;)V",
+                    Self::remove_player_listener,
+                    Default::default(),
+                ),
+                JavaMethodProto::new(
+                    "getControl",
+                    "(Ljava/lang/String;)Ljavax/microedition/media/Control;",
+                    Self::get_control,
+                    Default::default(),
+                ),
+                JavaMethodProto::new(
+                    "getControls",
+                    "()[Ljavax/microedition/media/Control;",
+                    Self::get_controls,
+                    Default::default(),
+                ),
+                JavaMethodProto::new("setLevel", "(I)I", Self::set_level, Default::default()),
+                JavaMethodProto::new("getLevel", "()I", Self::get_level, Default::default()),
+                JavaMethodProto::new("setMute", "(Z)V", Self::set_mute, Default::default()),
+                JavaMethodProto::new("isMuted", "()Z", Self::is_muted, Default::default()),
+                JavaMethodProto::new("setSequence", "([B)V", Self::set_sequence, Default::default()),
+            ],
+            fields: vec![
+                JavaFieldProto::new("audioHandle", "I", Default::default()),
+                JavaFieldProto::new("state", "I", Default::default()),
+                JavaFieldProto::new("loopCount", "I", Default::default()),
+                JavaFieldProto::new("level", "I", Default::default()),
+                JavaFieldProto::new("muted", "I", Default::default()),
+                JavaFieldProto::new("listeners", "Ljava/util/Vector;", Default::default()),
+            ],
+        }
+    }
+
+    async fn init(jvm: &Jvm, _: &mut WieJvmContext, mut this: ClassInstanceRef<Self>) -> Result<()> {
+        tracing::debug!("net.wie.AudioPlayer::<init>({:?})", &this);
+
+        let _: () = jvm.invoke_special(&this, "java/lang/Object", "<init>", "()V", ()).await?;
+
+        // tone player, which gets its audio from ToneControl.setSequence
+        let listeners = jvm.new_class("java/util/Vector", "()V", ()).await?;
+        jvm.put_field(&mut this, "listeners", "Ljava/util/Vector;", listeners).await?;
+        jvm.put_field(&mut this, "state", "I", UNREALIZED).await?;
+        jvm.put_field(&mut this, "loopCount", "I", 1).await?;
+        jvm.put_field(&mut this, "level", "I", 100).await?;
+
+        Ok(())
+    }
+
+    async fn init_with_stream(
+        jvm: &Jvm,
+        context: &mut WieJvmContext,
+        mut this: ClassInstanceRef<Self>,
+        stream: ClassInstanceRef<InputStream>,
+        r#type: ClassInstanceRef<String>,
+    ) -> Result<()> {
+        tracing::debug!("net.wie.AudioPlayer::<init>({:?}, {:?})", &stream, &r#type);
+
+        let _: () = jvm.invoke_special(&this, "net/wie/AudioPlayer", "<init>", "()V", ()).await?;
+
+        let data = JavaIoInputStream::read_until_end(jvm, &stream).await?;
+        let content_type = if r#type.is_null() {
+            None
+        } else {
+            Some(JavaLangString::to_rust_string(jvm, &r#type).await?)
+        };
+
+        let audio_handle = context.system().audio().load(&data, content_type.as_deref());
+        let Ok(audio_handle) = audio_handle else {
+            return Err(jvm.exception("javax/microedition/media/MediaException", "Unsupported media type").await);
+        };
+
+        jvm.put_field(&mut this, "audioHandle", "I", audio_handle as i32).await?;
+
+        Ok(())
+    }
+
+    async fn realize(jvm: &Jvm, _: &mut WieJvmContext, mut this: ClassInstanceRef<Self>) -> Result<()> {
+        tracing::debug!("net.wie.AudioPlayer::realize({:?})", &this);
+
+        if Self::check_state(jvm, &this).await? == UNREALIZED {
+            jvm.put_field(&mut this, "state", "I", REALIZED).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn prefetch(jvm: &Jvm, _: &mut WieJvmContext, mut this: ClassInstanceRef<Self>) -> Result<()> {
+        tracing::debug!("net.wie.AudioPlayer::prefetch({:?})", &this);
+
+        // audio is loaded on creation, so there's nothing to acquire
+        if Self::check_state(jvm, &this).await? < PREFETCHED {
+            jvm.put_field(&mut this, "state", "I", PREFETCHED).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn start(jvm: &Jvm, context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>) -> Result<()> {
+        tracing::debug!("net.wie.AudioPlayer::start({:?})", &this);
+
+        if Self::check_state(jvm, &this).await? == STARTED {
+            return Ok(());
+        }
+
+        let audio_handle = Self::handle(jvm, &this).await?;
+        let loop_count: i32 = jvm.get_field(&this, "loopCount", "I").await?;
+
+        let serial = context.system().audio().last_event_serial(audio_handle).unwrap_or(0);
+        let _ = context.system().audio().play(audio_handle, loop_count);
+
+        jvm.put_field(&mut this, "state", "I", STARTED).await?;
+
+        context.spawn(
+            jvm,
+            Box::new(EndOfMediaNotifier {
+                player: this.clone(),
+                audio_handle,
+                serial,
+            }),
+        )?;
+
+        Self::notify(jvm, &this, EVENT_STARTED, None.into()).await
+    }
+
+    async fn stop(jvm: &Jvm, context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>) -> Result<()> {
+        tracing::debug!("net.wie.AudioPlayer::stop({:?})", &this);
+
+        if Self::check_state(jvm, &this).await? != STARTED {
+            return Ok(());
+        }
+
+        // stopped player resumes from current media time on start
+        let audio_handle = Self::handle(jvm, &this).await?;
+        let _ = context.system().audio().stop(audio_handle);
+
+        jvm.put_field(&mut this, "state", "I", PREFETCHED).await?;
+
+        Self::notify(jvm, &this, EVENT_STOPPED, None.into()).await
+    }
+
+    async fn deallocate(jvm: &Jvm, _: &mut WieJvmContext, mut this: ClassInstanceRef<Self>) -> Result<()> {
+        tracing::debug!("net.wie.AudioPlayer::deallocate({:?})", &this);
+
+        let state = Self::check_state(jvm, &this).await?;
+        if state == STARTED {
+            let _: () = jvm.invoke_virtual(&this, "stop", "()V", ()).await?;
+        }
+
+        if state >= PREFETCHED {
+            jvm.put_field(&mut this, "state", "I", REALIZED).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn close(jvm: &Jvm, context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>) -> Result<()> {
+        tracing::debug!("net.wie.AudioPlayer::close({:?})", &this);
+
+        let state: i32 = jvm.get_field(&this, "state", "I").await?;
+        if state == CLOSED {
+            return Ok(());
+        }
+
+        let audio_handle = Self::handle(jvm, &this).await?;
+        let _ = context.system().audio().close(audio_handle);
+
+        jvm.put_field(&mut this, "state", "I", CLOSED).await?;
+
+        Self::notify(jvm, &this, EVENT_CLOSED, None.into()).await
+    }
+
+    async fn get_state(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> Result<i32> {
+        tracing::debug!("net.wie.AudioPlayer::getState({:?})", &this);
+
+        jvm.get_field(&this, "state", "I").await
+    }
+
+    async fn set_loop_count(jvm: &Jvm, _: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, count: i32) -> Result<()> {
+        tracing::debug!("net.wie.AudioPlayer::setLoopCount({:?}, {})", &this, count);
+
+        if Self::check_state(jvm, &this).await? == STARTED {
+            return Err(jvm.exception("java/lang/IllegalStateException", "Player is started").await);
+        }
+
+        if count == 0 {
+            return Err(jvm.exception("java/lang/IllegalArgumentException", "Loop count is zero").await);
+        }
+
+        jvm.put_field(&mut this, "loopCount", "I", count).await
+    }
+
+    async fn get_duration(jvm: &Jvm, context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> Result<i64> {
+        tracing::debug!("net.wie.AudioPlayer::getDuration({:?})", &this);
+
+        Self::check_state(jvm, &this).await?;
+
+        let audio_handle = Self::handle(jvm, &this).await?;
+        let duration = context.system().audio().duration(audio_handle);
+
+        Ok(match duration {
+            Ok(Some(x)) => x as i64 * 1000,
+            _ => TIME_UNKNOWN,
+        })
+    }
+
+    async fn get_media_time(jvm: &Jvm, context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> Result<i64> {
+        tracing::debug!("net.wie.AudioPlayer::getMediaTime({:?})", &this);
+
+        Self::check_state(jvm, &this).await?;
+
+        let audio_handle = Self::handle(jvm, &this).await?;
+        let position = context.system().audio().position(audio_handle);
+
+        Ok(position.map(|x| x as i64 * 1000).unwrap_or(TIME_UNKNOWN))
+    }
+
+    async fn set_media_time(jvm: &Jvm, context: &mut WieJvmContext, this: ClassInstanceRef<Self>, now: i64) -> Result<i64> {
+        tracing::debug!("net.wie.AudioPlayer::setMediaTime({:?}, {})", &this, now);
+
+        if Self::check_state(jvm, &this).await? == UNREALIZED {
+            return Err(jvm.exception("java/lang/IllegalStateException", "Player is unrealized").await);
+        }
+
+        let audio_handle = Self::handle(jvm, &this).await?;
+        let mut audio = context.system().audio();
+
+        let mut position = now.max(0) as u64 / 1000;
+        if let Ok(Some(duration)) = audio.duration(audio_handle) {
+            position = position.min(duration);
+        }
+
+        if audio.set_position(audio_handle, position).is_err() {
+            return Ok(TIME_UNKNOWN);
+        }
+
+        Ok(position as i64 * 1000)
+    }
+
+    async fn add_player_listener(
+        jvm: &Jvm,
+        _: &mut WieJvmContext,
+        this: ClassInstanceRef<Self>,
+        listener: ClassInstanceRef<PlayerListener>,
+    ) -> Result<()> {
+        tracing::debug!("net.wie.AudioPlayer::addPlayerListener({:?}, {:?})", &this, &listener);
+
+        Self::check_state(jvm, &this).await?;
+
+        if !listener.is_null() {
+            let listeners = jvm.get_field(&this, "listeners", "Ljava/util/Vector;").await?;
+            let _: () = jvm.invoke_virtual(&listeners, "addElement", "(Ljava/lang/Object;)V", (listener,)).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn remove_player_listener(
+        jvm: &Jvm,
+        _: &mut WieJvmContext,
+        this: ClassInstanceRef<Self>,
+        listener: ClassInstanceRef<PlayerListener>,
+    ) -> Result<()> {
+        tracing::debug!("net.wie.AudioPlayer::removePlayerListener({:?}, {:?})", &this, &listener);
+
+        Self::check_state(jvm, &this).await?;
+
+        let listeners = jvm.get_field(&this, "listeners", "Ljava/util/Vector;").await?;
+        let _: bool = jvm
+            .invoke_virtual(&listeners, "removeElement", "(Ljava/lang/Object;)Z", (listener,))
+            .await?;
+
+        Ok(())
+    }
+
+    async fn get_control(
+        jvm: &Jvm,
+        _: &mut WieJvmContext,
+        this: ClassInstanceRef<Self>,
+        control_type: ClassInstanceRef<String>,
+    ) -> Result<ClassInstanceRef<Self>> {
+        tracing::debug!("net.wie.AudioPlayer::getControl({:?}, {:?})", &this, &control_type);
+
+        if Self::check_state(jvm, &this).await? == UNREALIZED {
+            return Err(jvm.exception("java/lang/IllegalStateException", "Player is unrealized").await);
+        }
+
+        // control type may be a fully qualified name
+        let control_type = JavaLangString::to_rust_string(jvm, &control_type).await?;
+        let control_name = control_type.rsplit('.').next().unwrap();
+
+        if control_name == "VolumeControl" || control_name == "ToneControl" {
+            Ok(this)
+        } else {
+            Ok(None.into())
+        }
+    }
+
+    async fn get_controls(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> Result<ClassInstanceRef<Array<Control>>> {
+        tracing::debug!("net.wie.AudioPlayer::getControls({:?})", &this);
+
+        if Self::check_state(jvm, &this).await? == UNREALIZED {
+            return Err(jvm.exception("java/lang/IllegalStateException", "Player is unrealized").await);
+        }
+
+        // player itself is both of its controls, so it's listed once
+        let mut controls = jvm.instantiate_array("Ljavax/microedition/media/Control;", 1).await?;
+        jvm.store_array(&mut controls, 0, vec![this]).await?;
+
+        Ok(controls.into())
+    }
+
+    async fn set_level(jvm: &Jvm, context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, level: i32) -> Result<i32> {
+        tracing::debug!("net.wie.AudioPlayer::setLevel({:?}, {})", &this, level);
+
+        let level = level.clamp(0, 100);
+        let old_level: i32 = jvm.get_field(&this, "level", "I").await?;
+
+        jvm.put_field(&mut this, "level", "I", level).await?;
+        Self::apply_volume(jvm, context, &this).await?;
+
+        if level != old_level {
+            Self::notify(jvm, &this, EVENT_VOLUME_CHANGED, this.clone().into()).await?;
+        }
+
+        Ok(level)
+    }
+
+    async fn get_level(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> Result<i32> {
+        tracing::debug!("net.wie.AudioPlayer::getLevel({:?})", &this);
+
+        jvm.get_field(&this, "level", "I").await
+    }
+
+    async fn set_mute(jvm: &Jvm, context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, mute: bool) -> Result<()> {
+        tracing::debug!("net.wie.AudioPlayer::setMute({:?}, {})", &this, mute);
+
+        let old_muted: i32 = jvm.get_field(&this, "muted", "I").await?;
+
+        jvm.put_field(&mut this, "muted", "I", mute as i32).await?;
+        Self::apply_volume(jvm, context, &this).await?;
+
+        if mute as i32 != old_muted {
+            Self::notify(jvm, &this, EVENT_VOLUME_CHANGED, this.clone().into()).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn is_muted(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> Result<bool> {
+        tracing::debug!("net.wie.AudioPlayer::isMuted({:?})", &this);
+
+        let muted: i32 = jvm.get_field(&this, "muted", "I").await?;
+
+        Ok(muted != 0)
+    }
+
+    async fn set_sequence(
+        jvm: &Jvm,
+        context: &mut WieJvmContext,
+        mut this: ClassInstanceRef<Self>,
+        sequence: ClassInstanceRef<Array<i8>>,
+    ) -> Result<()> {
+        tracing::debug!("net.wie.AudioPlayer::setSequence({:?}, {:?})", &this, &sequence);
+
+        if Self::check_state(jvm, &this).await? >= PREFETCHED {
+            return Err(jvm.exception("java/lang/IllegalStateException", "Player is prefetched").await);
+        }
+
+        let length = jvm.array_length(&sequence).await?;
+        let data: Vec<i8> = jvm.load_array(&sequence, 0, length).await?;
+
+        let audio_handle = context.system().audio().load(bytemuck::cast_slice(&data), Some("audio/x-tone-seq"));
+        let Ok(audio_handle) = audio_handle else {
+            return Err(jvm.exception("java/lang/IllegalArgumentException", "Invalid tone sequence").await);
+        };
+
+        let old_audio_handle = Self::handle(jvm, &this).await?;
+        let _ = context.system().audio().close(old_audio_handle);
+
+        jvm.put_field(&mut this, "audioHandle", "I", audio_handle as i32).await?;
+        Self::apply_volume(jvm, context, &this).await
+    }
+
+    pub async fn audio_handle(jvm: &Jvm, player: &ClassInstanceRef<Player>) -> Result<AudioHandle> {
+        let audio_handle: i32 = jvm.get_field(player, "audioHandle", "I").await?;
+
+        Ok(audio_handle as _)
+    }
+
+    async fn handle(jvm: &Jvm, this: &ClassInstanceRef<Self>) -> Result<AudioHandle> {
+        let audio_handle: i32 = jvm.get_field(this, "audioHandle", "I").await?;
+
+        Ok(audio_handle as _)
+    }
+
+    // returns current state, throwing if player is closed
+    async fn check_state(jvm: &Jvm, this: &ClassInstanceRef<Self>) -> Result<i32> {
+        let state: i32 = jvm.get_field(this, "state", "I").await?;
+        if state == CLOSED {
+            return Err(jvm.exception("java/lang/IllegalStateException", "Player is closed").await);
+        }
+
+        Ok(state)
+    }
+
+    async fn apply_volume(jvm: &Jvm, context: &mut WieJvmContext, this: &ClassInstanceRef<Self>) -> Result<()> {
+        let level: i32 = jvm.get_field(this, "level", "I").await?;
+        let muted: i32 = jvm.get_field(this, "muted", "I").await?;
+
+        let audio_handle = Self::handle(jvm, this).await?;
+        let volume = if muted != 0 { 0 } else { level };
+        let _ = context.system().audio().set_volume(audio_handle, volume as _);
+
+        Ok(())
+    }
+
+    async fn notify(jvm: &Jvm, this: &ClassInstanceRef<Self>, event: &str, data: JavaValue) -> Result<()> {
+        let listeners = jvm.get_field(this, "listeners", "Ljava/util/Vector;").await?;
+        let count: i32 = jvm.invoke_virtual(&listeners, "size", "()I", ()).await?;
+
+        for i in 0..count {
+            let listener = jvm.invoke_virtual(&listeners, "elementAt", "(I)Ljava/lang/Object;", (i,)).await?;
+            let event = JavaLangString::from_rust_string(jvm, event).await?;
+
+            let _: () = jvm
+                .invoke_virtual(
+                    &listener,
+                    "playerUpdate",
+                    "(Ljavax/microedition/media/Player;Ljava/lang/String;Ljava/lang/Object;)V",
+                    (this.clone(), event, data.clone()),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+// moves player out of started state on end of media
+struct EndOfMediaNotifier {
+    player: ClassInstanceRef<AudioPlayer>,
+    audio_handle: AudioHandle,
+    serial: u64,
+}
+
+#[async_trait::async_trait]
+impl MethodBody<JavaError, WieJvmContext> for EndOfMediaNotifier {
+    async fn call(&self, jvm: &Jvm, context: &mut WieJvmContext, _args: Box<[JavaValue]>) -> core::result::Result<JavaValue, JavaError> {
+        jvm.attach_thread()?;
+
+        let mut serial = self.serial;
+        while let Some(event) = context.system().wait_audio_event(self.audio_handle, &mut serial).await {
+            match event {
+                AudioEvent::EndOfMedia => {
+                    let mut player = self.player.clone();
+
+                    let state: i32 = jvm.get_field(&player, "state", "I").await?;
+                    if state == STARTED {
+                        jvm.put_field(&mut player, "state", "I", PREFETCHED).await?;
+                    }
+
+                    // media time isn't boxed into Long, as games only check the event
+                    AudioPlayer::notify(jvm, &player, EVENT_END_OF_MEDIA, None.into()).await?;
+                    break;
+                }
+                AudioEvent::Stopped => break,
+                _ => {}
+            }
+        }
+
+        Ok(JavaValue::Void)
+    }
+}
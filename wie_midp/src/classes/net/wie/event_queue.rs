@@ -3,16 +3,17 @@ use alloc::vec;
 use java_class_proto::{JavaFieldProto, JavaMethodProto};
 use java_constants::{FieldAccessFlags, MethodAccessFlags};
 use java_runtime::classes::java::lang::Runnable;
-use jvm::{Array, ClassInstanceRef, Jvm, Result as JvmResult};
+use jvm::{runtime::JavaLangString, Array, ClassInstanceRef, Jvm, Result as JvmResult};
 
 use wie_backend::{Event, KeyCode};
 use wie_jvm_support::{WieJavaClassProto, WieJvmContext};
 
-use crate::classes::javax::microedition::midlet::MIDlet;
+use crate::classes::javax::microedition::{lcdui::Display, midlet::MIDlet};
 
 #[repr(i32)]
 enum EventQueueEvent {
     KeyEvent = 1,
+    PointerEvent = 2,
     RepaintEvent = 41,
 }
 
@@ -38,7 +39,27 @@ impl KeyboardEventType {
     }
 }
 
+// flag in last slot of key event. neither MIDP nor WIPI has a long press event, so it's delivered as a key repeat
+const KEY_EVENT_LONG_PRESS: i32 = 1;
+
+#[repr(i32)]
+#[derive(Debug)]
+#[allow(clippy::enum_variant_names)]
+pub enum PointerEventType {
+    PointerPressed = 1,
+    PointerReleased = 2,
+    PointerDragged = 3,
+}
+
+impl PointerEventType {
+    pub fn from_raw(raw: i32) -> Self {
+        unsafe { core::mem::transmute(raw) }
+    }
+}
+
+/// Key codes delivered to MIDP `Canvas`. SK-VM handsets use the same codes.
 #[repr(i32)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 #[allow(clippy::upper_case_acronyms)]
 #[allow(non_camel_case_types)]
 pub enum MIDPKeyCode {
@@ -47,6 +68,10 @@ pub enum MIDPKeyCode {
     LEFT = 2,
     RIGHT = 5,
     FIRE = 8,
+    GAME_A = 9,
+    GAME_B = 10,
+    GAME_C = 11,
+    GAME_D = 12,
 
     KEY_NUM0 = 48,
     KEY_NUM1 = 49,
@@ -61,15 +86,56 @@ pub enum MIDPKeyCode {
     KEY_POUND = 35, // #
     KEY_STAR = 42,  // *
 
+    // vendor specific negative codes
+    LEFT_SOFT = -6,
+    RIGHT_SOFT = -7,
+    SEND = -10,
+    END = -11,
+    VOLUME_UP = -12,
+    VOLUME_DOWN = -13,
+    CAMERA = -14,
     CLEAR = -16,
 }
 
 impl MIDPKeyCode {
-    pub fn from_raw(raw: i32) -> Self {
-        unsafe { core::mem::transmute(raw) }
+    const ALL: [Self; 29] = [
+        Self::UP,
+        Self::DOWN,
+        Self::LEFT,
+        Self::RIGHT,
+        Self::FIRE,
+        Self::GAME_A,
+        Self::GAME_B,
+        Self::GAME_C,
+        Self::GAME_D,
+        Self::KEY_NUM0,
+        Self::KEY_NUM1,
+        Self::KEY_NUM2,
+        Self::KEY_NUM3,
+        Self::KEY_NUM4,
+        Self::KEY_NUM5,
+        Self::KEY_NUM6,
+        Self::KEY_NUM7,
+        Self::KEY_NUM8,
+        Self::KEY_NUM9,
+        Self::KEY_POUND,
+        Self::KEY_STAR,
+        Self::LEFT_SOFT,
+        Self::RIGHT_SOFT,
+        Self::SEND,
+        Self::END,
+        Self::VOLUME_UP,
+        Self::VOLUME_DOWN,
+        Self::CAMERA,
+        Self::CLEAR,
+    ];
+
+    /// Returns `None` for codes which aren't generated by the emulator, e.g. ones passed by application.
+    pub fn from_raw(raw: i32) -> Option<Self> {
+        Self::ALL.into_iter().find(|x| *x as i32 == raw)
     }
 
-    fn from_key_code(keycode: KeyCode) -> Self {
+    pub fn from_key_code(keycode: KeyCode) -> Self {
         match keycode {
             KeyCode::UP => Self::UP,
             KeyCode::DOWN => Self::DOWN,
@@ -89,6 +155,17 @@ impl MIDPKeyCode {
             KeyCode::HASH => Self::KEY_POUND,
             KeyCode::STAR => Self::KEY_STAR,
             KeyCode::CLEAR => Self::CLEAR,
+            KeyCode::LEFT_SOFT => Self::LEFT_SOFT,
+            KeyCode::RIGHT_SOFT => Self::RIGHT_SOFT,
+            KeyCode::SEND => Self::SEND,
+            KeyCode::END => Self::END,
+            KeyCode::VOLUME_UP => Self::VOLUME_UP,
+            KeyCode::VOLUME_DOWN => Self::VOLUME_DOWN,
+            KeyCode::CAMERA => Self::CAMERA,
+            KeyCode::GAME_A => Self::GAME_A,
+            KeyCode::GAME_B => Self::GAME_B,
+            KeyCode::GAME_C => Self::GAME_C,
+            KeyCode::GAME_D => Self::GAME_D,
         }
     }
 }
@@ -166,6 +243,23 @@ impl EventQueue {
                         MIDPKeyCode::from_key_code(x) as _,
                         0,
                     ],
+                    Event::Keylongpress(x) => vec![
+                        EventQueueEvent::KeyEvent as _,
+                        KeyboardEventType::KeyRepeated as _,
+                        MIDPKeyCode::from_key_code(x) as _,
+                        KEY_EVENT_LONG_PRESS,
+                    ],
+                    Event::PointerDown(x, y) => vec![EventQueueEvent::PointerEvent as _, PointerEventType::PointerPressed as _, x, y],
+                    Event::PointerMove(x, y) => vec![EventQueueEvent::PointerEvent as _, PointerEventType::PointerDragged as _, x, y],
+                    Event::PointerUp(x, y) => vec![EventQueueEvent::PointerEvent as _, PointerEventType::PointerReleased as _, x, y],
+                    // text doesn't fit in event array, so it's handed to display right away
+                    Event::TextInput(text) => {
+                        let display = Self::current_display(jvm).await?;
+                        let text = JavaLangString::from_rust_string(jvm, &text).await?;
+                        let _: () = jvm.invoke_virtual(&display, "handleTextEvent", "(Ljava/lang/String;)V", (text,)).await?;
+
+                        continue;
+                    }
                 };
 
                 jvm.store_array(&mut event, 0, event_data).await?;
@@ -195,18 +289,7 @@ impl EventQueue {
     ) -> JvmResult<()> {
         tracing::debug!("net.wie.EventQueue::dispatchEvent({:?}, {:?})", &this, &event);
 
-        let current_midlet: ClassInstanceRef<MIDlet> = jvm
-            .get_static_field("javax/microedition/midlet/MIDlet", "currentMIDlet", "Ljavax/microedition/midlet/MIDlet;")
-            .await?;
-
-        let display = jvm
-            .invoke_static(
-                "javax/microedition/lcdui/Display",
-                "getDisplay",
-                "(Ljavax/microedition/midlet/MIDlet;)Ljavax/microedition/lcdui/Display;",
-                (current_midlet,),
-            )
-            .await?;
+        let display = Self::current_display(jvm).await?;
 
         let event = jvm.load_array(&event, 0, 4).await?;
         match EventQueueEvent::from_raw(event[0]) {
@@ -219,11 +302,30 @@ impl EventQueue {
 
                 let _: () = jvm.invoke_virtual(&display, "handleKeyEvent", "(II)V", (event_type as i32, code)).await?;
             }
+            EventQueueEvent::PointerEvent => {
+                let _: () = jvm
+                    .invoke_virtual(&display, "handlePointerEvent", "(III)V", (event[1], event[2], event[3]))
+                    .await?;
+            }
         }
 
         Ok(())
     }
 
+    async fn current_display(jvm: &Jvm) -> JvmResult<ClassInstanceRef<Display>> {
+        let current_midlet: ClassInstanceRef<MIDlet> = jvm
+            .get_static_field("javax/microedition/midlet/MIDlet", "currentMIDlet", "Ljavax/microedition/midlet/MIDlet;")
+            .await?;
+
+        jvm.invoke_static(
+            "javax/microedition/lcdui/Display",
+            "getDisplay",
+            "(Ljavax/microedition/midlet/MIDlet;)Ljavax/microedition/lcdui/Display;",
+            (current_midlet,),
+        )
+        .await
+    }
+
     async fn get_event_queue(jvm: &Jvm, _context: &mut WieJvmContext) -> JvmResult<ClassInstanceRef<Self>> {
         tracing::debug!("net.wie.EventQueue::getEventQueue()");
 
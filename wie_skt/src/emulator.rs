@@ -2,7 +2,7 @@ use alloc::{
     borrow::ToOwned,
     boxed::Box,
     collections::BTreeMap,
-    format, str,
+    format,
     string::{String, ToString},
     vec,
     vec::Vec,
@@ -12,7 +12,7 @@ use jvm::{runtime::JavaLangString, Result as JvmResult};
 
 use wie_backend::{Emulator, Event, Platform, System};
 use wie_jvm_support::{JvmSupport, RustJavaJvmImplementation};
-use wie_util::{Result, WieError};
+use wie_util::{Charset, Result, WieError};
 
 pub struct SktEmulator {
     system: System,
@@ -21,7 +21,7 @@ pub struct SktEmulator {
 impl SktEmulator {
     pub fn from_archive(platform: Box<dyn Platform>, files: BTreeMap<String, Vec<u8>>) -> Result<Self> {
         let msd_file = files.iter().find(|x| x.0.ends_with(".msd")).unwrap();
-        let msd = SktMsd::parse(msd_file.0, msd_file.1);
+        let msd = SktMsd::parse(msd_file.0, msd_file.1, platform.device_profile().charset);
 
         tracing::info!("Loading app {}, mclass {}", msd.id, msd.main_class);
 
@@ -127,12 +127,16 @@ impl SktEmulator {
 
 impl Emulator for SktEmulator {
     fn handle_event(&mut self, event: Event) {
-        self.system.event_queue().push(event)
+        self.system.handle_event(event)
     }
 
     fn tick(&mut self) -> Result<()> {
         self.system.tick()
     }
+
+    fn close(&mut self) {
+        self.system.close()
+    }
 }
 
 struct SktMsd {
@@ -142,7 +146,7 @@ struct SktMsd {
 }
 
 impl SktMsd {
-    pub fn parse(filename: &str, data: &[u8]) -> Self {
+    pub fn parse(filename: &str, data: &[u8], charset: Charset) -> Self {
         let mut main_class = String::new();
         let mut id = filename[..filename.find('.').unwrap()].into();
         let mut properties = BTreeMap::new();
@@ -152,10 +156,10 @@ impl SktMsd {
         for line in &mut lines {
             if line.starts_with(b"MIDlet-1:") {
                 let value = line[10..].split(|x| *x == b',').collect::<Vec<_>>();
-                main_class = str::from_utf8(value[2]).unwrap().trim().to_string();
+                main_class = charset.decode(value[2]).trim().to_string();
             }
             if line.starts_with(b"DD-ProgName") {
-                id = str::from_utf8(&line[12..]).unwrap().trim().to_string();
+                id = charset.decode(&line[12..]).trim().to_string();
             }
 
             let sep = line.iter().position(|x| *x == b':');
@@ -163,10 +167,10 @@ impl SktMsd {
                 let key = &line[..sep];
                 let value = &line[sep + 1..];
 
-                if let (Ok(key), Ok(value)) = (str::from_utf8(key), str::from_utf8(value)) {
-                    tracing::info!("Adding property {}={}", key.trim(), value.trim());
-                    properties.insert(key.trim().to_string(), value.trim().to_string());
-                }
+                let (key, value) = (charset.decode(key), charset.decode(value));
+
+                tracing::info!("Adding property {}={}", key.trim(), value.trim());
+                properties.insert(key.trim().to_string(), value.trim().to_string());
             }
         }
 
@@ -5,9 +5,10 @@ mod device;
 mod graphics_2d;
 mod math_fp;
 mod progress_bar;
+mod ui_component;
 mod vibration;
 
 pub use {
     audio_clip::AudioClip, audio_system::AudioSystem, back_light::BackLight, device::Device, graphics_2d::Graphics2D, math_fp::MathFP,
-    progress_bar::ProgressBar, vibration::Vibration,
+    progress_bar::ProgressBar, ui_component::UIComponent, vibration::Vibration,
 };
@@ -1,13 +1,16 @@
 use alloc::vec;
 
-use java_class_proto::JavaMethodProto;
-use java_constants::MethodAccessFlags;
+use java_class_proto::{JavaFieldProto, JavaMethodProto};
+use java_constants::{FieldAccessFlags, MethodAccessFlags};
 use java_runtime::classes::java::lang::String;
-use jvm::{ClassInstanceRef, Jvm, Result as JvmResult};
+use jvm::{runtime::JavaLangString, ClassInstanceRef, Jvm, Result as JvmResult};
 
 use wie_jvm_support::{WieJavaClassProto, WieJvmContext};
 
-use crate::classes::com::skt::m::audio_clip::AudioClip;
+use crate::classes::{com::skt::m::audio_clip::AudioClip, net::wie::SkvmAudioClip};
+
+// handset volume steps, shared by all formats
+const MAX_VOLUME: i32 = 5;
 
 // class com.skt.m.AudioSystem
 pub struct AudioSystem;
@@ -19,6 +22,7 @@ impl AudioSystem {
             parent_class: Some("java/lang/Object"),
             interfaces: vec![],
             methods: vec![
+                JavaMethodProto::new("<clinit>", "()V", Self::cl_init, MethodAccessFlags::STATIC),
                 JavaMethodProto::new(
                     "getAudioClip",
                     "(Ljava/lang/String;)Lcom/skt/m/AudioClip;",
@@ -29,31 +33,119 @@ impl AudioSystem {
                 JavaMethodProto::new("getVolume", "(Ljava/lang/String;)I", Self::get_volume, MethodAccessFlags::STATIC),
                 JavaMethodProto::new("setVolume", "(Ljava/lang/String;I)V", Self::set_volume, MethodAccessFlags::STATIC),
             ],
-            fields: vec![],
+            fields: vec![
+                JavaFieldProto::new("mmfVolume", "I", FieldAccessFlags::STATIC),
+                JavaFieldProto::new("midiVolume", "I", FieldAccessFlags::STATIC),
+                JavaFieldProto::new("wavVolume", "I", FieldAccessFlags::STATIC),
+                JavaFieldProto::new("clips", "Ljava/util/Vector;", FieldAccessFlags::STATIC),
+            ],
         }
     }
 
-    async fn get_audio_clip(_jvm: &Jvm, _context: &mut WieJvmContext, name: ClassInstanceRef<String>) -> JvmResult<ClassInstanceRef<AudioClip>> {
-        tracing::warn!("stub com.skt.m.AudioSystem::getAudioClip({:?})", name);
+    async fn cl_init(jvm: &Jvm, _context: &mut WieJvmContext) -> JvmResult<()> {
+        tracing::debug!("com.skt.m.AudioSystem::<clinit>()");
+
+        for field in ["mmfVolume", "midiVolume", "wavVolume"] {
+            jvm.put_static_field("com/skt/m/AudioSystem", field, "I", MAX_VOLUME).await?;
+        }
+
+        let clips = jvm.new_class("java/util/Vector", "()V", ()).await?;
+        jvm.put_static_field("com/skt/m/AudioSystem", "clips", "Ljava/util/Vector;", clips)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn get_audio_clip(jvm: &Jvm, _context: &mut WieJvmContext, format: ClassInstanceRef<String>) -> JvmResult<ClassInstanceRef<AudioClip>> {
+        tracing::debug!("com.skt.m.AudioSystem::getAudioClip({:?})", format);
 
-        Ok(None.into())
+        let format_string = JavaLangString::to_rust_string(jvm, &format).await?;
+        if Self::volume_field(&format_string).is_none() {
+            tracing::warn!("Unsupported audio format {}", format_string);
+
+            return Ok(None.into());
+        }
+
+        let clip = jvm.new_class("net/wie/SkvmAudioClip", "(Ljava/lang/String;)V", (format,)).await?;
+
+        Ok(clip.into())
     }
 
-    async fn get_max_volume(_jvm: &Jvm, _context: &mut WieJvmContext, format: ClassInstanceRef<String>) -> JvmResult<i32> {
-        tracing::warn!("stub com.skt.m.AudioSystem::getMaxVolume({:?})", format);
+    async fn get_max_volume(jvm: &Jvm, _context: &mut WieJvmContext, format: ClassInstanceRef<String>) -> JvmResult<i32> {
+        tracing::debug!("com.skt.m.AudioSystem::getMaxVolume({:?})", format);
+
+        let format = JavaLangString::to_rust_string(jvm, &format).await?;
 
-        Ok(0)
+        Ok(if Self::volume_field(&format).is_some() { MAX_VOLUME } else { 0 })
     }
 
-    async fn get_volume(_jvm: &Jvm, _context: &mut WieJvmContext, format: ClassInstanceRef<String>) -> JvmResult<i32> {
-        tracing::warn!("stub com.skt.m.AudioSystem::getVolume({:?})", format);
+    async fn get_volume(jvm: &Jvm, _context: &mut WieJvmContext, format: ClassInstanceRef<String>) -> JvmResult<i32> {
+        tracing::debug!("com.skt.m.AudioSystem::getVolume({:?})", format);
 
-        Ok(0)
+        let format = JavaLangString::to_rust_string(jvm, &format).await?;
+
+        Self::volume(jvm, &format).await
     }
 
-    async fn set_volume(_jvm: &Jvm, _context: &mut WieJvmContext, format: ClassInstanceRef<String>, level: i32) -> JvmResult<()> {
-        tracing::warn!("stub com.skt.m.AudioSystem::setVolume({:?}, {})", format, level);
+    async fn set_volume(jvm: &Jvm, context: &mut WieJvmContext, format: ClassInstanceRef<String>, level: i32) -> JvmResult<()> {
+        tracing::debug!("com.skt.m.AudioSystem::setVolume({:?}, {})", format, level);
+
+        let format = JavaLangString::to_rust_string(jvm, &format).await?;
+        let Some(field) = Self::volume_field(&format) else {
+            return Ok(());
+        };
+
+        jvm.put_static_field("com/skt/m/AudioSystem", field, "I", level.clamp(0, MAX_VOLUME))
+            .await?;
+
+        // open clips of the format follow the new volume
+        let clips = jvm.get_static_field("com/skt/m/AudioSystem", "clips", "Ljava/util/Vector;").await?;
+        let size: i32 = jvm.invoke_virtual(&clips, "size", "()I", ()).await?;
+        for i in 0..size {
+            let clip: ClassInstanceRef<SkvmAudioClip> = jvm.invoke_virtual(&clips, "elementAt", "(I)Ljava/lang/Object;", (i,)).await?;
+            SkvmAudioClip::apply_volume(jvm, context, &clip).await?;
+        }
 
         Ok(())
     }
+
+    /// Volume of `format` in percent.
+    pub async fn volume_percent(jvm: &Jvm, format: &str) -> JvmResult<u8> {
+        let volume = Self::volume(jvm, format).await?;
+
+        Ok((volume * 100 / MAX_VOLUME) as _)
+    }
+
+    pub async fn add_clip(jvm: &Jvm, clip: &ClassInstanceRef<SkvmAudioClip>) -> JvmResult<()> {
+        let clips = jvm.get_static_field("com/skt/m/AudioSystem", "clips", "Ljava/util/Vector;").await?;
+
+        jvm.invoke_virtual(&clips, "addElement", "(Ljava/lang/Object;)V", (clip.clone(),)).await
+    }
+
+    pub async fn remove_clip(jvm: &Jvm, clip: &ClassInstanceRef<SkvmAudioClip>) -> JvmResult<()> {
+        let clips = jvm.get_static_field("com/skt/m/AudioSystem", "clips", "Ljava/util/Vector;").await?;
+
+        let _: bool = jvm
+            .invoke_virtual(&clips, "removeElement", "(Ljava/lang/Object;)Z", (clip.clone(),))
+            .await?;
+
+        Ok(())
+    }
+
+    async fn volume(jvm: &Jvm, format: &str) -> JvmResult<i32> {
+        let Some(field) = Self::volume_field(format) else {
+            return Ok(0);
+        };
+
+        jvm.get_static_field("com/skt/m/AudioSystem", field, "I").await
+    }
+
+    fn volume_field(format: &str) -> Option<&'static str> {
+        Some(match format.to_ascii_lowercase().as_str() {
+            "mmf" | "smaf" | "ma2" | "ma3" | "ma5" => "mmfVolume",
+            "mid" | "midi" => "midiVolume",
+            "wav" | "wave" => "wavVolume",
+            _ => return None,
+        })
+    }
 }
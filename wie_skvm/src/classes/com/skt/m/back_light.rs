@@ -20,8 +20,11 @@ impl BackLight {
         }
     }
 
-    async fn on(_jvm: &Jvm, _: &mut WieJvmContext, timeout: i32) -> JvmResult<()> {
-        tracing::warn!("stub com.skt.m.BackLight::on({:?})", timeout);
+    async fn on(_jvm: &Jvm, context: &mut WieJvmContext, timeout: i32) -> JvmResult<()> {
+        tracing::debug!("com.skt.m.BackLight::on({:?})", timeout);
+
+        let timeout = (timeout > 0).then_some(timeout as u64);
+        context.system().device().set_backlight(true, timeout);
 
         Ok(())
     }
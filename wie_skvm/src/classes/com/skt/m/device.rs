@@ -27,20 +27,32 @@ impl Device {
         }
     }
 
-    async fn set_color_mode(_jvm: &Jvm, _context: &mut WieJvmContext, mode: i32) -> JvmResult<()> {
-        tracing::warn!("stub com.skt.m.Device::setColorMode({})", mode);
+    async fn set_color_mode(_jvm: &Jvm, context: &mut WieJvmContext, mode: i32) -> JvmResult<()> {
+        tracing::debug!("com.skt.m.Device::setColorMode({})", mode);
+
+        // mode is bits per pixel
+        let color_depth = match mode {
+            8 | 16 => Some(mode as u32),
+            _ => {
+                tracing::warn!("Unsupported color mode {}, using full color", mode);
+                None
+            }
+        };
+        context.system().device().set_color_depth(color_depth);
 
         Ok(())
     }
 
-    async fn is_backlight_enabled(_jvm: &Jvm, _context: &mut WieJvmContext) -> JvmResult<bool> {
-        tracing::warn!("stub com.skt.m.Device::isBacklightEnabled()");
+    async fn is_backlight_enabled(_jvm: &Jvm, context: &mut WieJvmContext) -> JvmResult<bool> {
+        tracing::debug!("com.skt.m.Device::isBacklightEnabled()");
 
-        Ok(true)
+        Ok(context.system().device().backlight())
     }
 
-    async fn set_backlight_enabled(_jvm: &Jvm, _context: &mut WieJvmContext, enabled: bool) -> JvmResult<()> {
-        tracing::warn!("stub com.skt.m.Device::setBacklightEnabled({:?})", enabled);
+    async fn set_backlight_enabled(_jvm: &Jvm, context: &mut WieJvmContext, enabled: bool) -> JvmResult<()> {
+        tracing::debug!("com.skt.m.Device::setBacklightEnabled({:?})", enabled);
+
+        context.system().device().set_backlight(enabled, None);
 
         Ok(())
     }
@@ -57,8 +69,10 @@ impl Device {
         Ok(())
     }
 
-    async fn set_key_repeat_time(_jvm: &Jvm, _context: &mut WieJvmContext, delay: i32, interval: i32) -> JvmResult<()> {
-        tracing::warn!("stub com.skt.m.Device::setKeyRepeatTime({}, {})", delay, interval);
+    async fn set_key_repeat_time(_jvm: &Jvm, context: &mut WieJvmContext, delay: i32, interval: i32) -> JvmResult<()> {
+        tracing::debug!("com.skt.m.Device::setKeyRepeatTime({}, {})", delay, interval);
+
+        context.system().event_queue().set_key_repeat(delay.max(0) as _, interval.max(0) as _);
 
         Ok(())
     }
@@ -1,11 +1,15 @@
 use alloc::vec;
 
-use java_class_proto::JavaMethodProto;
+use java_class_proto::{JavaFieldProto, JavaMethodProto};
 use java_constants::MethodAccessFlags;
 use jvm::{ClassInstanceRef, Jvm, Result as JvmResult};
 
+use wie_backend::canvas::{ArgbPixel, PixelType, VecImageBuffer};
 use wie_jvm_support::{WieJavaClassProto, WieJvmContext};
-use wie_midp::classes::javax::microedition::lcdui::{Graphics, Image};
+use wie_midp::classes::javax::microedition::{
+    lcdui::{Display, Graphics, Image},
+    midlet::MIDlet,
+};
 
 // class com.skt.m.Graphics2D
 pub struct Graphics2D;
@@ -34,21 +38,26 @@ impl Graphics2D {
                     "drawImage",
                     "(IILjavax/microedition/lcdui/Image;IIIII)V",
                     Self::draw_image,
-                    MethodAccessFlags::STATIC,
+                    Default::default(),
                 ),
             ],
-            fields: vec![],
+            fields: vec![JavaFieldProto::new("graphics", "Ljavax/microedition/lcdui/Graphics;", Default::default())],
         }
     }
 
-    async fn init(_jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>, graphics: ClassInstanceRef<Graphics>) -> JvmResult<()> {
+    async fn init(jvm: &Jvm, _context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, graphics: ClassInstanceRef<Graphics>) -> JvmResult<()> {
         tracing::debug!("com.skt.m.Graphics2D::<init>({:?}, {:?})", &this, graphics);
 
+        let _: () = jvm.invoke_special(&this, "java/lang/Object", "<init>", "()V", ()).await?;
+
+        jvm.put_field(&mut this, "graphics", "Ljavax/microedition/lcdui/Graphics;", graphics)
+            .await?;
+
         Ok(())
     }
 
     async fn get_graphics2d(jvm: &Jvm, _context: &mut WieJvmContext, graphics: ClassInstanceRef<Graphics>) -> JvmResult<ClassInstanceRef<Self>> {
-        tracing::warn!("stub com.skt.m.Graphics2D::getGraphics2D({:?})", graphics);
+        tracing::debug!("com.skt.m.Graphics2D::getGraphics2D({:?})", graphics);
 
         let instance = jvm
             .new_class("com/skt/m/Graphics2D", "(Ljavax/microedition/lcdui/Graphics;)V", (graphics,))
@@ -57,16 +66,34 @@ impl Graphics2D {
         Ok(instance.into())
     }
 
-    async fn capture_lcd(_jvm: &Jvm, _context: &mut WieJvmContext, x: i32, y: i32, width: i32, height: i32) -> JvmResult<ClassInstanceRef<Image>> {
-        tracing::warn!("stub com.skt.m.Graphics2D::captureLCD({}, {}, {}, {})", x, y, width, height);
+    async fn capture_lcd(jvm: &Jvm, _context: &mut WieJvmContext, x: i32, y: i32, width: i32, height: i32) -> JvmResult<ClassInstanceRef<Image>> {
+        tracing::debug!("com.skt.m.Graphics2D::captureLCD({}, {}, {}, {})", x, y, width, height);
+
+        let screen_image = Self::screen_image(jvm).await?;
+        let screen = Image::image(jvm, &screen_image).await?;
 
-        Ok(None.into())
+        // capture area is clamped to the screen
+        let (left, top) = (x.max(0), y.max(0));
+        let right = x.saturating_add(width).min(screen.width() as i32);
+        let bottom = y.saturating_add(height).min(screen.height() as i32);
+        if right <= left || bottom <= top {
+            return Ok(None.into());
+        }
+
+        let raw = (top..bottom)
+            .flat_map(|y| (left..right).map(move |x| (x, y)))
+            .map(|(x, y)| ArgbPixel::from_color(screen.get_pixel(x, y)))
+            .collect();
+        let capture = VecImageBuffer::<ArgbPixel>::from_raw((right - left) as _, (bottom - top) as _, raw);
+
+        Image::from_backend_image(jvm, &capture).await
     }
 
     #[allow(clippy::too_many_arguments)]
     async fn draw_image(
-        _jvm: &Jvm,
+        jvm: &Jvm,
         _context: &mut WieJvmContext,
+        this: ClassInstanceRef<Self>,
         tx: i32,
         ty: i32,
         src: ClassInstanceRef<Image>,
@@ -76,8 +103,9 @@ impl Graphics2D {
         sh: i32,
         mode: i32,
     ) -> JvmResult<()> {
-        tracing::warn!(
-            "stub com.skt.m.Graphics2D::drawImage({}, {}, {:?}, {}, {}, {}, {}, {})",
+        tracing::debug!(
+            "com.skt.m.Graphics2D::drawImage({:?}, {}, {}, {:?}, {}, {}, {}, {}, {})",
+            &this,
             tx,
             ty,
             &src,
@@ -88,6 +116,108 @@ impl Graphics2D {
             mode
         );
 
-        Ok(())
+        if src.is_null() {
+            return Err(jvm.exception("java/lang/NullPointerException", "src is null").await);
+        }
+
+        // mode values aren't known, as no SKVM reference defining them was available
+        if mode != 0 {
+            tracing::warn!("stub com.skt.m.Graphics2D::drawImage mode {:#x}", mode);
+        }
+
+        // drawn straight from source image, so only the part inside source and clip is touched
+        let src_image = Image::image(jvm, &src).await?;
+        let mut graphics: ClassInstanceRef<Graphics> = jvm.get_field(&this, "graphics", "Ljavax/microedition/lcdui/Graphics;").await?;
+        Graphics::draw_backend_image(jvm, &mut graphics, tx, ty, sw.max(0) as _, sh.max(0) as _, &*src_image, sx, sy).await
+    }
+
+    async fn screen_image(jvm: &Jvm) -> JvmResult<ClassInstanceRef<Image>> {
+        let current_midlet: ClassInstanceRef<MIDlet> = jvm
+            .get_static_field("javax/microedition/midlet/MIDlet", "currentMIDlet", "Ljavax/microedition/midlet/MIDlet;")
+            .await?;
+
+        let display: ClassInstanceRef<Display> = jvm
+            .invoke_static(
+                "javax/microedition/lcdui/Display",
+                "getDisplay",
+                "(Ljavax/microedition/midlet/MIDlet;)Ljavax/microedition/lcdui/Display;",
+                (current_midlet,),
+            )
+            .await?;
+
+        Display::screen_image(jvm, &display).await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::boxed::Box;
+
+    use jvm::ClassInstanceRef;
+
+    use test_utils::run_jvm_test;
+    use wie_midp::classes::javax::microedition::lcdui::{Graphics, Image};
+    use wie_util::Result;
+
+    use crate::get_protos;
+
+    use super::Graphics2D;
+
+    #[test]
+    fn test_draw_image() -> Result<()> {
+        run_jvm_test(Box::new([wie_midp::get_protos().into(), get_protos().into()]), |jvm| async move {
+            let src: ClassInstanceRef<Image> = jvm
+                .invoke_static(
+                    "javax/microedition/lcdui/Image",
+                    "createImage",
+                    "(II)Ljavax/microedition/lcdui/Image;",
+                    (2, 2),
+                )
+                .await?;
+            let src_graphics: ClassInstanceRef<Graphics> = jvm
+                .invoke_virtual(&src, "getGraphics", "()Ljavax/microedition/lcdui/Graphics;", ())
+                .await?;
+            let _: () = jvm.invoke_virtual(&src_graphics, "setColor", "(I)V", (0xff0000,)).await?;
+            let _: () = jvm.invoke_virtual(&src_graphics, "fillRect", "(IIII)V", (0, 0, 2, 2)).await?;
+
+            let dst: ClassInstanceRef<Image> = jvm
+                .invoke_static(
+                    "javax/microedition/lcdui/Image",
+                    "createImage",
+                    "(II)Ljavax/microedition/lcdui/Image;",
+                    (4, 4),
+                )
+                .await?;
+            let graphics: ClassInstanceRef<Graphics> = jvm
+                .invoke_virtual(&dst, "getGraphics", "()Ljavax/microedition/lcdui/Graphics;", ())
+                .await?;
+            let _: () = jvm.invoke_virtual(&graphics, "setClip", "(IIII)V", (0, 0, 2, 4)).await?;
+
+            let graphics2d: ClassInstanceRef<Graphics2D> = jvm
+                .invoke_static(
+                    "com/skt/m/Graphics2D",
+                    "getGraphics2D",
+                    "(Ljavax/microedition/lcdui/Graphics;)Lcom/skt/m/Graphics2D;",
+                    (graphics,),
+                )
+                .await?;
+            let _: () = jvm
+                .invoke_virtual(
+                    &graphics2d,
+                    "drawImage",
+                    "(IILjavax/microedition/lcdui/Image;IIIII)V",
+                    (1, 0, src, 0, 0, 2, 2, 0),
+                )
+                .await?;
+
+            // drawn on bound image, and clipped by its graphics
+            let dst = Image::image(&jvm, &dst).await?;
+            let pixel = dst.get_pixel(1, 0);
+            assert_eq!((pixel.r, pixel.g, pixel.b), (0xff, 0x00, 0x00));
+            let pixel = dst.get_pixel(2, 0);
+            assert_eq!((pixel.r, pixel.g, pixel.b), (0x00, 0x00, 0x00));
+
+            Ok(())
+        })
     }
 }
@@ -1,10 +1,20 @@
-use alloc::vec;
+use alloc::{format, vec};
 
-use java_class_proto::JavaMethodProto;
+use java_class_proto::{JavaFieldProto, JavaMethodProto};
 use java_runtime::classes::java::lang::String;
-use jvm::{ClassInstanceRef, Jvm, Result as JvmResult};
+use jvm::{runtime::JavaLangString, ClassInstanceRef, Jvm, Result as JvmResult};
 
 use wie_jvm_support::{WieJavaClassProto, WieJvmContext};
+use wie_midp::classes::javax::microedition::lcdui::Graphics;
+
+use super::{
+    ui_component::{FOREGROUND_COLOR, HCENTER, MARGIN, TOP},
+    UIComponent,
+};
+
+const BAR_COLOR: i32 = 0x3060c0;
+
+const BAR_HEIGHT: i32 = 12;
 
 // class com.skt.m.ProgressBar
 pub struct ProgressBar;
@@ -13,31 +23,105 @@ impl ProgressBar {
     pub fn as_proto() -> WieJavaClassProto {
         WieJavaClassProto {
             name: "com/skt/m/ProgressBar",
-            parent_class: Some("java/lang/Object"),
+            parent_class: Some("com/skt/m/UIComponent"),
             interfaces: vec![],
             methods: vec![
                 JavaMethodProto::new("<init>", "(Ljava/lang/String;)V", Self::init, Default::default()),
                 JavaMethodProto::new("setMaxValue", "(I)V", Self::set_max_value, Default::default()),
+                JavaMethodProto::new("getMaxValue", "()I", Self::get_max_value, Default::default()),
                 JavaMethodProto::new("setValue", "(I)V", Self::set_value, Default::default()),
+                JavaMethodProto::new("getValue", "()I", Self::get_value, Default::default()),
+                JavaMethodProto::new("paint", "(Ljavax/microedition/lcdui/Graphics;)V", Self::paint, Default::default()),
+            ],
+            fields: vec![
+                JavaFieldProto::new("maxValue", "I", Default::default()),
+                JavaFieldProto::new("value", "I", Default::default()),
             ],
-            fields: vec![],
         }
     }
 
-    async fn init(_jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>, name: ClassInstanceRef<String>) -> JvmResult<()> {
-        tracing::warn!("stub com.skt.m.ProgressBar::<init>({:?}, {:?})", &this, name);
+    async fn init(jvm: &Jvm, _context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, title: ClassInstanceRef<String>) -> JvmResult<()> {
+        tracing::debug!("com.skt.m.ProgressBar::<init>({:?}, {:?})", &this, title);
+
+        let _: () = jvm
+            .invoke_special(&this, "com/skt/m/UIComponent", "<init>", "(Ljava/lang/String;)V", (title,))
+            .await?;
+
+        jvm.put_field(&mut this, "maxValue", "I", 100).await?;
+        jvm.put_field(&mut this, "value", "I", 0).await?;
 
         Ok(())
     }
 
-    async fn set_max_value(_jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>, value: i32) -> JvmResult<()> {
-        tracing::warn!("stub com.skt.m.ProgressBar::setMaxValue({:?}, {:?})", &this, value);
+    async fn set_max_value(jvm: &Jvm, _context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, value: i32) -> JvmResult<()> {
+        tracing::debug!("com.skt.m.ProgressBar::setMaxValue({:?}, {:?})", &this, value);
 
-        Ok(())
+        if value <= 0 {
+            return Err(jvm.exception("java/lang/IllegalArgumentException", "maxValue must be positive").await);
+        }
+
+        let current: i32 = jvm.get_field(&this, "value", "I").await?;
+        jvm.put_field(&mut this, "maxValue", "I", value).await?;
+        jvm.put_field(&mut this, "value", "I", current.min(value)).await?;
+
+        jvm.invoke_virtual(&this, "repaint", "()V", ()).await
+    }
+
+    async fn get_max_value(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("com.skt.m.ProgressBar::getMaxValue({:?})", &this);
+
+        jvm.get_field(&this, "maxValue", "I").await
     }
 
-    async fn set_value(_jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>, value: i32) -> JvmResult<()> {
-        tracing::warn!("stub com.skt.m.ProgressBar::setValue({:?}, {:?})", &this, value);
+    async fn set_value(jvm: &Jvm, _context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, value: i32) -> JvmResult<()> {
+        tracing::debug!("com.skt.m.ProgressBar::setValue({:?}, {:?})", &this, value);
+
+        let max_value: i32 = jvm.get_field(&this, "maxValue", "I").await?;
+        jvm.put_field(&mut this, "value", "I", value.clamp(0, max_value)).await?;
+
+        jvm.invoke_virtual(&this, "repaint", "()V", ()).await
+    }
+
+    async fn get_value(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("com.skt.m.ProgressBar::getValue({:?})", &this);
+
+        jvm.get_field(&this, "value", "I").await
+    }
+
+    // title on top, bar with percentage in the middle of the screen
+    async fn paint(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>, graphics: ClassInstanceRef<Graphics>) -> JvmResult<()> {
+        tracing::debug!("com.skt.m.ProgressBar::paint({:?}, {:?})", &this, graphics);
+
+        let width: i32 = jvm.invoke_virtual(&this, "getWidth", "()I", ()).await?;
+        let height: i32 = jvm.invoke_virtual(&this, "getHeight", "()I", ()).await?;
+        let max_value: i32 = jvm.get_field(&this, "maxValue", "I").await?;
+        let value: i32 = jvm.get_field(&this, "value", "I").await?;
+
+        UIComponent::paint_frame(jvm, &this, &graphics).await?;
+
+        let (bar_x, bar_y, bar_width) = (MARGIN, (height - BAR_HEIGHT) / 2, width - MARGIN * 2);
+        let _: () = jvm
+            .invoke_virtual(&graphics, "drawRect", "(IIII)V", (bar_x, bar_y, bar_width, BAR_HEIGHT))
+            .await?;
+
+        let filled = (bar_width - 1) as i64 * value as i64 / max_value as i64;
+        if filled > 0 {
+            let _: () = jvm.invoke_virtual(&graphics, "setColor", "(I)V", (BAR_COLOR,)).await?;
+            let _: () = jvm
+                .invoke_virtual(&graphics, "fillRect", "(IIII)V", (bar_x + 1, bar_y + 1, filled as i32, BAR_HEIGHT - 1))
+                .await?;
+        }
+
+        let percent = JavaLangString::from_rust_string(jvm, &format!("{}%", value as i64 * 100 / max_value as i64)).await?;
+        let _: () = jvm.invoke_virtual(&graphics, "setColor", "(I)V", (FOREGROUND_COLOR,)).await?;
+        let _: () = jvm
+            .invoke_virtual(
+                &graphics,
+                "drawString",
+                "(Ljava/lang/String;III)V",
+                (percent, width / 2, bar_y + BAR_HEIGHT + MARGIN / 2, HCENTER | TOP),
+            )
+            .await?;
 
         Ok(())
     }
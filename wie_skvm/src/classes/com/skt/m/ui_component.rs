@@ -0,0 +1,112 @@
+use alloc::vec;
+
+use java_class_proto::{JavaFieldProto, JavaMethodProto};
+use java_runtime::classes::java::lang::String;
+use jvm::{ClassInstanceRef, Jvm, Result as JvmResult};
+
+use wie_jvm_support::{WieJavaClassProto, WieJvmContext};
+use wie_midp::classes::javax::microedition::lcdui::{Font, Graphics};
+
+// Graphics anchors
+pub const HCENTER: i32 = 1;
+pub const TOP: i32 = 16;
+
+pub const BACKGROUND_COLOR: i32 = 0xffffff;
+pub const FOREGROUND_COLOR: i32 = 0x000000;
+
+pub const MARGIN: i32 = 10;
+
+// class com.skt.m.UIComponent
+// base of com.skt.m widgets, drawn full screen with optional title on top
+pub struct UIComponent;
+
+impl UIComponent {
+    pub fn as_proto() -> WieJavaClassProto {
+        WieJavaClassProto {
+            name: "com/skt/m/UIComponent",
+            parent_class: Some("javax/microedition/lcdui/Canvas"),
+            interfaces: vec![],
+            methods: vec![
+                JavaMethodProto::new("<init>", "()V", Self::init, Default::default()),
+                JavaMethodProto::new("<init>", "(Ljava/lang/String;)V", Self::init_with_title, Default::default()),
+                JavaMethodProto::new("setTitle", "(Ljava/lang/String;)V", Self::set_title, Default::default()),
+                JavaMethodProto::new("getTitle", "()Ljava/lang/String;", Self::get_title, Default::default()),
+                JavaMethodProto::new("paint", "(Ljavax/microedition/lcdui/Graphics;)V", Self::paint, Default::default()),
+            ],
+            fields: vec![JavaFieldProto::new("title", "Ljava/lang/String;", Default::default())],
+        }
+    }
+
+    async fn init(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<()> {
+        tracing::debug!("com.skt.m.UIComponent::<init>({:?})", &this);
+
+        jvm.invoke_special(&this, "javax/microedition/lcdui/Canvas", "<init>", "()V", ()).await
+    }
+
+    async fn init_with_title(
+        jvm: &Jvm,
+        _context: &mut WieJvmContext,
+        mut this: ClassInstanceRef<Self>,
+        title: ClassInstanceRef<String>,
+    ) -> JvmResult<()> {
+        tracing::debug!("com.skt.m.UIComponent::<init>({:?}, {:?})", &this, title);
+
+        let _: () = jvm.invoke_special(&this, "javax/microedition/lcdui/Canvas", "<init>", "()V", ()).await?;
+
+        jvm.put_field(&mut this, "title", "Ljava/lang/String;", title).await
+    }
+
+    async fn set_title(jvm: &Jvm, _context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, title: ClassInstanceRef<String>) -> JvmResult<()> {
+        tracing::debug!("com.skt.m.UIComponent::setTitle({:?}, {:?})", &this, title);
+
+        jvm.put_field(&mut this, "title", "Ljava/lang/String;", title).await?;
+
+        jvm.invoke_virtual(&this, "repaint", "()V", ()).await
+    }
+
+    async fn get_title(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<ClassInstanceRef<String>> {
+        tracing::debug!("com.skt.m.UIComponent::getTitle({:?})", &this);
+
+        jvm.get_field(&this, "title", "Ljava/lang/String;").await
+    }
+
+    async fn paint(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>, graphics: ClassInstanceRef<Graphics>) -> JvmResult<()> {
+        tracing::debug!("com.skt.m.UIComponent::paint({:?}, {:?})", &this, graphics);
+
+        Self::paint_frame(jvm, &this, &graphics).await?;
+
+        Ok(())
+    }
+
+    /// Clears the screen and draws title. Returns y coordinate where contents of the widget start.
+    pub async fn paint_frame<T>(jvm: &Jvm, this: &ClassInstanceRef<T>, graphics: &ClassInstanceRef<Graphics>) -> JvmResult<i32> {
+        let width: i32 = jvm.invoke_virtual(this, "getWidth", "()I", ()).await?;
+        let height: i32 = jvm.invoke_virtual(this, "getHeight", "()I", ()).await?;
+        let title: ClassInstanceRef<String> = jvm.get_field(this, "title", "Ljava/lang/String;").await?;
+
+        let _: () = jvm.invoke_virtual(graphics, "setColor", "(I)V", (BACKGROUND_COLOR,)).await?;
+        let _: () = jvm.invoke_virtual(graphics, "fillRect", "(IIII)V", (0, 0, width, height)).await?;
+
+        let _: () = jvm.invoke_virtual(graphics, "setColor", "(I)V", (FOREGROUND_COLOR,)).await?;
+        if title.is_null() {
+            return Ok(MARGIN);
+        }
+
+        let _: () = jvm
+            .invoke_virtual(
+                graphics,
+                "drawString",
+                "(Ljava/lang/String;III)V",
+                (title, width / 2, MARGIN, HCENTER | TOP),
+            )
+            .await?;
+
+        Ok(MARGIN * 2 + Self::line_height(jvm, graphics).await?)
+    }
+
+    async fn line_height(jvm: &Jvm, graphics: &ClassInstanceRef<Graphics>) -> JvmResult<i32> {
+        let font: ClassInstanceRef<Font> = jvm.invoke_virtual(graphics, "getFont", "()Ljavax/microedition/lcdui/Font;", ()).await?;
+
+        jvm.invoke_virtual(&font, "getHeight", "()I", ()).await
+    }
+}
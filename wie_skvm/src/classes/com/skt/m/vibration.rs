@@ -17,19 +17,30 @@ impl Vibration {
             methods: vec![
                 JavaMethodProto::new("getLevelNum", "()I", Self::get_level_num, MethodAccessFlags::STATIC),
                 JavaMethodProto::new("start", "(II)V", Self::start, MethodAccessFlags::STATIC),
+                JavaMethodProto::new("stop", "()V", Self::stop, MethodAccessFlags::STATIC),
             ],
             fields: vec![],
         }
     }
 
-    async fn get_level_num(_jvm: &Jvm, _context: &mut WieJvmContext) -> JvmResult<i32> {
-        tracing::warn!("stub com.skt.m.Vibration::getLevelNum()");
+    async fn get_level_num(_jvm: &Jvm, context: &mut WieJvmContext) -> JvmResult<i32> {
+        tracing::debug!("com.skt.m.Vibration::getLevelNum()");
 
-        Ok(10)
+        Ok(context.system().device().vibration_level_count())
     }
 
-    async fn start(_jvm: &Jvm, _context: &mut WieJvmContext, level: i32, timeout: i32) -> JvmResult<()> {
-        tracing::warn!("stub com.skt.m.Vibration::start({}, {})", level, timeout);
+    async fn start(_jvm: &Jvm, context: &mut WieJvmContext, level: i32, timeout: i32) -> JvmResult<()> {
+        tracing::debug!("com.skt.m.Vibration::start({}, {})", level, timeout);
+
+        context.system().device().vibrate(level, timeout.max(0) as _);
+
+        Ok(())
+    }
+
+    async fn stop(_jvm: &Jvm, context: &mut WieJvmContext) -> JvmResult<()> {
+        tracing::debug!("com.skt.m.Vibration::stop()");
+
+        context.system().device().stop_vibration();
 
         Ok(())
     }
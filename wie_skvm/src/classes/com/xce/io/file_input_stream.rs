@@ -1,12 +1,12 @@
-use alloc::vec;
+use alloc::{vec, vec::Vec};
 
 use java_class_proto::{JavaFieldProto, JavaMethodProto};
 use java_runtime::classes::java::lang::String;
-use jvm::{ClassInstanceRef, Jvm, Result as JvmResult};
+use jvm::{Array, ClassInstanceRef, Jvm, Result as JvmResult};
 
 use wie_jvm_support::{WieJavaClassProto, WieJvmContext};
 
-use crate::classes::com::xce::io::x_file::XFile;
+use crate::classes::com::xce::io::x_file::{XFile, READ};
 
 // class com.xce.io.FileInputStream
 pub struct FileInputStream;
@@ -20,22 +20,22 @@ impl FileInputStream {
             methods: vec![
                 JavaMethodProto::new("<init>", "(Ljava/lang/String;)V", Self::init, Default::default()),
                 JavaMethodProto::new("<init>", "(Lcom/xce/io/XFile;)V", Self::init_with_file, Default::default()),
+                JavaMethodProto::new("read", "()I", Self::read, Default::default()),
+                JavaMethodProto::new("read", "([BII)I", Self::read_array, Default::default()),
                 JavaMethodProto::new("available", "()I", Self::available, Default::default()),
                 JavaMethodProto::new("close", "()V", Self::close, Default::default()),
             ],
-            fields: vec![JavaFieldProto::new("is", "Ljava/io/InputStream;", Default::default())],
+            fields: vec![JavaFieldProto::new("file", "Lcom/xce/io/XFile;", Default::default())],
         }
     }
 
-    async fn init(jvm: &Jvm, _context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, name: ClassInstanceRef<String>) -> JvmResult<()> {
+    async fn init(jvm: &Jvm, context: &mut WieJvmContext, this: ClassInstanceRef<Self>, name: ClassInstanceRef<String>) -> JvmResult<()> {
         tracing::debug!("com.xce.io.FileInputStream::<init>({:?}, {:?})", this, name);
 
-        let file = jvm.new_class("java/io/File", "(Ljava/lang/String;)V", (name,)).await?;
-        let is = jvm.new_class("java/io/FileInputStream", "(Ljava/io/File;)V", (file,)).await?;
+        // throws IOException if file doesn't exist
+        let file = jvm.new_class("com/xce/io/XFile", "(Ljava/lang/String;I)V", (name, READ)).await?;
 
-        jvm.put_field(&mut this, "is", "Ljava/io/InputStream;", is).await?;
-
-        Ok(())
+        Self::init_with_file(jvm, context, this, file.into()).await
     }
 
     async fn init_with_file(
@@ -46,27 +46,57 @@ impl FileInputStream {
     ) -> JvmResult<()> {
         tracing::debug!("com.xce.io.FileInputStream::<init>({:?}, {:?})", this, file);
 
-        let file = XFile::file(jvm, file).await?;
-        let is = jvm.new_class("java/io/FileInputStream", "(Ljava/io/File;)V", (file,)).await?;
-        jvm.put_field(&mut this, "is", "Ljava/io/InputStream;", is).await?;
+        if file.is_null() {
+            return Err(jvm.exception("java/lang/NullPointerException", "file is null").await);
+        }
+
+        jvm.put_field(&mut this, "file", "Lcom/xce/io/XFile;", file).await?;
 
         Ok(())
     }
 
-    async fn available(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+    async fn read(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("com.xce.io.FileInputStream::read({:?})", this);
+
+        let buf = jvm.instantiate_array("B", 1).await?;
+        let read: i32 = jvm.invoke_virtual(&this, "read", "([BII)I", (buf.clone(), 0, 1)).await?;
+        if read <= 0 {
+            return Ok(-1);
+        }
+
+        let byte: Vec<i8> = jvm.load_array(&buf, 0, 1).await?;
+
+        Ok(byte[0] as u8 as _)
+    }
+
+    async fn read_array(
+        jvm: &Jvm,
+        _context: &mut WieJvmContext,
+        this: ClassInstanceRef<Self>,
+        buf: ClassInstanceRef<Array<i8>>,
+        offset: i32,
+        length: i32,
+    ) -> JvmResult<i32> {
+        tracing::debug!("com.xce.io.FileInputStream::read({:?}, {:?}, {}, {})", this, buf, offset, length);
+
+        let file = jvm.get_field(&this, "file", "Lcom/xce/io/XFile;").await?;
+
+        jvm.invoke_virtual(&file, "read", "([BII)I", (buf, offset, length)).await
+    }
+
+    async fn available(jvm: &Jvm, context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
         tracing::debug!("com.xce.io.FileInputStream::available({:?})", this);
 
-        let is = jvm.get_field(&this, "is", "Ljava/io/InputStream;").await?;
-        let available = jvm.invoke_virtual(&is, "available", "()I", ()).await?;
+        let file = jvm.get_field(&this, "file", "Lcom/xce/io/XFile;").await?;
 
-        Ok(available)
+        XFile::available(jvm, context, &file).await
     }
 
     async fn close(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<()> {
         tracing::debug!("com.xce.io.FileInputStream::close({:?})", this);
 
-        let is = jvm.get_field(&this, "is", "Ljava/io/InputStream;").await?;
-        let _: () = jvm.invoke_virtual(&is, "close", "()V", ()).await?;
+        let file = jvm.get_field(&this, "file", "Lcom/xce/io/XFile;").await?;
+        let _: () = jvm.invoke_virtual(&file, "close", "()V", ()).await?;
 
         Ok(())
     }
@@ -2,11 +2,11 @@ use alloc::vec;
 
 use java_class_proto::{JavaFieldProto, JavaMethodProto};
 use java_runtime::classes::java::lang::String;
-use jvm::{ClassInstanceRef, Jvm, Result as JvmResult};
+use jvm::{Array, ClassInstanceRef, Jvm, Result as JvmResult};
 
 use wie_jvm_support::{WieJavaClassProto, WieJvmContext};
 
-use crate::classes::com::xce::io::x_file::XFile;
+use crate::classes::com::xce::io::x_file::{XFile, WRITE};
 
 // class com.xce.io.FileOutputStream
 pub struct FileOutputStream;
@@ -21,21 +21,22 @@ impl FileOutputStream {
                 JavaMethodProto::new("<init>", "(Ljava/lang/String;)V", Self::init, Default::default()),
                 JavaMethodProto::new("<init>", "(Lcom/xce/io/XFile;)V", Self::init_with_file, Default::default()),
                 JavaMethodProto::new("write", "(I)V", Self::write, Default::default()),
+                JavaMethodProto::new("write", "([BII)V", Self::write_array, Default::default()),
+                JavaMethodProto::new("flush", "()V", Self::flush, Default::default()),
                 JavaMethodProto::new("close", "()V", Self::close, Default::default()),
             ],
-            fields: vec![JavaFieldProto::new("os", "Ljava/io/OutputStream;", Default::default())],
+            fields: vec![JavaFieldProto::new("file", "Lcom/xce/io/XFile;", Default::default())],
         }
     }
 
-    async fn init(jvm: &Jvm, _context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, name: ClassInstanceRef<String>) -> JvmResult<()> {
+    async fn init(jvm: &Jvm, context: &mut WieJvmContext, this: ClassInstanceRef<Self>, name: ClassInstanceRef<String>) -> JvmResult<()> {
         tracing::debug!("com.xce.io.FileOutputStream::<init>({:?}, {:?})", this, name);
 
-        let file = jvm.new_class("java/io/File", "(Ljava/lang/String;)V", (name,)).await?;
-        let os = jvm.new_class("java/io/FileOutputStream", "(Ljava/io/File;)V", (file,)).await?;
+        // opening by name replaces existing contents
+        let mut file = jvm.new_class("com/xce/io/XFile", "(Ljava/lang/String;I)V", (name, WRITE)).await?.into();
+        XFile::truncate(jvm, context, &mut file).await?;
 
-        jvm.put_field(&mut this, "os", "Ljava/io/OutputStream;", os).await?;
-
-        Ok(())
+        Self::init_with_file(jvm, context, this, file).await
     }
 
     async fn init_with_file(
@@ -44,12 +45,13 @@ impl FileOutputStream {
         mut this: ClassInstanceRef<Self>,
         file: ClassInstanceRef<XFile>,
     ) -> JvmResult<()> {
-        tracing::debug!("com.xce.io.FileOutputStream::<init>({:?})", file);
+        tracing::debug!("com.xce.io.FileOutputStream::<init>({:?}, {:?})", this, file);
 
-        let file = XFile::file(jvm, file).await?;
-        let os = jvm.new_class("java/io/FileOutputStream", "(Ljava/io/File;)V", (file,)).await?;
+        if file.is_null() {
+            return Err(jvm.exception("java/lang/NullPointerException", "file is null").await);
+        }
 
-        jvm.put_field(&mut this, "os", "Ljava/io/OutputStream;", os).await?;
+        jvm.put_field(&mut this, "file", "Lcom/xce/io/XFile;", file).await?;
 
         Ok(())
     }
@@ -57,17 +59,40 @@ impl FileOutputStream {
     async fn write(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>, byte: i32) -> JvmResult<()> {
         tracing::debug!("com.xce.io.FileOutputStream::write({:?}, {:?})", this, byte);
 
-        let os = jvm.get_field(&this, "os", "Ljava/io/OutputStream;").await?;
-        let _: () = jvm.invoke_virtual(&os, "write", "(I)V", (byte,)).await?;
+        let mut buf = jvm.instantiate_array("B", 1).await?;
+        jvm.store_array(&mut buf, 0, vec![byte as i8]).await?;
+
+        jvm.invoke_virtual(&this, "write", "([BII)V", (buf, 0, 1)).await
+    }
+
+    async fn write_array(
+        jvm: &Jvm,
+        _context: &mut WieJvmContext,
+        this: ClassInstanceRef<Self>,
+        buf: ClassInstanceRef<Array<i8>>,
+        offset: i32,
+        length: i32,
+    ) -> JvmResult<()> {
+        tracing::debug!("com.xce.io.FileOutputStream::write({:?}, {:?}, {}, {})", this, buf, offset, length);
+
+        let file = jvm.get_field(&this, "file", "Lcom/xce/io/XFile;").await?;
+        let _: i32 = jvm.invoke_virtual(&file, "write", "([BII)I", (buf, offset, length)).await?;
+
+        Ok(())
+    }
+
+    async fn flush(_jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<()> {
+        tracing::debug!("com.xce.io.FileOutputStream::flush({:?})", this);
 
+        // writes go to the filesystem directly
         Ok(())
     }
 
     async fn close(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<()> {
         tracing::debug!("com.xce.io.FileOutputStream::close({:?})", this);
 
-        let os = jvm.get_field(&this, "os", "Ljava/io/OutputStream;").await?;
-        let _: () = jvm.invoke_virtual(&os, "close", "()V", ()).await?;
+        let file = jvm.get_field(&this, "file", "Lcom/xce/io/XFile;").await?;
+        let _: () = jvm.invoke_virtual(&file, "close", "()V", ()).await?;
 
         Ok(())
     }
@@ -1,12 +1,28 @@
-use alloc::vec;
+use alloc::{format, string::String as RustString, vec, vec::Vec};
+
+use bytemuck::cast_vec;
 
 use java_class_proto::{JavaFieldProto, JavaMethodProto};
 use java_constants::MethodAccessFlags;
-use java_runtime::classes::java::{io::File, lang::String};
+use java_runtime::classes::java::lang::String;
 use jvm::{runtime::JavaLangString, Array, ClassInstanceRef, Jvm, Result as JvmResult};
 
 use wie_jvm_support::{WieJavaClassProto, WieJvmContext};
 
+// open modes
+pub const READ: i32 = 1;
+pub const WRITE: i32 = 2;
+const READ_WRITE: i32 = 3;
+const CLOSED: i32 = 0;
+
+// seek origins
+const SEEK_SET: i32 = 0;
+const SEEK_CUR: i32 = 1;
+const SEEK_END: i32 = 2;
+
+// files are kept under application's own directory of shared filesystem, apart from jar contents
+const PRIVATE_ROOT: &str = "skvm_private";
+
 // class com.xce.io.XFile
 pub struct XFile;
 
@@ -21,98 +37,382 @@ impl XFile {
                 JavaMethodProto::new("exists", "(Ljava/lang/String;)Z", Self::exists, MethodAccessFlags::STATIC),
                 JavaMethodProto::new("filesize", "(Ljava/lang/String;)I", Self::filesize, MethodAccessFlags::STATIC),
                 JavaMethodProto::new("unlink", "(Ljava/lang/String;)I", Self::unlink, MethodAccessFlags::STATIC),
+                JavaMethodProto::new(
+                    "rename",
+                    "(Ljava/lang/String;Ljava/lang/String;)I",
+                    Self::rename,
+                    MethodAccessFlags::STATIC,
+                ),
+                JavaMethodProto::new("mkdir", "(Ljava/lang/String;)I", Self::mkdir, MethodAccessFlags::STATIC),
+                JavaMethodProto::new("list", "(Ljava/lang/String;)[Ljava/lang/String;", Self::list, MethodAccessFlags::STATIC),
+                JavaMethodProto::new("read", "([BII)I", Self::read, Default::default()),
                 JavaMethodProto::new("write", "([BII)I", Self::write, Default::default()),
+                JavaMethodProto::new("seek", "(II)I", Self::seek, Default::default()),
+                JavaMethodProto::new("tell", "()I", Self::tell, Default::default()),
                 JavaMethodProto::new("close", "()V", Self::close, Default::default()),
             ],
             fields: vec![
-                JavaFieldProto::new("file", "Ljava/io/File;", Default::default()),
-                JavaFieldProto::new("raf", "Ljava/io/RandomAccessFile;", Default::default()),
+                JavaFieldProto::new("path", "Ljava/lang/String;", Default::default()),
+                JavaFieldProto::new("mode", "I", Default::default()),
+                JavaFieldProto::new("position", "I", Default::default()),
             ],
         }
     }
 
     async fn init(
         jvm: &Jvm,
-        _context: &mut WieJvmContext,
+        context: &mut WieJvmContext,
         mut this: ClassInstanceRef<Self>,
         name: ClassInstanceRef<String>,
         mode: i32,
     ) -> JvmResult<()> {
         tracing::debug!("com.xce.io.XFile::<init>({:?}, {:?}, {:?})", this, name, mode);
 
-        let mode_string = JavaLangString::from_rust_string(
-            jvm,
+        let _: () = jvm.invoke_special(&this, "java/lang/Object", "<init>", "()V", ()).await?;
+
+        let Some(path) = Self::skt_path(jvm, context, &name).await? else {
+            return Err(jvm.exception("java/io/IOException", "Invalid filename").await);
+        };
+
+        let error = {
+            let mut filesystem = context.system().filesystem();
             match mode {
-                1 => "r",
-                2 => "rw",
-                3 => "rw",
-                _ => return Err(jvm.exception("java/io/IOException", "Invalid mode").await),
-            },
-        )
-        .await?;
-
-        let raf = jvm
-            .new_class(
-                "java/io/RandomAccessFile",
-                "(Ljava/lang/String;Ljava/lang/String;)V",
-                (name.clone(), mode_string),
-            )
-            .await?;
-        let file = jvm.new_class("java/io/File", "(Ljava/lang/String;)V", (name,)).await?;
-
-        jvm.put_field(&mut this, "file", "Ljava/io/File;", file).await?;
-        jvm.put_field(&mut this, "raf", "Ljava/io/RandomAccessFile;", raf).await?;
+                READ if !filesystem.exists(&path) => Some("File not found"),
+                READ => None,
+                WRITE | READ_WRITE if filesystem.is_directory(&path) => Some("Is a directory"),
+                // writable modes create missing file, but keep existing contents
+                WRITE | READ_WRITE => {
+                    if !filesystem.exists(&path) {
+                        filesystem.write(&path, 0, &[]);
+                    }
+                    None
+                }
+                _ => Some("Invalid mode"),
+            }
+        };
+        if let Some(error) = error {
+            return Err(jvm.exception("java/io/IOException", error).await);
+        }
+
+        let path = JavaLangString::from_rust_string(jvm, &path).await?;
+        jvm.put_field(&mut this, "path", "Ljava/lang/String;", path).await?;
+        jvm.put_field(&mut this, "mode", "I", mode).await?;
+        jvm.put_field(&mut this, "position", "I", 0).await?;
 
         Ok(())
     }
 
-    async fn exists(jvm: &Jvm, _context: &mut WieJvmContext, name: ClassInstanceRef<String>) -> JvmResult<bool> {
+    async fn exists(jvm: &Jvm, context: &mut WieJvmContext, name: ClassInstanceRef<String>) -> JvmResult<bool> {
         tracing::debug!("com.xce.io.XFile::exists({:?})", name);
 
-        let file = jvm.new_class("java/io/File", "(Ljava/lang/String;)V", (name,)).await?;
-        let exists = jvm.invoke_virtual(&file, "exists", "()Z", ()).await?;
+        let Some(path) = Self::skt_path(jvm, context, &name).await? else {
+            return Ok(false);
+        };
+        let filesystem = context.system().filesystem();
+
+        Ok(filesystem.exists(&path) || filesystem.is_directory(&path))
+    }
+
+    async fn filesize(jvm: &Jvm, context: &mut WieJvmContext, name: ClassInstanceRef<String>) -> JvmResult<i32> {
+        tracing::debug!("com.xce.io.XFile::filesize({:?})", name);
+
+        let size = match Self::skt_path(jvm, context, &name).await? {
+            Some(path) => context.system().filesystem().size(&path),
+            None => None,
+        };
+
+        match size {
+            Some(size) => Ok(size as _),
+            None => Err(jvm.exception("java/io/IOException", "File not found").await),
+        }
+    }
 
-        Ok(exists)
+    async fn unlink(jvm: &Jvm, context: &mut WieJvmContext, name: ClassInstanceRef<String>) -> JvmResult<i32> {
+        tracing::debug!("com.xce.io.XFile::unlink({:?})", name);
+
+        let Some(path) = Self::skt_path(jvm, context, &name).await? else {
+            return Ok(-1);
+        };
+        if path == Self::private_root(context) {
+            return Ok(-1);
+        }
+
+        Ok(if context.system().filesystem().remove(&path) { 0 } else { -1 })
     }
 
-    async fn filesize(jvm: &Jvm, _context: &mut WieJvmContext, name: ClassInstanceRef<String>) -> JvmResult<i32> {
-        tracing::warn!("stub com.xce.io.XFile::filesize({:?})", name);
+    async fn rename(jvm: &Jvm, context: &mut WieJvmContext, from: ClassInstanceRef<String>, to: ClassInstanceRef<String>) -> JvmResult<i32> {
+        tracing::debug!("com.xce.io.XFile::rename({:?}, {:?})", from, to);
+
+        let (Some(from), Some(to)) = (Self::skt_path(jvm, context, &from).await?, Self::skt_path(jvm, context, &to).await?) else {
+            return Ok(-1);
+        };
+        let root = Self::private_root(context);
+        if from == root || to == root {
+            return Ok(-1);
+        }
 
-        Err(jvm.exception("java/io/IOException", "File not found").await)
+        Ok(if context.system().filesystem().rename(&from, &to) { 0 } else { -1 })
     }
 
-    async fn unlink(_jvm: &Jvm, _context: &mut WieJvmContext, name: ClassInstanceRef<String>) -> JvmResult<i32> {
-        tracing::warn!("stub com.xce.io.XFile::unlink({:?})", name);
+    async fn mkdir(jvm: &Jvm, context: &mut WieJvmContext, name: ClassInstanceRef<String>) -> JvmResult<i32> {
+        tracing::debug!("com.xce.io.XFile::mkdir({:?})", name);
 
-        Ok(0)
+        let Some(path) = Self::skt_path(jvm, context, &name).await? else {
+            return Ok(-1);
+        };
+
+        Ok(if context.system().filesystem().create_directory(&path) { 0 } else { -1 })
+    }
+
+    async fn list(jvm: &Jvm, context: &mut WieJvmContext, name: ClassInstanceRef<String>) -> JvmResult<ClassInstanceRef<Array<String>>> {
+        tracing::debug!("com.xce.io.XFile::list({:?})", name);
+
+        let Some(path) = Self::skt_path(jvm, context, &name).await? else {
+            return Ok(None.into());
+        };
+
+        let entries = {
+            let filesystem = context.system().filesystem();
+            if !filesystem.is_directory(&path) {
+                return Ok(None.into());
+            }
+
+            filesystem.list(&path)
+        };
+
+        let mut names = Vec::with_capacity(entries.len());
+        for entry in entries {
+            names.push(JavaLangString::from_rust_string(jvm, &entry).await?);
+        }
+
+        let mut array = jvm.instantiate_array("Ljava/lang/String;", names.len()).await?;
+        jvm.store_array(&mut array, 0, names).await?;
+
+        Ok(array.into())
+    }
+
+    async fn read(
+        jvm: &Jvm,
+        context: &mut WieJvmContext,
+        mut this: ClassInstanceRef<Self>,
+        mut buf: ClassInstanceRef<Array<i8>>,
+        offset: i32,
+        length: i32,
+    ) -> JvmResult<i32> {
+        tracing::debug!("com.xce.io.XFile::read({:?}, {:?}, {}, {})", this, buf, offset, length);
+
+        Self::check_bounds(jvm, &buf, offset, length).await?;
+        let (path, _) = Self::open_path(jvm, &this).await?;
+        let position: i32 = jvm.get_field(&this, "position", "I").await?;
+
+        let mut data = vec![0; length as _];
+        let read = context
+            .system()
+            .filesystem()
+            .read(&path, position as _, length as _, &mut data)
+            .unwrap_or(0);
+        if read == 0 && length > 0 {
+            return Ok(-1);
+        }
+
+        let Some(new_position) = position.checked_add(read as i32) else {
+            return Err(jvm.exception("java/io/IOException", "File is too large").await);
+        };
+
+        data.truncate(read);
+        jvm.store_array(&mut buf, offset as _, cast_vec::<u8, i8>(data)).await?;
+        jvm.put_field(&mut this, "position", "I", new_position).await?;
+
+        Ok(read as _)
     }
 
     async fn write(
         jvm: &Jvm,
-        _context: &mut WieJvmContext,
-        this: ClassInstanceRef<Self>,
+        context: &mut WieJvmContext,
+        mut this: ClassInstanceRef<Self>,
         data: ClassInstanceRef<Array<i8>>,
         offset: i32,
         length: i32,
     ) -> JvmResult<i32> {
         tracing::debug!("com.xce.io.XFile::write({:?}, {:?}, {}, {})", this, data, offset, length);
 
-        let raf = jvm.get_field(&this, "raf", "Ljava/io/RandomAccessFile;").await?;
-        let _: () = jvm.invoke_virtual(&raf, "write", "([BII)V", (data, offset, length)).await?;
+        Self::check_bounds(jvm, &data, offset, length).await?;
+        let (path, mode) = Self::open_path(jvm, &this).await?;
+        if mode == READ {
+            return Err(jvm.exception("java/io/IOException", "File is opened read only").await);
+        }
+        let position: i32 = jvm.get_field(&this, "position", "I").await?;
+        let Some(new_position) = position.checked_add(length) else {
+            return Err(jvm.exception("java/io/IOException", "File is too large").await);
+        };
+
+        let data: Vec<i8> = jvm.load_array(&data, offset as _, length as _).await?;
+        let written = context.system().filesystem().write(&path, position as _, &cast_vec::<i8, u8>(data));
+        jvm.put_field(&mut this, "position", "I", new_position).await?;
 
-        Ok(length)
+        Ok(written as _)
     }
 
-    async fn close(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<()> {
+    async fn seek(jvm: &Jvm, context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, offset: i32, whence: i32) -> JvmResult<i32> {
+        tracing::debug!("com.xce.io.XFile::seek({:?}, {}, {})", this, offset, whence);
+
+        let (path, _) = Self::open_path(jvm, &this).await?;
+        let base = match whence {
+            SEEK_SET => 0,
+            SEEK_CUR => jvm.get_field(&this, "position", "I").await?,
+            SEEK_END => context.system().filesystem().size(&path).unwrap_or(0) as i32,
+            _ => return Err(jvm.exception("java/io/IOException", "Invalid origin").await),
+        };
+
+        let Some(position) = base.checked_add(offset).filter(|x| *x >= 0) else {
+            return Err(jvm.exception("java/io/IOException", "Invalid offset").await);
+        };
+
+        // seeking past the end is allowed, gap is filled with zeros on next write
+        jvm.put_field(&mut this, "position", "I", position).await?;
+
+        Ok(position)
+    }
+
+    async fn tell(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("com.xce.io.XFile::tell({:?})", this);
+
+        Self::open_path(jvm, &this).await?;
+
+        jvm.get_field(&this, "position", "I").await
+    }
+
+    async fn close(jvm: &Jvm, context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>) -> JvmResult<()> {
         tracing::debug!("com.xce.io.XFile::close({:?})", this);
 
-        let raf = jvm.get_field(&this, "raf", "Ljava/io/RandomAccessFile;").await?;
-        let _: () = jvm.invoke_virtual(&raf, "close", "()V", ()).await?;
+        // closing twice is allowed
+        jvm.put_field(&mut this, "mode", "I", CLOSED).await?;
+        context.system().filesystem().flush();
 
         Ok(())
     }
 
-    pub async fn file(jvm: &Jvm, this: ClassInstanceRef<Self>) -> JvmResult<ClassInstanceRef<File>> {
-        jvm.get_field(&this, "file", "Ljava/io/File;").await
+    /// Remaining bytes from current position.
+    pub async fn available(jvm: &Jvm, context: &mut WieJvmContext, this: &ClassInstanceRef<Self>) -> JvmResult<i32> {
+        let (path, _) = Self::open_path(jvm, this).await?;
+        let position: i32 = jvm.get_field(this, "position", "I").await?;
+        let size = context.system().filesystem().size(&path).unwrap_or(0) as i32;
+
+        Ok((size - position).max(0))
+    }
+
+    /// Discards file contents, as `FileOutputStream` does on open.
+    pub async fn truncate(jvm: &Jvm, context: &mut WieJvmContext, this: &mut ClassInstanceRef<Self>) -> JvmResult<()> {
+        let (path, _) = Self::open_path(jvm, this).await?;
+
+        context.system().filesystem().truncate(&path, 0);
+        jvm.put_field(this, "position", "I", 0).await
+    }
+
+    async fn open_path(jvm: &Jvm, this: &ClassInstanceRef<Self>) -> JvmResult<(RustString, i32)> {
+        let mode: i32 = jvm.get_field(this, "mode", "I").await?;
+        if mode == CLOSED {
+            return Err(jvm.exception("java/io/IOException", "File is closed").await);
+        }
+
+        let path = jvm.get_field(this, "path", "Ljava/lang/String;").await?;
+
+        Ok((JavaLangString::to_rust_string(jvm, &path).await?, mode))
+    }
+
+    async fn check_bounds(jvm: &Jvm, buf: &ClassInstanceRef<Array<i8>>, offset: i32, length: i32) -> JvmResult<()> {
+        if buf.is_null() {
+            return Err(jvm.exception("java/lang/NullPointerException", "buffer is null").await);
+        }
+
+        let buf_length = jvm.array_length(buf).await? as i32;
+        if offset < 0 || length < 0 || offset > buf_length - length {
+            return Err(jvm
+                .exception("java/lang/ArrayIndexOutOfBoundsException", "Invalid offset or length")
+                .await);
+        }
+
+        Ok(())
+    }
+
+    /// Path of `name` in shared filesystem, under application's private directory.
+    async fn skt_path(jvm: &Jvm, context: &mut WieJvmContext, name: &ClassInstanceRef<String>) -> JvmResult<Option<RustString>> {
+        if name.is_null() {
+            return Err(jvm.exception("java/lang/NullPointerException", "name is null").await);
+        }
+
+        let name = JavaLangString::to_rust_string(jvm, name).await?;
+        let Some(path) = normalize_skt_path(&name) else {
+            return Ok(None);
+        };
+
+        let root = Self::private_root(context);
+        context.system().filesystem().create_directory(&root);
+
+        Ok(Some(if path.is_empty() { root } else { format!("{}/{}", root, path) }))
+    }
+
+    fn private_root(context: &mut WieJvmContext) -> RustString {
+        format!("{}/{}", PRIVATE_ROOT, context.system().app_id())
+    }
+}
+
+// paths are relative to the application's private directory, '\' is accepted as separator and going above the root is not allowed
+fn normalize_skt_path(name: &str) -> Option<RustString> {
+    if name.is_empty() {
+        return None;
+    }
+
+    let mut components = Vec::new();
+    for component in name.split(['/', '\\']) {
+        match component {
+            "" | "." => {}
+            ".." => return None,
+            x => components.push(x),
+        }
+    }
+
+    Some(components.join("/"))
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::boxed::Box;
+
+    use jvm::{runtime::JavaLangString, Result as JvmResult};
+
+    use test_utils::run_jvm_test;
+    use wie_util::Result;
+
+    use crate::get_protos;
+
+    use super::{normalize_skt_path, SEEK_CUR, SEEK_SET, WRITE};
+
+    #[test]
+    fn test_normalize_skt_path() {
+        assert_eq!(normalize_skt_path("save.dat").as_deref(), Some("save.dat"));
+        assert_eq!(normalize_skt_path("/data\\./score.dat").as_deref(), Some("data/score.dat"));
+        assert_eq!(normalize_skt_path("/").as_deref(), Some(""));
+        assert_eq!(normalize_skt_path("../save.dat"), None);
+        assert_eq!(normalize_skt_path(""), None);
+    }
+
+    #[test]
+    fn test_seek_overflow() -> Result<()> {
+        run_jvm_test(Box::new([get_protos().into()]), |jvm| async move {
+            let name = JavaLangString::from_rust_string(&jvm, "save.dat").await?;
+            let file = jvm.new_class("com/xce/io/XFile", "(Ljava/lang/String;I)V", (name, WRITE)).await?;
+
+            let position: i32 = jvm.invoke_virtual(&file, "seek", "(II)I", (i32::MAX, SEEK_SET)).await?;
+            assert_eq!(position, i32::MAX);
+
+            let result: JvmResult<i32> = jvm.invoke_virtual(&file, "seek", "(II)I", (1, SEEK_CUR)).await;
+            assert!(result.is_err());
+
+            let tell: i32 = jvm.invoke_virtual(&file, "tell", "()I", ()).await?;
+            assert_eq!(tell, i32::MAX);
+
+            Ok(())
+        })
     }
 }
@@ -0,0 +1,3 @@
+mod skvm_audio_clip;
+
+pub use skvm_audio_clip::SkvmAudioClip;
@@ -0,0 +1,175 @@
+use alloc::{vec, vec::Vec};
+
+use java_class_proto::{JavaFieldProto, JavaMethodProto};
+use java_runtime::classes::java::lang::String;
+use jvm::{runtime::JavaLangString, Array, ClassInstanceRef, Jvm, Result as JvmResult};
+
+use wie_backend::AudioHandle;
+use wie_jvm_support::{WieJavaClassProto, WieJvmContext};
+
+use crate::classes::com::skt::m::AudioSystem;
+
+const NO_AUDIO: i32 = -1;
+
+// class net.wie.SkvmAudioClip
+pub struct SkvmAudioClip;
+
+impl SkvmAudioClip {
+    pub fn as_proto() -> WieJavaClassProto {
+        WieJavaClassProto {
+            name: "net/wie/SkvmAudioClip",
+            parent_class: Some("java/lang/Object"),
+            interfaces: vec!["com/skt/m/AudioClip"],
+            methods: vec![
+                JavaMethodProto::new("<init>", "(Ljava/lang/String;)V", Self::init, Default::default()),
+                JavaMethodProto::new("open", "([BII)V", Self::open, Default::default()),
+                JavaMethodProto::new("play", "()V", Self::play, Default::default()),
+                JavaMethodProto::new("loop", "()V", Self::r#loop, Default::default()),
+                JavaMethodProto::new("stop", "()V", Self::stop, Default::default()),
+                JavaMethodProto::new("pause", "()V", Self::pause, Default::default()),
+                JavaMethodProto::new("resume", "()V", Self::resume, Default::default()),
+                JavaMethodProto::new("close", "()V", Self::close, Default::default()),
+            ],
+            fields: vec![
+                JavaFieldProto::new("format", "Ljava/lang/String;", Default::default()),
+                JavaFieldProto::new("audioHandle", "I", Default::default()),
+            ],
+        }
+    }
+
+    async fn init(jvm: &Jvm, _context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, format: ClassInstanceRef<String>) -> JvmResult<()> {
+        tracing::debug!("net.wie.SkvmAudioClip::<init>({:?}, {:?})", &this, &format);
+
+        let _: () = jvm.invoke_special(&this, "java/lang/Object", "<init>", "()V", ()).await?;
+
+        jvm.put_field(&mut this, "format", "Ljava/lang/String;", format).await?;
+        jvm.put_field(&mut this, "audioHandle", "I", NO_AUDIO).await?;
+
+        Ok(())
+    }
+
+    async fn open(
+        jvm: &Jvm,
+        context: &mut WieJvmContext,
+        mut this: ClassInstanceRef<Self>,
+        data: ClassInstanceRef<Array<i8>>,
+        offset: i32,
+        length: i32,
+    ) -> JvmResult<()> {
+        tracing::debug!("net.wie.SkvmAudioClip::open({:?}, {:?}, {}, {})", &this, &data, offset, length);
+
+        let data: Vec<i8> = jvm.load_array(&data, offset as _, length as _).await?;
+        let format = Self::format(jvm, &this).await?;
+
+        // format is used only if it can't be detected from data
+        let audio_handle = context.system().audio().load(bytemuck::cast_slice(&data), Some(&format));
+        let Ok(audio_handle) = audio_handle else {
+            return Err(jvm.exception("java/io/IOException", "Unsupported audio data").await);
+        };
+
+        let old_audio_handle: i32 = jvm.get_field(&this, "audioHandle", "I").await?;
+        if old_audio_handle != NO_AUDIO {
+            let _ = context.system().audio().close(old_audio_handle as _);
+        } else {
+            AudioSystem::add_clip(jvm, &this).await?;
+        }
+
+        jvm.put_field(&mut this, "audioHandle", "I", audio_handle as i32).await?;
+        Self::apply_volume(jvm, context, &this).await
+    }
+
+    async fn play(jvm: &Jvm, context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<()> {
+        tracing::debug!("net.wie.SkvmAudioClip::play({:?})", &this);
+
+        Self::start(jvm, context, &this, 1).await
+    }
+
+    async fn r#loop(jvm: &Jvm, context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<()> {
+        tracing::debug!("net.wie.SkvmAudioClip::loop({:?})", &this);
+
+        Self::start(jvm, context, &this, -1).await
+    }
+
+    async fn stop(jvm: &Jvm, context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<()> {
+        tracing::debug!("net.wie.SkvmAudioClip::stop({:?})", &this);
+
+        if let Some(audio_handle) = Self::audio_handle(jvm, &this).await? {
+            let _ = context.system().audio().stop(audio_handle);
+        }
+
+        Ok(())
+    }
+
+    async fn pause(jvm: &Jvm, context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<()> {
+        tracing::debug!("net.wie.SkvmAudioClip::pause({:?})", &this);
+
+        if let Some(audio_handle) = Self::audio_handle(jvm, &this).await? {
+            let _ = context.system().audio().pause(audio_handle);
+        }
+
+        Ok(())
+    }
+
+    async fn resume(jvm: &Jvm, context: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<()> {
+        tracing::debug!("net.wie.SkvmAudioClip::resume({:?})", &this);
+
+        if let Some(audio_handle) = Self::audio_handle(jvm, &this).await? {
+            let _ = context.system().audio().resume(audio_handle);
+        }
+
+        Ok(())
+    }
+
+    async fn close(jvm: &Jvm, context: &mut WieJvmContext, mut this: ClassInstanceRef<Self>) -> JvmResult<()> {
+        tracing::debug!("net.wie.SkvmAudioClip::close({:?})", &this);
+
+        let Some(audio_handle) = Self::audio_handle(jvm, &this).await? else {
+            return Ok(());
+        };
+
+        let _ = context.system().audio().close(audio_handle);
+        jvm.put_field(&mut this, "audioHandle", "I", NO_AUDIO).await?;
+
+        AudioSystem::remove_clip(jvm, &this).await
+    }
+
+    /// Sets backend volume from `AudioSystem` volume of the clip format.
+    pub async fn apply_volume(jvm: &Jvm, context: &mut WieJvmContext, this: &ClassInstanceRef<Self>) -> JvmResult<()> {
+        let Some(audio_handle) = Self::audio_handle(jvm, this).await? else {
+            return Ok(());
+        };
+
+        let format = Self::format(jvm, this).await?;
+        let volume = AudioSystem::volume_percent(jvm, &format).await?;
+
+        let _ = context.system().audio().set_volume(audio_handle, volume);
+
+        Ok(())
+    }
+
+    // clip is always played from the beginning
+    async fn start(jvm: &Jvm, context: &mut WieJvmContext, this: &ClassInstanceRef<Self>, loop_count: i32) -> JvmResult<()> {
+        let Some(audio_handle) = Self::audio_handle(jvm, this).await? else {
+            return Err(jvm.exception("java/lang/IllegalStateException", "Clip is not open").await);
+        };
+
+        let mut audio = context.system().audio();
+        let _ = audio.stop(audio_handle);
+        let _ = audio.set_position(audio_handle, 0);
+        let _ = audio.play(audio_handle, loop_count);
+
+        Ok(())
+    }
+
+    async fn audio_handle(jvm: &Jvm, this: &ClassInstanceRef<Self>) -> JvmResult<Option<AudioHandle>> {
+        let audio_handle: i32 = jvm.get_field(this, "audioHandle", "I").await?;
+
+        Ok((audio_handle != NO_AUDIO).then_some(audio_handle as _))
+    }
+
+    async fn format(jvm: &Jvm, this: &ClassInstanceRef<Self>) -> JvmResult<alloc::string::String> {
+        let format = jvm.get_field(this, "format", "Ljava/lang/String;").await?;
+
+        JavaLangString::to_rust_string(jvm, &format).await
+    }
+}
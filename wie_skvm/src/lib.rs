@@ -5,7 +5,7 @@ use wie_jvm_support::WieJavaClassProto;
 
 pub mod classes;
 
-pub fn get_protos() -> [WieJavaClassProto; 13] {
+pub fn get_protos() -> [WieJavaClassProto; 16] {
     [
         classes::com::skt::m::AudioClip::as_proto(),
         classes::com::skt::m::AudioSystem::as_proto(),
@@ -15,10 +15,12 @@ pub fn get_protos() -> [WieJavaClassProto; 13] {
         classes::com::skt::m::MathFP::as_proto(),
         classes::com::skt::m::Vibration::as_proto(),
         classes::com::skt::m::ProgressBar::as_proto(),
+        classes::com::skt::m::UIComponent::as_proto(),
         classes::com::xce::io::FileInputStream::as_proto(),
         classes::com::xce::io::FileOutputStream::as_proto(),
         classes::com::xce::io::XFile::as_proto(),
         classes::com::xce::lcdui::Toolkit::as_proto(),
         classes::com::xce::lcdui::XDisplay::as_proto(),
+        classes::net::wie::SkvmAudioClip::as_proto(),
     ]
 }
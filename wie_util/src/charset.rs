@@ -0,0 +1,235 @@
+use alloc::{string::String, vec::Vec};
+
+use encoding_rs::EUC_KR;
+
+// modern hangul syllable range, U+AC00..=U+D7A3
+const HANGUL_BASE: u32 = 0xac00;
+const HANGUL_COUNT: u32 = 11172;
+
+// johab 5-bit jamo codes, indexed by unicode jamo index
+const JOHAB_CHOSEONG: [u16; 19] = [2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20];
+const JOHAB_JUNGSEONG: [u16; 21] = [3, 4, 5, 6, 7, 10, 11, 12, 13, 14, 15, 18, 19, 20, 21, 22, 23, 26, 27, 28, 29];
+const JOHAB_JONGSEONG: [u16; 28] = [
+    1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29,
+];
+const JOHAB_FILL_CHOSEONG: u16 = 1;
+const JOHAB_FILL_JUNGSEONG: u16 = 2;
+const JOHAB_FILL_JONGSEONG: u16 = 1;
+
+// hangul compatibility jamo for each choseong, U+3131..
+const COMPAT_CHOSEONG: [u32; 19] = [
+    0x3131, 0x3132, 0x3134, 0x3137, 0x3138, 0x3139, 0x3141, 0x3142, 0x3143, 0x3145, 0x3146, 0x3147, 0x3148, 0x3149, 0x314a, 0x314b, 0x314c, 0x314d,
+    0x314e,
+];
+const COMPAT_JUNGSEONG_BASE: u32 = 0x314f;
+
+/// Character sets used by korean handsets.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Charset {
+    /// KS X 1001 (KSC5601) in EUC-KR form, with CP949 extensions.
+    #[default]
+    Ksc5601,
+    /// KS X 1001 annex 3 johab, used by some older handsets.
+    Johab,
+    Utf8,
+}
+
+impl Charset {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_uppercase().replace('_', "-").as_str() {
+            "KSC5601" | "KSC-5601" | "KS-C-5601-1987" | "EUC-KR" | "EUCKR" | "CP949" | "MS949" | "X-WINDOWS-949" => Some(Self::Ksc5601),
+            "JOHAB" | "X-JOHAB" | "KSSM" | "CP1361" => Some(Self::Johab),
+            "UTF-8" | "UTF8" => Some(Self::Utf8),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Ksc5601 => "EUC-KR",
+            Self::Johab => "Johab",
+            Self::Utf8 => "UTF-8",
+        }
+    }
+
+    /// Guesses charset of `bytes`. Valid UTF-8 is treated as UTF-8, everything else as KSC5601.
+    pub fn detect(bytes: &[u8]) -> Self {
+        if core::str::from_utf8(bytes).is_ok() {
+            Self::Utf8
+        } else {
+            Self::Ksc5601
+        }
+    }
+
+    pub fn decode(&self, bytes: &[u8]) -> String {
+        match self {
+            Self::Ksc5601 => EUC_KR.decode_without_bom_handling(bytes).0.into_owned(),
+            Self::Johab => decode_johab(bytes),
+            Self::Utf8 => String::from_utf8_lossy(bytes).into_owned(),
+        }
+    }
+
+    pub fn encode(&self, string: &str) -> Vec<u8> {
+        match self {
+            Self::Ksc5601 => EUC_KR.encode(string).0.into_owned(),
+            Self::Johab => encode_johab(string),
+            Self::Utf8 => string.as_bytes().to_vec(),
+        }
+    }
+}
+
+fn decode_johab(bytes: &[u8]) -> String {
+    let mut result = String::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let lead = bytes[i];
+        if lead < 0x80 || i + 1 >= bytes.len() {
+            result.push(if lead < 0x80 { lead as char } else { char::REPLACEMENT_CHARACTER });
+            i += 1;
+            continue;
+        }
+
+        let trail = bytes[i + 1];
+        i += 2;
+
+        let decoded = if lead <= 0xd3 {
+            decode_johab_hangul(((lead as u16) << 8) | trail as u16)
+        } else {
+            johab_symbol_to_ksc5601(lead, trail).and_then(|x| {
+                let decoded = EUC_KR.decode_without_bom_handling(&x).0;
+                decoded.chars().next().filter(|&x| x != char::REPLACEMENT_CHARACTER)
+            })
+        };
+
+        result.push(decoded.unwrap_or(char::REPLACEMENT_CHARACTER));
+    }
+
+    result
+}
+
+fn decode_johab_hangul(code: u16) -> Option<char> {
+    let choseong = (code >> 10) & 0x1f;
+    let jungseong = (code >> 5) & 0x1f;
+    let jongseong = code & 0x1f;
+
+    let choseong_index = JOHAB_CHOSEONG.iter().position(|&x| x == choseong);
+    let jungseong_index = JOHAB_JUNGSEONG.iter().position(|&x| x == jungseong);
+    let jongseong_index = JOHAB_JONGSEONG.iter().position(|&x| x == jongseong);
+
+    let codepoint = match (choseong_index, jungseong_index, jongseong_index) {
+        (Some(l), Some(v), Some(t)) => HANGUL_BASE + ((l * 21 + v) * 28 + t) as u32,
+        // standalone jamo
+        (Some(l), None, _) if jungseong == JOHAB_FILL_JUNGSEONG && jongseong == JOHAB_FILL_JONGSEONG => COMPAT_CHOSEONG[l],
+        (None, Some(v), _) if choseong == JOHAB_FILL_CHOSEONG && jongseong == JOHAB_FILL_JONGSEONG => COMPAT_JUNGSEONG_BASE + v as u32,
+        _ => return None,
+    };
+
+    char::from_u32(codepoint)
+}
+
+// johab symbol and hanja area maps two ks x 1001 rows into one lead byte
+fn johab_symbol_to_ksc5601(lead: u8, trail: u8) -> Option<[u8; 2]> {
+    let row_base = match lead {
+        0xd9..=0xde => 0x21 + (lead - 0xd9) * 2,
+        0xe0..=0xf9 => 0x4a + (lead - 0xe0) * 2,
+        _ => return None,
+    };
+
+    let (row, column) = match trail {
+        0x31..=0x7e => (row_base, trail - 0x31 + 0x21),
+        0x91..=0xa0 => (row_base, trail - 0x91 + 0x6f),
+        0xa1..=0xfe => (row_base + 1, trail - 0xa1 + 0x21),
+        _ => return None,
+    };
+
+    Some([row | 0x80, column | 0x80])
+}
+
+fn ksc5601_to_johab_symbol(row: u8, column: u8) -> Option<[u8; 2]> {
+    let (row, column) = (row & 0x7f, column & 0x7f);
+    if !(0x21..=0x7e).contains(&column) {
+        return None;
+    }
+
+    let (lead, odd) = match row {
+        0x21..=0x2c => (0xd9 + (row - 0x21) / 2, (row - 0x21) % 2 == 1),
+        0x4a..=0x7d => (0xe0 + (row - 0x4a) / 2, (row - 0x4a) % 2 == 1),
+        _ => return None,
+    };
+
+    let trail = if odd {
+        column - 0x21 + 0xa1
+    } else if column <= 0x6e {
+        column - 0x21 + 0x31
+    } else {
+        column - 0x6f + 0x91
+    };
+
+    Some([lead, trail])
+}
+
+fn encode_johab(string: &str) -> Vec<u8> {
+    let mut result = Vec::with_capacity(string.len() * 2);
+
+    for c in string.chars() {
+        let codepoint = c as u32;
+
+        if codepoint < 0x80 {
+            result.push(codepoint as u8);
+        } else if (HANGUL_BASE..HANGUL_BASE + HANGUL_COUNT).contains(&codepoint) {
+            let index = codepoint - HANGUL_BASE;
+            let (l, v, t) = (index / (21 * 28), (index % (21 * 28)) / 28, index % 28);
+
+            let code = 0x8000 | (JOHAB_CHOSEONG[l as usize] << 10) | (JOHAB_JUNGSEONG[v as usize] << 5) | JOHAB_JONGSEONG[t as usize];
+            result.extend_from_slice(&code.to_be_bytes());
+        } else if let Some(l) = COMPAT_CHOSEONG.iter().position(|&x| x == codepoint) {
+            let code = 0x8000 | (JOHAB_CHOSEONG[l] << 10) | (JOHAB_FILL_JUNGSEONG << 5) | JOHAB_FILL_JONGSEONG;
+            result.extend_from_slice(&code.to_be_bytes());
+        } else if (COMPAT_JUNGSEONG_BASE..COMPAT_JUNGSEONG_BASE + 21).contains(&codepoint) {
+            let v = (codepoint - COMPAT_JUNGSEONG_BASE) as usize;
+            let code = 0x8000 | (JOHAB_FILL_CHOSEONG << 10) | (JOHAB_JUNGSEONG[v] << 5) | JOHAB_FILL_JONGSEONG;
+            result.extend_from_slice(&code.to_be_bytes());
+        } else {
+            let mut buf = [0; 4];
+            let (ksc5601, _, unmappable) = EUC_KR.encode(c.encode_utf8(&mut buf));
+
+            match ksc5601_to_johab_symbol(ksc5601[0], *ksc5601.get(1).unwrap_or(&0)) {
+                Some(x) if !unmappable && ksc5601.len() == 2 => result.extend_from_slice(&x),
+                _ => result.push(b'?'),
+            }
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Charset;
+
+    #[test]
+    fn test_ksc5601() {
+        let bytes = [0xc7, 0xd1, 0xb1, 0xdb, b'1'];
+
+        assert_eq!(Charset::Ksc5601.decode(&bytes), "한글1");
+        assert_eq!(Charset::Ksc5601.encode("한글1"), bytes);
+    }
+
+    #[test]
+    fn test_johab() {
+        // 한, 글, ㄱ, ㅏ, ※
+        let bytes = [0xd0, 0x65, 0x8b, 0x69, 0x88, 0x41, 0x84, 0x61, 0xd9, 0x68];
+
+        assert_eq!(Charset::Johab.decode(&bytes), "한글ㄱㅏ※");
+        assert_eq!(Charset::Johab.encode("한글ㄱㅏ※"), bytes);
+    }
+
+    #[test]
+    fn test_detect() {
+        assert_eq!(Charset::detect("한글".as_bytes()), Charset::Utf8);
+        assert_eq!(Charset::detect(&[0xc7, 0xd1, 0xb1, 0xdb]), Charset::Ksc5601);
+        assert_eq!(Charset::from_name("ksc5601"), Some(Charset::Ksc5601));
+        assert_eq!(Charset::from_name("x-johab"), Some(Charset::Johab));
+    }
+}
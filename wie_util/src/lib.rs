@@ -1,6 +1,8 @@
 #![no_std]
 extern crate alloc;
 
+mod charset;
+
 use alloc::{string::String, vec::Vec};
 use core::{
     error::Error,
@@ -10,6 +12,8 @@ use core::{
 
 use bytemuck::{bytes_of, bytes_of_mut, AnyBitPattern, NoUninit};
 
+pub use self::charset::Charset;
+
 #[derive(Debug)]
 pub enum WieError {
     InvalidMemoryAccess(u32),
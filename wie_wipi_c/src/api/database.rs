@@ -1,4 +1,4 @@
-use alloc::{borrow::ToOwned, boxed::Box, str, string::String, vec};
+use alloc::{borrow::ToOwned, boxed::Box, vec};
 use core::mem::size_of;
 
 use bytemuck::{Pod, Zeroable};
@@ -17,17 +17,16 @@ struct DatabaseHandle {
 pub async fn open_database(context: &mut dyn WIPICContext, ptr_name: WIPICWord, record_size: i32, create: i32, mode: i32) -> Result<i32> {
     tracing::debug!("MC_dbOpenDataBase({:#x}, {}, {}, {})", ptr_name, record_size, create, mode);
 
-    let name = String::from_utf8(read_null_terminated_string_bytes(context, ptr_name)?).unwrap();
+    let name_bytes = read_null_terminated_string_bytes(context, ptr_name)?;
 
     if record_size == 1 {
         // TODO: is parameter record_size correct??
         return Ok(-12); // M_E_NOENT
     }
 
-    let name_bytes = name.as_bytes();
     let mut handle = DatabaseHandle { name: [0; 32] };
 
-    handle.name[..name_bytes.len()].copy_from_slice(name_bytes);
+    handle.name[..name_bytes.len()].copy_from_slice(&name_bytes);
 
     let ptr_handle = context.alloc_raw(size_of::<DatabaseHandle>() as _)?;
     write_generic(context, ptr_handle, handle)?;
@@ -140,8 +139,8 @@ fn get_database_from_db_id(context: &mut dyn WIPICContext, db_id: i32) -> Box<dy
     let handle: DatabaseHandle = read_generic(context, db_id as _).unwrap();
 
     let name_length = handle.name.iter().position(|&c| c == 0).unwrap_or(handle.name.len());
-    let db_name = str::from_utf8(&handle.name[..name_length]).unwrap();
+    let db_name = context.system().charset().decode(&handle.name[..name_length]);
     let app_id = context.system().app_id().to_owned();
 
-    context.system().platform().database_repository().open(db_name, &app_id)
+    context.system().platform().database_repository().open(&db_name, &app_id)
 }
@@ -2,22 +2,25 @@ mod framebuffer;
 mod grp_context;
 mod image;
 
+use alloc::{string::String, vec, vec::Vec};
 use core::mem::size_of;
 
-use bytemuck::Zeroable;
+use bytemuck::{cast_slice, cast_slice_mut, Zeroable};
 
-use wie_backend::canvas::{Clip, Color, PixelType, Rgb8Pixel};
-use wie_util::{read_generic, write_generic, Result};
+use wie_backend::canvas::{raster, Clip, Color, CompositeOp, Font, FontFace, FontSize, FontStyle, Image, PixelType, Rgb8Pixel, TextAlignment};
+use wie_util::{read_generic, read_null_terminated_string_bytes, write_generic, Result, WieError};
 
 use crate::{context::WIPICContext, WIPICMemoryId, WIPICWord};
 
+pub(crate) use self::framebuffer::WIPICFramebuffer;
 use self::{
-    framebuffer::{WIPICDisplayInfo, WIPICFramebuffer},
+    framebuffer::WIPICDisplayInfo,
     grp_context::{WIPICGraphicsContext, WIPICGraphicsContextIdx},
     image::WIPICImage,
 };
 
 const FRAMEBUFFER_DEPTH: u32 = 16; // XXX hardcode to 16bpp as some game requires 16bpp framebuffer
+const FONT_HANDLE_TAG: i32 = 0x100;
 
 pub async fn get_screen_framebuffer(context: &mut dyn WIPICContext, a0: WIPICWord) -> Result<WIPICMemoryId> {
     tracing::debug!("MC_grpGetScreenFrameBuffer({:#x})", a0);
@@ -48,6 +51,7 @@ pub async fn set_context(context: &mut dyn WIPICContext, p_grp_ctx: WIPICWord, o
     tracing::debug!("MC_grpSetContext({:#x}, {:?}, {:#x})", p_grp_ctx, op, pv);
 
     let mut grp_ctx: WIPICGraphicsContext = read_generic(context, p_grp_ctx)?;
+    let mut enabled = true;
     match op {
         WIPICGraphicsContextIdx::ClipIdx => {
             grp_ctx.clip = read_generic(context, pv)?;
@@ -63,11 +67,16 @@ pub async fn set_context(context: &mut dyn WIPICContext, p_grp_ctx: WIPICWord, o
         }
         WIPICGraphicsContextIdx::AlphaIdx => {
             grp_ctx.alpha = pv;
-            // grp_ctx.pixel_op_func_ptr = todo!();
-            // grp_ctx.param1 = todo!();
+            grp_ctx.set(WIPICGraphicsContextIdx::PixelopIdx, false);
+            grp_ctx.set(WIPICGraphicsContextIdx::XorModeIdx, false);
         }
         WIPICGraphicsContextIdx::PixelopIdx => {
             grp_ctx.pixel_op_func_ptr = pv;
+            enabled = pv != 0;
+            if enabled {
+                grp_ctx.set(WIPICGraphicsContextIdx::AlphaIdx, false);
+                grp_ctx.set(WIPICGraphicsContextIdx::XorModeIdx, false);
+            }
         }
         WIPICGraphicsContextIdx::PixelParam1Idx => {
             grp_ctx.param1 = pv;
@@ -78,47 +87,251 @@ pub async fn set_context(context: &mut dyn WIPICContext, p_grp_ctx: WIPICWord, o
         WIPICGraphicsContextIdx::StyleIdx => {
             grp_ctx.style = pv;
         }
+        WIPICGraphicsContextIdx::XorModeIdx => {
+            enabled = pv != 0;
+            if enabled {
+                grp_ctx.set(WIPICGraphicsContextIdx::AlphaIdx, false);
+                grp_ctx.set(WIPICGraphicsContextIdx::PixelopIdx, false);
+            }
+        }
         WIPICGraphicsContextIdx::OffsetIdx => {
             grp_ctx.offset = read_generic(context, pv)?;
         }
         _ => {
             tracing::warn!("MC_grpSetContext({:#x}, {:?}, {:#x}): ignoring invalid op", p_grp_ctx, op, pv);
+
+            return Ok(());
         }
     }
+    grp_ctx.set(op, enabled);
     write_generic(context, p_grp_ctx, grp_ctx)?;
 
     Ok(())
 }
 
+pub async fn get_context(context: &mut dyn WIPICContext, p_grp_ctx: WIPICWord, op: WIPICGraphicsContextIdx, pv: WIPICWord) -> Result<()> {
+    tracing::debug!("MC_grpGetContext({:#x}, {:?}, {:#x})", p_grp_ctx, op, pv);
+
+    let grp_ctx: WIPICGraphicsContext = read_generic(context, p_grp_ctx)?;
+    match op {
+        WIPICGraphicsContextIdx::ClipIdx => write_generic(context, pv, grp_ctx.clip)?,
+        WIPICGraphicsContextIdx::FgPixelIdx => write_generic(context, pv, grp_ctx.fgpxl)?,
+        WIPICGraphicsContextIdx::BgPixelIdx => write_generic(context, pv, grp_ctx.bgpxl)?,
+        WIPICGraphicsContextIdx::TransPixelIdx => write_generic(context, pv, grp_ctx.transpxl)?,
+        WIPICGraphicsContextIdx::AlphaIdx => write_generic(context, pv, grp_ctx.alpha)?,
+        WIPICGraphicsContextIdx::PixelopIdx => write_generic(context, pv, grp_ctx.pixel_op_func_ptr)?,
+        WIPICGraphicsContextIdx::PixelParam1Idx => write_generic(context, pv, grp_ctx.param1)?,
+        WIPICGraphicsContextIdx::FontIdx => write_generic(context, pv, grp_ctx.font)?,
+        WIPICGraphicsContextIdx::StyleIdx => write_generic(context, pv, grp_ctx.style)?,
+        WIPICGraphicsContextIdx::XorModeIdx => write_generic(context, pv, grp_ctx.is_set(op) as WIPICWord)?,
+        WIPICGraphicsContextIdx::OffsetIdx => write_generic(context, pv, grp_ctx.offset)?,
+        _ => {
+            tracing::warn!("MC_grpGetContext({:#x}, {:?}, {:#x}): ignoring invalid op", p_grp_ctx, op, pv);
+        }
+    }
+
+    Ok(())
+}
+
 pub async fn put_pixel(context: &mut dyn WIPICContext, dst_fb: WIPICMemoryId, x: i32, y: i32, p_gctx: WIPICWord) -> Result<()> {
     tracing::debug!("MC_grpPutPixel({:#x}, {}, {}, {:?})", dst_fb.0, x, y, p_gctx);
 
-    let framebuffer: WIPICFramebuffer = read_generic(context, context.data_ptr(dst_fb)?)?;
-    let gctx: WIPICGraphicsContext = read_generic(context, p_gctx)?;
+    paint_shape(context, dst_fb, p_gctx, |_| vec![(x, y)]).await
+}
+
+pub async fn draw_line(context: &mut dyn WIPICContext, dst: WIPICMemoryId, x1: i32, y1: i32, x2: i32, y2: i32, pgc: WIPICWord) -> Result<()> {
+    tracing::debug!("MC_grpDrawLine({:#x}, {}, {}, {}, {}, {:#x})", dst.0, x1, y1, x2, y2, pgc);
 
-    let mut canvas = framebuffer.canvas(context)?;
-    canvas.put_pixel(x as _, y as _, Rgb8Pixel::to_color(gctx.fgpxl));
-    Ok(())
+    paint_shape(context, dst, pgc, |clip| raster::line(x1, y1, x2, y2, clip)).await
+}
+
+pub async fn draw_rect(context: &mut dyn WIPICContext, dst: WIPICMemoryId, x: i32, y: i32, w: i32, h: i32, pgc: WIPICWord) -> Result<()> {
+    tracing::debug!("MC_grpDrawRect({:#x}, {}, {}, {}, {}, {:#x})", dst.0, x, y, w, h, pgc);
+
+    paint_shape(context, dst, pgc, |clip| raster::rect(x, y, w.max(0) as _, h.max(0) as _, clip)).await
 }
 
 pub async fn fill_rect(context: &mut dyn WIPICContext, dst_fb: WIPICMemoryId, x: i32, y: i32, w: i32, h: i32, p_gctx: WIPICWord) -> Result<()> {
     tracing::debug!("MC_grpFillRect({:#x}, {}, {}, {}, {}, {:#x})", dst_fb.0, x, y, w, h, p_gctx);
 
-    let framebuffer: WIPICFramebuffer = read_generic(context, context.data_ptr(dst_fb)?)?;
-    let gctx: WIPICGraphicsContext = read_generic(context, p_gctx)?;
-    let mut canvas = framebuffer.canvas(context)?;
+    paint_shape(context, dst_fb, p_gctx, |clip| {
+        raster::fill_rect(x, y, w.max(0) as _, h.max(0) as _, clip)
+    })
+    .await
+}
 
-    let clip = Clip {
-        x: x as _,
-        y: x as _,
-        width: w as _,
-        height: h as _,
-    };
+#[allow(clippy::too_many_arguments)]
+pub async fn draw_arc(
+    context: &mut dyn WIPICContext,
+    dst: WIPICMemoryId,
+    x: i32,
+    y: i32,
+    w: i32,
+    h: i32,
+    start_angle: i32,
+    arc_angle: i32,
+    pgc: WIPICWord,
+) -> Result<()> {
+    tracing::debug!(
+        "MC_grpDrawArc({:#x}, {}, {}, {}, {}, {}, {}, {:#x})",
+        dst.0,
+        x,
+        y,
+        w,
+        h,
+        start_angle,
+        arc_angle,
+        pgc
+    );
+
+    paint_shape(context, dst, pgc, |clip| {
+        raster::arc(x, y, w.max(0) as _, h.max(0) as _, start_angle, arc_angle, clip)
+    })
+    .await
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn fill_arc(
+    context: &mut dyn WIPICContext,
+    dst: WIPICMemoryId,
+    x: i32,
+    y: i32,
+    w: i32,
+    h: i32,
+    start_angle: i32,
+    arc_angle: i32,
+    pgc: WIPICWord,
+) -> Result<()> {
+    tracing::debug!(
+        "MC_grpFillArc({:#x}, {}, {}, {}, {}, {}, {}, {:#x})",
+        dst.0,
+        x,
+        y,
+        w,
+        h,
+        start_angle,
+        arc_angle,
+        pgc
+    );
+
+    paint_shape(context, dst, pgc, |clip| {
+        raster::fill_arc(x, y, w.max(0) as _, h.max(0) as _, start_angle, arc_angle, clip)
+    })
+    .await
+}
+
+pub async fn draw_polygon(
+    context: &mut dyn WIPICContext,
+    dst: WIPICMemoryId,
+    ptr_x_points: WIPICWord,
+    ptr_y_points: WIPICWord,
+    count: i32,
+    pgc: WIPICWord,
+) -> Result<()> {
+    tracing::debug!(
+        "MC_grpDrawPolygon({:#x}, {:#x}, {:#x}, {}, {:#x})",
+        dst.0,
+        ptr_x_points,
+        ptr_y_points,
+        count,
+        pgc
+    );
+
+    let points = read_points(context, ptr_x_points, ptr_y_points, count)?;
+
+    paint_shape(context, dst, pgc, |clip| raster::polygon(&points, clip)).await
+}
+
+pub async fn fill_polygon(
+    context: &mut dyn WIPICContext,
+    dst: WIPICMemoryId,
+    ptr_x_points: WIPICWord,
+    ptr_y_points: WIPICWord,
+    count: i32,
+    pgc: WIPICWord,
+) -> Result<()> {
+    tracing::debug!(
+        "MC_grpFillPolygon({:#x}, {:#x}, {:#x}, {}, {:#x})",
+        dst.0,
+        ptr_x_points,
+        ptr_y_points,
+        count,
+        pgc
+    );
+
+    let points = read_points(context, ptr_x_points, ptr_y_points, count)?;
+
+    paint_shape(context, dst, pgc, |clip| raster::fill_polygon(&points, clip)).await
+}
+
+pub async fn get_rgb_pixels(context: &mut dyn WIPICContext, src: WIPICMemoryId, x: i32, y: i32, w: i32, h: i32, ptr_buf: WIPICWord) -> Result<()> {
+    tracing::debug!("MC_grpGetRGBPixels({:#x}, {}, {}, {}, {}, {:#x})", src.0, x, y, w, h, ptr_buf);
+
+    if rgb_buffer_size(w, h).is_none() {
+        return Err(WieError::InvalidMemoryAccess(ptr_buf));
+    }
+
+    let framebuffer: WIPICFramebuffer = read_generic(context, context.data_ptr(src)?)?;
+    let image = framebuffer.image(context)?;
+
+    // pixels outside of framebuffer are zero
+    let (width, height) = (image.width() as i64, image.height() as i64);
+    let pixels = (y as i64..y as i64 + h as i64)
+        .flat_map(|y| (x as i64..x as i64 + w as i64).map(move |x| (x, y)))
+        .map(|(x, y)| {
+            if x < 0 || y < 0 || x >= width || y >= height {
+                0
+            } else {
+                Rgb8Pixel::from_color(image.get_pixel(x as _, y as _))
+            }
+        })
+        .collect::<Vec<_>>();
+
+    context.write_bytes(ptr_buf, cast_slice(&pixels))
+}
+
+pub async fn set_rgb_pixels(context: &mut dyn WIPICContext, dst: WIPICMemoryId, x: i32, y: i32, w: i32, h: i32, ptr_buf: WIPICWord) -> Result<()> {
+    tracing::debug!("MC_grpSetRGBPixels({:#x}, {}, {}, {}, {}, {:#x})", dst.0, x, y, w, h, ptr_buf);
+
+    if rgb_buffer_size(w, h).is_none() {
+        return Err(WieError::InvalidMemoryAccess(ptr_buf));
+    }
+
+    let framebuffer: WIPICFramebuffer = read_generic(context, context.data_ptr(dst)?)?;
+
+    // only rows and columns inside the framebuffer are read from the buffer
+    let (left, right) = (x.max(0), x.saturating_add(w).min(framebuffer.width as i32));
+    let (top, bottom) = (y.max(0), y.saturating_add(h).min(framebuffer.height as i32));
+    if left >= right || top >= bottom {
+        return Ok(());
+    }
+
+    let mut rows = Vec::with_capacity((bottom - top) as _);
+    for row_y in top..bottom {
+        // fits in u32, as whole buffer size does
+        let offset = ((row_y - y) as u32 * w as u32 + (left - x) as u32) * 4;
+        let address = ptr_buf.checked_add(offset).ok_or(WieError::InvalidMemoryAccess(ptr_buf))?;
+
+        let mut row = vec![0u32; (right - left) as _];
+        context.read_bytes(address, cast_slice_mut(&mut row))?;
+        rows.push(row);
+    }
+
+    let mut canvas = framebuffer.canvas(context)?;
+    for (row_y, row) in (top..bottom).zip(rows) {
+        for (x, pixel) in (left..right).zip(row) {
+            canvas.put_pixel(x, row_y, Rgb8Pixel::to_color(pixel));
+        }
+    }
 
-    canvas.fill_rect(x as _, y as _, w as _, h as _, Rgb8Pixel::to_color(gctx.fgpxl), clip);
     Ok(())
 }
 
+/// Byte size of `w` by `h` rgb pixel buffer, `None` if it doesn't fit in address space. Negative sizes are empty.
+fn rgb_buffer_size(w: i32, h: i32) -> Option<u32> {
+    (w.max(0) as u32).checked_mul(h.max(0) as u32)?.checked_mul(4)
+}
+
 pub async fn create_image(
     context: &mut dyn WIPICContext,
     ptr_image: WIPICWord,
@@ -128,15 +341,50 @@ pub async fn create_image(
 ) -> Result<WIPICWord> {
     tracing::debug!("MC_grpCreateImage({:#x}, {:#x}, {}, {})", ptr_image, image_data.0, offset, len);
 
-    let image = WIPICImage::new(context, image_data, offset, len)?;
+    let decoded = WIPICImage::decode(context, image_data, offset, len)?;
+    let image = WIPICImage::new(context, &*decoded, image_data, offset, len)?;
 
     let memory = context.alloc(size_of::<WIPICImage>() as WIPICWord)?;
     write_generic(context, ptr_image, memory)?;
     write_generic(context, context.data_ptr(memory)?, image)?;
 
+    if image.animated != 0 {
+        context.system().animations().insert(memory.0, decoded);
+    }
+
+    Ok(1) // MC_GRP_IMAGE_DONE
+}
+
+pub async fn destroy_image(context: &mut dyn WIPICContext, image: WIPICMemoryId) -> Result<()> {
+    tracing::debug!("MC_grpDestroyImage({:#x})", image.0);
+
+    let wipic_image: WIPICImage = read_generic(context, context.data_ptr(image)?)?;
+
+    // decoded framebuffer is allocated on creation, source image data is owned by caller and left as is
+    context.free(wipic_image.img.buf)?;
+    context.free(image)?;
+    context.system().animations().remove(image.0);
+
+    Ok(())
+}
+
+pub async fn decode_next_image(context: &mut dyn WIPICContext, image: WIPICMemoryId) -> Result<WIPICWord> {
+    tracing::debug!("MC_grpDecodeNextImage({:#x})", image.0);
+
+    let mut wipic_image: WIPICImage = read_generic(context, context.data_ptr(image)?)?;
+    wipic_image.decode_next_frame(context, image)?;
+    write_generic(context, context.data_ptr(image)?, wipic_image)?;
+
     Ok(1) // MC_GRP_IMAGE_DONE
 }
 
+pub async fn get_image_framebuffer(_context: &mut dyn WIPICContext, image: WIPICMemoryId) -> Result<WIPICMemoryId> {
+    tracing::debug!("MC_grpGetImageFrameBuffer({:#x})", image.0);
+
+    // image framebuffer is the first member of image, so image handle is also a framebuffer handle
+    Ok(image)
+}
+
 #[allow(clippy::too_many_arguments)]
 pub async fn draw_image(
     context: &mut dyn WIPICContext,
@@ -163,22 +411,14 @@ pub async fn draw_image(
         graphics_context
     );
 
-    let framebuffer: WIPICFramebuffer = read_generic(context, context.data_ptr(framebuffer)?)?;
+    let gctx = read_context(context, graphics_context)?;
     let image: WIPICImage = read_generic(context, context.data_ptr(image)?)?;
 
     let src_image = image.img.image(context)?;
-    let mut canvas = framebuffer.canvas(context)?;
-
-    let clip = Clip {
-        x: dx as _,
-        y: dy as _,
-        width: w as _,
-        height: h as _,
-    };
-
-    canvas.draw(dx as _, dy as _, w as _, h as _, &*src_image, sx as _, sy as _, clip);
+    let clip = shape_clip(context, framebuffer, &gctx)?;
+    let pixels = image_pixels(&*src_image, dx, dy, w, h, sx, sy, &gctx, clip);
 
-    Ok(())
+    paint(context, framebuffer, &gctx, pixels).await
 }
 
 pub async fn flush(
@@ -285,21 +525,14 @@ pub async fn copy_area(
         return Ok(());
     }
 
+    let gctx = read_context(context, pgc)?;
     let framebuffer: WIPICFramebuffer = read_generic(context, context.data_ptr(dst)?)?;
 
     let image = framebuffer.image(context)?;
-    let mut canvas = framebuffer.canvas(context)?;
-
-    let clip = Clip {
-        x: dx as _,
-        y: dy as _,
-        width: w as _,
-        height: h as _,
-    };
+    let clip = shape_clip(context, dst, &gctx)?;
+    let pixels = image_pixels(&*image, dx, dy, w, h, x, y, &gctx, clip);
 
-    canvas.draw(dx as _, dy as _, w as _, h as _, &*image, x as _, y as _, clip);
-
-    Ok(())
+    paint(context, dst, &gctx, pixels).await
 }
 
 pub async fn create_offscreen_framebuffer(context: &mut dyn WIPICContext, w: i32, h: i32) -> Result<WIPICMemoryId> {
@@ -347,48 +580,288 @@ pub async fn copy_frame_buffer(
         pgc
     );
 
+    let gctx = read_context(context, pgc)?;
     let src_framebuffer: WIPICFramebuffer = read_generic(context, context.data_ptr(src)?)?;
-    let dst_framebuffer: WIPICFramebuffer = read_generic(context, context.data_ptr(dst)?)?;
 
     let src_image = src_framebuffer.image(context)?;
-    let mut dst_canvas = dst_framebuffer.canvas(context)?;
+    let clip = shape_clip(context, dst, &gctx)?;
+    let pixels = image_pixels(&*src_image, dx, dy, w, h, sx, sy, &gctx, clip);
 
-    let clip = Clip {
-        x: dx as _,
-        y: dy as _,
-        width: w as _,
-        height: h as _,
-    };
+    paint(context, dst, &gctx, pixels).await
+}
+
+pub async fn get_font(_: &mut dyn WIPICContext, face: i32, size: i32, style: i32) -> Result<i32> {
+    tracing::debug!("MC_grpGetFont({}, {}, {})", face, size, style);
+
+    // face, size and style flags don't overlap, so we can pack them into font handle
+    Ok(FONT_HANDLE_TAG | (face & 0x60) | (size & 0x18) | (style & 0x07))
+}
+
+pub async fn get_font_height(context: &mut dyn WIPICContext, font: i32) -> Result<i32> {
+    tracing::debug!("MC_grpGetFontHeight({})", font);
+
+    Ok(font_from_handle(context, font).height() as _)
+}
+
+pub async fn get_font_ascent(context: &mut dyn WIPICContext, font: i32) -> Result<i32> {
+    tracing::debug!("MC_grpGetFontAscent({})", font);
+
+    Ok(font_from_handle(context, font).ascent() as _)
+}
+
+pub async fn get_font_descent(context: &mut dyn WIPICContext, font: i32) -> Result<i32> {
+    tracing::debug!("MC_grpGetFontDescent({})", font);
+
+    Ok(font_from_handle(context, font).descent() as _)
+}
+
+pub async fn get_string_width(context: &mut dyn WIPICContext, font: i32, ptr_string: WIPICWord, length: i32) -> Result<i32> {
+    tracing::debug!("MC_grpGetStringWidth({}, {:#x}, {})", font, ptr_string, length);
+
+    let string = read_string(context, ptr_string, length)?;
+
+    Ok(font_from_handle(context, font).string_width(&string) as _)
+}
+
+pub async fn get_unicode_string_width(context: &mut dyn WIPICContext, font: i32, ptr_string: WIPICWord, length: i32) -> Result<i32> {
+    tracing::debug!("MC_grpGetUnicodeStringWidth({}, {:#x}, {})", font, ptr_string, length);
+
+    let string = read_unicode_string(context, ptr_string, length)?;
+
+    Ok(font_from_handle(context, font).string_width(&string) as _)
+}
+
+pub async fn draw_string(
+    context: &mut dyn WIPICContext,
+    dst: WIPICMemoryId,
+    x: i32,
+    y: i32,
+    ptr_string: WIPICWord,
+    length: i32,
+    pgc: WIPICWord,
+) -> Result<()> {
+    tracing::debug!("MC_grpDrawString({:#x}, {}, {}, {:#x}, {}, {:#x})", dst.0, x, y, ptr_string, length, pgc);
+
+    let string = read_string(context, ptr_string, length)?;
+
+    draw_text(context, dst, x, y, &string, pgc)
+}
+
+pub async fn draw_unicode_string(
+    context: &mut dyn WIPICContext,
+    dst: WIPICMemoryId,
+    x: i32,
+    y: i32,
+    ptr_string: WIPICWord,
+    length: i32,
+    pgc: WIPICWord,
+) -> Result<()> {
+    tracing::debug!(
+        "MC_grpDrawUnicodeString({:#x}, {}, {}, {:#x}, {}, {:#x})",
+        dst.0,
+        x,
+        y,
+        ptr_string,
+        length,
+        pgc
+    );
+
+    let string = read_unicode_string(context, ptr_string, length)?;
+
+    draw_text(context, dst, x, y, &string, pgc)
+}
+
+fn draw_text(context: &mut dyn WIPICContext, dst: WIPICMemoryId, x: i32, y: i32, string: &str, pgc: WIPICWord) -> Result<()> {
+    let framebuffer: WIPICFramebuffer = read_generic(context, context.data_ptr(dst)?)?;
+    let gctx = read_context(context, pgc)?;
+    let clip = gctx.clip(framebuffer.width, framebuffer.height);
+    let (offset_x, offset_y) = gctx.offset();
+    let font = font_from_handle(context, gctx.font as _);
 
-    dst_canvas.draw(dx as _, dy as _, w as _, h as _, &*src_image, sx as _, sy as _, clip);
+    let mut canvas = framebuffer.canvas(context)?;
+    canvas.set_composite_op(gctx.composite_op());
+    canvas.draw_text(
+        string,
+        x.saturating_add(offset_x),
+        y.saturating_add(offset_y),
+        &font,
+        Rgb8Pixel::to_color(gctx.fgpxl),
+        TextAlignment::Left,
+        clip,
+    );
 
     Ok(())
 }
 
-pub async fn get_font(_: &mut dyn WIPICContext, face: i32, size: i32, style: i32) -> Result<i32> {
-    tracing::warn!("stub MC_grpGetFont({}, {}, {})", face, size, style);
+fn read_context(context: &mut dyn WIPICContext, pgc: WIPICWord) -> Result<WIPICGraphicsContext> {
+    if pgc == 0 {
+        return Ok(WIPICGraphicsContext::zeroed());
+    }
 
-    Ok(0)
+    read_generic(context, pgc)
 }
 
-pub async fn get_font_height(_: &mut dyn WIPICContext, font: i32) -> Result<i32> {
-    tracing::warn!("stub MC_grpGetFontHeight({})", font);
+fn read_points(context: &mut dyn WIPICContext, ptr_x_points: WIPICWord, ptr_y_points: WIPICWord, count: i32) -> Result<Vec<(i32, i32)>> {
+    let address = |ptr: WIPICWord, i: WIPICWord| {
+        i.checked_mul(4)
+            .and_then(|x| ptr.checked_add(x))
+            .ok_or(WieError::InvalidMemoryAccess(ptr))
+    };
+
+    (0..count.max(0) as WIPICWord)
+        .map(|i| {
+            let x: i32 = read_generic(context, address(ptr_x_points, i)?)?;
+            let y: i32 = read_generic(context, address(ptr_y_points, i)?)?;
 
-    Ok(10)
+            Ok((x, y))
+        })
+        .collect()
 }
 
-pub async fn get_string_width(_: &mut dyn WIPICContext, font: i32, ptr_string: WIPICWord, length: i32) -> Result<i32> {
-    tracing::warn!("stub MC_grpGetStringWidth({}, {:#x}, {})", font, ptr_string, length);
+// pixels of `src` at (`sx`, `sy`) copied to `w`x`h` area at (`dx`, `dy`), only where visible in `clip`
+#[allow(clippy::too_many_arguments)]
+fn image_pixels(
+    src: &dyn Image,
+    dx: i32,
+    dy: i32,
+    w: i32,
+    h: i32,
+    sx: i32,
+    sy: i32,
+    gctx: &WIPICGraphicsContext,
+    clip: Clip,
+) -> Vec<(i32, i32, Color)> {
+    let transparent = gctx.is_set(WIPICGraphicsContextIdx::TransPixelIdx).then_some(gctx.transpxl);
+    let (shift_x, shift_y) = (sx as i64 - dx as i64, sy as i64 - dy as i64);
+
+    raster::fill_rect(dx, dy, w.max(0) as _, h.max(0) as _, clip)
+        .into_iter()
+        .filter_map(|(x, y)| {
+            let (src_x, src_y) = (x as i64 + shift_x, y as i64 + shift_y);
+            if src_x < 0 || src_y < 0 || src_x >= src.width() as i64 || src_y >= src.height() as i64 {
+                return None;
+            }
+
+            let color = src.get_pixel(src_x as _, src_y as _);
+            if transparent == Some(Rgb8Pixel::from_color(color)) {
+                return None;
+            }
+
+            Some((x, y, color))
+        })
+        .collect()
+}
+
+// `rasterize` gets the visible area in shape coordinates, so only pixels which can be drawn are produced
+async fn paint_shape(
+    context: &mut dyn WIPICContext,
+    dst: WIPICMemoryId,
+    pgc: WIPICWord,
+    rasterize: impl FnOnce(Clip) -> Vec<(i32, i32)>,
+) -> Result<()> {
+    let gctx = read_context(context, pgc)?;
+    let color = Rgb8Pixel::to_color(gctx.fgpxl);
+    let pixels = rasterize(shape_clip(context, dst, &gctx)?);
+
+    paint(context, dst, &gctx, pixels.into_iter().map(|(x, y)| (x, y, color)).collect()).await
+}
 
-    Ok(10)
+// clip of graphics context, moved back by its offset
+fn shape_clip(context: &mut dyn WIPICContext, dst: WIPICMemoryId, gctx: &WIPICGraphicsContext) -> Result<Clip> {
+    let framebuffer: WIPICFramebuffer = read_generic(context, context.data_ptr(dst)?)?;
+    let (offset_x, offset_y) = gctx.offset();
+
+    Ok(gctx
+        .clip(framebuffer.width, framebuffer.height)
+        .translate(offset_x.saturating_neg(), offset_y.saturating_neg()))
 }
 
-pub async fn draw_string(_: &mut dyn WIPICContext, dst: WIPICMemoryId, x: i32, y: i32, string: WIPICWord, length: i32, pgc: WIPICWord) -> Result<()> {
-    tracing::warn!("stub MC_grpDrawString({:#x}, {}, {}, {:#x}, {}, {:#x})", dst.0, x, y, string, length, pgc);
+// writes pixels to framebuffer with offset, clip and pixel operation of graphics context applied
+async fn paint(context: &mut dyn WIPICContext, dst: WIPICMemoryId, gctx: &WIPICGraphicsContext, pixels: Vec<(i32, i32, Color)>) -> Result<()> {
+    let framebuffer: WIPICFramebuffer = read_generic(context, context.data_ptr(dst)?)?;
+    let clip = gctx.clip(framebuffer.width, framebuffer.height);
+    let (offset_x, offset_y) = gctx.offset();
+
+    let pixels = pixels
+        .into_iter()
+        .map(|(x, y, color)| (x.saturating_add(offset_x), y.saturating_add(offset_y), color))
+        .filter(|&(x, y, color)| clip.contains(x, y) && color.a != 0)
+        .collect::<Vec<_>>();
+    // user pixel operation is arm code, so we have to run it outside of canvas
+    let (pixels, composite_op) = if gctx.is_set(WIPICGraphicsContextIdx::PixelopIdx) {
+        let image = framebuffer.image(context)?;
+
+        let mut result = Vec::with_capacity(pixels.len());
+        for (x, y, color) in pixels {
+            let args = [Rgb8Pixel::from_color(color), Rgb8Pixel::from_color(image.get_pixel(x, y)), gctx.param1];
+            let pixel = context.call_function(gctx.pixel_op_func_ptr, &args).await?;
+
+            result.push((x, y, Rgb8Pixel::to_color(pixel)));
+        }
+
+        (result, CompositeOp::default())
+    } else {
+        (pixels, gctx.composite_op())
+    };
+
+    let mut canvas = framebuffer.canvas(context)?;
+    canvas.set_composite_op(composite_op);
+    for (x, y, color) in pixels {
+        canvas.draw_pixel(x, y, color);
+    }
 
     Ok(())
 }
 
+pub(crate) fn font_from_handle(context: &mut dyn WIPICContext, handle: i32) -> Font {
+    if handle & FONT_HANDLE_TAG == 0 {
+        // not obtained from MC_grpGetFont, use system font
+        return context.system().default_font();
+    }
+
+    let face = match handle & 0x60 {
+        0x20 => FontFace::Monospace,
+        0x40 => FontFace::Proportional,
+        _ => FontFace::System,
+    };
+    let size = match handle & 0x18 {
+        0x08 => FontSize::Small,
+        0x10 => FontSize::Large,
+        _ => FontSize::Medium,
+    };
+
+    context.system().font(face, size, FontStyle::from_bits_truncate((handle & 0x07) as _))
+}
+
+pub(crate) fn read_string(context: &mut dyn WIPICContext, ptr_string: WIPICWord, length: i32) -> Result<String> {
+    let bytes = if length < 0 {
+        read_null_terminated_string_bytes(context, ptr_string)?
+    } else {
+        let mut bytes = vec![0; length as usize];
+        context.read_bytes(ptr_string, &mut bytes)?;
+
+        bytes.into_iter().take_while(|&x| x != 0).collect()
+    };
+
+    Ok(context.system().charset().decode(&bytes))
+}
+
+fn read_unicode_string(context: &mut dyn WIPICContext, ptr_string: WIPICWord, length: i32) -> Result<String> {
+    let mut chars = Vec::new();
+    let mut cursor = ptr_string;
+    while length < 0 || chars.len() < length as usize {
+        let char: u16 = read_generic(context, cursor)?;
+        if char == 0 {
+            break;
+        }
+
+        chars.push(char);
+        cursor += 2;
+    }
+
+    Ok(String::from_utf16_lossy(&chars))
+}
+
 pub async fn repaint(context: &mut dyn WIPICContext, lcd: i32, x: i32, y: i32, width: i32, height: i32) -> Result<()> {
     tracing::warn!("stub MC_grpRepaint({}, {}, {}, {}, {})", lcd, x, y, width, height);
 
@@ -438,3 +911,87 @@ pub async fn get_framebuffer_height(context: &mut dyn WIPICContext, framebuffer:
 
     Ok(framebuffer.height as _)
 }
+
+#[cfg(test)]
+mod test {
+    use alloc::boxed::Box;
+
+    use wie_util::{read_generic, write_generic, Result};
+
+    use crate::{context::test::TestContext, method::MethodImpl, WIPICContext, WIPICWord};
+
+    use super::{create_offscreen_framebuffer, fill_rect, get_rgb_pixels, init_context, set_context, set_rgb_pixels, WIPICGraphicsContextIdx};
+
+    #[futures_test::test]
+    async fn test_fill_rect_context() -> Result<()> {
+        let mut context = TestContext::new();
+
+        let _ = context.alloc_raw(4)?; // avoid null graphics context
+        let pgc = context.alloc_raw(0x40)?;
+        let clip = context.alloc_raw(16)?;
+        let buf = context.alloc_raw(4 * 4 * 4)?;
+        let framebuffer = create_offscreen_framebuffer(&mut context, 4, 4).await?;
+
+        let set_context = set_context.into_body();
+        init_context(&mut context, pgc).await?;
+        write_generic(&mut context, clip, [1u32, 1, 2, 2])?;
+        set_context
+            .call(&mut context, Box::new([pgc, WIPICGraphicsContextIdx::ClipIdx as _, clip]))
+            .await?;
+        set_context
+            .call(&mut context, Box::new([pgc, WIPICGraphicsContextIdx::FgPixelIdx as _, 0xffffff]))
+            .await?;
+
+        let inside = |x: u32, y: u32| (1..=2).contains(&x) && (1..=2).contains(&y);
+
+        fill_rect(&mut context, framebuffer, 0, 0, 4, 4, pgc).await?;
+        get_rgb_pixels(&mut context, framebuffer, 0, 0, 4, 4, buf).await?;
+        for i in 0..16 {
+            let pixel: WIPICWord = read_generic(&context, buf + i * 4)?;
+            assert_eq!(pixel, if inside(i % 4, i / 4) { 0xffffff } else { 0 });
+        }
+
+        // xor with same color clears the pixels
+        set_context
+            .call(&mut context, Box::new([pgc, WIPICGraphicsContextIdx::XorModeIdx as _, 1]))
+            .await?;
+        fill_rect(&mut context, framebuffer, 0, 0, 4, 4, pgc).await?;
+        get_rgb_pixels(&mut context, framebuffer, 0, 0, 4, 4, buf).await?;
+        for i in 0..16 {
+            let pixel: WIPICWord = read_generic(&context, buf + i * 4)?;
+            assert_eq!(pixel, 0);
+        }
+
+        Ok(())
+    }
+
+    #[futures_test::test]
+    async fn test_set_rgb_pixels() -> Result<()> {
+        let mut context = TestContext::new();
+
+        let _ = context.alloc_raw(4)?;
+        let src = context.alloc_raw(3 * 3 * 4)?;
+        let buf = context.alloc_raw(4 * 4 * 4)?;
+        let framebuffer = create_offscreen_framebuffer(&mut context, 4, 4).await?;
+
+        let colors: [WIPICWord; 9] = [0xff0000, 0x00ff00, 0x0000ff, 0xffffff, 0xff0000, 0x00ff00, 0x0000ff, 0xffffff, 0xff0000];
+        for (i, color) in colors.iter().enumerate() {
+            write_generic(&mut context, src + i as WIPICWord * 4, *color)?;
+        }
+
+        // only top left 2x2 of the buffer is inside the framebuffer
+        set_rgb_pixels(&mut context, framebuffer, 2, 2, 3, 3, src).await?;
+        get_rgb_pixels(&mut context, framebuffer, 0, 0, 4, 4, buf).await?;
+        for i in 0..16 {
+            let (x, y) = (i % 4, i / 4);
+            let pixel: WIPICWord = read_generic(&context, buf + i * 4)?;
+            let expected = if x >= 2 && y >= 2 { colors[((y - 2) * 3 + x - 2) as usize] } else { 0 };
+            assert_eq!(pixel, expected);
+        }
+
+        assert!(set_rgb_pixels(&mut context, framebuffer, 0, 0, 0x10000, 0x10000, src).await.is_err());
+        set_rgb_pixels(&mut context, framebuffer, 0, 0, -1, 4, src).await?;
+
+        Ok(())
+    }
+}
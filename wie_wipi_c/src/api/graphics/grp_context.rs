@@ -2,6 +2,8 @@ use core::mem;
 
 use bytemuck::{Pod, Zeroable};
 
+use wie_backend::canvas::{Clip, CompositeOp};
+
 use crate::{method::ParamConverter, WIPICContext, WIPICWord};
 
 /// _MC_GrpContext
@@ -24,8 +26,71 @@ pub struct WIPICGraphicsContext {
     pub style: WIPICWord,
 }
 
+impl WIPICGraphicsContext {
+    pub fn is_set(&self, idx: WIPICGraphicsContextIdx) -> bool {
+        self.mask & (1 << idx as u32) != 0
+    }
+
+    pub fn set(&mut self, idx: WIPICGraphicsContextIdx, value: bool) {
+        if value {
+            self.mask |= 1 << idx as u32;
+        } else {
+            self.mask &= !(1 << idx as u32);
+        }
+    }
+
+    /// Clip area limited to `width`x`height` framebuffer. Whole framebuffer if clip is not set.
+    pub fn clip(&self, width: u32, height: u32) -> Clip {
+        let framebuffer = Clip { x: 0, y: 0, width, height };
+        if !self.is_set(WIPICGraphicsContextIdx::ClipIdx) {
+            return framebuffer;
+        }
+
+        let [x1, y1, x2, y2] = self.clip.map(|x| x as i32);
+        if x2 < x1 || y2 < y1 {
+            return Clip {
+                x: 0,
+                y: 0,
+                width: 0,
+                height: 0,
+            };
+        }
+
+        // bottom-right corner is inclusive
+        let clip = Clip {
+            x: x1,
+            y: y1,
+            width: x2.abs_diff(x1).saturating_add(1),
+            height: y2.abs_diff(y1).saturating_add(1),
+        };
+
+        clip.intersect(&framebuffer)
+    }
+
+    /// Composite operation for alpha and xor mode. User pixel operation should be handled by caller.
+    pub fn composite_op(&self) -> CompositeOp {
+        if self.is_set(WIPICGraphicsContextIdx::XorModeIdx) {
+            CompositeOp::Xor
+        } else if self.is_set(WIPICGraphicsContextIdx::AlphaIdx) {
+            CompositeOp::SourceOver {
+                alpha: self.alpha.min(0xff) as _,
+            }
+        } else {
+            CompositeOp::default()
+        }
+    }
+
+    pub fn offset(&self) -> (i32, i32) {
+        if self.is_set(WIPICGraphicsContextIdx::OffsetIdx) {
+            (self.offset[0] as _, self.offset[1] as _)
+        } else {
+            (0, 0)
+        }
+    }
+}
+
 #[repr(u32)]
-#[derive(Debug)]
+#[derive(Clone, Copy, Debug)]
 #[allow(dead_code)]
 pub enum WIPICGraphicsContextIdx {
     ClipIdx = 0,
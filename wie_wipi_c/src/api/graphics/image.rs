@@ -1,8 +1,8 @@
-use alloc::vec;
+use alloc::{boxed::Box, vec};
 
 use bytemuck::{Pod, Zeroable};
 
-use wie_backend::canvas::decode_image;
+use wie_backend::canvas::{decode_image, Image};
 use wie_util::Result;
 
 use crate::{context::WIPICContext, WIPICMemoryId, WIPICWord};
@@ -24,26 +24,57 @@ pub struct WIPICImage {
 }
 
 impl WIPICImage {
-    pub fn new(context: &mut dyn WIPICContext, buf: WIPICMemoryId, offset: WIPICWord, len: WIPICWord) -> Result<Self> {
-        let ptr_image_data = context.data_ptr(buf)?;
-
-        let mut data = vec![0; len as _];
-        context.read_bytes(ptr_image_data + offset, &mut data)?;
-        let image = decode_image(&data)?;
-
-        let img_framebuffer = WIPICFramebuffer::from_image(context, &*image)?;
+    pub fn new(context: &mut dyn WIPICContext, image: &dyn Image, buf: WIPICMemoryId, offset: WIPICWord, len: WIPICWord) -> Result<Self> {
+        let img_framebuffer = WIPICFramebuffer::from_image(context, image)?;
         let mask_framebuffer = WIPICFramebuffer::empty();
 
+        let frames = image.frames();
+
         Ok(Self {
             img: img_framebuffer,
             mask: mask_framebuffer,
-            loop_count: 0,
-            delay: 0,
-            animated: 0,
+            loop_count: image.loop_count(),
+            delay: frames.first().map(|x| x.delay).unwrap_or(0),
+            animated: !frames.is_empty() as _,
             buf,
             offset,
             current: 0,
             len,
         })
     }
+
+    // frames are decoded on creation and kept in system animations under image handle
+    pub fn decode_next_frame(&mut self, context: &mut dyn WIPICContext, handle: WIPICMemoryId) -> Result<()> {
+        if self.animated == 0 {
+            return Ok(());
+        }
+
+        let (current, raw, delay) = {
+            let animations = context.system().animations();
+            let frame_count = animations.frame_count(handle.0);
+            if frame_count == 0 {
+                return Ok(());
+            }
+
+            let current = (self.current as usize + 1) % frame_count;
+            let frame = animations.frame(handle.0, current).unwrap();
+
+            (current, frame.image.raw().to_vec(), frame.delay)
+        };
+
+        self.img.write(context, &raw)?;
+        self.current = current as _;
+        self.delay = delay;
+
+        Ok(())
+    }
+
+    pub fn decode(context: &mut dyn WIPICContext, buf: WIPICMemoryId, offset: WIPICWord, len: WIPICWord) -> Result<Box<dyn Image>> {
+        let ptr_image_data = context.data_ptr(buf)?;
+
+        let mut data = vec![0; len as _];
+        context.read_bytes(ptr_image_data + offset, &mut data)?;
+
+        decode_image(&data)
+    }
 }
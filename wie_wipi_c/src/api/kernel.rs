@@ -1,9 +1,9 @@
-use alloc::{boxed::Box, format, str, string::String, vec::Vec};
+use alloc::{boxed::Box, format, string::String, vec, vec::Vec};
 use core::{iter, mem::size_of};
 
 use bytemuck::{Pod, Zeroable};
 
-use wie_backend::Instant;
+use wie_backend::{Instant, SharedBuffer};
 use wie_util::{read_generic, read_null_terminated_string_bytes, write_generic, write_null_terminated_string_bytes, Result, WieError};
 
 use crate::{context::WIPICContext, method::MethodBody, WIPICMemoryId, WIPICResult, WIPICWord};
@@ -27,6 +27,10 @@ struct ResourceHandle {
     name: [u8; 32], // TODO hardcoded max size
 }
 
+// we only run a single program, launched by the handset
+const PROGRAM_ID: i32 = 1;
+const PARENT_PROGRAM_ID: i32 = 0;
+
 pub async fn current_time(context: &mut dyn WIPICContext) -> Result<u64> {
     tracing::debug!("MC_knlCurrentTime()");
 
@@ -48,6 +52,9 @@ pub async fn set_system_property(_context: &mut dyn WIPICContext, ptr_id: WIPICW
 pub async fn def_timer(context: &mut dyn WIPICContext, ptr_timer: WIPICWord, fn_callback: WIPICWord) -> Result<()> {
     tracing::debug!("MC_knlDefTimer({:#x}, {:#x})", ptr_timer, fn_callback);
 
+    // redefining a running timer stops it, as its task would be lost otherwise
+    context.system().abort_timer(ptr_timer as _);
+
     let timer = WIPICTimer {
         unk1: 0,
         unk2: 0,
@@ -82,10 +89,13 @@ pub async fn set_timer(
     impl MethodBody<WieError> for TimerCallback {
         #[tracing::instrument(name = "timer", skip_all)]
         async fn call(&self, context: &mut dyn WIPICContext, _: Box<[WIPICWord]>) -> Result<WIPICResult> {
-            let timer: WIPICTimer = read_generic(context, self.ptr_timer)?;
-
             context.system().sleep(self.wakeup).await;
 
+            // callback may set the timer again, so it should not see this task as running
+            context.system().release_timer(self.ptr_timer as _);
+
+            let timer: WIPICTimer = read_generic(context, self.ptr_timer)?;
+
             context.call_function(timer.fn_callback, &[self.ptr_timer, self.param]).await?;
 
             Ok(WIPICResult { results: Vec::new() })
@@ -94,13 +104,22 @@ pub async fn set_timer(
 
     let wakeup = context.system().platform().now() + (((timeout_high as u64) << 32) | (timeout_low as u64)) as _;
 
-    context.spawn(Box::new(TimerCallback { ptr_timer, wakeup, param }))?;
+    // setting running timer again restarts it
+    let task_id = context.spawn(Box::new(TimerCallback { ptr_timer, wakeup, param }))?;
+    context.system().set_timer(ptr_timer as _, task_id);
+
+    let mut timer: WIPICTimer = read_generic(context, ptr_timer)?;
+    timer.time = wakeup.raw();
+    timer.param = param;
+    write_generic(context, ptr_timer, timer)?;
 
     Ok(())
 }
 
-pub async fn unset_timer(_: &mut dyn WIPICContext, a0: WIPICWord) -> Result<()> {
-    tracing::warn!("stub MC_knlUnsetTimer({:#x})", a0);
+pub async fn unset_timer(context: &mut dyn WIPICContext, ptr_timer: WIPICWord) -> Result<()> {
+    tracing::debug!("MC_knlUnsetTimer({:#x})", ptr_timer);
+
+    context.system().abort_timer(ptr_timer as _);
 
     Ok(())
 }
@@ -133,7 +152,8 @@ pub async fn free(context: &mut dyn WIPICContext, memory: WIPICMemoryId) -> Resu
 pub async fn get_resource_id(context: &mut dyn WIPICContext, ptr_name: WIPICWord, ptr_size: WIPICWord) -> Result<i32> {
     tracing::debug!("MC_knlGetResourceID({:#x}, {:#x})", ptr_name, ptr_size);
 
-    let name = String::from_utf8(read_null_terminated_string_bytes(context, ptr_name)?).unwrap();
+    let name_bytes = read_null_terminated_string_bytes(context, ptr_name)?;
+    let name = context.system().charset().decode(&name_bytes);
 
     let size = context.get_resource_size(&name).await?;
 
@@ -144,9 +164,8 @@ pub async fn get_resource_id(context: &mut dyn WIPICContext, ptr_name: WIPICWord
     let size = size.unwrap();
 
     // TODO it leaks handle every time.. should we assign id for every file?
-    let name_bytes = name.as_bytes();
     let mut handle = ResourceHandle { name: [0; 32] };
-    handle.name[..name_bytes.len()].copy_from_slice(name_bytes);
+    handle.name[..name_bytes.len()].copy_from_slice(&name_bytes);
 
     let ptr_handle = context.alloc_raw(size_of::<ResourceHandle>() as _)?;
     write_generic(context, ptr_handle, handle)?;
@@ -160,9 +179,9 @@ pub async fn get_resource(context: &mut dyn WIPICContext, id: WIPICWord, buf: WI
 
     let handle: ResourceHandle = read_generic(context, id)?;
     let name_length = handle.name.iter().position(|&c| c == 0).unwrap_or(handle.name.len());
-    let name = str::from_utf8(&handle.name[..name_length]).unwrap();
+    let name = context.system().charset().decode(&handle.name[..name_length]);
 
-    let data = context.read_resource(name).await?;
+    let data = context.read_resource(&name).await?;
 
     if data.len() as u32 > buf_size {
         return Ok(-1);
@@ -177,7 +196,7 @@ pub async fn printk(context: &mut dyn WIPICContext, ptr_format: WIPICWord, a0: W
     tracing::warn!("stub MC_knlPrintk({:#x}, {:#x}, {:#x}, {:#x}, {:#x})", ptr_format, a0, a1, a2, a3);
 
     let format_string = read_null_terminated_string_bytes(context, ptr_format)?;
-    let format_string = encoding_rs::EUC_KR.decode(&format_string).0;
+    let format_string = context.system().charset().decode(&format_string);
 
     let result = sprintf(context, &format_string, &[a0, a1, a2, a3])?;
 
@@ -206,15 +225,15 @@ pub async fn sprintk(
     );
 
     let format_string = read_null_terminated_string_bytes(context, ptr_format)?;
-    let format_string = encoding_rs::EUC_KR.decode(&format_string).0;
+    let format_string = context.system().charset().decode(&format_string);
 
     let result = sprintf(context, &format_string, &[a0, a1, a2, a3])?;
 
-    let result_bytes = encoding_rs::EUC_KR.encode(&result).0;
+    let result_bytes = context.system().charset().encode(&result);
 
     write_null_terminated_string_bytes(context, dest, &result_bytes)?;
 
-    Ok(result.len() as _)
+    Ok(result_bytes.len() as _)
 }
 
 pub async fn get_total_memory(_context: &mut dyn WIPICContext) -> Result<i32> {
@@ -262,7 +281,7 @@ fn sprintf(context: &mut dyn WIPICContext, format: &str, args: &[u32]) -> Result
                         }
 
                         let str = read_null_terminated_string_bytes(context, ptr)?;
-                        let str = encoding_rs::EUC_KR.decode(&str).0;
+                        let str = context.system().charset().decode(&str);
 
                         result += &str;
                         break;
@@ -296,10 +315,162 @@ fn sprintf(context: &mut dyn WIPICContext, format: &str, args: &[u32]) -> Result
     Ok(result)
 }
 
-pub async fn get_cur_program_id(_context: &mut dyn WIPICContext) -> Result<WIPICWord> {
-    tracing::warn!("stub MC_knlGetCurProgramID()");
+pub async fn get_cur_program_id(_context: &mut dyn WIPICContext) -> Result<i32> {
+    tracing::debug!("MC_knlGetCurProgramID()");
+
+    Ok(PROGRAM_ID)
+}
+
+pub async fn get_parent_program_id(_context: &mut dyn WIPICContext) -> Result<i32> {
+    tracing::debug!("MC_knlGetParentProgramID()");
+
+    Ok(PARENT_PROGRAM_ID)
+}
+
+pub async fn execute(context: &mut dyn WIPICContext, ptr_name: WIPICWord, ptr_args: WIPICWord) -> Result<i32> {
+    let name = context.system().charset().decode(&read_null_terminated_string_bytes(context, ptr_name)?);
+
+    tracing::warn!(
+        "MC_knlExecute({:#x}: {}, {:#x}): other programs are not installed",
+        ptr_name,
+        name,
+        ptr_args
+    );
+
+    Ok(-12) // M_E_NOENT
+}
+
+pub async fn load(context: &mut dyn WIPICContext, ptr_name: WIPICWord, ptr_args: WIPICWord) -> Result<i32> {
+    let name = context.system().charset().decode(&read_null_terminated_string_bytes(context, ptr_name)?);
+
+    tracing::warn!("MC_knlLoad({:#x}: {}, {:#x}): other programs are not installed", ptr_name, name, ptr_args);
+
+    Ok(-12) // M_E_NOENT
+}
+
+pub async fn exit(context: &mut dyn WIPICContext, code: i32) -> Result<()> {
+    tracing::debug!("MC_knlExit({})", code);
+
+    context.system().exit();
+
+    Ok(())
+}
+
+pub async fn program_stop(context: &mut dyn WIPICContext, id: i32) -> Result<i32> {
+    tracing::debug!("MC_knlProgramStop({})", id);
+
+    if id != PROGRAM_ID {
+        return Ok(-9); // M_E_INVALID
+    }
+
+    context.system().exit();
+
+    Ok(0)
+}
+
+pub async fn get_program_name(context: &mut dyn WIPICContext, id: i32, ptr_buf: WIPICWord, buf_size: i32) -> Result<i32> {
+    tracing::debug!("MC_knlGetProgramName({}, {:#x}, {})", id, ptr_buf, buf_size);
+
+    if id != PROGRAM_ID {
+        return Ok(-9); // M_E_INVALID
+    }
+
+    let name_bytes = context.system().charset().encode(&context.system().app_name());
+    if name_bytes.len() as i32 >= buf_size {
+        return Ok(-18); // M_E_SHORTBUF
+    }
+
+    write_null_terminated_string_bytes(context, ptr_buf, &name_bytes)?;
+
+    Ok(name_bytes.len() as _)
+}
+
+pub async fn create_shared_buf(context: &mut dyn WIPICContext, ptr_name: WIPICWord, size: i32) -> Result<i32> {
+    tracing::debug!("MC_knlCreateSharedBuf({:#x}, {})", ptr_name, size);
+
+    let name = context.system().charset().decode(&read_null_terminated_string_bytes(context, ptr_name)?);
+    if size <= 0 {
+        return Ok(-9); // M_E_INVALID
+    }
+    if context.system().shared_buffers().get(&name).is_some() {
+        return Ok(-1); // M_E_ERROR
+    }
+
+    let memory = context.alloc(size as _)?;
+    let zero = iter::repeat(0).take(size as usize).collect::<Vec<_>>();
+    context.write_bytes(context.data_ptr(memory)?, &zero)?;
+
+    let buffer = SharedBuffer {
+        handle: memory.0,
+        size: size as _,
+    };
+    context.system().shared_buffers().create(&name, buffer);
+
+    Ok(memory.0 as _)
+}
+
+pub async fn destroy_shared_buf(context: &mut dyn WIPICContext, handle: WIPICWord) -> Result<i32> {
+    tracing::debug!("MC_knlDestroySharedBuf({:#x})", handle);
+
+    let buffer = context.system().shared_buffers().destroy(handle);
+    if buffer.is_none() {
+        return Ok(-25); // M_E_INVALIDHANDLE
+    }
+
+    context.free(WIPICMemoryId(handle))?;
+
+    Ok(0)
+}
+
+pub async fn get_shared_buf(context: &mut dyn WIPICContext, ptr_name: WIPICWord) -> Result<i32> {
+    tracing::debug!("MC_knlGetSharedBuf({:#x})", ptr_name);
+
+    let name = context.system().charset().decode(&read_null_terminated_string_bytes(context, ptr_name)?);
+
+    let buffer = context.system().shared_buffers().get(&name);
+    match buffer {
+        Some(x) => Ok(x.handle as _),
+        None => Ok(-12), // M_E_NOENT
+    }
+}
+
+pub async fn get_shared_buf_size(context: &mut dyn WIPICContext, handle: WIPICWord) -> Result<i32> {
+    tracing::debug!("MC_knlGetSharedBufSize({:#x})", handle);
+
+    let buffer = context.system().shared_buffers().find(handle);
+    match buffer {
+        Some(x) => Ok(x.size as _),
+        None => Ok(-25), // M_E_INVALIDHANDLE
+    }
+}
+
+/// Returns new handle of the buffer, as contents may be moved like realloc.
+pub async fn resize_shared_buf(context: &mut dyn WIPICContext, handle: WIPICWord, size: i32) -> Result<i32> {
+    tracing::debug!("MC_knlResizeSharedBuf({:#x}, {})", handle, size);
+
+    let buffer = context.system().shared_buffers().find(handle);
+    let Some(buffer) = buffer else {
+        return Ok(-25); // M_E_INVALIDHANDLE
+    };
+    if size <= 0 {
+        return Ok(-9); // M_E_INVALID
+    }
+
+    let mut data = vec![0; buffer.size as usize];
+    context.read_bytes(context.data_ptr(WIPICMemoryId(handle))?, &mut data)?;
+    data.resize(size as _, 0);
+
+    let memory = context.alloc(size as _)?;
+    context.write_bytes(context.data_ptr(memory)?, &data)?;
+    context.free(WIPICMemoryId(handle))?;
+
+    let resized = SharedBuffer {
+        handle: memory.0,
+        size: size as _,
+    };
+    context.system().shared_buffers().update(handle, resized);
 
-    Ok(1)
+    Ok(memory.0 as _)
 }
 #[cfg(test)]
 mod test {
@@ -1,10 +1,17 @@
-use alloc::vec;
+use alloc::{boxed::Box, string::String, vec, vec::Vec};
 
 use bytemuck::{Pod, Zeroable};
 
-use wie_util::{read_generic, write_generic, Result};
+use wie_backend::{AudioEvent, AudioHandle};
+use wie_util::{read_generic, read_null_terminated_string_bytes, write_generic, Result, WieError};
 
-use crate::{context::WIPICContext, WIPICWord};
+use crate::{context::WIPICContext, method::MethodBody, WIPICResult, WIPICWord};
+
+// status passed to clip callback
+const MDA_STATUS_STOPPED: WIPICWord = 2;
+const MDA_STATUS_END_OF_MEDIA: WIPICWord = 3;
+
+const CLIP_TYPE_LENGTH: usize = 32;
 
 #[repr(C)]
 #[derive(Clone, Copy, Pod, Zeroable)]
@@ -73,19 +80,52 @@ struct MdaClip {
 
     // not in sdk, for internal usage
     handle: u32,
+    callback: WIPICWord,
+    content_type: [u8; CLIP_TYPE_LENGTH], // null terminated type given on creation
+}
+
+impl MdaClip {
+    fn content_type(&self) -> Option<String> {
+        let length = self.content_type.iter().position(|&x| x == 0).unwrap_or(CLIP_TYPE_LENGTH);
+        if length == 0 {
+            return None;
+        }
+
+        Some(String::from_utf8_lossy(&self.content_type[..length]).into_owned())
+    }
 }
 
 pub async fn clip_create(context: &mut dyn WIPICContext, ptr_type: WIPICWord, buf_size: WIPICWord, callback: WIPICWord) -> Result<WIPICWord> {
     tracing::debug!("MC_mdaClipCreate({:#x}, {:#x}, {:#x})", ptr_type, buf_size, callback);
 
-    let clip = context.alloc_raw(size_of::<MdaClip>() as u32)?;
+    let ptr_clip = context.alloc_raw(size_of::<MdaClip>() as u32)?;
 
-    Ok(clip)
+    // type is kept to load data put later, last byte is left as terminator
+    let mut content_type = [0; CLIP_TYPE_LENGTH];
+    if ptr_type != 0 {
+        let type_bytes = read_null_terminated_string_bytes(context, ptr_type)?;
+        let length = type_bytes.len().min(CLIP_TYPE_LENGTH - 1);
+        content_type[..length].copy_from_slice(&type_bytes[..length]);
+    }
+
+    let clip = MdaClip {
+        callback,
+        content_type,
+        ..MdaClip::zeroed()
+    };
+    write_generic(context, ptr_clip, clip)?;
+
+    Ok(ptr_clip)
 }
 
 pub async fn clip_free(context: &mut dyn WIPICContext, clip: WIPICWord) -> Result<WIPICWord> {
     tracing::debug!("MC_mdaClipFree({:#x})", clip);
 
+    let clip_data: MdaClip = read_generic(context, clip)?;
+    if clip_data.handle != 0 {
+        let _ = context.system().audio().close(clip_data.handle);
+    }
+
     context.free_raw(clip, size_of::<MdaClip>() as u32)?;
 
     Ok(0)
@@ -125,7 +165,9 @@ pub async fn clip_put_data(context: &mut dyn WIPICContext, ptr_clip: WIPICWord,
     let mut data = vec![0; buf_size as _];
     context.read_bytes(buf, &mut data)?;
 
-    let handle = context.system().audio().load_smaf(&data);
+    let mut clip: MdaClip = read_generic(context, ptr_clip)?;
+
+    let handle = context.system().audio().load(&data, clip.content_type().as_deref());
     if let Err(x) = handle {
         tracing::error!("Failed to load audio: {:?}", x);
         return Ok(0);
@@ -133,7 +175,9 @@ pub async fn clip_put_data(context: &mut dyn WIPICContext, ptr_clip: WIPICWord,
 
     let handle = handle.unwrap();
 
-    let mut clip: MdaClip = read_generic(context, ptr_clip)?;
+    if clip.handle != 0 {
+        let _ = context.system().audio().close(clip.handle);
+    }
     clip.handle = handle;
     write_generic(context, ptr_clip, clip)?;
 
@@ -146,22 +190,31 @@ pub async fn clip_get_data(_context: &mut dyn WIPICContext, clip: WIPICWord, buf
     Ok(0)
 }
 
-pub async fn clip_set_position(_context: &mut dyn WIPICContext, clip: WIPICWord, ms: WIPICWord) -> Result<WIPICWord> {
-    tracing::warn!("stub MC_mdaClipSetPosition({:#x}, {:#x})", clip, ms);
+pub async fn clip_set_position(context: &mut dyn WIPICContext, clip: WIPICWord, ms: WIPICWord) -> Result<WIPICWord> {
+    tracing::debug!("MC_mdaClipSetPosition({:#x}, {:#x})", clip, ms);
 
-    Ok(0)
+    let clip: MdaClip = read_generic(context, clip)?;
+    let result = context.system().audio().set_position(clip.handle, ms as _);
+
+    Ok(if result.is_ok() { 0 } else { -1i32 as _ })
 }
 
-pub async fn clip_get_volume(_context: &mut dyn WIPICContext, clip: WIPICWord) -> Result<WIPICWord> {
-    tracing::warn!("stub MC_mdaClipGetVolume({:#x})", clip);
+pub async fn clip_get_volume(context: &mut dyn WIPICContext, clip: WIPICWord) -> Result<WIPICWord> {
+    tracing::debug!("MC_mdaClipGetVolume({:#x})", clip);
 
-    Ok(0)
+    let clip: MdaClip = read_generic(context, clip)?;
+    let volume = context.system().audio().volume(clip.handle);
+
+    Ok(volume.map(|x| x as _).unwrap_or(-1i32 as _))
 }
 
-pub async fn clip_set_volume(_context: &mut dyn WIPICContext, clip: WIPICWord, volume: WIPICWord) -> Result<WIPICWord> {
-    tracing::warn!("stub MC_mdaClipSetVolume({:#x}, {:#x})", clip, volume);
+pub async fn clip_set_volume(context: &mut dyn WIPICContext, clip: WIPICWord, volume: WIPICWord) -> Result<WIPICWord> {
+    tracing::debug!("MC_mdaClipSetVolume({:#x}, {})", clip, volume);
 
-    Ok(0)
+    let clip: MdaClip = read_generic(context, clip)?;
+    let result = context.system().audio().set_volume(clip.handle, volume.min(100) as _);
+
+    Ok(if result.is_ok() { 0 } else { -1i32 as _ })
 }
 
 pub async fn get_volume(_context: &mut dyn WIPICContext) -> Result<WIPICWord> {
@@ -170,18 +223,65 @@ pub async fn get_volume(_context: &mut dyn WIPICContext) -> Result<WIPICWord> {
     Ok(0)
 }
 
-pub async fn play(context: &mut dyn WIPICContext, ptr_clip: WIPICWord, repeat: WIPICWord) -> Result<()> {
+pub async fn play(context: &mut dyn WIPICContext, ptr_clip: WIPICWord, repeat: WIPICWord) -> Result<i32> {
     tracing::debug!("MC_mdaPlay({:#x}, {})", ptr_clip, repeat);
 
     let clip: MdaClip = read_generic(context, ptr_clip)?;
 
-    let result = context.system().audio().play(clip.handle);
+    let loop_count = if repeat != 0 { -1 } else { 1 };
+    let result = context.system().audio().play(clip.handle, loop_count);
 
     if let Err(x) = result {
-        tracing::error!("Failed to load audio: {:?}", x);
+        tracing::error!("Failed to play audio: {:?}", x);
+        return Ok(-1); // M_E_ERROR
+    }
+
+    if clip.callback != 0 {
+        struct ClipCallback {
+            ptr_clip: WIPICWord,
+            callback: WIPICWord,
+            handle: AudioHandle,
+            serial: u64,
+        }
+
+        #[async_trait::async_trait]
+        impl MethodBody<WieError> for ClipCallback {
+            #[tracing::instrument(name = "clip", skip_all)]
+            async fn call(&self, context: &mut dyn WIPICContext, _: Box<[WIPICWord]>) -> Result<WIPICResult> {
+                let mut serial = self.serial;
+
+                while let Some(event) = context.system().wait_audio_event(self.handle, &mut serial).await {
+                    let status = match event {
+                        AudioEvent::EndOfMedia => MDA_STATUS_END_OF_MEDIA,
+                        AudioEvent::Stopped => MDA_STATUS_STOPPED,
+                        _ => continue,
+                    };
+
+                    context.call_function(self.callback, &[self.ptr_clip, status]).await?;
+                    break;
+                }
+
+                Ok(WIPICResult { results: Vec::new() })
+            }
+        }
+
+        let serial = match context.system().audio().last_event_serial(clip.handle) {
+            Ok(x) => x,
+            Err(x) => {
+                tracing::error!("Failed to get audio event serial: {:?}", x);
+                return Ok(-25); // M_E_INVALIDHANDLE
+            }
+        };
+
+        context.spawn(Box::new(ClipCallback {
+            ptr_clip,
+            callback: clip.callback,
+            handle: clip.handle,
+            serial,
+        }))?;
     }
 
-    Ok(())
+    Ok(0)
 }
 
 pub async fn clip_alloc_player(_context: &mut dyn WIPICContext, clip: WIPICWord, param: WIPICWord) -> Result<WIPICWord> {
@@ -196,8 +296,10 @@ pub async fn clip_free_player(_context: &mut dyn WIPICContext, clip: WIPICWord)
     Ok(0)
 }
 
-pub async fn vibrator(_context: &mut dyn WIPICContext, level: i32, timeout: i32) -> Result<WIPICWord> {
-    tracing::warn!("stub MC_mdaVibrator({}, {})", level, timeout);
+pub async fn vibrator(context: &mut dyn WIPICContext, level: i32, timeout: i32) -> Result<WIPICWord> {
+    tracing::debug!("MC_mdaVibrator({}, {})", level, timeout);
+
+    context.system().device().vibrate(level, timeout.max(0) as _);
 
     Ok(0)
 }
@@ -208,22 +310,34 @@ pub async fn set_mute_state(_context: &mut dyn WIPICContext, source: i32, b_mute
     Ok(0)
 }
 
-pub async fn pause(_context: &mut dyn WIPICContext, clip: WIPICWord) -> Result<WIPICWord> {
-    tracing::warn!("stub MC_mdaPause({:#x})", clip);
+pub async fn pause(context: &mut dyn WIPICContext, clip: WIPICWord) -> Result<WIPICWord> {
+    tracing::debug!("MC_mdaPause({:#x})", clip);
 
-    Ok(0)
+    let clip: MdaClip = read_generic(context, clip)?;
+    let result = context.system().audio().pause(clip.handle);
+
+    Ok(if result.is_ok() { 0 } else { -1i32 as _ })
 }
 
-pub async fn resume(_context: &mut dyn WIPICContext, clip: WIPICWord) -> Result<WIPICWord> {
-    tracing::warn!("stub MC_mdaResume({:#x})", clip);
+pub async fn resume(context: &mut dyn WIPICContext, clip: WIPICWord) -> Result<WIPICWord> {
+    tracing::debug!("MC_mdaResume({:#x})", clip);
 
-    Ok(0)
+    let clip: MdaClip = read_generic(context, clip)?;
+    let result = context.system().audio().resume(clip.handle);
+
+    Ok(if result.is_ok() { 0 } else { -1i32 as _ })
 }
 
-pub async fn stop(_context: &mut dyn WIPICContext, clip: WIPICWord) -> Result<WIPICWord> {
-    tracing::warn!("stub MC_mdaStop({:#x})", clip);
+pub async fn stop(context: &mut dyn WIPICContext, clip: WIPICWord) -> Result<WIPICWord> {
+    tracing::debug!("MC_mdaStop({:#x})", clip);
 
-    Ok(0)
+    let clip: MdaClip = read_generic(context, clip)?;
+
+    // next play starts from the beginning
+    let mut audio = context.system().audio();
+    let result = audio.stop(clip.handle).and_then(|_| audio.set_position(clip.handle, 0));
+
+    Ok(if result.is_ok() { 0 } else { -1i32 as _ })
 }
 
 pub async fn record(_context: &mut dyn WIPICContext, clip: WIPICWord) -> Result<WIPICWord> {
@@ -231,3 +345,31 @@ pub async fn record(_context: &mut dyn WIPICContext, clip: WIPICWord) -> Result<
 
     Ok(0)
 }
+
+#[cfg(test)]
+mod test {
+    use wie_util::{read_generic, ByteWrite, Result};
+
+    use crate::{context::test::TestContext, WIPICContext};
+
+    use super::{clip_create, MdaClip};
+
+    #[futures_test::test]
+    async fn test_clip_create_type() -> Result<()> {
+        let mut context = TestContext::new();
+
+        let _ = context.alloc_raw(4)?; // avoid null type
+        let ptr_type = context.alloc_raw(8)?;
+        context.write_bytes(ptr_type, b"MMF\0")?;
+
+        let ptr_clip = clip_create(&mut context, ptr_type, 0, 0).await?;
+        let clip: MdaClip = read_generic(&context, ptr_clip)?;
+        assert_eq!(clip.content_type().as_deref(), Some("MMF"));
+
+        let ptr_clip = clip_create(&mut context, 0, 0, 0).await?;
+        let clip: MdaClip = read_generic(&context, ptr_clip)?;
+        assert_eq!(clip.content_type(), None);
+
+        Ok(())
+    }
+}
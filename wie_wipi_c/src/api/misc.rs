@@ -2,14 +2,37 @@ use wie_util::Result;
 
 use crate::{context::WIPICContext, WIPICWord};
 
-pub async fn back_light(
-    _context: &mut dyn WIPICContext,
-    id: WIPICWord,
-    on_off: WIPICWord,
-    color: WIPICWord,
-    timeout: WIPICWord,
-) -> Result<WIPICWord> {
-    tracing::warn!("stub MC_miscBackLight({}, {}, {}, {})", id, on_off, color, timeout);
+pub async fn back_light(context: &mut dyn WIPICContext, id: WIPICWord, on_off: WIPICWord, color: WIPICWord, timeout: WIPICWord) -> Result<WIPICWord> {
+    tracing::debug!("MC_miscBackLight({}, {}, {}, {})", id, on_off, color, timeout);
+
+    // backlight color isn't supported
+    let timeout = (timeout as i32 > 0).then_some(timeout as u64);
+    context.system().device().set_backlight(on_off != 0, timeout);
 
     Ok(0)
 }
+
+pub async fn set_led(context: &mut dyn WIPICContext, id: WIPICWord, on_off: WIPICWord) -> Result<WIPICWord> {
+    tracing::debug!("MC_miscSetLed({}, {})", id, on_off);
+
+    let result = context.system().device().set_led(id as _, on_off != 0);
+
+    Ok(if result { 0 } else { -1i32 as _ })
+}
+
+pub async fn get_led(context: &mut dyn WIPICContext, id: WIPICWord) -> Result<WIPICWord> {
+    tracing::debug!("MC_miscGetLed({})", id);
+
+    let led = context.system().device().led(id as _);
+
+    Ok(match led {
+        Some(x) => x as _,
+        None => -1i32 as _,
+    })
+}
+
+pub async fn get_led_count(context: &mut dyn WIPICContext) -> Result<WIPICWord> {
+    tracing::debug!("MC_miscGetLedCount()");
+
+    Ok(context.system().device().led_count() as _)
+}
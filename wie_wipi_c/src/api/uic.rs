@@ -1,9 +1,1330 @@
-use wie_util::Result;
+use alloc::{format, string::String, vec::Vec};
+use core::mem::size_of;
 
-use crate::{context::WIPICContext, WIPICMemoryId};
+use bytemuck::{Pod, Zeroable};
+
+use wie_backend::{
+    canvas::{Canvas, Clip, Color, Font, PixelType, Rgb8Pixel, TextAlignment},
+    InputMode, KeyCode,
+};
+use wie_util::{read_generic, read_null_terminated_string_bytes, write_generic, write_null_terminated_string_bytes, Charset, Result};
+
+use crate::{
+    api::graphics::{font_from_handle, read_string, WIPICFramebuffer},
+    context::WIPICContext,
+    event::{
+        MH_KEY_0, MH_KEY_1, MH_KEY_2, MH_KEY_3, MH_KEY_4, MH_KEY_5, MH_KEY_6, MH_KEY_7, MH_KEY_8, MH_KEY_9, MH_KEY_ASTERISK, MH_KEY_CLEAR,
+        MH_KEY_DOWN, MH_KEY_LEFT, MH_KEY_POUND, MH_KEY_RIGHT, MH_KEY_SELECT, MH_KEY_SOFT1, MH_KEY_UP, MV_KEY_PRESS_EVENT, MV_KEY_REPEAT_EVENT,
+    },
+    WIPICMemoryId, WIPICWord,
+};
+
+// events passed to component callback, with item index or text size.
+// provisional, not taken from WIPI reference
+const UIC_EVENT_SELECT: i32 = 1;
+const UIC_EVENT_CHANGE: i32 = 2;
+const UIC_EVENT_CANCEL: i32 = 3;
+
+const DEFAULT_FG_PIXEL: WIPICWord = 0x000000;
+const DEFAULT_BG_PIXEL: WIPICWord = 0xffffff;
+const HIGHLIGHT_PIXEL: WIPICWord = 0x3060c0;
+const DISABLED_PIXEL: WIPICWord = 0x808080;
+const DEFAULT_MAX_TEXT_SIZE: i32 = 256;
+const PADDING: i32 = 2;
+const POPUP_MARGIN: i32 = 16;
+
+// class ids taken by MC_uicCreate and names returned by MC_uicGetClassName. provisional, not taken from WIPI reference
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum UicClass {
+    Menu = 1,
+    List = 2,
+    Popup = 3,
+    Text = 4,
+    MessageBox = 5,
+}
+
+impl UicClass {
+    fn from_raw(raw: WIPICWord) -> Option<Self> {
+        Some(match raw {
+            1 => Self::Menu,
+            2 => Self::List,
+            3 => Self::Popup,
+            4 => Self::Text,
+            5 => Self::MessageBox,
+            _ => return None,
+        })
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Menu => "MENU",
+            Self::List => "LIST",
+            Self::Popup => "POPUP",
+            Self::Text => "TEXT",
+            Self::MessageBox => "MSGBOX",
+        }
+    }
+
+    fn has_items(self) -> bool {
+        matches!(self, Self::Menu | Self::List | Self::Popup)
+    }
+
+    fn has_text(self) -> bool {
+        matches!(self, Self::Text | Self::MessageBox)
+    }
+}
+
+/// Component state. Kept in app memory like other wipi c objects, so handle is just a memory id.
+/// Layout is emulator's own and not read by applications, as handle is opaque to them.
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+struct WIPICUic {
+    class: WIPICWord,
+    class_name: WIPICMemoryId,
+    /// zero size places component by its class when painted
+    x: i32,
+    y: i32,
+    width: i32,
+    height: i32,
+    enabled: WIPICWord,
+    fn_callback: WIPICWord,
+    callback_param: WIPICWord,
+    fn_event_handler: WIPICWord,
+    font: i32,
+    fg_pixel: WIPICWord,
+    bg_pixel: WIPICWord,
+    label: WIPICMemoryId,
+    label_alignment: i32,
+    /// array of item string memory ids
+    items: WIPICMemoryId,
+    item_count: i32,
+    active_item: i32,
+    /// first visible item, or first visible line of message box
+    top_item: i32,
+    text: WIPICMemoryId,
+    max_text_size: i32,
+    /// in characters
+    cursor: i32,
+    input_mode: i32,
+    line_gap: i32,
+    /// target of last MC_uicPaint, repainted on changes
+    framebuffer: WIPICMemoryId,
+}
+
+impl WIPICUic {
+    fn class(&self) -> Option<UicClass> {
+        UicClass::from_raw(self.class)
+    }
+}
 
 pub async fn create_application_context(_context: &mut dyn WIPICContext) -> Result<WIPICMemoryId> {
     tracing::warn!("stub MC_uicCreateApplicationContext");
 
     Ok(WIPICMemoryId(0))
 }
+
+pub async fn get_class(_context: &mut dyn WIPICContext, class: WIPICWord) -> Result<WIPICWord> {
+    tracing::debug!("MC_uicGetClass({})", class);
+
+    Ok(UicClass::from_raw(class).map(|x| x as WIPICWord).unwrap_or(0))
+}
+
+pub async fn create(context: &mut dyn WIPICContext, class: WIPICWord) -> Result<WIPICMemoryId> {
+    tracing::debug!("MC_uicCreate({})", class);
+
+    let Some(class) = UicClass::from_raw(class) else {
+        tracing::warn!("MC_uicCreate({}): unknown class", class);
+
+        return Ok(WIPICMemoryId(0));
+    };
+
+    let uic = WIPICUic {
+        class: class as _,
+        class_name: alloc_string(context, class.name())?,
+        enabled: 1,
+        fg_pixel: DEFAULT_FG_PIXEL,
+        bg_pixel: DEFAULT_BG_PIXEL,
+        active_item: -1,
+        max_text_size: DEFAULT_MAX_TEXT_SIZE,
+        input_mode: mode_to_raw(if class == UicClass::Text {
+            InputMode::Hangul
+        } else {
+            InputMode::LowerCase
+        }),
+        ..WIPICUic::zeroed()
+    };
+
+    let memory = context.alloc(size_of::<WIPICUic>() as _)?;
+    write_uic(context, memory, &uic)?;
+
+    Ok(memory)
+}
+
+pub async fn destroy(context: &mut dyn WIPICContext, uic_id: WIPICMemoryId) -> Result<()> {
+    tracing::debug!("MC_uicDestroy({:#x})", uic_id.0);
+
+    let mut uic = read_uic(context, uic_id)?;
+
+    context.system().input_method().detach(uic_id.0 as u64);
+    set_items(context, &mut uic, Vec::new())?;
+    free_memory(context, uic.text)?;
+    free_memory(context, uic.label)?;
+    free_memory(context, uic.class_name)?;
+
+    context.free(uic_id)
+}
+
+pub async fn repaint(context: &mut dyn WIPICContext, uic_id: WIPICMemoryId) -> Result<()> {
+    tracing::debug!("MC_uicRepaint({:#x})", uic_id.0);
+
+    repaint_to_screen(context, uic_id)
+}
+
+pub async fn paint(context: &mut dyn WIPICContext, uic_id: WIPICMemoryId, framebuffer: WIPICMemoryId) -> Result<()> {
+    tracing::debug!("MC_uicPaint({:#x}, {:#x})", uic_id.0, framebuffer.0);
+
+    let mut uic = read_uic(context, uic_id)?;
+    uic.framebuffer = framebuffer;
+    write_uic(context, uic_id, &uic)?;
+
+    paint_uic(context, uic_id, framebuffer)
+}
+
+pub async fn get_class_name(context: &mut dyn WIPICContext, uic_id: WIPICMemoryId) -> Result<WIPICWord> {
+    tracing::debug!("MC_uicGetClassName({:#x})", uic_id.0);
+
+    let uic = read_uic(context, uic_id)?;
+
+    context.data_ptr(uic.class_name)
+}
+
+pub async fn is_instance(context: &mut dyn WIPICContext, uic_id: WIPICMemoryId, class: WIPICWord) -> Result<i32> {
+    tracing::debug!("MC_uicIsInstance({:#x}, {})", uic_id.0, class);
+
+    let uic = read_uic(context, uic_id)?;
+
+    Ok((uic.class == class) as _)
+}
+
+/// Returns 1 if the event is consumed by the component.
+pub async fn handle_event(context: &mut dyn WIPICContext, uic_id: WIPICMemoryId, r#type: i32, param1: i32, param2: i32) -> Result<i32> {
+    tracing::debug!("MC_uicHandleEvent({:#x}, {}, {}, {})", uic_id.0, r#type, param1, param2);
+
+    let uic = read_uic(context, uic_id)?;
+    if uic.enabled == 0 {
+        return Ok(0);
+    }
+
+    if uic.fn_event_handler != 0 {
+        let handled = context
+            .call_function(uic.fn_event_handler, &[uic_id.0, r#type as _, param1 as _, param2 as _])
+            .await?;
+        if handled != 0 {
+            return Ok(1);
+        }
+    }
+
+    let repeat = match r#type {
+        MV_KEY_PRESS_EVENT => false,
+        MV_KEY_REPEAT_EVENT => true,
+        _ => return Ok(0),
+    };
+
+    let result = match uic.class() {
+        Some(UicClass::Menu) | Some(UicClass::List) | Some(UicClass::Popup) => handle_list_key(context, uic_id, uic, param1, repeat)?,
+        Some(UicClass::Text) => handle_text_key(context, uic_id, uic, param1, repeat)?,
+        Some(UicClass::MessageBox) => handle_message_box_key(context, uic_id, uic, param1)?,
+        None => None,
+    };
+
+    let Some(callback_event) = result else {
+        return Ok(0);
+    };
+
+    repaint_to_screen(context, uic_id)?;
+
+    // callback goes last, as it may destroy the component
+    if let Some((event, value)) = callback_event {
+        let uic = read_uic(context, uic_id)?;
+        if uic.fn_callback != 0 {
+            context
+                .call_function(uic.fn_callback, &[uic_id.0, event as _, value as _, uic.callback_param])
+                .await?;
+        }
+    }
+
+    Ok(1)
+}
+
+pub async fn configure(context: &mut dyn WIPICContext, uic_id: WIPICMemoryId, x: i32, y: i32, width: i32, height: i32) -> Result<()> {
+    tracing::debug!("MC_uicConfigure({:#x}, {}, {}, {}, {})", uic_id.0, x, y, width, height);
+
+    let mut uic = read_uic(context, uic_id)?;
+    uic.x = x;
+    uic.y = y;
+    uic.width = width.max(0);
+    uic.height = height.max(0);
+
+    write_uic(context, uic_id, &uic)
+}
+
+pub async fn get_geometry(context: &mut dyn WIPICContext, uic_id: WIPICMemoryId, ptr_rect: WIPICWord) -> Result<()> {
+    tracing::debug!("MC_uicGetGeometry({:#x}, {:#x})", uic_id.0, ptr_rect);
+
+    let uic = read_uic(context, uic_id)?;
+
+    write_generic(context, ptr_rect, [uic.x, uic.y, uic.width, uic.height])
+}
+
+pub async fn set_enable(context: &mut dyn WIPICContext, uic_id: WIPICMemoryId, enable: WIPICWord) -> Result<()> {
+    tracing::debug!("MC_uicSetEnable({:#x}, {})", uic_id.0, enable);
+
+    let mut uic = read_uic(context, uic_id)?;
+    uic.enabled = (enable != 0) as _;
+
+    write_uic(context, uic_id, &uic)
+}
+
+pub async fn set_callback(context: &mut dyn WIPICContext, uic_id: WIPICMemoryId, fn_callback: WIPICWord, param: WIPICWord) -> Result<()> {
+    tracing::debug!("MC_uicSetCallback({:#x}, {:#x}, {:#x})", uic_id.0, fn_callback, param);
+
+    let mut uic = read_uic(context, uic_id)?;
+    uic.fn_callback = fn_callback;
+    uic.callback_param = param;
+
+    write_uic(context, uic_id, &uic)
+}
+
+pub async fn set_event_handler(context: &mut dyn WIPICContext, uic_id: WIPICMemoryId, fn_handler: WIPICWord) -> Result<()> {
+    tracing::debug!("MC_uicSetEventHandler({:#x}, {:#x})", uic_id.0, fn_handler);
+
+    let mut uic = read_uic(context, uic_id)?;
+    uic.fn_event_handler = fn_handler;
+
+    write_uic(context, uic_id, &uic)
+}
+
+pub async fn set_font(context: &mut dyn WIPICContext, uic_id: WIPICMemoryId, font: i32) -> Result<()> {
+    tracing::debug!("MC_uicSetFont({:#x}, {})", uic_id.0, font);
+
+    let mut uic = read_uic(context, uic_id)?;
+    uic.font = font;
+
+    write_uic(context, uic_id, &uic)
+}
+
+pub async fn get_font(context: &mut dyn WIPICContext, uic_id: WIPICMemoryId) -> Result<i32> {
+    tracing::debug!("MC_uicGetFont({:#x})", uic_id.0);
+
+    Ok(read_uic(context, uic_id)?.font)
+}
+
+pub async fn set_fg_color(context: &mut dyn WIPICContext, uic_id: WIPICMemoryId, pixel: WIPICWord) -> Result<()> {
+    tracing::debug!("MC_uicSetFgColor({:#x}, {:#x})", uic_id.0, pixel);
+
+    let mut uic = read_uic(context, uic_id)?;
+    uic.fg_pixel = pixel;
+
+    write_uic(context, uic_id, &uic)
+}
+
+pub async fn set_bg_color(context: &mut dyn WIPICContext, uic_id: WIPICMemoryId, pixel: WIPICWord) -> Result<()> {
+    tracing::debug!("MC_uicSetBgColor({:#x}, {:#x})", uic_id.0, pixel);
+
+    let mut uic = read_uic(context, uic_id)?;
+    uic.bg_pixel = pixel;
+
+    write_uic(context, uic_id, &uic)
+}
+
+pub async fn set_label(context: &mut dyn WIPICContext, uic_id: WIPICMemoryId, ptr_label: WIPICWord) -> Result<()> {
+    tracing::debug!("MC_uicSetLabel({:#x}, {:#x})", uic_id.0, ptr_label);
+
+    let mut uic = read_uic(context, uic_id)?;
+    free_memory(context, uic.label)?;
+    uic.label = if ptr_label == 0 {
+        WIPICMemoryId(0)
+    } else {
+        let label = read_string(context, ptr_label, -1)?;
+        alloc_string(context, &label)?
+    };
+
+    write_uic(context, uic_id, &uic)
+}
+
+pub async fn get_label(context: &mut dyn WIPICContext, uic_id: WIPICMemoryId) -> Result<WIPICWord> {
+    tracing::debug!("MC_uicGetLabel({:#x})", uic_id.0);
+
+    let uic = read_uic(context, uic_id)?;
+    if uic.label.0 == 0 {
+        return Ok(0);
+    }
+
+    context.data_ptr(uic.label)
+}
+
+pub async fn set_label_alignment(context: &mut dyn WIPICContext, uic_id: WIPICMemoryId, alignment: i32) -> Result<()> {
+    tracing::debug!("MC_uicSetLabelAlignment({:#x}, {})", uic_id.0, alignment);
+
+    let mut uic = read_uic(context, uic_id)?;
+    uic.label_alignment = alignment;
+
+    write_uic(context, uic_id, &uic)
+}
+
+pub async fn add_menu_item(context: &mut dyn WIPICContext, uic_id: WIPICMemoryId, ptr_label: WIPICWord, index: i32) -> Result<i32> {
+    tracing::debug!("MC_uicAddMenuItem({:#x}, {:#x}, {})", uic_id.0, ptr_label, index);
+
+    add_item(context, uic_id, ptr_label, index)
+}
+
+pub async fn get_menu_item(context: &mut dyn WIPICContext, uic_id: WIPICMemoryId, index: i32) -> Result<WIPICWord> {
+    tracing::debug!("MC_uicGetMenuItem({:#x}, {})", uic_id.0, index);
+
+    get_item(context, uic_id, index)
+}
+
+pub async fn remove_menu_item(context: &mut dyn WIPICContext, uic_id: WIPICMemoryId, index: i32) -> Result<i32> {
+    tracing::debug!("MC_uicRemoveMenuItem({:#x}, {})", uic_id.0, index);
+
+    remove_item(context, uic_id, index)
+}
+
+pub async fn set_active_menu_item(context: &mut dyn WIPICContext, uic_id: WIPICMemoryId, index: i32) -> Result<i32> {
+    tracing::debug!("MC_uicSetActiveMenuItem({:#x}, {})", uic_id.0, index);
+
+    set_active_item(context, uic_id, index)
+}
+
+pub async fn get_active_menu_item(context: &mut dyn WIPICContext, uic_id: WIPICMemoryId) -> Result<i32> {
+    tracing::debug!("MC_uicGetActiveMenuItem({:#x})", uic_id.0);
+
+    Ok(read_uic(context, uic_id)?.active_item)
+}
+
+pub async fn add_list_item(context: &mut dyn WIPICContext, uic_id: WIPICMemoryId, ptr_label: WIPICWord, index: i32) -> Result<i32> {
+    tracing::debug!("MC_uicAddListItem({:#x}, {:#x}, {})", uic_id.0, ptr_label, index);
+
+    add_item(context, uic_id, ptr_label, index)
+}
+
+pub async fn get_list_item(context: &mut dyn WIPICContext, uic_id: WIPICMemoryId, index: i32) -> Result<WIPICWord> {
+    tracing::debug!("MC_uicGetListItem({:#x}, {})", uic_id.0, index);
+
+    get_item(context, uic_id, index)
+}
+
+pub async fn remove_list_item(context: &mut dyn WIPICContext, uic_id: WIPICMemoryId, index: i32) -> Result<i32> {
+    tracing::debug!("MC_uicRemoveListItem({:#x}, {})", uic_id.0, index);
+
+    remove_item(context, uic_id, index)
+}
+
+pub async fn set_active_list_item(context: &mut dyn WIPICContext, uic_id: WIPICMemoryId, index: i32) -> Result<i32> {
+    tracing::debug!("MC_uicSetActiveListItem({:#x}, {})", uic_id.0, index);
+
+    set_active_item(context, uic_id, index)
+}
+
+pub async fn get_active_list_item(context: &mut dyn WIPICContext, uic_id: WIPICMemoryId) -> Result<i32> {
+    tracing::debug!("MC_uicGetActiveListItem({:#x})", uic_id.0);
+
+    Ok(read_uic(context, uic_id)?.active_item)
+}
+
+/// Inserts text at character position `pos`, or at cursor if `pos` is negative.
+pub async fn insert_text(context: &mut dyn WIPICContext, uic_id: WIPICMemoryId, pos: i32, ptr_text: WIPICWord, length: i32) -> Result<i32> {
+    tracing::debug!("MC_uicInsertText({:#x}, {}, {:#x}, {})", uic_id.0, pos, ptr_text, length);
+
+    let mut uic = read_uic(context, uic_id)?;
+    if !uic.class().is_some_and(UicClass::has_text) {
+        return Ok(-9); // M_E_INVALID
+    }
+
+    let inserted = read_string(context, ptr_text, length)?;
+
+    let mut text = read_memory_string(context, uic.text)?.chars().collect::<Vec<_>>();
+    let pos = if pos < 0 { uic.cursor } else { pos };
+    if pos as usize > text.len() {
+        return Ok(-9); // M_E_INVALID
+    }
+
+    let inserted_count = inserted.chars().count() as i32;
+    text.splice(pos as usize..pos as usize, inserted.chars());
+    if uic.cursor >= pos {
+        uic.cursor += inserted_count;
+    }
+
+    set_text(context, &mut uic, text.into_iter().collect())?;
+    write_uic(context, uic_id, &uic)?;
+
+    Ok(0) // M_E_SUCCESS
+}
+
+pub async fn delete_text(context: &mut dyn WIPICContext, uic_id: WIPICMemoryId, pos: i32, length: i32) -> Result<i32> {
+    tracing::debug!("MC_uicDeleteText({:#x}, {}, {})", uic_id.0, pos, length);
+
+    let mut uic = read_uic(context, uic_id)?;
+    if !uic.class().is_some_and(UicClass::has_text) {
+        return Ok(-9); // M_E_INVALID
+    }
+
+    let mut text = read_memory_string(context, uic.text)?.chars().collect::<Vec<_>>();
+    if pos < 0 || pos as usize > text.len() || length < 0 {
+        return Ok(-9); // M_E_INVALID
+    }
+
+    let end = pos.saturating_add(length).min(text.len() as i32);
+    text.drain(pos as usize..end as usize);
+    if uic.cursor > end {
+        uic.cursor -= end - pos;
+    } else if uic.cursor > pos {
+        uic.cursor = pos;
+    }
+
+    set_text(context, &mut uic, text.into_iter().collect())?;
+    write_uic(context, uic_id, &uic)?;
+
+    Ok(0) // M_E_SUCCESS
+}
+
+pub async fn get_max_text_size(context: &mut dyn WIPICContext, uic_id: WIPICMemoryId) -> Result<i32> {
+    tracing::debug!("MC_uicGetMaxTextSize({:#x})", uic_id.0);
+
+    Ok(read_uic(context, uic_id)?.max_text_size)
+}
+
+pub async fn set_max_text_size(context: &mut dyn WIPICContext, uic_id: WIPICMemoryId, size: i32) -> Result<()> {
+    tracing::debug!("MC_uicSetMaxTextSize({:#x}, {})", uic_id.0, size);
+
+    let mut uic = read_uic(context, uic_id)?;
+    uic.max_text_size = size.max(0);
+
+    let text = read_memory_string(context, uic.text)?;
+    set_text(context, &mut uic, text)?;
+
+    write_uic(context, uic_id, &uic)
+}
+
+/// Size of text in bytes, excluding null terminator.
+pub async fn get_text_size(context: &mut dyn WIPICContext, uic_id: WIPICMemoryId) -> Result<i32> {
+    tracing::debug!("MC_uicGetTextSize({:#x})", uic_id.0);
+
+    let uic = read_uic(context, uic_id)?;
+    let text = read_memory_string(context, uic.text)?;
+
+    Ok(context.system().charset().encode(&text).len() as _)
+}
+
+pub async fn get_text(context: &mut dyn WIPICContext, uic_id: WIPICMemoryId, ptr_buf: WIPICWord, buf_size: i32) -> Result<i32> {
+    tracing::debug!("MC_uicGetText({:#x}, {:#x}, {})", uic_id.0, ptr_buf, buf_size);
+
+    if buf_size <= 0 {
+        return Ok(-18); // M_E_SHORTBUF
+    }
+
+    let uic = read_uic(context, uic_id)?;
+    let text = read_memory_string(context, uic.text)?;
+    let text = fit_text(context.system().charset(), &text, buf_size - 1);
+    let bytes = context.system().charset().encode(&text);
+
+    write_null_terminated_string_bytes(context, ptr_buf, &bytes)?;
+
+    Ok(bytes.len() as _)
+}
+
+pub async fn get_cursor_position(context: &mut dyn WIPICContext, uic_id: WIPICMemoryId) -> Result<i32> {
+    tracing::debug!("OEMC_uicGetCursorPosition({:#x})", uic_id.0);
+
+    Ok(read_uic(context, uic_id)?.cursor)
+}
+
+pub async fn set_cursor_position(context: &mut dyn WIPICContext, uic_id: WIPICMemoryId, pos: i32) -> Result<()> {
+    tracing::debug!("OEMC_uicSetCursorPosition({:#x}, {})", uic_id.0, pos);
+
+    let mut uic = read_uic(context, uic_id)?;
+    let length = read_memory_string(context, uic.text)?.chars().count() as i32;
+    uic.cursor = pos.clamp(0, length);
+
+    write_uic(context, uic_id, &uic)
+}
+
+pub async fn set_line_gap(context: &mut dyn WIPICContext, uic_id: WIPICMemoryId, gap: i32) -> Result<()> {
+    tracing::debug!("OEMC_uicSetLineGap({:#x}, {})", uic_id.0, gap);
+
+    let mut uic = read_uic(context, uic_id)?;
+    uic.line_gap = gap.max(0);
+
+    write_uic(context, uic_id, &uic)
+}
+
+pub async fn get_line_gap(context: &mut dyn WIPICContext, uic_id: WIPICMemoryId) -> Result<i32> {
+    tracing::debug!("OEMC_uicGetLineGap({:#x})", uic_id.0);
+
+    Ok(read_uic(context, uic_id)?.line_gap)
+}
+
+// callback event to send after repaint, None if key is not consumed
+type KeyResult = Option<Option<(i32, i32)>>;
+
+fn handle_list_key(context: &mut dyn WIPICContext, uic_id: WIPICMemoryId, mut uic: WIPICUic, key: i32, repeat: bool) -> Result<KeyResult> {
+    let count = uic.item_count;
+    let class = uic.class();
+
+    let result = match key {
+        MH_KEY_UP | MH_KEY_DOWN if count > 0 => {
+            let step = if key == MH_KEY_UP { count - 1 } else { 1 };
+            uic.active_item = (uic.active_item.max(0) + step) % count;
+
+            Some((UIC_EVENT_CHANGE, uic.active_item))
+        }
+        _ if repeat => return Ok(None),
+        MH_KEY_SELECT if uic.active_item >= 0 => Some((UIC_EVENT_SELECT, uic.active_item)),
+        MH_KEY_CLEAR if class != Some(UicClass::List) => Some((UIC_EVENT_CANCEL, uic.active_item)),
+        // menu items are numbered, so digit picks one right away
+        MH_KEY_1..=MH_KEY_9 if class == Some(UicClass::Menu) && key - MH_KEY_1 < count => {
+            uic.active_item = key - MH_KEY_1;
+
+            Some((UIC_EVENT_SELECT, uic.active_item))
+        }
+        _ => return Ok(None),
+    };
+
+    write_uic(context, uic_id, &uic)?;
+
+    Ok(Some(result))
+}
+
+fn handle_text_key(context: &mut dyn WIPICContext, uic_id: WIPICMemoryId, mut uic: WIPICUic, key: i32, repeat: bool) -> Result<KeyResult> {
+    let owner = uic_id.0 as u64;
+    context.system().input_method().attach(owner, mode_from_raw(uic.input_mode));
+
+    let mut text = read_memory_string(context, uic.text)?.chars().collect::<Vec<_>>();
+    let old_text = text.clone();
+
+    let mut result = None;
+    match key {
+        MH_KEY_CLEAR => {
+            if !context.system().input_method().backspace() {
+                if uic.cursor == 0 {
+                    return Ok(None);
+                }
+
+                uic.cursor -= 1;
+                text.remove(uic.cursor as usize);
+            }
+        }
+        _ if repeat => return Ok(None),
+        MH_KEY_LEFT => {
+            if !context.system().input_method().composition().is_empty() {
+                context.system().input_method().commit();
+            } else if uic.cursor > 0 {
+                uic.cursor -= 1;
+            } else {
+                return Ok(None);
+            }
+        }
+        MH_KEY_RIGHT => {
+            if !context.system().input_method().composition().is_empty() {
+                context.system().input_method().commit();
+            } else if (uic.cursor as usize) < text.len() {
+                uic.cursor += 1;
+            } else {
+                return Ok(None);
+            }
+        }
+        MH_KEY_SELECT => {
+            context.system().input_method().commit();
+            result = Some(UIC_EVENT_SELECT);
+        }
+        _ => {
+            let Some(key) = key_code(key) else {
+                return Ok(None);
+            };
+
+            let now = context.system().platform().now();
+            if !context.system().input_method().key(key, now) {
+                return Ok(None);
+            }
+        }
+    }
+
+    let (committed, input_mode) = {
+        let mut input_method = context.system().input_method();
+
+        (input_method.take_committed(), input_method.mode())
+    };
+    uic.input_mode = mode_to_raw(input_mode);
+
+    let cursor = uic.cursor as usize;
+    text.splice(cursor..cursor, committed.chars());
+    uic.cursor += committed.chars().count() as i32;
+
+    set_text(context, &mut uic, text.into_iter().collect())?;
+    let text = read_memory_string(context, uic.text)?;
+    write_uic(context, uic_id, &uic)?;
+
+    let text_size = context.system().charset().encode(&text).len() as i32;
+    let event = match result {
+        Some(event) => Some((event, text_size)),
+        None if text.chars().ne(old_text) => Some((UIC_EVENT_CHANGE, text_size)),
+        None => None,
+    };
+
+    Ok(Some(event))
+}
+
+fn handle_message_box_key(context: &mut dyn WIPICContext, uic_id: WIPICMemoryId, mut uic: WIPICUic, key: i32) -> Result<KeyResult> {
+    let result = match key {
+        MH_KEY_UP if uic.top_item > 0 => {
+            uic.top_item -= 1;
+
+            None
+        }
+        // clamped on paint, where line count is known
+        MH_KEY_DOWN => {
+            uic.top_item += 1;
+
+            None
+        }
+        MH_KEY_SELECT => Some((UIC_EVENT_SELECT, 0)),
+        MH_KEY_CLEAR => Some((UIC_EVENT_CANCEL, 0)),
+        _ => return Ok(None),
+    };
+
+    write_uic(context, uic_id, &uic)?;
+
+    Ok(Some(result))
+}
+
+fn repaint_to_screen(context: &mut dyn WIPICContext, uic_id: WIPICMemoryId) -> Result<()> {
+    let uic = read_uic(context, uic_id)?;
+    if uic.framebuffer.0 == 0 {
+        // not painted yet, app paints it on its next frame
+        let mut platform = context.system().platform();
+        platform.screen().request_redraw()?;
+
+        return Ok(());
+    }
+
+    paint_uic(context, uic_id, uic.framebuffer)?;
+
+    let framebuffer: WIPICFramebuffer = read_generic(context, context.data_ptr(uic.framebuffer)?)?;
+    let image = framebuffer.image(context)?;
+
+    let mut platform = context.system().platform();
+    platform.screen().paint(&*image);
+
+    Ok(())
+}
+
+fn paint_uic(context: &mut dyn WIPICContext, uic_id: WIPICMemoryId, framebuffer: WIPICMemoryId) -> Result<()> {
+    let mut uic = read_uic(context, uic_id)?;
+    let Some(class) = uic.class() else {
+        return Ok(());
+    };
+
+    if class == UicClass::Text {
+        take_host_text(context, uic_id, &mut uic)?;
+    }
+
+    let framebuffer: WIPICFramebuffer = read_generic(context, context.data_ptr(framebuffer)?)?;
+    let label = read_memory_string(context, uic.label)?;
+    let text = read_memory_string(context, uic.text)?;
+    let items = item_strings(context, &uic)?;
+
+    // composition is shown only in the text component being typed into
+    let (composition, mode_label) = if class == UicClass::Text {
+        let input_method = context.system().input_method();
+        if input_method.is_attached(uic_id.0 as u64) {
+            (Some(input_method.composition()), input_method.mode().label())
+        } else {
+            (None, "")
+        }
+    } else {
+        (None, "")
+    };
+
+    let font = font_from_handle(context, uic.font);
+    let style = Style::new(&uic, &font, framebuffer.width as _, framebuffer.height as _);
+
+    let mut canvas = framebuffer.canvas(context)?;
+    match class {
+        UicClass::Menu | UicClass::List | UicClass::Popup => paint_list(&mut **canvas, &style, &mut uic, class, &label, &items),
+        UicClass::Text => paint_text(&mut **canvas, &style, &uic, &label, &text, composition.as_deref(), mode_label),
+        UicClass::MessageBox => paint_message_box(&mut **canvas, &style, &mut uic, &label, &text),
+    }
+    drop(canvas);
+
+    // scroll position is updated while painting
+    write_uic(context, uic_id, &uic)
+}
+
+// text typed on host keyboard is left in input method, as frontend doesn't know which component is being typed into.
+// so it goes to the text component attached to input method, which is the one last typed into with handset keys.
+fn take_host_text(context: &mut dyn WIPICContext, uic_id: WIPICMemoryId, uic: &mut WIPICUic) -> Result<()> {
+    let committed = {
+        let mut input_method = context.system().input_method();
+        if !input_method.is_attached(uic_id.0 as u64) {
+            return Ok(());
+        }
+
+        input_method.take_committed()
+    };
+    if committed.is_empty() {
+        return Ok(());
+    }
+
+    let mut text = read_memory_string(context, uic.text)?.chars().collect::<Vec<_>>();
+    let cursor = uic.cursor as usize;
+    text.splice(cursor..cursor, committed.chars());
+    uic.cursor += committed.chars().count() as i32;
+
+    set_text(context, uic, text.into_iter().collect())?;
+    write_uic(context, uic_id, uic)
+}
+
+struct Style<'a> {
+    font: &'a Font,
+    fb_width: i32,
+    fb_height: i32,
+    fg: Color,
+    bg: Color,
+    highlight: Color,
+    line_height: i32,
+}
+
+impl<'a> Style<'a> {
+    fn new(uic: &WIPICUic, font: &'a Font, fb_width: i32, fb_height: i32) -> Self {
+        Self {
+            font,
+            fb_width,
+            fb_height,
+            fg: Rgb8Pixel::to_color(if uic.enabled != 0 { uic.fg_pixel } else { DISABLED_PIXEL }),
+            bg: Rgb8Pixel::to_color(uic.bg_pixel),
+            highlight: Rgb8Pixel::to_color(HIGHLIGHT_PIXEL),
+            line_height: font.height() as i32 + uic.line_gap,
+        }
+    }
+
+    // configured geometry, or default placement of the class fitting `content_height`
+    fn bounds(&self, uic: &WIPICUic, class: UicClass, content_height: i32) -> (i32, i32, i32, i32) {
+        if uic.width > 0 && uic.height > 0 {
+            return (uic.x, uic.y, uic.width, uic.height);
+        }
+
+        match class {
+            UicClass::Popup | UicClass::MessageBox => {
+                let width = self.popup_width(uic);
+                let height = content_height.min(self.fb_height - POPUP_MARGIN * 2);
+
+                ((self.fb_width - width) / 2, (self.fb_height - height) / 2, width, height)
+            }
+            UicClass::Text => (0, 0, self.fb_width, content_height),
+            UicClass::Menu | UicClass::List => (0, 0, self.fb_width, self.fb_height),
+        }
+    }
+
+    fn popup_width(&self, uic: &WIPICUic) -> i32 {
+        if uic.width > 0 {
+            uic.width
+        } else {
+            self.fb_width - POPUP_MARGIN * 2
+        }
+    }
+
+    fn title_height(&self, label: &str) -> i32 {
+        if label.is_empty() {
+            0
+        } else {
+            self.line_height + PADDING * 2
+        }
+    }
+}
+
+fn clip(x: i32, y: i32, width: i32, height: i32) -> Clip {
+    Clip {
+        x,
+        y,
+        width: width.max(0) as _,
+        height: height.max(0) as _,
+    }
+}
+
+fn label_alignment(alignment: i32) -> TextAlignment {
+    match alignment {
+        1 => TextAlignment::Center,
+        2 => TextAlignment::Right,
+        _ => TextAlignment::Left,
+    }
+}
+
+fn paint_frame(canvas: &mut dyn Canvas, style: &Style, (x, y, width, height): (i32, i32, i32, i32), uic: &WIPICUic, label: &str) {
+    let bounds = clip(x, y, width, height);
+
+    canvas.fill_rect(x, y, width.max(0) as _, height.max(0) as _, style.bg, bounds);
+    canvas.draw_rect(x, y, width.max(0) as _, height.max(0) as _, style.fg, bounds);
+
+    if !label.is_empty() {
+        let title_height = style.title_height(label);
+        canvas.fill_rect(x, y, width.max(0) as _, title_height as _, style.fg, bounds);
+
+        let alignment = label_alignment(uic.label_alignment);
+        let text_x = match alignment {
+            TextAlignment::Left => x + PADDING * 2,
+            TextAlignment::Center => x + width / 2,
+            TextAlignment::Right => x + width - PADDING * 2,
+        };
+        canvas.draw_text(label, text_x, y + PADDING, style.font, style.bg, alignment, bounds);
+    }
+}
+
+fn paint_list(canvas: &mut dyn Canvas, style: &Style, uic: &mut WIPICUic, class: UicClass, label: &str, items: &[String]) {
+    let title_height = style.title_height(label);
+    let content_height = title_height + items.len() as i32 * style.line_height + PADDING * 2;
+    let (x, y, width, height) = style.bounds(uic, class, content_height);
+
+    paint_frame(canvas, style, (x, y, width, height), uic, label);
+
+    // keep active item visible
+    let visible = ((height - title_height - PADDING * 2) / style.line_height).max(1);
+    if uic.active_item >= 0 {
+        if uic.active_item < uic.top_item {
+            uic.top_item = uic.active_item;
+        } else if uic.active_item >= uic.top_item + visible {
+            uic.top_item = uic.active_item - visible + 1;
+        }
+    }
+    uic.top_item = uic.top_item.clamp(0, (items.len() as i32 - visible).max(0));
+
+    let rows = clip(x + 1, y + title_height + PADDING, width - 2, height - title_height - PADDING * 2);
+    for (i, item) in items.iter().enumerate().skip(uic.top_item as _).take(visible as _) {
+        let row_y = rows.y + (i as i32 - uic.top_item) * style.line_height;
+        let item = if class == UicClass::Menu {
+            format!("{}. {}", i + 1, item)
+        } else {
+            item.clone()
+        };
+
+        let color = if i as i32 == uic.active_item {
+            canvas.fill_rect(rows.x, row_y, rows.width, style.line_height as _, style.highlight, rows);
+
+            style.bg
+        } else {
+            style.fg
+        };
+        canvas.draw_text(
+            &item,
+            x + PADDING * 2,
+            row_y + uic.line_gap / 2,
+            style.font,
+            color,
+            TextAlignment::Left,
+            rows,
+        );
+    }
+}
+
+fn paint_text(canvas: &mut dyn Canvas, style: &Style, uic: &WIPICUic, label: &str, text: &str, composition: Option<&str>, mode_label: &str) {
+    let content_height = style.line_height * 2 + PADDING * 3;
+    let (x, y, width, height) = style.bounds(uic, UicClass::Text, content_height);
+    let bounds = clip(x, y, width, height);
+
+    canvas.fill_rect(x, y, width.max(0) as _, height.max(0) as _, style.bg, bounds);
+    canvas.draw_text(label, x + PADDING, y + PADDING, style.font, style.fg, TextAlignment::Left, bounds);
+    canvas.draw_text(
+        mode_label,
+        x + width - PADDING,
+        y + PADDING,
+        style.font,
+        style.fg,
+        TextAlignment::Right,
+        bounds,
+    );
+
+    let box_y = y + style.line_height + PADDING;
+    let box_height = height - style.line_height - PADDING;
+    canvas.draw_rect(x, box_y, width.max(0) as _, box_height.max(0) as _, style.fg, bounds);
+
+    // composition goes at the cursor
+    let mut display = text.chars().collect::<Vec<_>>();
+    let composition = composition.unwrap_or_default().chars().collect::<Vec<_>>();
+    let cursor = (uic.cursor as usize).min(display.len());
+    display.splice(cursor..cursor, composition.iter().copied());
+
+    let before_cursor = display[..cursor + composition.len()].iter().collect::<String>();
+    let display = display.into_iter().collect::<String>();
+
+    // scroll so that cursor is visible
+    let inner = clip(x + PADDING, box_y + PADDING, width - PADDING * 2, box_height - PADDING * 2);
+    let cursor_x = style.font.string_width(&before_cursor) as i32;
+    let scroll = (cursor_x - inner.width as i32 + 1).max(0);
+    let text_x = inner.x - scroll;
+
+    canvas.draw_text(&display, text_x, inner.y, style.font, style.fg, TextAlignment::Left, inner);
+
+    if mode_label.is_empty() {
+        return;
+    }
+
+    let composition_width = style.font.string_width(&composition.iter().collect::<String>()) as i32;
+    if composition_width > 0 {
+        let underline_y = inner.y + style.font.height() as i32;
+        canvas.draw_line(
+            text_x + cursor_x - composition_width,
+            underline_y,
+            text_x + cursor_x - 1,
+            underline_y,
+            style.fg,
+        );
+    }
+    canvas.fill_rect(text_x + cursor_x, inner.y, 1, style.font.height(), style.fg, inner);
+}
+
+fn paint_message_box(canvas: &mut dyn Canvas, style: &Style, uic: &mut WIPICUic, label: &str, text: &str) {
+    const BUTTON_LABEL: &str = "확인";
+
+    let title_height = style.title_height(label);
+    let button_height = style.line_height + PADDING * 2;
+    let lines = wrap_lines(style.font, text, style.popup_width(uic) - PADDING * 4);
+
+    let content_height = title_height + lines.len() as i32 * style.line_height + button_height + PADDING * 3;
+    let (x, y, width, height) = style.bounds(uic, UicClass::MessageBox, content_height);
+
+    paint_frame(canvas, style, (x, y, width, height), uic, label);
+
+    let visible = ((height - title_height - button_height - PADDING * 3) / style.line_height).max(1);
+    uic.top_item = uic.top_item.clamp(0, (lines.len() as i32 - visible).max(0));
+
+    let body = clip(x + 1, y + title_height + PADDING, width - 2, visible * style.line_height);
+    for (i, line) in lines.iter().enumerate().skip(uic.top_item as _).take(visible as _) {
+        let line_y = body.y + (i as i32 - uic.top_item) * style.line_height;
+        canvas.draw_text(line, x + PADDING * 2, line_y, style.font, style.fg, TextAlignment::Left, body);
+    }
+
+    let button_width = style.font.string_width(BUTTON_LABEL) as i32 + PADDING * 4;
+    let button = clip(
+        x + (width - button_width) / 2,
+        y + height - button_height - PADDING,
+        button_width,
+        button_height,
+    );
+    canvas.fill_rect(button.x, button.y, button.width, button.height, style.highlight, button);
+    canvas.draw_text(
+        BUTTON_LABEL,
+        button.x + button_width / 2,
+        button.y + PADDING,
+        style.font,
+        style.bg,
+        TextAlignment::Center,
+        button,
+    );
+}
+
+fn wrap_lines(font: &Font, text: &str, width: i32) -> Vec<String> {
+    let mut result = Vec::new();
+    for paragraph in text.split('\n') {
+        let mut line = String::new();
+        for c in paragraph.chars() {
+            if !line.is_empty() && (font.string_width(&line) + font.char_width(c)) as i32 > width {
+                result.push(core::mem::take(&mut line));
+            }
+            line.push(c);
+        }
+        result.push(line);
+    }
+
+    result
+}
+
+fn add_item(context: &mut dyn WIPICContext, uic_id: WIPICMemoryId, ptr_label: WIPICWord, index: i32) -> Result<i32> {
+    let mut uic = read_uic(context, uic_id)?;
+    if !uic.class().is_some_and(UicClass::has_items) {
+        return Ok(-9); // M_E_INVALID
+    }
+
+    let label = read_string(context, ptr_label, -1)?;
+    let mut items = items(context, &uic)?;
+
+    // out of range index appends
+    let index = if index < 0 || index as usize > items.len() {
+        items.len()
+    } else {
+        index as usize
+    };
+    items.insert(index, alloc_string(context, &label)?);
+
+    if uic.active_item < 0 {
+        uic.active_item = 0;
+    } else if uic.active_item >= index as i32 {
+        uic.active_item += 1;
+    }
+
+    set_items(context, &mut uic, items)?;
+    write_uic(context, uic_id, &uic)?;
+
+    Ok(index as _)
+}
+
+fn get_item(context: &mut dyn WIPICContext, uic_id: WIPICMemoryId, index: i32) -> Result<WIPICWord> {
+    let uic = read_uic(context, uic_id)?;
+    let items = items(context, &uic)?;
+
+    match items.get(index as usize) {
+        Some(&item) if index >= 0 => context.data_ptr(item),
+        _ => Ok(0),
+    }
+}
+
+fn remove_item(context: &mut dyn WIPICContext, uic_id: WIPICMemoryId, index: i32) -> Result<i32> {
+    let mut uic = read_uic(context, uic_id)?;
+    let mut items = items(context, &uic)?;
+    if index < 0 || index as usize >= items.len() {
+        return Ok(-9); // M_E_INVALID
+    }
+
+    let item = items.remove(index as _);
+    context.free(item)?;
+
+    if uic.active_item > index || uic.active_item as usize == items.len() {
+        uic.active_item -= 1;
+    }
+
+    set_items(context, &mut uic, items)?;
+    write_uic(context, uic_id, &uic)?;
+
+    Ok(0) // M_E_SUCCESS
+}
+
+fn set_active_item(context: &mut dyn WIPICContext, uic_id: WIPICMemoryId, index: i32) -> Result<i32> {
+    let mut uic = read_uic(context, uic_id)?;
+    if index < 0 || index >= uic.item_count {
+        return Ok(-9); // M_E_INVALID
+    }
+
+    uic.active_item = index;
+    write_uic(context, uic_id, &uic)?;
+
+    Ok(0) // M_E_SUCCESS
+}
+
+fn items(context: &mut dyn WIPICContext, uic: &WIPICUic) -> Result<Vec<WIPICMemoryId>> {
+    if uic.item_count == 0 {
+        return Ok(Vec::new());
+    }
+
+    let base = context.data_ptr(uic.items)?;
+
+    (0..uic.item_count as WIPICWord)
+        .map(|i| read_generic(context, base + i * size_of::<WIPICMemoryId>() as WIPICWord))
+        .collect()
+}
+
+fn set_items(context: &mut dyn WIPICContext, uic: &mut WIPICUic, items: Vec<WIPICMemoryId>) -> Result<()> {
+    if items.is_empty() {
+        for item in self::items(context, uic)? {
+            context.free(item)?;
+        }
+    }
+    free_memory(context, uic.items)?;
+
+    uic.items = WIPICMemoryId(0);
+    uic.item_count = items.len() as _;
+    if items.is_empty() {
+        return Ok(());
+    }
+
+    uic.items = context.alloc((items.len() * size_of::<WIPICMemoryId>()) as _)?;
+    let base = context.data_ptr(uic.items)?;
+    for (i, item) in items.into_iter().enumerate() {
+        write_generic(context, base + (i * size_of::<WIPICMemoryId>()) as WIPICWord, item)?;
+    }
+
+    Ok(())
+}
+
+fn item_strings(context: &mut dyn WIPICContext, uic: &WIPICUic) -> Result<Vec<String>> {
+    items(context, uic)?.into_iter().map(|x| read_memory_string(context, x)).collect()
+}
+
+// stores text cut to max text size, keeping cursor inside
+fn set_text(context: &mut dyn WIPICContext, uic: &mut WIPICUic, text: String) -> Result<()> {
+    let text = fit_text(context.system().charset(), &text, uic.max_text_size);
+
+    free_memory(context, uic.text)?;
+    uic.text = alloc_string(context, &text)?;
+    uic.cursor = uic.cursor.clamp(0, text.chars().count() as _);
+
+    Ok(())
+}
+
+// longest prefix of `text` taking at most `size` bytes when encoded
+fn fit_text(charset: Charset, text: &str, size: i32) -> String {
+    let mut length = 0;
+
+    text.chars()
+        .take_while(|&x| {
+            length += charset.encode(x.encode_utf8(&mut [0; 4])).len() as i32;
+
+            length <= size
+        })
+        .collect()
+}
+
+fn read_uic(context: &mut dyn WIPICContext, uic_id: WIPICMemoryId) -> Result<WIPICUic> {
+    read_generic(context, context.data_ptr(uic_id)?)
+}
+
+fn write_uic(context: &mut dyn WIPICContext, uic_id: WIPICMemoryId, uic: &WIPICUic) -> Result<()> {
+    write_generic(context, context.data_ptr(uic_id)?, *uic)
+}
+
+fn alloc_string(context: &mut dyn WIPICContext, string: &str) -> Result<WIPICMemoryId> {
+    let bytes = context.system().charset().encode(string);
+
+    let memory = context.alloc(bytes.len() as WIPICWord + 1)?;
+    write_null_terminated_string_bytes(context, context.data_ptr(memory)?, &bytes)?;
+
+    Ok(memory)
+}
+
+fn read_memory_string(context: &mut dyn WIPICContext, memory: WIPICMemoryId) -> Result<String> {
+    if memory.0 == 0 {
+        return Ok(String::new());
+    }
+
+    let bytes = read_null_terminated_string_bytes(context, context.data_ptr(memory)?)?;
+
+    Ok(context.system().charset().decode(&bytes))
+}
+
+fn free_memory(context: &mut dyn WIPICContext, memory: WIPICMemoryId) -> Result<()> {
+    if memory.0 == 0 {
+        return Ok(());
+    }
+
+    context.free(memory)
+}
+
+fn key_code(key: i32) -> Option<KeyCode> {
+    Some(match key {
+        MH_KEY_0 => KeyCode::NUM0,
+        MH_KEY_1 => KeyCode::NUM1,
+        MH_KEY_2 => KeyCode::NUM2,
+        MH_KEY_3 => KeyCode::NUM3,
+        MH_KEY_4 => KeyCode::NUM4,
+        MH_KEY_5 => KeyCode::NUM5,
+        MH_KEY_6 => KeyCode::NUM6,
+        MH_KEY_7 => KeyCode::NUM7,
+        MH_KEY_8 => KeyCode::NUM8,
+        MH_KEY_9 => KeyCode::NUM9,
+        MH_KEY_ASTERISK => KeyCode::STAR,
+        MH_KEY_POUND => KeyCode::HASH,
+        MH_KEY_SOFT1 => KeyCode::LEFT_SOFT,
+        _ => return None,
+    })
+}
+
+fn mode_to_raw(input_mode: InputMode) -> i32 {
+    match input_mode {
+        InputMode::Hangul => 0,
+        InputMode::UpperCase => 1,
+        InputMode::LowerCase => 2,
+        InputMode::Number => 3,
+    }
+}
+
+fn mode_from_raw(raw: i32) -> InputMode {
+    match raw {
+        1 => InputMode::UpperCase,
+        2 => InputMode::LowerCase,
+        3 => InputMode::Number,
+        _ => InputMode::Hangul,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::string::String;
+
+    use wie_util::{read_null_terminated_string_bytes, write_null_terminated_string_bytes, Result};
+
+    use crate::{context::test::TestContext, WIPICContext, WIPICMemoryId};
+
+    use super::{
+        add_list_item, create, delete_text, get_active_list_item, get_list_item, get_text, get_text_size, insert_text, remove_list_item,
+        set_max_text_size, UicClass,
+    };
+
+    fn string(context: &mut TestContext, value: &str) -> Result<u32> {
+        let address = context.alloc_raw(value.len() as u32 + 1)?;
+        write_null_terminated_string_bytes(context, address, value.as_bytes())?;
+
+        Ok(address)
+    }
+
+    async fn item(context: &mut TestContext, uic: WIPICMemoryId, index: i32) -> Result<Option<String>> {
+        let address = get_list_item(context, uic, index).await?;
+        if address == 0 {
+            return Ok(None);
+        }
+
+        Ok(Some(String::from_utf8(read_null_terminated_string_bytes(context, address)?).unwrap()))
+    }
+
+    #[futures_test::test]
+    async fn test_list_items() -> Result<()> {
+        let mut context = TestContext::new();
+        let _ = context.alloc_raw(4)?; // avoid null handle
+
+        let list = create(&mut context, UicClass::List as _).await?;
+        assert_eq!(get_active_list_item(&mut context, list).await?, -1);
+
+        let (a, b, c) = (string(&mut context, "a")?, string(&mut context, "b")?, string(&mut context, "c")?);
+        assert_eq!(add_list_item(&mut context, list, a, -1).await?, 0);
+        assert_eq!(add_list_item(&mut context, list, c, -1).await?, 1);
+        assert_eq!(add_list_item(&mut context, list, b, 1).await?, 1);
+        assert_eq!(get_active_list_item(&mut context, list).await?, 0);
+
+        assert_eq!(item(&mut context, list, 1).await?.as_deref(), Some("b"));
+        assert_eq!(item(&mut context, list, 3).await?, None);
+
+        // inserting before active item keeps it active
+        assert_eq!(add_list_item(&mut context, list, a, 0).await?, 0);
+        assert_eq!(get_active_list_item(&mut context, list).await?, 1);
+
+        assert_eq!(remove_list_item(&mut context, list, 0).await?, 0);
+        assert_eq!(remove_list_item(&mut context, list, 5).await?, -9);
+        assert_eq!(item(&mut context, list, 2).await?.as_deref(), Some("c"));
+        assert_eq!(get_active_list_item(&mut context, list).await?, 0);
+
+        Ok(())
+    }
+
+    #[futures_test::test]
+    async fn test_text() -> Result<()> {
+        let mut context = TestContext::new();
+        let _ = context.alloc_raw(4)?; // avoid null handle
+
+        let text = create(&mut context, UicClass::Text as _).await?;
+        let hello = string(&mut context, "hello")?;
+        let comma = string(&mut context, ", ")?;
+        let buf = context.alloc_raw(16)?;
+
+        assert_eq!(insert_text(&mut context, text, -1, hello, -1).await?, 0);
+        assert_eq!(insert_text(&mut context, text, 5, hello, 3).await?, 0);
+        assert_eq!(insert_text(&mut context, text, 5, comma, -1).await?, 0);
+        assert_eq!(insert_text(&mut context, text, 20, comma, -1).await?, -9);
+        assert_eq!(get_text_size(&mut context, text).await?, 10);
+
+        assert_eq!(get_text(&mut context, text, buf, 16).await?, 10);
+        assert_eq!(read_null_terminated_string_bytes(&context, buf)?, b"hello, hel");
+
+        assert_eq!(delete_text(&mut context, text, 0, 7).await?, 0);
+        set_max_text_size(&mut context, text, 2).await?;
+        assert_eq!(get_text(&mut context, text, buf, 16).await?, 2);
+        assert_eq!(read_null_terminated_string_bytes(&context, buf)?, b"he");
+
+        Ok(())
+    }
+}
@@ -18,7 +18,8 @@ pub trait WIPICContext: ByteRead + ByteWrite + Send {
     fn register_function(&mut self, method: WIPICMethodBody) -> Result<WIPICWord>;
     async fn call_function(&mut self, address: WIPICWord, args: &[WIPICWord]) -> Result<WIPICWord>;
     fn system(&mut self) -> &mut System;
-    fn spawn(&mut self, callback: WIPICMethodBody) -> Result<()>;
+    /// Spawns `callback` as a new task, returning its task id.
+    fn spawn(&mut self, callback: WIPICMethodBody) -> Result<u64>;
     async fn get_resource_size(&self, name: &str) -> Result<Option<usize>>;
     async fn read_resource(&self, name: &str) -> Result<Vec<u8>>;
 }
@@ -81,6 +82,7 @@ impl ResultConverter<()> for () {
 pub mod test {
     use alloc::{boxed::Box, vec::Vec};
 
+    use test_utils::TestPlatform;
     use wie_backend::System;
     use wie_util::{ByteRead, ByteWrite, Result};
 
@@ -89,6 +91,7 @@ pub mod test {
     pub struct TestContext {
         memory: [u8; 0x10000],
         last_alloc: usize,
+        system: System,
     }
 
     impl TestContext {
@@ -97,6 +100,7 @@ pub mod test {
             Self {
                 memory: [0; 0x10000],
                 last_alloc: 0,
+                system: System::new(Box::new(TestPlatform::new()), "test"),
             }
         }
     }
@@ -135,10 +139,10 @@ pub mod test {
         }
 
         fn system(&mut self) -> &mut System {
-            todo!()
+            &mut self.system
         }
 
-        fn spawn(&mut self, _callback: WIPICMethodBody) -> Result<()> {
+        fn spawn(&mut self, _callback: WIPICMethodBody) -> Result<u64> {
             todo!()
         }
 
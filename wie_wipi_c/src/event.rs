@@ -0,0 +1,34 @@
+//! Event types passed to clet `handleCletEvent`, with key code or pointer position as parameters.
+
+pub const MV_KEY_PRESS_EVENT: i32 = 502;
+pub const MV_KEY_RELEASE_EVENT: i32 = 503;
+pub const MV_KEY_REPEAT_EVENT: i32 = 504;
+pub const MV_POINTER_PRESS_EVENT: i32 = 505;
+pub const MV_POINTER_RELEASE_EVENT: i32 = 506;
+pub const MV_POINTER_MOVE_EVENT: i32 = 507;
+
+// MH_KEY_* key codes passed with key events, shared with WIPI java. number keys use their ascii codes
+pub const MH_KEY_0: i32 = 0x30;
+pub const MH_KEY_1: i32 = 0x31;
+pub const MH_KEY_2: i32 = 0x32;
+pub const MH_KEY_3: i32 = 0x33;
+pub const MH_KEY_4: i32 = 0x34;
+pub const MH_KEY_5: i32 = 0x35;
+pub const MH_KEY_6: i32 = 0x36;
+pub const MH_KEY_7: i32 = 0x37;
+pub const MH_KEY_8: i32 = 0x38;
+pub const MH_KEY_9: i32 = 0x39;
+pub const MH_KEY_ASTERISK: i32 = 0x2a;
+pub const MH_KEY_POUND: i32 = 0x23;
+pub const MH_KEY_UP: i32 = -1;
+pub const MH_KEY_DOWN: i32 = -2;
+pub const MH_KEY_LEFT: i32 = -3;
+pub const MH_KEY_RIGHT: i32 = -4;
+pub const MH_KEY_SELECT: i32 = -5;
+pub const MH_KEY_SOFT1: i32 = -6;
+pub const MH_KEY_SOFT2: i32 = -7;
+pub const MH_KEY_SEND: i32 = -10;
+pub const MH_KEY_END: i32 = -11;
+pub const MH_KEY_VOLUME_UP: i32 = -13;
+pub const MH_KEY_VOLUME_DOWN: i32 = -14;
+pub const MH_KEY_CLEAR: i32 = -16;
@@ -1,4 +1,9 @@
 mod card_canvas;
+mod shell_card;
 mod wipi_midlet;
 
-pub use self::{card_canvas::CardCanvas, wipi_midlet::WIPIMIDlet};
+pub use self::{
+    card_canvas::{CardCanvas, WIPIKeyCode},
+    shell_card::ShellCard,
+    wipi_midlet::WIPIMIDlet,
+};
@@ -1,42 +1,86 @@
 use alloc::vec;
 
 use java_class_proto::{JavaFieldProto, JavaMethodProto};
-use jvm::{ClassInstanceRef, Jvm, Result as JvmResult};
+use jvm::{runtime::JavaLangString, ClassInstanceRef, Jvm, Result as JvmResult};
 
+use java_runtime::classes::java::lang::String;
 use wie_jvm_support::{WieJavaClassProto, WieJvmContext};
 use wie_midp::classes::{javax::microedition::lcdui::Graphics, net::wie::MIDPKeyCode};
+use wie_wipi_c::event::{
+    MH_KEY_0, MH_KEY_1, MH_KEY_2, MH_KEY_3, MH_KEY_4, MH_KEY_5, MH_KEY_6, MH_KEY_7, MH_KEY_8, MH_KEY_9, MH_KEY_ASTERISK, MH_KEY_CLEAR, MH_KEY_DOWN,
+    MH_KEY_END, MH_KEY_LEFT, MH_KEY_POUND, MH_KEY_RIGHT, MH_KEY_SELECT, MH_KEY_SEND, MH_KEY_SOFT1, MH_KEY_SOFT2, MH_KEY_UP, MH_KEY_VOLUME_DOWN,
+    MH_KEY_VOLUME_UP,
+};
 
-use crate::classes::org::kwis::msp::lcdui::Card;
+use crate::classes::org::kwis::msp::{lcdui::Card, lwc::TextComponent};
 
+/// WIPI `MH_KEY_*` key codes.
 #[repr(i32)]
 #[allow(clippy::upper_case_acronyms)]
-#[derive(Copy, Clone)]
-enum WIPIKeyCode {
-    UP = -1,
-    DOWN = -2,
-    LEFT = -3,
-    RIGHT = -4,
-    FIRE = -5, // Ok
-
-    NUM0 = 48,
-    NUM1 = 49,
-    NUM2 = 50,
-    NUM3 = 51,
-    NUM4 = 52,
-    NUM5 = 53,
-    NUM6 = 54,
-    NUM7 = 55,
-    NUM8 = 56,
-    NUM9 = 57,
-    HASH = 35, // #
-    STAR = 42, // *
-
-    CLEAR = -16,
+#[derive(Copy, Clone, Eq, PartialEq)]
+pub enum WIPIKeyCode {
+    UP = MH_KEY_UP,
+    DOWN = MH_KEY_DOWN,
+    LEFT = MH_KEY_LEFT,
+    RIGHT = MH_KEY_RIGHT,
+    FIRE = MH_KEY_SELECT, // Ok
+
+    NUM0 = MH_KEY_0,
+    NUM1 = MH_KEY_1,
+    NUM2 = MH_KEY_2,
+    NUM3 = MH_KEY_3,
+    NUM4 = MH_KEY_4,
+    NUM5 = MH_KEY_5,
+    NUM6 = MH_KEY_6,
+    NUM7 = MH_KEY_7,
+    NUM8 = MH_KEY_8,
+    NUM9 = MH_KEY_9,
+    HASH = MH_KEY_POUND,
+    STAR = MH_KEY_ASTERISK,
+
+    SOFT1 = MH_KEY_SOFT1,
+    SOFT2 = MH_KEY_SOFT2,
+    SEND = MH_KEY_SEND,
+    END = MH_KEY_END,
+    VOLUMEUP = MH_KEY_VOLUME_UP,
+    VOLUMEDOWN = MH_KEY_VOLUME_DOWN,
+    CLEAR = MH_KEY_CLEAR,
 }
 
 impl WIPIKeyCode {
-    fn from_midp_key_code(keycode: MIDPKeyCode) -> Self {
-        match keycode {
+    pub fn from_raw(key_code: i32) -> Option<Self> {
+        Some(match key_code {
+            x if x == Self::UP as i32 => Self::UP,
+            x if x == Self::DOWN as i32 => Self::DOWN,
+            x if x == Self::LEFT as i32 => Self::LEFT,
+            x if x == Self::RIGHT as i32 => Self::RIGHT,
+            x if x == Self::FIRE as i32 => Self::FIRE,
+            x if x == Self::NUM0 as i32 => Self::NUM0,
+            x if x == Self::NUM1 as i32 => Self::NUM1,
+            x if x == Self::NUM2 as i32 => Self::NUM2,
+            x if x == Self::NUM3 as i32 => Self::NUM3,
+            x if x == Self::NUM4 as i32 => Self::NUM4,
+            x if x == Self::NUM5 as i32 => Self::NUM5,
+            x if x == Self::NUM6 as i32 => Self::NUM6,
+            x if x == Self::NUM7 as i32 => Self::NUM7,
+            x if x == Self::NUM8 as i32 => Self::NUM8,
+            x if x == Self::NUM9 as i32 => Self::NUM9,
+            x if x == Self::HASH as i32 => Self::HASH,
+            x if x == Self::STAR as i32 => Self::STAR,
+            x if x == Self::SOFT1 as i32 => Self::SOFT1,
+            x if x == Self::SOFT2 as i32 => Self::SOFT2,
+            x if x == Self::SEND as i32 => Self::SEND,
+            x if x == Self::END as i32 => Self::END,
+            x if x == Self::VOLUMEUP as i32 => Self::VOLUMEUP,
+            x if x == Self::VOLUMEDOWN as i32 => Self::VOLUMEDOWN,
+            x if x == Self::CLEAR as i32 => Self::CLEAR,
+            _ => return None,
+        })
+    }
+
+    // camera and game keys have no wipi key code
+    fn from_midp_key_code(keycode: MIDPKeyCode) -> Option<Self> {
+        Some(match keycode {
             MIDPKeyCode::UP => Self::UP,
             MIDPKeyCode::DOWN => Self::DOWN,
             MIDPKeyCode::LEFT => Self::LEFT,
@@ -54,11 +98,31 @@ impl WIPIKeyCode {
             MIDPKeyCode::KEY_NUM9 => Self::NUM9,
             MIDPKeyCode::KEY_POUND => Self::HASH,
             MIDPKeyCode::KEY_STAR => Self::STAR,
+            MIDPKeyCode::LEFT_SOFT => Self::SOFT1,
+            MIDPKeyCode::RIGHT_SOFT => Self::SOFT2,
+            MIDPKeyCode::SEND => Self::SEND,
+            MIDPKeyCode::END => Self::END,
+            MIDPKeyCode::VOLUME_UP => Self::VOLUMEUP,
+            MIDPKeyCode::VOLUME_DOWN => Self::VOLUMEDOWN,
             MIDPKeyCode::CLEAR => Self::CLEAR,
+            MIDPKeyCode::CAMERA | MIDPKeyCode::GAME_A | MIDPKeyCode::GAME_B | MIDPKeyCode::GAME_C | MIDPKeyCode::GAME_D => return None,
+        })
+    }
+
+    // unknown codes, e.g. ones passed by application, are kept as is
+    fn convert(midp_key_code: i32) -> Option<i32> {
+        match MIDPKeyCode::from_raw(midp_key_code) {
+            Some(x) => Self::from_midp_key_code(x).map(|x| x as _),
+            None => Some(midp_key_code),
         }
     }
 }
 
+// org.kwis.msp.lcdui.Card pointerNotify types
+const POINTER_PRESSED: i32 = 1;
+const POINTER_RELEASED: i32 = 2;
+const POINTER_DRAGGED: i32 = 3;
+
 // class net.wie.CardCanvas
 pub struct CardCanvas;
 
@@ -74,8 +138,13 @@ impl CardCanvas {
                 JavaMethodProto::new("keyPressed", "(I)V", Self::key_pressed, Default::default()),
                 JavaMethodProto::new("keyRepeated", "(I)V", Self::key_repeated, Default::default()),
                 JavaMethodProto::new("keyReleased", "(I)V", Self::key_released, Default::default()),
+                JavaMethodProto::new("pointerPressed", "(II)V", Self::pointer_pressed, Default::default()),
+                JavaMethodProto::new("pointerReleased", "(II)V", Self::pointer_released, Default::default()),
+                JavaMethodProto::new("pointerDragged", "(II)V", Self::pointer_dragged, Default::default()),
                 JavaMethodProto::new("pushCard", "(Lorg/kwis/msp/lcdui/Card;)V", Self::push_card, Default::default()),
+                JavaMethodProto::new("removeCard", "(Lorg/kwis/msp/lcdui/Card;)V", Self::remove_card, Default::default()),
                 JavaMethodProto::new("removeAllCards", "()V", Self::remove_all_cards, Default::default()),
+                JavaMethodProto::new("textInput", "(Ljava/lang/String;)V", Self::text_input, Default::default()),
             ],
             fields: vec![JavaFieldProto::new("cards", "Ljava/util/Vector;", Default::default())],
         }
@@ -112,17 +181,42 @@ impl CardCanvas {
         Ok(())
     }
 
+    async fn text_input(jvm: &Jvm, context: &mut WieJvmContext, this: ClassInstanceRef<Self>, text: ClassInstanceRef<String>) -> JvmResult<()> {
+        tracing::debug!("net.wie.CardCanvas::textInput({:?}, {:?})", this, text);
+
+        if TextComponent::insert_focused(jvm, text.clone()).await? {
+            return Ok(());
+        }
+
+        // no lwc text field, leave it to native text component attached to input method, which picks it up when painted
+        let text = JavaLangString::to_rust_string(jvm, &text).await?;
+        {
+            let mut input_method = context.system().input_method();
+            if !input_method.has_owner() {
+                tracing::debug!("No text field to type into, dropping {:?}", text);
+
+                return Ok(());
+            }
+            input_method.insert(&text);
+        }
+
+        jvm.invoke_virtual(&this, "repaint", "()V", ()).await
+    }
+
     async fn key_pressed(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>, key_code: i32) -> JvmResult<()> {
         tracing::debug!("net.wie.CardCanvas::keyPressed({:?}, {})", this, key_code);
 
-        let key_code = WIPIKeyCode::from_midp_key_code(MIDPKeyCode::from_raw(key_code));
+        let Some(key_code) = WIPIKeyCode::convert(key_code) else {
+            return Ok(());
+        };
 
         let cards = jvm.get_field(&this, "cards", "Ljava/util/Vector;").await?;
         let length = jvm.invoke_virtual(&cards, "size", "()I", ()).await?;
 
-        for i in 0..length {
+        // topmost card first
+        for i in (0..length).rev() {
             let card = jvm.invoke_virtual(&cards, "elementAt", "(I)Ljava/lang/Object;", (i,)).await?;
-            let propagate: bool = jvm.invoke_virtual(&card, "keyNotify", "(II)Z", (1i32, key_code as i32)).await?;
+            let propagate: bool = jvm.invoke_virtual(&card, "keyNotify", "(II)Z", (1i32, key_code)).await?;
 
             if !propagate {
                 break;
@@ -135,14 +229,17 @@ impl CardCanvas {
     async fn key_repeated(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>, key_code: i32) -> JvmResult<()> {
         tracing::debug!("net.wie.CardCanvas::keyRepeated({:?}, {})", this, key_code);
 
-        let key_code = WIPIKeyCode::from_midp_key_code(MIDPKeyCode::from_raw(key_code));
+        let Some(key_code) = WIPIKeyCode::convert(key_code) else {
+            return Ok(());
+        };
 
         let cards = jvm.get_field(&this, "cards", "Ljava/util/Vector;").await?;
         let length = jvm.invoke_virtual(&cards, "size", "()I", ()).await?;
 
-        for i in 0..length {
+        // topmost card first
+        for i in (0..length).rev() {
             let card = jvm.invoke_virtual(&cards, "elementAt", "(I)Ljava/lang/Object;", (i,)).await?;
-            let propagate: bool = jvm.invoke_virtual(&card, "keyNotify", "(II)Z", (3i32, key_code as i32)).await?;
+            let propagate: bool = jvm.invoke_virtual(&card, "keyNotify", "(II)Z", (3i32, key_code)).await?;
 
             if !propagate {
                 break;
@@ -155,14 +252,52 @@ impl CardCanvas {
     async fn key_released(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>, key_code: i32) -> JvmResult<()> {
         tracing::debug!("net.wie.CardCanvas::keyReleased({:?}, {})", this, key_code);
 
-        let key_code = WIPIKeyCode::from_midp_key_code(MIDPKeyCode::from_raw(key_code));
+        let Some(key_code) = WIPIKeyCode::convert(key_code) else {
+            return Ok(());
+        };
 
         let cards = jvm.get_field(&this, "cards", "Ljava/util/Vector;").await?;
         let length = jvm.invoke_virtual(&cards, "size", "()I", ()).await?;
 
-        for i in 0..length {
+        // topmost card first
+        for i in (0..length).rev() {
+            let card = jvm.invoke_virtual(&cards, "elementAt", "(I)Ljava/lang/Object;", (i,)).await?;
+            let propagate: bool = jvm.invoke_virtual(&card, "keyNotify", "(II)Z", (2i32, key_code)).await?;
+
+            if !propagate {
+                break;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn pointer_pressed(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>, x: i32, y: i32) -> JvmResult<()> {
+        tracing::debug!("net.wie.CardCanvas::pointerPressed({:?}, {}, {})", this, x, y);
+
+        Self::pointer_notify(jvm, &this, POINTER_PRESSED, x, y).await
+    }
+
+    async fn pointer_released(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>, x: i32, y: i32) -> JvmResult<()> {
+        tracing::debug!("net.wie.CardCanvas::pointerReleased({:?}, {}, {})", this, x, y);
+
+        Self::pointer_notify(jvm, &this, POINTER_RELEASED, x, y).await
+    }
+
+    async fn pointer_dragged(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>, x: i32, y: i32) -> JvmResult<()> {
+        tracing::debug!("net.wie.CardCanvas::pointerDragged({:?}, {}, {})", this, x, y);
+
+        Self::pointer_notify(jvm, &this, POINTER_DRAGGED, x, y).await
+    }
+
+    async fn pointer_notify(jvm: &Jvm, this: &ClassInstanceRef<Self>, r#type: i32, x: i32, y: i32) -> JvmResult<()> {
+        let cards = jvm.get_field(this, "cards", "Ljava/util/Vector;").await?;
+        let length = jvm.invoke_virtual(&cards, "size", "()I", ()).await?;
+
+        // topmost card first
+        for i in (0..length).rev() {
             let card = jvm.invoke_virtual(&cards, "elementAt", "(I)Ljava/lang/Object;", (i,)).await?;
-            let propagate: bool = jvm.invoke_virtual(&card, "keyNotify", "(II)Z", (2i32, key_code as i32)).await?;
+            let propagate: bool = jvm.invoke_virtual(&card, "pointerNotify", "(III)Z", (r#type, x, y)).await?;
 
             if !propagate {
                 break;
@@ -186,6 +321,23 @@ impl CardCanvas {
         Ok(())
     }
 
+    async fn remove_card(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>, c: ClassInstanceRef<Card>) -> JvmResult<()> {
+        tracing::debug!("net.wie.CardCanvas::removeCard({:?}, {:?})", &this, &c);
+
+        let cards = jvm.get_field(&this, "cards", "Ljava/util/Vector;").await?;
+        let removed: bool = jvm.invoke_virtual(&cards, "removeElement", "(Ljava/lang/Object;)Z", (c.clone(),)).await?;
+
+        if removed {
+            let _: () = jvm
+                .invoke_virtual(&c, "setCanvas", "(Ljavax/microedition/lcdui/Canvas;)V", (None,))
+                .await?;
+            let _: () = jvm.invoke_virtual(&c, "showNotify", "(Z)V", (false,)).await?;
+            let _: () = jvm.invoke_virtual(&this, "repaint", "()V", ()).await?;
+        }
+
+        Ok(())
+    }
+
     async fn remove_all_cards(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<()> {
         tracing::debug!("net.wie.CardCanvas::removeAllCards");
 
@@ -0,0 +1,55 @@
+use alloc::vec;
+
+use java_class_proto::{JavaFieldProto, JavaMethodProto};
+use jvm::{ClassInstanceRef, Jvm, Result as JvmResult};
+
+use wie_jvm_support::{WieJavaClassProto, WieJvmContext};
+
+use crate::classes::org::kwis::msp::{lcdui::Graphics, lwc::ShellComponent};
+
+// class net.wie.ShellCard
+pub struct ShellCard;
+
+impl ShellCard {
+    pub fn as_proto() -> WieJavaClassProto {
+        WieJavaClassProto {
+            name: "net/wie/ShellCard",
+            parent_class: Some("org/kwis/msp/lcdui/Card"),
+            interfaces: vec![],
+            methods: vec![
+                JavaMethodProto::new("<init>", "(Lorg/kwis/msp/lwc/ShellComponent;)V", Self::init, Default::default()),
+                JavaMethodProto::new("paint", "(Lorg/kwis/msp/lcdui/Graphics;)V", Self::paint, Default::default()),
+                JavaMethodProto::new("keyNotify", "(II)Z", Self::key_notify, Default::default()),
+            ],
+            fields: vec![JavaFieldProto::new("shell", "Lorg/kwis/msp/lwc/ShellComponent;", Default::default())],
+        }
+    }
+
+    async fn init(jvm: &Jvm, _: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, shell: ClassInstanceRef<ShellComponent>) -> JvmResult<()> {
+        tracing::debug!("net.wie.ShellCard::<init>({:?}, {:?})", &this, &shell);
+
+        let _: () = jvm.invoke_special(&this, "org/kwis/msp/lcdui/Card", "<init>", "()V", ()).await?;
+
+        jvm.put_field(&mut this, "shell", "Lorg/kwis/msp/lwc/ShellComponent;", shell).await?;
+
+        Ok(())
+    }
+
+    async fn paint(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>, graphics: ClassInstanceRef<Graphics>) -> JvmResult<()> {
+        tracing::debug!("net.wie.ShellCard::paint({:?}, {:?})", &this, &graphics);
+
+        let shell = jvm.get_field(&this, "shell", "Lorg/kwis/msp/lwc/ShellComponent;").await?;
+
+        jvm.invoke_virtual(&shell, "paint", "(Lorg/kwis/msp/lcdui/Graphics;)V", (graphics,)).await
+    }
+
+    // keys not consumed by the shell propagate to cards below
+    async fn key_notify(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>, r#type: i32, key: i32) -> JvmResult<bool> {
+        tracing::debug!("net.wie.ShellCard::keyNotify({:?}, {}, {})", &this, r#type, key);
+
+        let shell = jvm.get_field(&this, "shell", "Lorg/kwis/msp/lwc/ShellComponent;").await?;
+        let consumed: bool = jvm.invoke_virtual(&shell, "keyNotify", "(II)Z", (r#type, key)).await?;
+
+        Ok(!consumed)
+    }
+}
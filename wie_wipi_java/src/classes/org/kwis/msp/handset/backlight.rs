@@ -20,8 +20,10 @@ impl BackLight {
         }
     }
 
-    async fn always_on(_: &Jvm, _: &mut WieJvmContext) -> JvmResult<()> {
-        tracing::warn!("stub org.kwis.msp.handset.Backlight::alwaysOn");
+    async fn always_on(_: &Jvm, context: &mut WieJvmContext) -> JvmResult<()> {
+        tracing::debug!("org.kwis.msp.handset.Backlight::alwaysOn()");
+
+        context.system().device().set_backlight(true, None);
 
         Ok(())
     }
@@ -1,4 +1,4 @@
-use alloc::vec;
+use alloc::{string::ToString, vec};
 
 use java_class_proto::JavaMethodProto;
 use java_constants::MethodAccessFlags;
@@ -26,16 +26,16 @@ impl HandsetProperty {
         }
     }
 
-    async fn get_system_property(jvm: &Jvm, _: &mut WieJvmContext, name: ClassInstanceRef<String>) -> JvmResult<ClassInstanceRef<String>> {
+    async fn get_system_property(jvm: &Jvm, context: &mut WieJvmContext, name: ClassInstanceRef<String>) -> JvmResult<ClassInstanceRef<String>> {
         let name = JavaLangString::to_rust_string(jvm, &name).await?;
         tracing::warn!("stub org.kwis.msp.handset.HandsetProperty::getSystemProperty({})", name);
 
         let value = match name.as_ref() {
-            "VIBRATORLEVEL" => "0",
-            _ => "",
+            "VIBRATORLEVEL" => context.system().device().vibration_level_count().to_string(),
+            _ => "".into(),
         };
 
-        let result = JavaLangString::from_rust_string(jvm, value).await?;
+        let result = JavaLangString::from_rust_string(jvm, &value).await?;
         Ok(result.into())
     }
 }
@@ -42,6 +42,7 @@ impl Display {
                 JavaMethodProto::new("isDoubleBuffered", "()Z", Self::is_double_buffered, Default::default()),
                 JavaMethodProto::new("getDockedCard", "()Lorg/kwis/msp/lcdui/Card;", Self::get_docked_card, Default::default()),
                 JavaMethodProto::new("pushCard", "(Lorg/kwis/msp/lcdui/Card;)V", Self::push_card, Default::default()),
+                JavaMethodProto::new("removeCard", "(Lorg/kwis/msp/lcdui/Card;)V", Self::remove_card, Default::default()),
                 JavaMethodProto::new("removeAllCards", "()V", Self::remove_all_cards, Default::default()),
                 JavaMethodProto::new(
                     "addJletEventListener",
@@ -148,6 +149,17 @@ impl Display {
         Ok(())
     }
 
+    async fn remove_card(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>, c: ClassInstanceRef<Card>) -> JvmResult<()> {
+        tracing::debug!("org.kwis.msp.lcdui.Display::removeCard({:?}, {:?})", &this, &c);
+
+        let card_canvas = jvm.get_field(&this, "cardCanvas", "Lnet/wie/CardCanvas;").await?;
+        let _: () = jvm
+            .invoke_virtual(&card_canvas, "removeCard", "(Lorg/kwis/msp/lcdui/Card;)V", (c,))
+            .await?;
+
+        Ok(())
+    }
+
     async fn remove_all_cards(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<()> {
         tracing::debug!("org.kwis.msp.lcdui.Display::removeAllCards({:?})", &this);
 
@@ -136,10 +136,11 @@ impl Graphics {
             .await
     }
 
-    async fn set_alpha(_jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>, alpha: i32) -> JvmResult<()> {
-        tracing::warn!("stub org.kwis.msp.lcdui.Graphics::setAlpha({:?}, {})", &this, alpha);
+    async fn set_alpha(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>, alpha: i32) -> JvmResult<()> {
+        tracing::debug!("org.kwis.msp.lcdui.Graphics::setAlpha({:?}, {})", &this, alpha);
 
-        Ok(())
+        let mut midp_graphics = jvm.get_field(&this, "midpGraphics", "Ljavax/microedition/lcdui/Graphics;").await?;
+        MidpGraphics::set_alpha(jvm, &mut midp_graphics, alpha).await
     }
 
     async fn fill_rect(
@@ -521,9 +522,10 @@ impl Graphics {
         jvm.invoke_virtual(&midp_graphics, "setGrayScale", "(I)V", (value,)).await
     }
 
-    async fn set_xor_mode(_jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>, xor_mode: bool) -> JvmResult<()> {
-        tracing::warn!("stub org.kwis.msp.lcdui.Graphics::setXORMode({:?}, {})", &this, xor_mode);
+    async fn set_xor_mode(jvm: &Jvm, _context: &mut WieJvmContext, this: ClassInstanceRef<Self>, xor_mode: bool) -> JvmResult<()> {
+        tracing::debug!("org.kwis.msp.lcdui.Graphics::setXORMode({:?}, {})", &this, xor_mode);
 
-        Ok(())
+        let mut midp_graphics = jvm.get_field(&this, "midpGraphics", "Ljavax/microedition/lcdui/Graphics;").await?;
+        MidpGraphics::set_xor_mode(jvm, &mut midp_graphics, xor_mode).await
     }
 }
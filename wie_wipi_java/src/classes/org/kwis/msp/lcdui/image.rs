@@ -1,10 +1,13 @@
-use alloc::vec;
+use alloc::{vec, vec::Vec};
+
+use bytemuck::cast_vec;
 
 use java_class_proto::{JavaFieldProto, JavaMethodProto};
 use java_constants::MethodAccessFlags;
 use java_runtime::classes::java::lang::String;
-use jvm::{Array, ClassInstanceRef, Jvm, Result as JvmResult};
+use jvm::{runtime::JavaLangString, Array, ClassInstanceRef, Jvm, Result as JvmResult};
 
+use wie_backend::canvas::Image as BackendImage;
 use wie_jvm_support::{WieJavaClassProto, WieJvmContext};
 use wie_midp::classes::javax::microedition::lcdui::{Graphics as MidpGraphics, Image as MidpImage};
 
@@ -42,8 +45,19 @@ impl Image {
                 JavaMethodProto::new("getGraphics", "()Lorg/kwis/msp/lcdui/Graphics;", Self::get_graphics, Default::default()),
                 JavaMethodProto::new("getWidth", "()I", Self::get_width, Default::default()),
                 JavaMethodProto::new("getHeight", "()I", Self::get_height, Default::default()),
+                JavaMethodProto::new("getFrameCount", "()I", Self::get_frame_count, Default::default()),
+                JavaMethodProto::new("getCurrentFrame", "()I", Self::get_current_frame, Default::default()),
+                JavaMethodProto::new("setCurrentFrame", "(I)V", Self::set_current_frame, Default::default()),
+                JavaMethodProto::new("getFrameDelay", "(I)I", Self::get_frame_delay, Default::default()),
+                JavaMethodProto::new("getLoopCount", "()I", Self::get_loop_count, Default::default()),
+            ],
+            fields: vec![
+                JavaFieldProto::new("midpImage", "Ljavax/microedition/lcdui/Image;", Default::default()),
+                JavaFieldProto::new("frames", "[Ljavax/microedition/lcdui/Image;", Default::default()),
+                JavaFieldProto::new("frameDelays", "[I", Default::default()),
+                JavaFieldProto::new("currentFrame", "I", Default::default()),
+                JavaFieldProto::new("loopCount", "I", Default::default()),
             ],
-            fields: vec![JavaFieldProto::new("midpImage", "Ljavax/microedition/lcdui/Image;", Default::default())],
         }
     }
 
@@ -77,45 +91,38 @@ impl Image {
     async fn create_image_from_name(jvm: &Jvm, _: &mut WieJvmContext, name: ClassInstanceRef<String>) -> JvmResult<ClassInstanceRef<Image>> {
         tracing::debug!("org.kwis.msp.lcdui.Image::createImage({:?})", &name);
 
-        let midp_image: ClassInstanceRef<MidpImage> = jvm
-            .invoke_static(
-                "javax/microedition/lcdui/Image",
-                "createImage",
-                "(Ljava/lang/String;)Ljavax/microedition/lcdui/Image;",
-                (name,),
-            )
-            .await?;
+        let name = JavaLangString::to_rust_string(jvm, &name).await?;
+        let image = MidpImage::decode_resource(jvm, &name).await?;
 
-        let instance = jvm
-            .new_class("org/kwis/msp/lcdui/Image", "(Ljavax/microedition/lcdui/Image;)V", (midp_image,))
-            .await?;
-
-        Ok(instance.into())
+        Self::from_backend_image(jvm, &*image).await
     }
 
     async fn create_image_from_data(
         jvm: &Jvm,
         _: &mut WieJvmContext,
         data: ClassInstanceRef<Array<i8>>,
-        width: i32,
-        height: i32,
+        offset: i32,
+        length: i32,
     ) -> JvmResult<ClassInstanceRef<Image>> {
-        tracing::debug!("org.kwis.msp.lcdui.Image::createImage({:?}, {}, {})", &data, width, height);
+        tracing::debug!("org.kwis.msp.lcdui.Image::createImage({:?}, {}, {})", &data, offset, length);
 
-        let midp_image: ClassInstanceRef<MidpImage> = jvm
-            .invoke_static(
-                "javax/microedition/lcdui/Image",
-                "createImage",
-                "([BII)Ljavax/microedition/lcdui/Image;",
-                (data, width, height),
-            )
-            .await?;
+        if data.is_null() {
+            return Err(jvm.exception("java/lang/NullPointerException", "data is null").await);
+        }
 
-        let instance = jvm
-            .new_class("org/kwis/msp/lcdui/Image", "(Ljavax/microedition/lcdui/Image;)V", (midp_image,))
-            .await?;
+        let data_length = jvm.array_length(&data).await? as i32;
+        if offset < 0 || length < 0 || offset > data_length - length {
+            return Err(jvm
+                .exception("java/lang/ArrayIndexOutOfBoundsException", "Invalid offset or length")
+                .await);
+        }
 
-        Ok(instance.into())
+        let mut image_data = vec![0; length as usize];
+        jvm.array_raw_buffer(&data).await?.read(offset as _, &mut image_data)?;
+
+        let image = MidpImage::decode(jvm, &cast_vec(image_data)).await?;
+
+        Self::from_backend_image(jvm, &*image).await
     }
 
     async fn get_graphics(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Image>) -> JvmResult<ClassInstanceRef<Graphics>> {
@@ -150,7 +157,97 @@ impl Image {
         jvm.invoke_virtual(&midp_image, "getHeight", "()I", ()).await
     }
 
+    async fn get_frame_count(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Image>) -> JvmResult<i32> {
+        tracing::debug!("org.kwis.msp.lcdui.Image::getFrameCount({:?})", &this);
+
+        let frames: ClassInstanceRef<Array<MidpImage>> = jvm.get_field(&this, "frames", "[Ljavax/microedition/lcdui/Image;").await?;
+        if frames.is_null() {
+            return Ok(1);
+        }
+
+        Ok(jvm.array_length(&frames).await? as _)
+    }
+
+    async fn get_current_frame(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Image>) -> JvmResult<i32> {
+        tracing::debug!("org.kwis.msp.lcdui.Image::getCurrentFrame({:?})", &this);
+
+        jvm.get_field(&this, "currentFrame", "I").await
+    }
+
+    async fn set_current_frame(jvm: &Jvm, _: &mut WieJvmContext, mut this: ClassInstanceRef<Image>, index: i32) -> JvmResult<()> {
+        tracing::debug!("org.kwis.msp.lcdui.Image::setCurrentFrame({:?}, {})", &this, index);
+
+        let frames: ClassInstanceRef<Array<MidpImage>> = jvm.get_field(&this, "frames", "[Ljavax/microedition/lcdui/Image;").await?;
+        if frames.is_null() {
+            return Ok(());
+        }
+
+        let frame_count = jvm.array_length(&frames).await? as i32;
+        if index < 0 || index >= frame_count {
+            return Err(jvm.exception("java/lang/IndexOutOfBoundsException", "invalid frame index").await);
+        }
+
+        // drawing and getGraphics use midpImage, so switching frames is replacing it
+        let frame: Vec<ClassInstanceRef<MidpImage>> = jvm.load_array(&frames, index as _, 1).await?;
+        jvm.put_field(&mut this, "midpImage", "Ljavax/microedition/lcdui/Image;", frame[0].clone())
+            .await?;
+        jvm.put_field(&mut this, "currentFrame", "I", index).await?;
+
+        Ok(())
+    }
+
+    async fn get_frame_delay(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Image>, index: i32) -> JvmResult<i32> {
+        tracing::debug!("org.kwis.msp.lcdui.Image::getFrameDelay({:?}, {})", &this, index);
+
+        let delays: ClassInstanceRef<Array<i32>> = jvm.get_field(&this, "frameDelays", "[I").await?;
+        if delays.is_null() || index < 0 || index as usize >= jvm.array_length(&delays).await? {
+            return Ok(0);
+        }
+
+        let delay: Vec<i32> = jvm.load_array(&delays, index as _, 1).await?;
+
+        Ok(delay[0])
+    }
+
+    async fn get_loop_count(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Image>) -> JvmResult<i32> {
+        tracing::debug!("org.kwis.msp.lcdui.Image::getLoopCount({:?})", &this);
+
+        jvm.get_field(&this, "loopCount", "I").await
+    }
+
     pub async fn midp_image(jvm: &Jvm, this: &ClassInstanceRef<Image>) -> JvmResult<ClassInstanceRef<MidpImage>> {
         jvm.get_field(this, "midpImage", "Ljavax/microedition/lcdui/Image;").await
     }
+
+    // frames of animated image are kept as separate immutable images
+    async fn from_backend_image(jvm: &Jvm, image: &dyn BackendImage) -> JvmResult<ClassInstanceRef<Image>> {
+        let midp_image = MidpImage::from_backend_image(jvm, &*image).await?;
+
+        let mut instance: ClassInstanceRef<Image> = jvm
+            .new_class("org/kwis/msp/lcdui/Image", "(Ljavax/microedition/lcdui/Image;)V", (midp_image,))
+            .await?
+            .into();
+
+        let frames = image.frames();
+        if !frames.is_empty() {
+            let mut midp_frames = Vec::with_capacity(frames.len());
+            for frame in frames {
+                midp_frames.push(MidpImage::from_backend_image(jvm, &*frame.image).await?);
+            }
+
+            let mut frames_array = jvm.instantiate_array("Ljavax/microedition/lcdui/Image;", frames.len()).await?;
+            jvm.store_array(&mut frames_array, 0, midp_frames).await?;
+
+            let mut delays_array = jvm.instantiate_array("I", frames.len()).await?;
+            jvm.store_array(&mut delays_array, 0, frames.iter().map(|x| x.delay as i32).collect::<Vec<_>>())
+                .await?;
+
+            jvm.put_field(&mut instance, "frames", "[Ljavax/microedition/lcdui/Image;", frames_array)
+                .await?;
+            jvm.put_field(&mut instance, "frameDelays", "[I", delays_array).await?;
+            jvm.put_field(&mut instance, "loopCount", "I", image.loop_count() as i32).await?;
+        }
+
+        Ok(instance)
+    }
 }
@@ -1,12 +1,16 @@
 mod annunciator_component;
+mod button_component;
 mod component;
+mod component_listener;
 mod container_component;
+mod list_component;
 mod shell_component;
 mod text_box_component;
 mod text_component;
 mod text_field_component;
 
 pub use self::{
-    annunciator_component::AnnunciatorComponent, component::Component, container_component::ContainerComponent, shell_component::ShellComponent,
-    text_box_component::TextBoxComponent, text_component::TextComponent, text_field_component::TextFieldComponent,
+    annunciator_component::AnnunciatorComponent, button_component::ButtonComponent, component::Component, component_listener::ComponentListener,
+    container_component::ContainerComponent, list_component::ListComponent, shell_component::ShellComponent, text_box_component::TextBoxComponent,
+    text_component::TextComponent, text_field_component::TextFieldComponent,
 };
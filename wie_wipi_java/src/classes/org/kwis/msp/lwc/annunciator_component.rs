@@ -1,10 +1,25 @@
-use alloc::vec;
+use alloc::{format, vec};
 
 use java_class_proto::JavaMethodProto;
 use jvm::{ClassInstanceRef, Jvm, Result as JvmResult};
 
 use wie_jvm_support::{WieJavaClassProto, WieJvmContext};
 
+use crate::classes::org::kwis::msp::{
+    lcdui::Graphics,
+    lwc::{
+        component::{FOCUS_TEXT_COLOR, PADDING},
+        Component,
+    },
+};
+
+const BACKGROUND_COLOR: i32 = 0x202020;
+
+// handsets show korean standard time
+const TIMEZONE_OFFSET: u64 = 9 * 60 * 60 * 1000;
+
+const SIGNAL_BARS: i32 = 4;
+
 // class org.kwis.msp.lwc.AnnunciatorComponent
 pub struct AnnunciatorComponent;
 
@@ -16,21 +31,102 @@ impl AnnunciatorComponent {
             interfaces: vec![],
             methods: vec![
                 JavaMethodProto::new("<init>", "(Z)V", Self::init, Default::default()),
-                JavaMethodProto::new("show", "()V", Self::show, Default::default()),
+                JavaMethodProto::new("getPreferredHeight", "()I", Self::get_preferred_height, Default::default()),
+                JavaMethodProto::new("keyNotify", "(II)Z", Self::key_notify, Default::default()),
+                JavaMethodProto::new(
+                    "paintContent",
+                    "(Lorg/kwis/msp/lcdui/Graphics;)V",
+                    Self::paint_content,
+                    Default::default(),
+                ),
             ],
             fields: vec![],
         }
     }
 
-    async fn init(_: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<AnnunciatorComponent>, a0: bool) -> JvmResult<()> {
-        tracing::warn!("stub org.kwis.msp.lwc.AnnunciatorComponent::<init>({:?}, {})", &this, a0);
+    async fn init(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<AnnunciatorComponent>, visible: bool) -> JvmResult<()> {
+        tracing::debug!("org.kwis.msp.lwc.AnnunciatorComponent::<init>({:?}, {})", &this, visible);
+
+        let _: () = jvm.invoke_special(&this, "org/kwis/msp/lwc/ShellComponent", "<init>", "()V", ()).await?;
+
+        // annunciator is a strip on top of the screen
+        let width: i32 = jvm.invoke_virtual(&this, "getWidth", "()I", ()).await?;
+        let height: i32 = jvm.invoke_virtual(&this, "getPreferredHeight", "()I", ()).await?;
+        let _: () = jvm.invoke_virtual(&this, "setSize", "(II)V", (width, height)).await?;
+
+        jvm.invoke_virtual(&this, "setVisible", "(Z)V", (visible,)).await
+    }
+
+    async fn get_preferred_height(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<AnnunciatorComponent>) -> JvmResult<i32> {
+        tracing::debug!("org.kwis.msp.lwc.AnnunciatorComponent::getPreferredHeight({:?})", &this);
 
-        Ok(())
+        Ok(Component::font_height(jvm).await? + PADDING * 2)
     }
 
-    async fn show(_: &Jvm, _: &mut WieJvmContext) -> JvmResult<()> {
-        tracing::warn!("stub org.kwis.msp.lwc.AnnunciatorComponent::show");
+    // annunciator never takes keys
+    async fn key_notify(_: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<AnnunciatorComponent>, r#type: i32, key: i32) -> JvmResult<bool> {
+        tracing::debug!("org.kwis.msp.lwc.AnnunciatorComponent::keyNotify({:?}, {}, {})", &this, r#type, key);
+
+        Ok(false)
+    }
+
+    // signal strength on the left, clock in the middle, battery on the right
+    async fn paint_content(
+        jvm: &Jvm,
+        context: &mut WieJvmContext,
+        this: ClassInstanceRef<AnnunciatorComponent>,
+        graphics: ClassInstanceRef<Graphics>,
+    ) -> JvmResult<()> {
+        tracing::debug!("org.kwis.msp.lwc.AnnunciatorComponent::paintContent({:?}, {:?})", &this, &graphics);
+
+        let (width, height) = Component::size(jvm, &this).await?;
+        let icon_height = height - PADDING * 2;
+
+        Component::fill_rect(jvm, &graphics, BACKGROUND_COLOR, 0, 0, width, height).await?;
+
+        for i in 0..SIGNAL_BARS {
+            let bar_height = icon_height * (i + 1) / SIGNAL_BARS;
+            Component::fill_rect(
+                jvm,
+                &graphics,
+                FOCUS_TEXT_COLOR,
+                PADDING + i * 3,
+                PADDING + icon_height - bar_height,
+                2,
+                bar_height,
+            )
+            .await?;
+        }
+
+        let battery_width = icon_height * 2;
+        let battery_x = width - PADDING * 2 - battery_width;
+        Component::draw_rect(jvm, &graphics, FOCUS_TEXT_COLOR, battery_x, PADDING, battery_width - 1, icon_height - 1).await?;
+        Component::fill_rect(
+            jvm,
+            &graphics,
+            FOCUS_TEXT_COLOR,
+            battery_x + battery_width,
+            PADDING + icon_height / 4,
+            2,
+            icon_height / 2,
+        )
+        .await?;
+        Component::fill_rect(
+            jvm,
+            &graphics,
+            FOCUS_TEXT_COLOR,
+            battery_x + 2,
+            PADDING + 2,
+            battery_width - 4,
+            icon_height - 4,
+        )
+        .await?;
+
+        let now = context.system().platform().now().raw() + TIMEZONE_OFFSET;
+        let minutes = now / 1000 / 60;
+        let time = format!("{:02}:{:02}", (minutes / 60) % 24, minutes % 60);
 
-        Ok(())
+        let time_width = Component::string_width(jvm, &time).await?;
+        Component::draw_string(jvm, &graphics, FOCUS_TEXT_COLOR, &time, (width - time_width) / 2, PADDING).await
     }
 }
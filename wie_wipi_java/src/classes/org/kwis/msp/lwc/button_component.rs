@@ -0,0 +1,110 @@
+use alloc::vec;
+
+use java_class_proto::{JavaFieldProto, JavaMethodProto};
+use java_runtime::classes::java::lang::String;
+use jvm::{runtime::JavaLangString, ClassInstanceRef, Jvm, Result as JvmResult};
+
+use wie_jvm_support::{WieJavaClassProto, WieJvmContext};
+
+use crate::classes::{
+    net::wie::WIPIKeyCode,
+    org::kwis::msp::{
+        lcdui::Graphics,
+        lwc::{
+            component::{BACKGROUND_COLOR, BORDER_COLOR, EVENT_ACTION, FOCUS_COLOR, FOCUS_TEXT_COLOR, FOREGROUND_COLOR, KEY_PRESSED, PADDING},
+            Component,
+        },
+    },
+};
+
+// class org.kwis.msp.lwc.ButtonComponent
+pub struct ButtonComponent;
+
+impl ButtonComponent {
+    pub fn as_proto() -> WieJavaClassProto {
+        WieJavaClassProto {
+            name: "org/kwis/msp/lwc/ButtonComponent",
+            parent_class: Some("org/kwis/msp/lwc/Component"),
+            interfaces: vec![],
+            methods: vec![
+                JavaMethodProto::new("<init>", "(Ljava/lang/String;)V", Self::init, Default::default()),
+                JavaMethodProto::new("getLabel", "()Ljava/lang/String;", Self::get_label, Default::default()),
+                JavaMethodProto::new("setLabel", "(Ljava/lang/String;)V", Self::set_label, Default::default()),
+                JavaMethodProto::new("isFocusable", "()Z", Self::is_focusable, Default::default()),
+                JavaMethodProto::new("keyNotify", "(II)Z", Self::key_notify, Default::default()),
+                JavaMethodProto::new(
+                    "paintContent",
+                    "(Lorg/kwis/msp/lcdui/Graphics;)V",
+                    Self::paint_content,
+                    Default::default(),
+                ),
+            ],
+            fields: vec![JavaFieldProto::new("label", "Ljava/lang/String;", Default::default())],
+        }
+    }
+
+    async fn init(jvm: &Jvm, _: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, label: ClassInstanceRef<String>) -> JvmResult<()> {
+        tracing::debug!("org.kwis.msp.lwc.ButtonComponent::<init>({:?}, {:?})", &this, &label);
+
+        let _: () = jvm.invoke_special(&this, "org/kwis/msp/lwc/Component", "<init>", "()V", ()).await?;
+
+        jvm.put_field(&mut this, "label", "Ljava/lang/String;", label).await
+    }
+
+    async fn get_label(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<ClassInstanceRef<String>> {
+        tracing::debug!("org.kwis.msp.lwc.ButtonComponent::getLabel({:?})", &this);
+
+        jvm.get_field(&this, "label", "Ljava/lang/String;").await
+    }
+
+    async fn set_label(jvm: &Jvm, _: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, label: ClassInstanceRef<String>) -> JvmResult<()> {
+        tracing::debug!("org.kwis.msp.lwc.ButtonComponent::setLabel({:?}, {:?})", &this, &label);
+
+        jvm.put_field(&mut this, "label", "Ljava/lang/String;", label).await?;
+
+        jvm.invoke_virtual(&this, "repaint", "()V", ()).await
+    }
+
+    async fn is_focusable(_: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<bool> {
+        tracing::debug!("org.kwis.msp.lwc.ButtonComponent::isFocusable({:?})", &this);
+
+        Ok(true)
+    }
+
+    async fn key_notify(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>, r#type: i32, key: i32) -> JvmResult<bool> {
+        tracing::debug!("org.kwis.msp.lwc.ButtonComponent::keyNotify({:?}, {}, {})", &this, r#type, key);
+
+        if r#type != KEY_PRESSED || WIPIKeyCode::from_raw(key) != Some(WIPIKeyCode::FIRE) {
+            return Ok(false);
+        }
+
+        Component::notify_listener(jvm, &this, EVENT_ACTION).await?;
+
+        Ok(true)
+    }
+
+    async fn paint_content(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>, graphics: ClassInstanceRef<Graphics>) -> JvmResult<()> {
+        tracing::debug!("org.kwis.msp.lwc.ButtonComponent::paintContent({:?}, {:?})", &this, &graphics);
+
+        let (width, height) = Component::size(jvm, &this).await?;
+        let focused: bool = jvm.invoke_virtual(&this, "hasFocus", "()Z", ()).await?;
+
+        let (background_color, text_color) = if focused {
+            (FOCUS_COLOR, FOCUS_TEXT_COLOR)
+        } else {
+            (BACKGROUND_COLOR, FOREGROUND_COLOR)
+        };
+        Component::fill_rect(jvm, &graphics, background_color, 0, 0, width, height).await?;
+        Component::draw_rect(jvm, &graphics, BORDER_COLOR, 0, 0, width - 1, height - 1).await?;
+
+        let label: ClassInstanceRef<String> = jvm.get_field(&this, "label", "Ljava/lang/String;").await?;
+        if label.is_null() {
+            return Ok(());
+        }
+        let label = JavaLangString::to_rust_string(jvm, &label).await?;
+
+        // label is centered
+        let label_width = Component::string_width(jvm, &label).await?;
+        Component::draw_string(jvm, &graphics, text_color, &label, (width - label_width) / 2, PADDING).await
+    }
+}
@@ -1,10 +1,35 @@
 use alloc::vec;
 
-use java_class_proto::JavaMethodProto;
-use jvm::{ClassInstanceRef, Jvm, Result as JvmResult};
+use java_class_proto::{JavaFieldProto, JavaMethodProto};
+use jvm::{runtime::JavaLangString, ClassInstanceRef, Jvm, Result as JvmResult};
 
 use wie_jvm_support::{WieJavaClassProto, WieJvmContext};
 
+use crate::classes::org::kwis::msp::{
+    lcdui::{Font, Graphics},
+    lwc::{ComponentListener, ContainerComponent},
+};
+
+// keyNotify types, same as org.kwis.msp.lcdui.Card
+pub const KEY_PRESSED: i32 = 1;
+pub const KEY_RELEASED: i32 = 2;
+pub const KEY_REPEATED: i32 = 3;
+
+// ComponentListener events
+pub const EVENT_ACTION: i32 = 1;
+pub const EVENT_CHANGED: i32 = 2;
+
+pub const BACKGROUND_COLOR: i32 = 0xffffff;
+pub const FOREGROUND_COLOR: i32 = 0x000000;
+pub const FOCUS_COLOR: i32 = 0x3060c0;
+pub const FOCUS_TEXT_COLOR: i32 = 0xffffff;
+pub const BORDER_COLOR: i32 = 0x808080;
+
+// Graphics anchor
+pub const TOP_LEFT: i32 = 16 | 4;
+
+pub const PADDING: i32 = 2;
+
 // class org.kwis.msp.lwc.Component
 pub struct Component;
 
@@ -15,29 +40,321 @@ impl Component {
             parent_class: Some("java/lang/Object"),
             interfaces: vec![],
             methods: vec![
-                JavaMethodProto::new("keyNotify", "(II)Z", Self::key_notify, Default::default()),
-                JavaMethodProto::new("setFocus", "()V", Self::set_focus, Default::default()),
+                JavaMethodProto::new("<init>", "()V", Self::init, Default::default()),
+                JavaMethodProto::new("getX", "()I", Self::get_x, Default::default()),
+                JavaMethodProto::new("getY", "()I", Self::get_y, Default::default()),
+                JavaMethodProto::new("getWidth", "()I", Self::get_width, Default::default()),
                 JavaMethodProto::new("getHeight", "()I", Self::get_height, Default::default()),
+                JavaMethodProto::new("setLocation", "(II)V", Self::set_location, Default::default()),
+                JavaMethodProto::new("setSize", "(II)V", Self::set_size, Default::default()),
+                JavaMethodProto::new("getPreferredWidth", "()I", Self::get_preferred_width, Default::default()),
+                JavaMethodProto::new("getPreferredHeight", "()I", Self::get_preferred_height, Default::default()),
+                JavaMethodProto::new("isFocusable", "()Z", Self::is_focusable, Default::default()),
+                JavaMethodProto::new("hasFocus", "()Z", Self::has_focus, Default::default()),
+                JavaMethodProto::new("setFocus", "()V", Self::set_focus, Default::default()),
+                JavaMethodProto::new("isVisible", "()Z", Self::is_visible, Default::default()),
+                JavaMethodProto::new("setVisible", "(Z)V", Self::set_visible, Default::default()),
+                JavaMethodProto::new(
+                    "getParent",
+                    "()Lorg/kwis/msp/lwc/ContainerComponent;",
+                    Self::get_parent,
+                    Default::default(),
+                ),
+                JavaMethodProto::new(
+                    "setComponentListener",
+                    "(Lorg/kwis/msp/lwc/ComponentListener;)V",
+                    Self::set_component_listener,
+                    Default::default(),
+                ),
+                JavaMethodProto::new("keyNotify", "(II)Z", Self::key_notify, Default::default()),
+                JavaMethodProto::new("paint", "(Lorg/kwis/msp/lcdui/Graphics;)V", Self::paint, Default::default()),
+                JavaMethodProto::new(
+                    "paintContent",
+                    "(Lorg/kwis/msp/lcdui/Graphics;)V",
+                    Self::paint_content,
+                    Default::default(),
+                ),
+                JavaMethodProto::new("repaint", "()V", Self::repaint, Default::default()),
+                // wie private
+                JavaMethodProto::new(
+                    "setParent",
+                    "(Lorg/kwis/msp/lwc/ContainerComponent;)V",
+                    Self::set_parent,
+                    Default::default(),
+                ),
+                JavaMethodProto::new("setFocused", "(Z)V", Self::set_focused, Default::default()),
             ],
-            fields: vec![],
+            fields: vec![
+                JavaFieldProto::new("x", "I", Default::default()),
+                JavaFieldProto::new("y", "I", Default::default()),
+                JavaFieldProto::new("w", "I", Default::default()),
+                JavaFieldProto::new("h", "I", Default::default()),
+                JavaFieldProto::new("visible", "Z", Default::default()),
+                JavaFieldProto::new("focused", "Z", Default::default()),
+                JavaFieldProto::new("parent", "Lorg/kwis/msp/lwc/ContainerComponent;", Default::default()),
+                JavaFieldProto::new("listener", "Lorg/kwis/msp/lwc/ComponentListener;", Default::default()),
+            ],
+        }
+    }
+
+    async fn init(jvm: &Jvm, _: &mut WieJvmContext, mut this: ClassInstanceRef<Self>) -> JvmResult<()> {
+        tracing::debug!("org.kwis.msp.lwc.Component::<init>({:?})", &this);
+
+        let _: () = jvm.invoke_special(&this, "java/lang/Object", "<init>", "()V", ()).await?;
+
+        jvm.put_field(&mut this, "visible", "Z", true).await?;
+
+        Ok(())
+    }
+
+    async fn get_x(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("org.kwis.msp.lwc.Component::getX({:?})", &this);
+
+        jvm.get_field(&this, "x", "I").await
+    }
+
+    async fn get_y(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("org.kwis.msp.lwc.Component::getY({:?})", &this);
+
+        jvm.get_field(&this, "y", "I").await
+    }
+
+    // size is preferred size until it's set by layout or application
+    async fn get_width(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("org.kwis.msp.lwc.Component::getWidth({:?})", &this);
+
+        let width: i32 = jvm.get_field(&this, "w", "I").await?;
+        if width > 0 {
+            return Ok(width);
+        }
+
+        jvm.invoke_virtual(&this, "getPreferredWidth", "()I", ()).await
+    }
+
+    async fn get_height(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("org.kwis.msp.lwc.Component::getHeight({:?})", &this);
+
+        let height: i32 = jvm.get_field(&this, "h", "I").await?;
+        if height > 0 {
+            return Ok(height);
+        }
+
+        jvm.invoke_virtual(&this, "getPreferredHeight", "()I", ()).await
+    }
+
+    async fn set_location(jvm: &Jvm, _: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, x: i32, y: i32) -> JvmResult<()> {
+        tracing::debug!("org.kwis.msp.lwc.Component::setLocation({:?}, {}, {})", &this, x, y);
+
+        jvm.put_field(&mut this, "x", "I", x).await?;
+        jvm.put_field(&mut this, "y", "I", y).await?;
+
+        Ok(())
+    }
+
+    async fn set_size(jvm: &Jvm, _: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, width: i32, height: i32) -> JvmResult<()> {
+        tracing::debug!("org.kwis.msp.lwc.Component::setSize({:?}, {}, {})", &this, width, height);
+
+        jvm.put_field(&mut this, "w", "I", width).await?;
+        jvm.put_field(&mut this, "h", "I", height).await?;
+
+        Ok(())
+    }
+
+    // components fill the width of the parent, or the screen if there's no parent
+    async fn get_preferred_width(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("org.kwis.msp.lwc.Component::getPreferredWidth({:?})", &this);
+
+        let parent: ClassInstanceRef<ContainerComponent> = jvm.get_field(&this, "parent", "Lorg/kwis/msp/lwc/ContainerComponent;").await?;
+        if !parent.is_null() {
+            return jvm.invoke_virtual(&parent, "getWidth", "()I", ()).await;
+        }
+
+        let display = jvm
+            .invoke_static("org/kwis/msp/lcdui/Display", "getDefaultDisplay", "()Lorg/kwis/msp/lcdui/Display;", ())
+            .await?;
+
+        jvm.invoke_virtual(&display, "getWidth", "()I", ()).await
+    }
+
+    async fn get_preferred_height(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("org.kwis.msp.lwc.Component::getPreferredHeight({:?})", &this);
+
+        Ok(Self::font_height(jvm).await? + PADDING * 2)
+    }
+
+    async fn is_focusable(_: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<bool> {
+        tracing::debug!("org.kwis.msp.lwc.Component::isFocusable({:?})", &this);
+
+        Ok(false)
+    }
+
+    async fn has_focus(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<bool> {
+        tracing::debug!("org.kwis.msp.lwc.Component::hasFocus({:?})", &this);
+
+        jvm.get_field(&this, "focused", "Z").await
+    }
+
+    async fn set_focus(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<()> {
+        tracing::debug!("org.kwis.msp.lwc.Component::setFocus({:?})", &this);
+
+        // focus is owned by the parent, which unfocuses previous component
+        let parent: ClassInstanceRef<ContainerComponent> = jvm.get_field(&this, "parent", "Lorg/kwis/msp/lwc/ContainerComponent;").await?;
+        if parent.is_null() {
+            return jvm.invoke_virtual(&this, "setFocused", "(Z)V", (true,)).await;
         }
+
+        jvm.invoke_virtual(&parent, "setFocus", "(Lorg/kwis/msp/lwc/Component;)V", (this,)).await
     }
 
+    async fn is_visible(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<bool> {
+        tracing::debug!("org.kwis.msp.lwc.Component::isVisible({:?})", &this);
+
+        jvm.get_field(&this, "visible", "Z").await
+    }
+
+    async fn set_visible(jvm: &Jvm, _: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, visible: bool) -> JvmResult<()> {
+        tracing::debug!("org.kwis.msp.lwc.Component::setVisible({:?}, {})", &this, visible);
+
+        jvm.put_field(&mut this, "visible", "Z", visible).await?;
+
+        jvm.invoke_virtual(&this, "repaint", "()V", ()).await
+    }
+
+    async fn get_parent(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<ClassInstanceRef<ContainerComponent>> {
+        tracing::debug!("org.kwis.msp.lwc.Component::getParent({:?})", &this);
+
+        jvm.get_field(&this, "parent", "Lorg/kwis/msp/lwc/ContainerComponent;").await
+    }
+
+    async fn set_component_listener(
+        jvm: &Jvm,
+        _: &mut WieJvmContext,
+        mut this: ClassInstanceRef<Self>,
+        listener: ClassInstanceRef<ComponentListener>,
+    ) -> JvmResult<()> {
+        tracing::debug!("org.kwis.msp.lwc.Component::setComponentListener({:?}, {:?})", &this, &listener);
+
+        jvm.put_field(&mut this, "listener", "Lorg/kwis/msp/lwc/ComponentListener;", listener)
+            .await
+    }
+
+    // returns true if the key is consumed
     async fn key_notify(_: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>, r#type: i32, chr: i32) -> JvmResult<bool> {
-        tracing::warn!("stub org.kwis.msp.lwc.Component::keyNotify({:?}, {:?}, {:?})", &this, r#type, chr);
+        tracing::debug!("org.kwis.msp.lwc.Component::keyNotify({:?}, {:?}, {:?})", &this, r#type, chr);
+
+        Ok(false)
+    }
+
+    async fn paint(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>, graphics: ClassInstanceRef<Graphics>) -> JvmResult<()> {
+        tracing::debug!("org.kwis.msp.lwc.Component::paint({:?}, {:?})", &this, &graphics);
 
-        Ok(true)
+        let visible: bool = jvm.get_field(&this, "visible", "Z").await?;
+        if !visible {
+            return Ok(());
+        }
+
+        // content is painted in component coordinates
+        let x: i32 = jvm.get_field(&this, "x", "I").await?;
+        let y: i32 = jvm.get_field(&this, "y", "I").await?;
+
+        let _: () = jvm.invoke_virtual(&graphics, "translate", "(II)V", (x, y)).await?;
+        let result: JvmResult<()> = jvm
+            .invoke_virtual(&this, "paintContent", "(Lorg/kwis/msp/lcdui/Graphics;)V", (graphics.clone(),))
+            .await;
+        let _: () = jvm.invoke_virtual(&graphics, "translate", "(II)V", (-x, -y)).await?;
+
+        result
     }
 
-    async fn set_focus(_: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<()> {
-        tracing::warn!("stub org.kwis.msp.lwc.Component::setFocus({:?})", &this,);
+    async fn paint_content(_: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>, graphics: ClassInstanceRef<Graphics>) -> JvmResult<()> {
+        tracing::debug!("org.kwis.msp.lwc.Component::paintContent({:?}, {:?})", &this, &graphics);
 
         Ok(())
     }
 
-    async fn get_height(_: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
-        tracing::warn!("stub org.kwis.msp.lwc.Component::getHeight({:?})", &this,);
+    // repaint request goes up to the shell, which owns the card
+    async fn repaint(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<()> {
+        tracing::debug!("org.kwis.msp.lwc.Component::repaint({:?})", &this);
+
+        let parent: ClassInstanceRef<ContainerComponent> = jvm.get_field(&this, "parent", "Lorg/kwis/msp/lwc/ContainerComponent;").await?;
+        if parent.is_null() {
+            return Ok(());
+        }
+
+        jvm.invoke_virtual(&parent, "repaint", "()V", ()).await
+    }
+
+    async fn set_parent(
+        jvm: &Jvm,
+        _: &mut WieJvmContext,
+        mut this: ClassInstanceRef<Self>,
+        parent: ClassInstanceRef<ContainerComponent>,
+    ) -> JvmResult<()> {
+        tracing::debug!("org.kwis.msp.lwc.Component::setParent({:?}, {:?})", &this, &parent);
+
+        jvm.put_field(&mut this, "parent", "Lorg/kwis/msp/lwc/ContainerComponent;", parent).await
+    }
+
+    async fn set_focused(jvm: &Jvm, _: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, focused: bool) -> JvmResult<()> {
+        tracing::debug!("org.kwis.msp.lwc.Component::setFocused({:?}, {})", &this, focused);
+
+        jvm.put_field(&mut this, "focused", "Z", focused).await?;
+
+        jvm.invoke_virtual(&this, "repaint", "()V", ()).await
+    }
+
+    /// Calls `componentNotify` of the listener, if any.
+    pub async fn notify_listener<T>(jvm: &Jvm, this: &ClassInstanceRef<T>, event: i32) -> JvmResult<()> {
+        let listener: ClassInstanceRef<ComponentListener> = jvm.get_field(this, "listener", "Lorg/kwis/msp/lwc/ComponentListener;").await?;
+        if listener.is_null() {
+            return Ok(());
+        }
+
+        jvm.invoke_virtual(&listener, "componentNotify", "(Lorg/kwis/msp/lwc/Component;I)V", (this.clone(), event))
+            .await
+    }
+
+    pub async fn font_height(jvm: &Jvm) -> JvmResult<i32> {
+        let font: ClassInstanceRef<Font> = jvm
+            .invoke_static("org/kwis/msp/lcdui/Font", "getDefaultFont", "()Lorg/kwis/msp/lcdui/Font;", ())
+            .await?;
+
+        jvm.invoke_virtual(&font, "getHeight", "()I", ()).await
+    }
+
+    pub async fn string_width(jvm: &Jvm, string: &str) -> JvmResult<i32> {
+        let font: ClassInstanceRef<Font> = jvm
+            .invoke_static("org/kwis/msp/lcdui/Font", "getDefaultFont", "()Lorg/kwis/msp/lcdui/Font;", ())
+            .await?;
+        let string = JavaLangString::from_rust_string(jvm, string).await?;
+
+        jvm.invoke_virtual(&font, "stringWidth", "(Ljava/lang/String;)I", (string,)).await
+    }
+
+    pub async fn size<T>(jvm: &Jvm, this: &ClassInstanceRef<T>) -> JvmResult<(i32, i32)> {
+        let width: i32 = jvm.invoke_virtual(this, "getWidth", "()I", ()).await?;
+        let height: i32 = jvm.invoke_virtual(this, "getHeight", "()I", ()).await?;
+
+        Ok((width, height))
+    }
+
+    pub async fn fill_rect(jvm: &Jvm, graphics: &ClassInstanceRef<Graphics>, color: i32, x: i32, y: i32, width: i32, height: i32) -> JvmResult<()> {
+        let _: () = jvm.invoke_virtual(graphics, "setColor", "(I)V", (color,)).await?;
+
+        jvm.invoke_virtual(graphics, "fillRect", "(IIII)V", (x, y, width, height)).await
+    }
+
+    pub async fn draw_rect(jvm: &Jvm, graphics: &ClassInstanceRef<Graphics>, color: i32, x: i32, y: i32, width: i32, height: i32) -> JvmResult<()> {
+        let _: () = jvm.invoke_virtual(graphics, "setColor", "(I)V", (color,)).await?;
+
+        jvm.invoke_virtual(graphics, "drawRect", "(IIII)V", (x, y, width, height)).await
+    }
+
+    pub async fn draw_string(jvm: &Jvm, graphics: &ClassInstanceRef<Graphics>, color: i32, string: &str, x: i32, y: i32) -> JvmResult<()> {
+        let string = JavaLangString::from_rust_string(jvm, string).await?;
+        let _: () = jvm.invoke_virtual(graphics, "setColor", "(I)V", (color,)).await?;
 
-        Ok(0)
+        jvm.invoke_virtual(graphics, "drawString", "(Ljava/lang/String;III)V", (string, x, y, TOP_LEFT))
+            .await
     }
 }
@@ -0,0 +1,24 @@
+use alloc::vec;
+
+use java_class_proto::JavaMethodProto;
+
+use wie_jvm_support::WieJavaClassProto;
+
+// interface org.kwis.msp.lwc.ComponentListener
+pub struct ComponentListener;
+
+impl ComponentListener {
+    pub fn as_proto() -> WieJavaClassProto {
+        WieJavaClassProto {
+            name: "org/kwis/msp/lwc/ComponentListener",
+            parent_class: None,
+            interfaces: vec![],
+            methods: vec![JavaMethodProto::new_abstract(
+                "componentNotify",
+                "(Lorg/kwis/msp/lwc/Component;I)V",
+                Default::default(),
+            )],
+            fields: vec![],
+        }
+    }
+}
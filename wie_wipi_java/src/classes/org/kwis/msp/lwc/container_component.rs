@@ -1,6 +1,22 @@
 use alloc::vec;
 
-use wie_jvm_support::WieJavaClassProto;
+use java_class_proto::{JavaFieldProto, JavaMethodProto};
+use jvm::{ClassInstanceRef, Jvm, Result as JvmResult};
+
+use wie_jvm_support::{WieJavaClassProto, WieJvmContext};
+
+use crate::classes::{
+    net::wie::WIPIKeyCode,
+    org::kwis::msp::{
+        lcdui::Graphics,
+        lwc::{
+            component::{KEY_PRESSED, KEY_REPEATED},
+            Component,
+        },
+    },
+};
+
+const NO_FOCUS: i32 = -1;
 
 // class org.kwis.msp.lwc.ContainerComponent
 pub struct ContainerComponent;
@@ -11,8 +27,311 @@ impl ContainerComponent {
             name: "org/kwis/msp/lwc/ContainerComponent",
             parent_class: Some("org/kwis/msp/lwc/Component"),
             interfaces: vec![],
-            methods: vec![],
-            fields: vec![],
+            methods: vec![
+                JavaMethodProto::new("<init>", "()V", Self::init, Default::default()),
+                JavaMethodProto::new("addComponent", "(Lorg/kwis/msp/lwc/Component;)V", Self::add_component, Default::default()),
+                JavaMethodProto::new(
+                    "removeComponent",
+                    "(Lorg/kwis/msp/lwc/Component;)V",
+                    Self::remove_component,
+                    Default::default(),
+                ),
+                JavaMethodProto::new("removeAllComponents", "()V", Self::remove_all_components, Default::default()),
+                JavaMethodProto::new("getComponentCount", "()I", Self::get_component_count, Default::default()),
+                JavaMethodProto::new("getComponent", "(I)Lorg/kwis/msp/lwc/Component;", Self::get_component, Default::default()),
+                JavaMethodProto::new("getFocus", "()Lorg/kwis/msp/lwc/Component;", Self::get_focus, Default::default()),
+                JavaMethodProto::new(
+                    "setFocus",
+                    "(Lorg/kwis/msp/lwc/Component;)V",
+                    Self::set_focus_component,
+                    Default::default(),
+                ),
+                JavaMethodProto::new("isFocusable", "()Z", Self::is_focusable, Default::default()),
+                JavaMethodProto::new("layout", "()V", Self::layout, Default::default()),
+                JavaMethodProto::new("getPreferredHeight", "()I", Self::get_preferred_height, Default::default()),
+                JavaMethodProto::new("keyNotify", "(II)Z", Self::key_notify, Default::default()),
+                JavaMethodProto::new(
+                    "paintContent",
+                    "(Lorg/kwis/msp/lcdui/Graphics;)V",
+                    Self::paint_content,
+                    Default::default(),
+                ),
+            ],
+            fields: vec![
+                JavaFieldProto::new("components", "Ljava/util/Vector;", Default::default()),
+                JavaFieldProto::new("focusIndex", "I", Default::default()),
+            ],
+        }
+    }
+
+    async fn init(jvm: &Jvm, _: &mut WieJvmContext, mut this: ClassInstanceRef<Self>) -> JvmResult<()> {
+        tracing::debug!("org.kwis.msp.lwc.ContainerComponent::<init>({:?})", &this);
+
+        let _: () = jvm.invoke_special(&this, "org/kwis/msp/lwc/Component", "<init>", "()V", ()).await?;
+
+        let components = jvm.new_class("java/util/Vector", "()V", ()).await?;
+        jvm.put_field(&mut this, "components", "Ljava/util/Vector;", components).await?;
+        jvm.put_field(&mut this, "focusIndex", "I", NO_FOCUS).await?;
+
+        Ok(())
+    }
+
+    async fn add_component(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>, component: ClassInstanceRef<Component>) -> JvmResult<()> {
+        tracing::debug!("org.kwis.msp.lwc.ContainerComponent::addComponent({:?}, {:?})", &this, &component);
+
+        if component.is_null() {
+            return Err(jvm.exception("java/lang/NullPointerException", "component is null").await);
+        }
+
+        let components = jvm.get_field(&this, "components", "Ljava/util/Vector;").await?;
+        let _: () = jvm
+            .invoke_virtual(&components, "addElement", "(Ljava/lang/Object;)V", (component.clone(),))
+            .await?;
+        let _: () = jvm
+            .invoke_virtual(&component, "setParent", "(Lorg/kwis/msp/lwc/ContainerComponent;)V", (this.clone(),))
+            .await?;
+
+        // first focusable component gets the focus
+        let focus_index: i32 = jvm.get_field(&this, "focusIndex", "I").await?;
+        let focusable: bool = jvm.invoke_virtual(&component, "isFocusable", "()Z", ()).await?;
+        if focus_index == NO_FOCUS && focusable {
+            let _: () = jvm
+                .invoke_virtual(&this, "setFocus", "(Lorg/kwis/msp/lwc/Component;)V", (component,))
+                .await?;
+        }
+
+        let _: () = jvm.invoke_virtual(&this, "layout", "()V", ()).await?;
+
+        jvm.invoke_virtual(&this, "repaint", "()V", ()).await
+    }
+
+    async fn remove_component(
+        jvm: &Jvm,
+        _: &mut WieJvmContext,
+        mut this: ClassInstanceRef<Self>,
+        component: ClassInstanceRef<Component>,
+    ) -> JvmResult<()> {
+        tracing::debug!("org.kwis.msp.lwc.ContainerComponent::removeComponent({:?}, {:?})", &this, &component);
+
+        let components = jvm.get_field(&this, "components", "Ljava/util/Vector;").await?;
+        let index: i32 = jvm
+            .invoke_virtual(&components, "indexOf", "(Ljava/lang/Object;)I", (component.clone(),))
+            .await?;
+        if index < 0 {
+            return Ok(());
+        }
+
+        let _: () = jvm.invoke_virtual(&components, "removeElementAt", "(I)V", (index,)).await?;
+        let _: () = jvm
+            .invoke_virtual(&component, "setParent", "(Lorg/kwis/msp/lwc/ContainerComponent;)V", (None,))
+            .await?;
+
+        let focus_index: i32 = jvm.get_field(&this, "focusIndex", "I").await?;
+        if focus_index == index {
+            let _: () = jvm.invoke_virtual(&component, "setFocused", "(Z)V", (false,)).await?;
+            jvm.put_field(&mut this, "focusIndex", "I", NO_FOCUS).await?;
+            Self::move_focus(jvm, &this, 1).await?;
+        } else if focus_index > index {
+            jvm.put_field(&mut this, "focusIndex", "I", focus_index - 1).await?;
+        }
+
+        let _: () = jvm.invoke_virtual(&this, "layout", "()V", ()).await?;
+
+        jvm.invoke_virtual(&this, "repaint", "()V", ()).await
+    }
+
+    async fn remove_all_components(jvm: &Jvm, _: &mut WieJvmContext, mut this: ClassInstanceRef<Self>) -> JvmResult<()> {
+        tracing::debug!("org.kwis.msp.lwc.ContainerComponent::removeAllComponents({:?})", &this);
+
+        let components = jvm.get_field(&this, "components", "Ljava/util/Vector;").await?;
+        let count: i32 = jvm.invoke_virtual(&components, "size", "()I", ()).await?;
+        for i in 0..count {
+            let component: ClassInstanceRef<Component> = jvm.invoke_virtual(&components, "elementAt", "(I)Ljava/lang/Object;", (i,)).await?;
+            let _: () = jvm
+                .invoke_virtual(&component, "setParent", "(Lorg/kwis/msp/lwc/ContainerComponent;)V", (None,))
+                .await?;
+        }
+
+        let _: () = jvm.invoke_virtual(&components, "removeAllElements", "()V", ()).await?;
+        jvm.put_field(&mut this, "focusIndex", "I", NO_FOCUS).await?;
+
+        jvm.invoke_virtual(&this, "repaint", "()V", ()).await
+    }
+
+    async fn get_component_count(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("org.kwis.msp.lwc.ContainerComponent::getComponentCount({:?})", &this);
+
+        let components = jvm.get_field(&this, "components", "Ljava/util/Vector;").await?;
+
+        jvm.invoke_virtual(&components, "size", "()I", ()).await
+    }
+
+    async fn get_component(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>, index: i32) -> JvmResult<ClassInstanceRef<Component>> {
+        tracing::debug!("org.kwis.msp.lwc.ContainerComponent::getComponent({:?}, {})", &this, index);
+
+        let components = jvm.get_field(&this, "components", "Ljava/util/Vector;").await?;
+
+        jvm.invoke_virtual(&components, "elementAt", "(I)Ljava/lang/Object;", (index,)).await
+    }
+
+    async fn get_focus(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<ClassInstanceRef<Component>> {
+        tracing::debug!("org.kwis.msp.lwc.ContainerComponent::getFocus({:?})", &this);
+
+        Self::focused_component(jvm, &this).await
+    }
+
+    async fn set_focus_component(
+        jvm: &Jvm,
+        _: &mut WieJvmContext,
+        mut this: ClassInstanceRef<Self>,
+        component: ClassInstanceRef<Component>,
+    ) -> JvmResult<()> {
+        tracing::debug!("org.kwis.msp.lwc.ContainerComponent::setFocus({:?}, {:?})", &this, &component);
+
+        let components = jvm.get_field(&this, "components", "Ljava/util/Vector;").await?;
+        let index: i32 = jvm
+            .invoke_virtual(&components, "indexOf", "(Ljava/lang/Object;)I", (component.clone(),))
+            .await?;
+        if index < 0 {
+            return Ok(());
+        }
+
+        let previous = Self::focused_component(jvm, &this).await?;
+        if !previous.is_null() {
+            let _: () = jvm.invoke_virtual(&previous, "setFocused", "(Z)V", (false,)).await?;
+        }
+
+        jvm.put_field(&mut this, "focusIndex", "I", index).await?;
+
+        jvm.invoke_virtual(&component, "setFocused", "(Z)V", (true,)).await
+    }
+
+    async fn is_focusable(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<bool> {
+        tracing::debug!("org.kwis.msp.lwc.ContainerComponent::isFocusable({:?})", &this);
+
+        let focus_index: i32 = jvm.get_field(&this, "focusIndex", "I").await?;
+
+        Ok(focus_index != NO_FOCUS)
+    }
+
+    // children are stacked vertically with the width of the container
+    async fn layout(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<()> {
+        tracing::debug!("org.kwis.msp.lwc.ContainerComponent::layout({:?})", &this);
+
+        let width: i32 = jvm.invoke_virtual(&this, "getWidth", "()I", ()).await?;
+        let components = jvm.get_field(&this, "components", "Ljava/util/Vector;").await?;
+        let count: i32 = jvm.invoke_virtual(&components, "size", "()I", ()).await?;
+
+        let mut y = 0;
+        for i in 0..count {
+            let component: ClassInstanceRef<Component> = jvm.invoke_virtual(&components, "elementAt", "(I)Ljava/lang/Object;", (i,)).await?;
+            let visible: bool = jvm.invoke_virtual(&component, "isVisible", "()Z", ()).await?;
+            if !visible {
+                continue;
+            }
+
+            let height: i32 = jvm.invoke_virtual(&component, "getPreferredHeight", "()I", ()).await?;
+            let _: () = jvm.invoke_virtual(&component, "setLocation", "(II)V", (0, y)).await?;
+            let _: () = jvm.invoke_virtual(&component, "setSize", "(II)V", (width, height)).await?;
+
+            y += height;
+        }
+
+        Ok(())
+    }
+
+    async fn get_preferred_height(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("org.kwis.msp.lwc.ContainerComponent::getPreferredHeight({:?})", &this);
+
+        let components = jvm.get_field(&this, "components", "Ljava/util/Vector;").await?;
+        let count: i32 = jvm.invoke_virtual(&components, "size", "()I", ()).await?;
+
+        let mut height = 0;
+        for i in 0..count {
+            let component: ClassInstanceRef<Component> = jvm.invoke_virtual(&components, "elementAt", "(I)Ljava/lang/Object;", (i,)).await?;
+            let visible: bool = jvm.invoke_virtual(&component, "isVisible", "()Z", ()).await?;
+            if visible {
+                let component_height: i32 = jvm.invoke_virtual(&component, "getPreferredHeight", "()I", ()).await?;
+                height += component_height;
+            }
+        }
+
+        Ok(height)
+    }
+
+    // focused child gets the key first. up and down keys not consumed by it move the focus
+    async fn key_notify(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>, r#type: i32, key: i32) -> JvmResult<bool> {
+        tracing::debug!("org.kwis.msp.lwc.ContainerComponent::keyNotify({:?}, {}, {})", &this, r#type, key);
+
+        let focused = Self::focused_component(jvm, &this).await?;
+        if !focused.is_null() {
+            let consumed: bool = jvm.invoke_virtual(&focused, "keyNotify", "(II)Z", (r#type, key)).await?;
+            if consumed {
+                return Ok(true);
+            }
+        }
+
+        if r#type != KEY_PRESSED && r#type != KEY_REPEATED {
+            return Ok(false);
+        }
+
+        match WIPIKeyCode::from_raw(key) {
+            Some(WIPIKeyCode::UP) => Self::move_focus(jvm, &this, -1).await,
+            Some(WIPIKeyCode::DOWN) => Self::move_focus(jvm, &this, 1).await,
+            _ => Ok(false),
+        }
+    }
+
+    async fn paint_content(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>, graphics: ClassInstanceRef<Graphics>) -> JvmResult<()> {
+        tracing::debug!("org.kwis.msp.lwc.ContainerComponent::paintContent({:?}, {:?})", &this, &graphics);
+
+        let components = jvm.get_field(&this, "components", "Ljava/util/Vector;").await?;
+        let count: i32 = jvm.invoke_virtual(&components, "size", "()I", ()).await?;
+        for i in 0..count {
+            let component: ClassInstanceRef<Component> = jvm.invoke_virtual(&components, "elementAt", "(I)Ljava/lang/Object;", (i,)).await?;
+            let _: () = jvm
+                .invoke_virtual(&component, "paint", "(Lorg/kwis/msp/lcdui/Graphics;)V", (graphics.clone(),))
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn focused_component(jvm: &Jvm, this: &ClassInstanceRef<Self>) -> JvmResult<ClassInstanceRef<Component>> {
+        let focus_index: i32 = jvm.get_field(this, "focusIndex", "I").await?;
+        if focus_index == NO_FOCUS {
+            return Ok(None.into());
+        }
+
+        let components = jvm.get_field(this, "components", "Ljava/util/Vector;").await?;
+
+        jvm.invoke_virtual(&components, "elementAt", "(I)Ljava/lang/Object;", (focus_index,))
+            .await
+    }
+
+    // moves focus to the next visible focusable component in `direction`. returns false if there's none
+    async fn move_focus(jvm: &Jvm, this: &ClassInstanceRef<Self>, direction: i32) -> JvmResult<bool> {
+        let focus_index: i32 = jvm.get_field(this, "focusIndex", "I").await?;
+        let components = jvm.get_field(this, "components", "Ljava/util/Vector;").await?;
+        let count: i32 = jvm.invoke_virtual(&components, "size", "()I", ()).await?;
+
+        let mut index = if focus_index == NO_FOCUS && direction < 0 { count } else { focus_index };
+        loop {
+            index += direction;
+            if index < 0 || index >= count {
+                return Ok(false);
+            }
+
+            let component: ClassInstanceRef<Component> = jvm.invoke_virtual(&components, "elementAt", "(I)Ljava/lang/Object;", (index,)).await?;
+            let visible: bool = jvm.invoke_virtual(&component, "isVisible", "()Z", ()).await?;
+            let focusable: bool = jvm.invoke_virtual(&component, "isFocusable", "()Z", ()).await?;
+            if visible && focusable {
+                let _: () = jvm
+                    .invoke_virtual(this, "setFocus", "(Lorg/kwis/msp/lwc/Component;)V", (component,))
+                    .await?;
+
+                return Ok(true);
+            }
         }
     }
 }
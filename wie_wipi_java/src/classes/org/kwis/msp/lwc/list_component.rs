@@ -0,0 +1,235 @@
+use alloc::vec;
+
+use java_class_proto::{JavaFieldProto, JavaMethodProto};
+use java_runtime::classes::java::lang::String;
+use jvm::{runtime::JavaLangString, ClassInstanceRef, Jvm, Result as JvmResult};
+
+use wie_jvm_support::{WieJavaClassProto, WieJvmContext};
+
+use crate::classes::{
+    net::wie::WIPIKeyCode,
+    org::kwis::msp::{
+        lcdui::Graphics,
+        lwc::{
+            component::{
+                BACKGROUND_COLOR, BORDER_COLOR, EVENT_ACTION, EVENT_CHANGED, FOCUS_COLOR, FOCUS_TEXT_COLOR, FOREGROUND_COLOR, KEY_PRESSED,
+                KEY_REPEATED, PADDING,
+            },
+            Component,
+        },
+    },
+};
+
+const NO_SELECTION: i32 = -1;
+
+// class org.kwis.msp.lwc.ListComponent
+pub struct ListComponent;
+
+impl ListComponent {
+    pub fn as_proto() -> WieJavaClassProto {
+        WieJavaClassProto {
+            name: "org/kwis/msp/lwc/ListComponent",
+            parent_class: Some("org/kwis/msp/lwc/Component"),
+            interfaces: vec![],
+            methods: vec![
+                JavaMethodProto::new("<init>", "()V", Self::init, Default::default()),
+                JavaMethodProto::new("addItem", "(Ljava/lang/String;)V", Self::add_item, Default::default()),
+                JavaMethodProto::new("removeItem", "(I)V", Self::remove_item, Default::default()),
+                JavaMethodProto::new("removeAllItems", "()V", Self::remove_all_items, Default::default()),
+                JavaMethodProto::new("getItem", "(I)Ljava/lang/String;", Self::get_item, Default::default()),
+                JavaMethodProto::new("getItemCount", "()I", Self::get_item_count, Default::default()),
+                JavaMethodProto::new("getSelectedIndex", "()I", Self::get_selected_index, Default::default()),
+                JavaMethodProto::new("setSelectedIndex", "(I)V", Self::set_selected_index, Default::default()),
+                JavaMethodProto::new("isFocusable", "()Z", Self::is_focusable, Default::default()),
+                JavaMethodProto::new("getPreferredHeight", "()I", Self::get_preferred_height, Default::default()),
+                JavaMethodProto::new("keyNotify", "(II)Z", Self::key_notify, Default::default()),
+                JavaMethodProto::new(
+                    "paintContent",
+                    "(Lorg/kwis/msp/lcdui/Graphics;)V",
+                    Self::paint_content,
+                    Default::default(),
+                ),
+            ],
+            fields: vec![
+                JavaFieldProto::new("items", "Ljava/util/Vector;", Default::default()),
+                JavaFieldProto::new("selectedIndex", "I", Default::default()),
+            ],
+        }
+    }
+
+    async fn init(jvm: &Jvm, _: &mut WieJvmContext, mut this: ClassInstanceRef<Self>) -> JvmResult<()> {
+        tracing::debug!("org.kwis.msp.lwc.ListComponent::<init>({:?})", &this);
+
+        let _: () = jvm.invoke_special(&this, "org/kwis/msp/lwc/Component", "<init>", "()V", ()).await?;
+
+        let items = jvm.new_class("java/util/Vector", "()V", ()).await?;
+        jvm.put_field(&mut this, "items", "Ljava/util/Vector;", items).await?;
+        jvm.put_field(&mut this, "selectedIndex", "I", NO_SELECTION).await?;
+
+        Ok(())
+    }
+
+    async fn add_item(jvm: &Jvm, _: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, item: ClassInstanceRef<String>) -> JvmResult<()> {
+        tracing::debug!("org.kwis.msp.lwc.ListComponent::addItem({:?}, {:?})", &this, &item);
+
+        let items = jvm.get_field(&this, "items", "Ljava/util/Vector;").await?;
+        let _: () = jvm.invoke_virtual(&items, "addElement", "(Ljava/lang/Object;)V", (item,)).await?;
+
+        // first item is selected automatically
+        let selected_index: i32 = jvm.get_field(&this, "selectedIndex", "I").await?;
+        if selected_index == NO_SELECTION {
+            jvm.put_field(&mut this, "selectedIndex", "I", 0).await?;
+        }
+
+        jvm.invoke_virtual(&this, "repaint", "()V", ()).await
+    }
+
+    async fn remove_item(jvm: &Jvm, _: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, index: i32) -> JvmResult<()> {
+        tracing::debug!("org.kwis.msp.lwc.ListComponent::removeItem({:?}, {})", &this, index);
+
+        let items = jvm.get_field(&this, "items", "Ljava/util/Vector;").await?;
+        let _: () = jvm.invoke_virtual(&items, "removeElementAt", "(I)V", (index,)).await?;
+
+        let count = Self::item_count(jvm, &this).await?;
+        let selected_index: i32 = jvm.get_field(&this, "selectedIndex", "I").await?;
+        if selected_index >= count {
+            jvm.put_field(&mut this, "selectedIndex", "I", count - 1).await?;
+        }
+
+        jvm.invoke_virtual(&this, "repaint", "()V", ()).await
+    }
+
+    async fn remove_all_items(jvm: &Jvm, _: &mut WieJvmContext, mut this: ClassInstanceRef<Self>) -> JvmResult<()> {
+        tracing::debug!("org.kwis.msp.lwc.ListComponent::removeAllItems({:?})", &this);
+
+        let items = jvm.get_field(&this, "items", "Ljava/util/Vector;").await?;
+        let _: () = jvm.invoke_virtual(&items, "removeAllElements", "()V", ()).await?;
+        jvm.put_field(&mut this, "selectedIndex", "I", NO_SELECTION).await?;
+
+        jvm.invoke_virtual(&this, "repaint", "()V", ()).await
+    }
+
+    async fn get_item(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>, index: i32) -> JvmResult<ClassInstanceRef<String>> {
+        tracing::debug!("org.kwis.msp.lwc.ListComponent::getItem({:?}, {})", &this, index);
+
+        Self::item(jvm, &this, index).await
+    }
+
+    async fn get_item_count(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("org.kwis.msp.lwc.ListComponent::getItemCount({:?})", &this);
+
+        Self::item_count(jvm, &this).await
+    }
+
+    async fn get_selected_index(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("org.kwis.msp.lwc.ListComponent::getSelectedIndex({:?})", &this);
+
+        jvm.get_field(&this, "selectedIndex", "I").await
+    }
+
+    async fn set_selected_index(jvm: &Jvm, _: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, index: i32) -> JvmResult<()> {
+        tracing::debug!("org.kwis.msp.lwc.ListComponent::setSelectedIndex({:?}, {})", &this, index);
+
+        let count = Self::item_count(jvm, &this).await?;
+        if index < 0 || index >= count {
+            return Err(jvm.exception("java/lang/IndexOutOfBoundsException", "invalid item index").await);
+        }
+
+        jvm.put_field(&mut this, "selectedIndex", "I", index).await?;
+
+        jvm.invoke_virtual(&this, "repaint", "()V", ()).await
+    }
+
+    async fn is_focusable(_: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<bool> {
+        tracing::debug!("org.kwis.msp.lwc.ListComponent::isFocusable({:?})", &this);
+
+        Ok(true)
+    }
+
+    async fn get_preferred_height(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<i32> {
+        tracing::debug!("org.kwis.msp.lwc.ListComponent::getPreferredHeight({:?})", &this);
+
+        let count = Self::item_count(jvm, &this).await?;
+        let font_height = Component::font_height(jvm).await?;
+
+        Ok(count.max(1) * (font_height + PADDING * 2) + PADDING * 2)
+    }
+
+    // up and down move selection, and are passed to the parent only at either end of the list
+    async fn key_notify(jvm: &Jvm, _: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, r#type: i32, key: i32) -> JvmResult<bool> {
+        tracing::debug!("org.kwis.msp.lwc.ListComponent::keyNotify({:?}, {}, {})", &this, r#type, key);
+
+        if r#type != KEY_PRESSED && r#type != KEY_REPEATED {
+            return Ok(false);
+        }
+
+        let count = Self::item_count(jvm, &this).await?;
+        let selected_index: i32 = jvm.get_field(&this, "selectedIndex", "I").await?;
+
+        let new_index = match WIPIKeyCode::from_raw(key) {
+            Some(WIPIKeyCode::UP) => selected_index - 1,
+            Some(WIPIKeyCode::DOWN) => selected_index + 1,
+            Some(WIPIKeyCode::FIRE) if selected_index != NO_SELECTION => {
+                Component::notify_listener(jvm, &this, EVENT_ACTION).await?;
+
+                return Ok(true);
+            }
+            _ => return Ok(false),
+        };
+
+        if new_index < 0 || new_index >= count {
+            return Ok(false);
+        }
+
+        jvm.put_field(&mut this, "selectedIndex", "I", new_index).await?;
+        Component::notify_listener(jvm, &this, EVENT_CHANGED).await?;
+        let _: () = jvm.invoke_virtual(&this, "repaint", "()V", ()).await?;
+
+        Ok(true)
+    }
+
+    async fn paint_content(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>, graphics: ClassInstanceRef<Graphics>) -> JvmResult<()> {
+        tracing::debug!("org.kwis.msp.lwc.ListComponent::paintContent({:?}, {:?})", &this, &graphics);
+
+        let (width, height) = Component::size(jvm, &this).await?;
+        let focused: bool = jvm.invoke_virtual(&this, "hasFocus", "()Z", ()).await?;
+        let count = Self::item_count(jvm, &this).await?;
+        let selected_index: i32 = jvm.get_field(&this, "selectedIndex", "I").await?;
+        let item_height = Component::font_height(jvm).await? + PADDING * 2;
+
+        Component::fill_rect(jvm, &graphics, BACKGROUND_COLOR, 0, 0, width, height).await?;
+        Component::draw_rect(jvm, &graphics, BORDER_COLOR, 0, 0, width - 1, height - 1).await?;
+
+        for i in 0..count {
+            let y = PADDING + i * item_height;
+
+            let item = Self::item(jvm, &this, i).await?;
+            let item = JavaLangString::to_rust_string(jvm, &item).await?;
+
+            // selection is highlighted only while the list has focus
+            let text_color = if i == selected_index && focused {
+                Component::fill_rect(jvm, &graphics, FOCUS_COLOR, PADDING, y, width - PADDING * 2, item_height).await?;
+
+                FOCUS_TEXT_COLOR
+            } else {
+                FOREGROUND_COLOR
+            };
+
+            Component::draw_string(jvm, &graphics, text_color, &item, PADDING * 2, y + PADDING).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn item(jvm: &Jvm, this: &ClassInstanceRef<Self>, index: i32) -> JvmResult<ClassInstanceRef<String>> {
+        let items = jvm.get_field(this, "items", "Ljava/util/Vector;").await?;
+
+        jvm.invoke_virtual(&items, "elementAt", "(I)Ljava/lang/Object;", (index,)).await
+    }
+
+    async fn item_count(jvm: &Jvm, this: &ClassInstanceRef<Self>) -> JvmResult<i32> {
+        let items = jvm.get_field(this, "items", "Ljava/util/Vector;").await?;
+
+        jvm.invoke_virtual(&items, "size", "()I", ()).await
+    }
+}
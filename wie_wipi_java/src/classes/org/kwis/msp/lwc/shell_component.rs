@@ -1,6 +1,14 @@
 use alloc::vec;
 
-use wie_jvm_support::WieJavaClassProto;
+use java_class_proto::{JavaFieldProto, JavaMethodProto};
+use jvm::{ClassInstanceRef, Jvm, Result as JvmResult};
+
+use wie_jvm_support::{WieJavaClassProto, WieJvmContext};
+
+use crate::classes::org::kwis::msp::{
+    lcdui::{Card, Display, Graphics},
+    lwc::{component::BACKGROUND_COLOR, Component},
+};
 
 // class org.kwis.msp.lwc.ShellComponent
 pub struct ShellComponent;
@@ -11,8 +19,114 @@ impl ShellComponent {
             name: "org/kwis/msp/lwc/ShellComponent",
             parent_class: Some("org/kwis/msp/lwc/ContainerComponent"),
             interfaces: vec![],
-            methods: vec![],
-            fields: vec![],
+            methods: vec![
+                JavaMethodProto::new("<init>", "()V", Self::init, Default::default()),
+                JavaMethodProto::new("show", "()V", Self::show, Default::default()),
+                JavaMethodProto::new("hide", "()V", Self::hide, Default::default()),
+                JavaMethodProto::new("isShown", "()Z", Self::is_shown, Default::default()),
+                JavaMethodProto::new("repaint", "()V", Self::repaint, Default::default()),
+                JavaMethodProto::new(
+                    "paintContent",
+                    "(Lorg/kwis/msp/lcdui/Graphics;)V",
+                    Self::paint_content,
+                    Default::default(),
+                ),
+            ],
+            fields: vec![JavaFieldProto::new("card", "Lorg/kwis/msp/lcdui/Card;", Default::default())],
         }
     }
+
+    async fn init(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<()> {
+        tracing::debug!("org.kwis.msp.lwc.ShellComponent::<init>({:?})", &this);
+
+        let _: () = jvm
+            .invoke_special(&this, "org/kwis/msp/lwc/ContainerComponent", "<init>", "()V", ())
+            .await?;
+
+        // shell covers the whole screen by default
+        let display = Self::display(jvm).await?;
+        let width: i32 = jvm.invoke_virtual(&display, "getWidth", "()I", ()).await?;
+        let height: i32 = jvm.invoke_virtual(&display, "getHeight", "()I", ()).await?;
+
+        let _: () = jvm.invoke_virtual(&this, "setSize", "(II)V", (width, height)).await?;
+
+        Ok(())
+    }
+
+    async fn show(jvm: &Jvm, _: &mut WieJvmContext, mut this: ClassInstanceRef<Self>) -> JvmResult<()> {
+        tracing::debug!("org.kwis.msp.lwc.ShellComponent::show({:?})", &this);
+
+        let card: ClassInstanceRef<Card> = jvm.get_field(&this, "card", "Lorg/kwis/msp/lcdui/Card;").await?;
+        if !card.is_null() {
+            return Ok(());
+        }
+
+        let _: () = jvm.invoke_virtual(&this, "layout", "()V", ()).await?;
+
+        let card = jvm
+            .new_class("net/wie/ShellCard", "(Lorg/kwis/msp/lwc/ShellComponent;)V", (this.clone(),))
+            .await?;
+        jvm.put_field(&mut this, "card", "Lorg/kwis/msp/lcdui/Card;", card.clone()).await?;
+
+        let display = Self::display(jvm).await?;
+        let _: () = jvm.invoke_virtual(&display, "pushCard", "(Lorg/kwis/msp/lcdui/Card;)V", (card,)).await?;
+
+        jvm.invoke_virtual(&this, "repaint", "()V", ()).await
+    }
+
+    async fn hide(jvm: &Jvm, _: &mut WieJvmContext, mut this: ClassInstanceRef<Self>) -> JvmResult<()> {
+        tracing::debug!("org.kwis.msp.lwc.ShellComponent::hide({:?})", &this);
+
+        let card: ClassInstanceRef<Card> = jvm.get_field(&this, "card", "Lorg/kwis/msp/lcdui/Card;").await?;
+        if card.is_null() {
+            return Ok(());
+        }
+
+        let display = Self::display(jvm).await?;
+        let _: () = jvm
+            .invoke_virtual(&display, "removeCard", "(Lorg/kwis/msp/lcdui/Card;)V", (card,))
+            .await?;
+
+        jvm.put_field(&mut this, "card", "Lorg/kwis/msp/lcdui/Card;", None).await
+    }
+
+    async fn is_shown(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<bool> {
+        tracing::debug!("org.kwis.msp.lwc.ShellComponent::isShown({:?})", &this);
+
+        let card: ClassInstanceRef<Card> = jvm.get_field(&this, "card", "Lorg/kwis/msp/lcdui/Card;").await?;
+
+        Ok(!card.is_null())
+    }
+
+    async fn repaint(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>) -> JvmResult<()> {
+        tracing::debug!("org.kwis.msp.lwc.ShellComponent::repaint({:?})", &this);
+
+        let card: ClassInstanceRef<Card> = jvm.get_field(&this, "card", "Lorg/kwis/msp/lcdui/Card;").await?;
+        if card.is_null() {
+            return Ok(());
+        }
+
+        jvm.invoke_virtual(&card, "repaint", "()V", ()).await
+    }
+
+    async fn paint_content(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>, graphics: ClassInstanceRef<Graphics>) -> JvmResult<()> {
+        tracing::debug!("org.kwis.msp.lwc.ShellComponent::paintContent({:?}, {:?})", &this, &graphics);
+
+        let (width, height) = Component::size(jvm, &this).await?;
+        Component::fill_rect(jvm, &graphics, BACKGROUND_COLOR, 0, 0, width, height).await?;
+
+        jvm.invoke_special(
+            &this,
+            "org/kwis/msp/lwc/ContainerComponent",
+            "paintContent",
+            "(Lorg/kwis/msp/lcdui/Graphics;)V",
+            (graphics,),
+        )
+        .await
+    }
+
+    async fn display(jvm: &Jvm) -> JvmResult<ClassInstanceRef<Display>> {
+        jvm.invoke_static("org/kwis/msp/lcdui/Display", "getDefaultDisplay", "()Lorg/kwis/msp/lcdui/Display;", ())
+            .await
+    }
 }
@@ -1,4 +1,4 @@
-use alloc::vec;
+use alloc::{string::String as RustString, vec, vec::Vec};
 
 use java_class_proto::JavaMethodProto;
 use java_runtime::classes::java::lang::String;
@@ -6,6 +6,16 @@ use jvm::{ClassInstanceRef, Jvm, Result as JvmResult};
 
 use wie_jvm_support::{WieJavaClassProto, WieJvmContext};
 
+use crate::classes::org::kwis::msp::{
+    lcdui::Graphics,
+    lwc::{
+        component::{BACKGROUND_COLOR, BORDER_COLOR, FOCUS_COLOR, FOREGROUND_COLOR, PADDING},
+        Component, TextComponent,
+    },
+};
+
+const VISIBLE_LINES: i32 = 4;
+
 // class org.kwis.msp.lwc.TextBoxComponent
 pub struct TextBoxComponent;
 
@@ -15,25 +25,104 @@ impl TextBoxComponent {
             name: "org/kwis/msp/lwc/TextBoxComponent",
             parent_class: Some("org/kwis/msp/lwc/TextComponent"),
             interfaces: vec![],
-            methods: vec![JavaMethodProto::new("<init>", "(Ljava/lang/String;I)V", Self::init, Default::default())],
+            methods: vec![
+                JavaMethodProto::new("<init>", "(Ljava/lang/String;I)V", Self::init, Default::default()),
+                JavaMethodProto::new("getPreferredHeight", "()I", Self::get_preferred_height, Default::default()),
+                JavaMethodProto::new(
+                    "paintContent",
+                    "(Lorg/kwis/msp/lcdui/Graphics;)V",
+                    Self::paint_content,
+                    Default::default(),
+                ),
+            ],
             fields: vec![],
         }
     }
 
     async fn init(
-        _: &Jvm,
+        jvm: &Jvm,
         _: &mut WieJvmContext,
         this: ClassInstanceRef<TextBoxComponent>,
         data: ClassInstanceRef<String>,
         constraint: i32,
     ) -> JvmResult<()> {
-        tracing::warn!(
-            "stub org.kwis.msp.lwc.TextBoxComponent::<init>({:?}, {:?}, {:?})",
+        tracing::debug!("org.kwis.msp.lwc.TextBoxComponent::<init>({:?}, {:?}, {:?})", &this, &data, constraint);
+
+        jvm.invoke_special(
             &this,
-            &data,
-            constraint
-        );
+            "org/kwis/msp/lwc/TextComponent",
+            "<init>",
+            "(Ljava/lang/String;I)V",
+            (data, constraint),
+        )
+        .await
+    }
+
+    async fn get_preferred_height(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<TextBoxComponent>) -> JvmResult<i32> {
+        tracing::debug!("org.kwis.msp.lwc.TextBoxComponent::getPreferredHeight({:?})", &this);
+
+        let font_height = Component::font_height(jvm).await?;
+
+        Ok(font_height * VISIBLE_LINES + PADDING * 2)
+    }
+
+    // text is wrapped at box width, scrolled to show the last lines
+    async fn paint_content(
+        jvm: &Jvm,
+        _: &mut WieJvmContext,
+        this: ClassInstanceRef<TextBoxComponent>,
+        graphics: ClassInstanceRef<Graphics>,
+    ) -> JvmResult<()> {
+        tracing::debug!("org.kwis.msp.lwc.TextBoxComponent::paintContent({:?}, {:?})", &this, &graphics);
+
+        let (width, height) = Component::size(jvm, &this).await?;
+        let focused: bool = jvm.invoke_virtual(&this, "hasFocus", "()Z", ()).await?;
+        let font_height = Component::font_height(jvm).await?;
+
+        Component::fill_rect(jvm, &graphics, BACKGROUND_COLOR, 0, 0, width, height).await?;
+        let border_color = if focused { FOCUS_COLOR } else { BORDER_COLOR };
+        Component::draw_rect(jvm, &graphics, border_color, 0, 0, width - 1, height - 1).await?;
+
+        let text = TextComponent::display_text(jvm, &this).await?;
+        let lines = Self::wrap(jvm, &text, width - PADDING * 4).await?;
+
+        let visible_lines = ((height - PADDING * 2) / font_height.max(1)).max(1) as usize;
+        let first_line = lines.len().saturating_sub(visible_lines);
+        for (i, line) in lines[first_line..].iter().enumerate() {
+            Component::draw_string(jvm, &graphics, FOREGROUND_COLOR, line, PADDING * 2, PADDING + i as i32 * font_height).await?;
+        }
+
+        if focused {
+            let mode_label = TextComponent::mode_label(jvm, &this).await?;
+            let mode_width = Component::string_width(jvm, mode_label).await?;
+            Component::draw_string(jvm, &graphics, BORDER_COLOR, mode_label, width - PADDING * 2 - mode_width, PADDING).await?;
+
+            let last_line = lines.last().map(|x| x.as_str()).unwrap_or_default();
+            let cursor_x = PADDING * 2 + Component::string_width(jvm, last_line).await?;
+            let cursor_y = PADDING + (lines.len() - first_line).saturating_sub(1) as i32 * font_height;
+            Component::fill_rect(jvm, &graphics, FOREGROUND_COLOR, cursor_x, cursor_y, 1, font_height).await?;
+        }
 
         Ok(())
     }
+
+    async fn wrap(jvm: &Jvm, text: &str, width: i32) -> JvmResult<Vec<RustString>> {
+        let mut lines = vec![RustString::new()];
+
+        for chr in text.chars() {
+            if chr == '\n' {
+                lines.push(RustString::new());
+                continue;
+            }
+
+            let line = lines.last_mut().unwrap();
+            line.push(chr);
+            if line.chars().count() > 1 && Component::string_width(jvm, line).await? > width {
+                line.pop();
+                lines.push(chr.into());
+            }
+        }
+
+        Ok(lines)
+    }
 }
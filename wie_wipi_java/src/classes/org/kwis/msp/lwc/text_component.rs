@@ -1,11 +1,27 @@
-use alloc::vec;
+use alloc::{string::String as RustString, vec};
 
-use java_class_proto::JavaMethodProto;
+use java_class_proto::{JavaFieldProto, JavaMethodProto};
+use java_constants::FieldAccessFlags;
 use java_runtime::classes::java::lang::String;
 use jvm::{runtime::JavaLangString, ClassInstanceRef, Jvm, Result as JvmResult};
 
+use wie_backend::{InputMode, KeyCode};
 use wie_jvm_support::{WieJavaClassProto, WieJvmContext};
 
+use crate::classes::{
+    net::wie::WIPIKeyCode,
+    org::kwis::msp::lwc::{
+        component::{EVENT_ACTION, EVENT_CHANGED, KEY_PRESSED, KEY_REPEATED},
+        Component,
+    },
+};
+
+// constraints
+const NUMERIC: i32 = 2;
+const PHONENUMBER: i32 = 3;
+const CONSTRAINT_MASK: i32 = 0xffff;
+const PASSWORD: i32 = 0x10000;
+
 // class org.kwis.msp.lwc.TextComponent
 pub struct TextComponent;
 
@@ -16,24 +32,332 @@ impl TextComponent {
             parent_class: Some("org/kwis/msp/lwc/Component"),
             interfaces: vec![],
             methods: vec![
+                JavaMethodProto::new("<init>", "(Ljava/lang/String;I)V", Self::init, Default::default()),
                 JavaMethodProto::new("setMaxLength", "(I)V", Self::set_max_length, Default::default()),
+                JavaMethodProto::new("getMaxLength", "()I", Self::get_max_length, Default::default()),
                 JavaMethodProto::new("getString", "()Ljava/lang/String;", Self::get_string, Default::default()),
+                JavaMethodProto::new("setString", "(Ljava/lang/String;)V", Self::set_string, Default::default()),
+                JavaMethodProto::new("isFocusable", "()Z", Self::is_focusable, Default::default()),
+                JavaMethodProto::new("keyNotify", "(II)Z", Self::key_notify, Default::default()),
+                // wie private methods...
+                JavaMethodProto::new("setFocused", "(Z)V", Self::set_focused, Default::default()),
+                JavaMethodProto::new("insertText", "(Ljava/lang/String;)V", Self::insert_text, Default::default()),
+            ],
+            fields: vec![
+                JavaFieldProto::new("text", "Ljava/lang/String;", Default::default()),
+                JavaFieldProto::new("composition", "Ljava/lang/String;", Default::default()),
+                JavaFieldProto::new("constraint", "I", Default::default()),
+                JavaFieldProto::new("maxLength", "I", Default::default()),
+                JavaFieldProto::new("inputMode", "I", Default::default()),
+                JavaFieldProto::new("focusedComponent", "Lorg/kwis/msp/lwc/TextComponent;", FieldAccessFlags::STATIC),
             ],
-            fields: vec![],
         }
     }
 
-    async fn set_max_length(_: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<TextComponent>, max_length: i32) -> JvmResult<()> {
-        tracing::warn!("stub org.kwis.msp.lwc.TextFieldComponent::<init>({:?}, {})", &this, max_length);
+    async fn init(
+        jvm: &Jvm,
+        _: &mut WieJvmContext,
+        mut this: ClassInstanceRef<TextComponent>,
+        text: ClassInstanceRef<String>,
+        constraint: i32,
+    ) -> JvmResult<()> {
+        tracing::debug!("org.kwis.msp.lwc.TextComponent::<init>({:?}, {:?}, {})", &this, &text, constraint);
+
+        let _: () = jvm.invoke_special(&this, "org/kwis/msp/lwc/Component", "<init>", "()V", ()).await?;
+
+        let text = if text.is_null() {
+            JavaLangString::from_rust_string(jvm, "").await?.into()
+        } else {
+            text
+        };
+        let composition = JavaLangString::from_rust_string(jvm, "").await?;
+        let input_mode = if Self::is_numeric(constraint) {
+            InputMode::Number
+        } else {
+            InputMode::Hangul
+        };
+
+        jvm.put_field(&mut this, "text", "Ljava/lang/String;", text).await?;
+        jvm.put_field(&mut this, "composition", "Ljava/lang/String;", composition).await?;
+        jvm.put_field(&mut this, "constraint", "I", constraint).await?;
+        jvm.put_field(&mut this, "inputMode", "I", Self::mode_to_field(input_mode)).await?;
+
+        Ok(())
+    }
+
+    async fn set_max_length(jvm: &Jvm, _: &mut WieJvmContext, mut this: ClassInstanceRef<TextComponent>, max_length: i32) -> JvmResult<()> {
+        tracing::debug!("org.kwis.msp.lwc.TextComponent::setMaxLength({:?}, {})", &this, max_length);
+
+        jvm.put_field(&mut this, "maxLength", "I", max_length).await?;
+
+        // existing text is cut to the new length
+        let text = Self::text(jvm, &this).await?;
+        if max_length > 0 && text.chars().count() > max_length as usize {
+            let text = text.chars().take(max_length as _).collect::<RustString>();
+            Self::set_text(jvm, &mut this, &text).await?;
+        }
 
         Ok(())
     }
 
+    async fn get_max_length(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<TextComponent>) -> JvmResult<i32> {
+        tracing::debug!("org.kwis.msp.lwc.TextComponent::getMaxLength({:?})", &this);
+
+        jvm.get_field(&this, "maxLength", "I").await
+    }
+
     async fn get_string(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<TextComponent>) -> JvmResult<ClassInstanceRef<String>> {
-        tracing::warn!("stub org.kwis.msp.lwc.TextFieldComponent::<init>({:?})", &this);
+        tracing::debug!("org.kwis.msp.lwc.TextComponent::getString({:?})", &this);
+
+        jvm.get_field(&this, "text", "Ljava/lang/String;").await
+    }
+
+    async fn set_string(
+        jvm: &Jvm,
+        context: &mut WieJvmContext,
+        mut this: ClassInstanceRef<TextComponent>,
+        text: ClassInstanceRef<String>,
+    ) -> JvmResult<()> {
+        tracing::debug!("org.kwis.msp.lwc.TextComponent::setString({:?}, {:?})", &this, &text);
+
+        let text = if text.is_null() {
+            RustString::new()
+        } else {
+            JavaLangString::to_rust_string(jvm, &text).await?
+        };
+
+        // replaced text drops what's being composed
+        let focused: bool = jvm.get_field(&this, "focused", "Z").await?;
+        if focused {
+            let input_mode = Self::input_mode(jvm, &this).await?;
+            context.system().input_method().reset(input_mode);
+        }
+        let composition = JavaLangString::from_rust_string(jvm, "").await?;
+        jvm.put_field(&mut this, "composition", "Ljava/lang/String;", composition).await?;
 
-        let result = JavaLangString::from_rust_string(jvm, "temp").await?;
+        Self::set_text(jvm, &mut this, &text).await
+    }
+
+    async fn is_focusable(_: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<TextComponent>) -> JvmResult<bool> {
+        tracing::debug!("org.kwis.msp.lwc.TextComponent::isFocusable({:?})", &this);
+
+        Ok(true)
+    }
+
+    // number keys go to the input method, clear deletes last character
+    async fn key_notify(jvm: &Jvm, context: &mut WieJvmContext, mut this: ClassInstanceRef<TextComponent>, r#type: i32, key: i32) -> JvmResult<bool> {
+        tracing::debug!("org.kwis.msp.lwc.TextComponent::keyNotify({:?}, {}, {})", &this, r#type, key);
+
+        let wipi_key_code = WIPIKeyCode::from_raw(key);
+        if r#type != KEY_PRESSED && !(r#type == KEY_REPEATED && wipi_key_code == Some(WIPIKeyCode::CLEAR)) {
+            return Ok(false);
+        }
+
+        let constraint: i32 = jvm.get_field(&this, "constraint", "I").await?;
+
+        match wipi_key_code {
+            Some(WIPIKeyCode::CLEAR) => {
+                let handled = context.system().input_method().backspace();
+                if !handled {
+                    let mut text = Self::text(jvm, &this).await?;
+                    if text.pop().is_none() {
+                        return Ok(false);
+                    }
+                    Self::set_text(jvm, &mut this, &text).await?;
+                }
+            }
+            Some(WIPIKeyCode::RIGHT) => context.system().input_method().commit(),
+            Some(WIPIKeyCode::FIRE) => {
+                context.system().input_method().commit();
+                Self::update_input(jvm, context, &mut this).await?;
+
+                Component::notify_listener(jvm, &this, EVENT_ACTION).await?;
+
+                return Ok(true);
+            }
+            Some(WIPIKeyCode::STAR | WIPIKeyCode::SOFT1) if Self::is_numeric(constraint) => return Ok(false),
+            _ => {
+                let Some(key_code) = wipi_key_code.and_then(Self::key_code) else {
+                    return Ok(false);
+                };
+
+                let now = context.system().platform().now();
+                if !context.system().input_method().key(key_code, now) {
+                    return Ok(false);
+                }
+            }
+        }
+
+        Self::update_input(jvm, context, &mut this).await?;
+
+        Ok(true)
+    }
+
+    // input method follows the focused text component
+    async fn set_focused(jvm: &Jvm, context: &mut WieJvmContext, mut this: ClassInstanceRef<TextComponent>, focused: bool) -> JvmResult<()> {
+        tracing::debug!("org.kwis.msp.lwc.TextComponent::setFocused({:?}, {})", &this, focused);
+
+        let input_mode = Self::input_mode(jvm, &this).await?;
+        if focused {
+            context.system().input_method().reset(input_mode);
+            jvm.put_static_field(
+                "org/kwis/msp/lwc/TextComponent",
+                "focusedComponent",
+                "Lorg/kwis/msp/lwc/TextComponent;",
+                this.clone(),
+            )
+            .await?;
+        } else {
+            context.system().input_method().commit();
+            Self::update_input(jvm, context, &mut this).await?;
+            jvm.put_static_field(
+                "org/kwis/msp/lwc/TextComponent",
+                "focusedComponent",
+                "Lorg/kwis/msp/lwc/TextComponent;",
+                None,
+            )
+            .await?;
+        }
+
+        jvm.invoke_special(&this, "org/kwis/msp/lwc/Component", "setFocused", "(Z)V", (focused,))
+            .await
+    }
+
+    // text typed on host keyboard goes after what's being composed
+    async fn insert_text(
+        jvm: &Jvm,
+        context: &mut WieJvmContext,
+        mut this: ClassInstanceRef<TextComponent>,
+        text: ClassInstanceRef<String>,
+    ) -> JvmResult<()> {
+        tracing::debug!("org.kwis.msp.lwc.TextComponent::insertText({:?}, {:?})", &this, &text);
+
+        let constraint: i32 = jvm.get_field(&this, "constraint", "I").await?;
+        let mut text = JavaLangString::to_rust_string(jvm, &text).await?;
+        if Self::is_numeric(constraint) {
+            text.retain(|x| x.is_ascii_digit());
+        }
+
+        context.system().input_method().insert(&text);
+
+        Self::update_input(jvm, context, &mut this).await
+    }
+
+    /// Inserts `text` into the focused text component. Returns false if there's none.
+    pub async fn insert_focused(jvm: &Jvm, text: ClassInstanceRef<String>) -> JvmResult<bool> {
+        let focused: ClassInstanceRef<TextComponent> = jvm
+            .get_static_field("org/kwis/msp/lwc/TextComponent", "focusedComponent", "Lorg/kwis/msp/lwc/TextComponent;")
+            .await?;
+        if focused.is_null() {
+            return Ok(false);
+        }
+
+        let _: () = jvm.invoke_virtual(&focused, "insertText", "(Ljava/lang/String;)V", (text,)).await?;
+
+        Ok(true)
+    }
+
+    /// Text to draw, including composition and with password characters masked.
+    pub async fn display_text<T>(jvm: &Jvm, this: &ClassInstanceRef<T>) -> JvmResult<RustString> {
+        let constraint: i32 = jvm.get_field(this, "constraint", "I").await?;
+        let mut text = Self::text(jvm, this).await?;
+
+        let composition = jvm.get_field(this, "composition", "Ljava/lang/String;").await?;
+        text.push_str(&JavaLangString::to_rust_string(jvm, &composition).await?);
+
+        if constraint & PASSWORD != 0 {
+            Ok(text.chars().map(|_| '*').collect())
+        } else {
+            Ok(text)
+        }
+    }
+
+    /// Input mode indicator shown while focused.
+    pub async fn mode_label<T>(jvm: &Jvm, this: &ClassInstanceRef<T>) -> JvmResult<&'static str> {
+        Ok(Self::input_mode(jvm, this).await?.label())
+    }
+
+    /// Appends text committed by the input method, and updates composition and input mode.
+    pub async fn update_input<T>(jvm: &Jvm, context: &mut WieJvmContext, this: &mut ClassInstanceRef<T>) -> JvmResult<()> {
+        let (committed, composition, input_mode) = {
+            let mut input_method = context.system().input_method();
+
+            (input_method.take_committed(), input_method.composition(), input_method.mode())
+        };
+
+        jvm.put_field(this, "inputMode", "I", Self::mode_to_field(input_mode)).await?;
+        let composition = JavaLangString::from_rust_string(jvm, &composition).await?;
+        jvm.put_field(this, "composition", "Ljava/lang/String;", composition).await?;
+
+        if committed.is_empty() {
+            return jvm.invoke_virtual(this, "repaint", "()V", ()).await;
+        }
+
+        let max_length: i32 = jvm.get_field(this, "maxLength", "I").await?;
+        let mut text = Self::text(jvm, this).await?;
+        text.push_str(&committed);
+        if max_length > 0 {
+            text = text.chars().take(max_length as _).collect();
+        }
+
+        Self::set_text(jvm, this, &text).await
+    }
+
+    async fn input_mode<T>(jvm: &Jvm, this: &ClassInstanceRef<T>) -> JvmResult<InputMode> {
+        let input_mode: i32 = jvm.get_field(this, "inputMode", "I").await?;
+
+        Ok(match input_mode {
+            1 => InputMode::UpperCase,
+            2 => InputMode::LowerCase,
+            3 => InputMode::Number,
+            _ => InputMode::Hangul,
+        })
+    }
+
+    fn mode_to_field(input_mode: InputMode) -> i32 {
+        match input_mode {
+            InputMode::Hangul => 0,
+            InputMode::UpperCase => 1,
+            InputMode::LowerCase => 2,
+            InputMode::Number => 3,
+        }
+    }
+
+    async fn text<T>(jvm: &Jvm, this: &ClassInstanceRef<T>) -> JvmResult<RustString> {
+        let text = jvm.get_field(this, "text", "Ljava/lang/String;").await?;
+
+        JavaLangString::to_rust_string(jvm, &text).await
+    }
+
+    async fn set_text<T>(jvm: &Jvm, this: &mut ClassInstanceRef<T>, text: &str) -> JvmResult<()> {
+        let text = JavaLangString::from_rust_string(jvm, text).await?;
+        jvm.put_field(this, "text", "Ljava/lang/String;", text).await?;
+
+        Component::notify_listener(jvm, this, EVENT_CHANGED).await?;
+
+        jvm.invoke_virtual(this, "repaint", "()V", ()).await
+    }
+
+    fn key_code(key: WIPIKeyCode) -> Option<KeyCode> {
+        Some(match key {
+            WIPIKeyCode::NUM0 => KeyCode::NUM0,
+            WIPIKeyCode::NUM1 => KeyCode::NUM1,
+            WIPIKeyCode::NUM2 => KeyCode::NUM2,
+            WIPIKeyCode::NUM3 => KeyCode::NUM3,
+            WIPIKeyCode::NUM4 => KeyCode::NUM4,
+            WIPIKeyCode::NUM5 => KeyCode::NUM5,
+            WIPIKeyCode::NUM6 => KeyCode::NUM6,
+            WIPIKeyCode::NUM7 => KeyCode::NUM7,
+            WIPIKeyCode::NUM8 => KeyCode::NUM8,
+            WIPIKeyCode::NUM9 => KeyCode::NUM9,
+            WIPIKeyCode::STAR => KeyCode::STAR,
+            WIPIKeyCode::HASH => KeyCode::HASH,
+            WIPIKeyCode::SOFT1 => KeyCode::LEFT_SOFT,
+            _ => return None,
+        })
+    }
 
-        Ok(result.into())
+    fn is_numeric(constraint: i32) -> bool {
+        matches!(constraint & CONSTRAINT_MASK, NUMERIC | PHONENUMBER)
     }
 }
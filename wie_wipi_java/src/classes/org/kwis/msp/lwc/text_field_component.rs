@@ -6,6 +6,14 @@ use jvm::{ClassInstanceRef, Jvm, Result as JvmResult};
 
 use wie_jvm_support::{WieJavaClassProto, WieJvmContext};
 
+use crate::classes::org::kwis::msp::{
+    lcdui::Graphics,
+    lwc::{
+        component::{BACKGROUND_COLOR, BORDER_COLOR, FOCUS_COLOR, FOREGROUND_COLOR, PADDING},
+        Component, TextComponent,
+    },
+};
+
 // class org.kwis.msp.lwc.TextFieldComponent
 pub struct TextFieldComponent;
 
@@ -15,24 +23,73 @@ impl TextFieldComponent {
             name: "org/kwis/msp/lwc/TextFieldComponent",
             parent_class: Some("org/kwis/msp/lwc/TextComponent"),
             interfaces: vec![],
-            methods: vec![JavaMethodProto::new("<init>", "(Ljava/lang/String;I)V", Self::init, Default::default())],
+            methods: vec![
+                JavaMethodProto::new("<init>", "(Ljava/lang/String;I)V", Self::init, Default::default()),
+                JavaMethodProto::new(
+                    "paintContent",
+                    "(Lorg/kwis/msp/lcdui/Graphics;)V",
+                    Self::paint_content,
+                    Default::default(),
+                ),
+            ],
             fields: vec![],
         }
     }
 
     async fn init(
-        _: &Jvm,
+        jvm: &Jvm,
         _: &mut WieJvmContext,
         this: ClassInstanceRef<TextFieldComponent>,
         data: ClassInstanceRef<String>,
         constraint: i32,
     ) -> JvmResult<()> {
-        tracing::warn!(
-            "stub org.kwis.msp.lwc.TextFieldComponent::<init>({:?}, {:?}, {:?})",
+        tracing::debug!("org.kwis.msp.lwc.TextFieldComponent::<init>({:?}, {:?}, {:?})", &this, &data, constraint);
+
+        jvm.invoke_special(
             &this,
-            &data,
-            constraint
-        );
+            "org/kwis/msp/lwc/TextComponent",
+            "<init>",
+            "(Ljava/lang/String;I)V",
+            (data, constraint),
+        )
+        .await
+    }
+
+    // single line box, scrolled to show the end of the text
+    async fn paint_content(
+        jvm: &Jvm,
+        _: &mut WieJvmContext,
+        this: ClassInstanceRef<TextFieldComponent>,
+        graphics: ClassInstanceRef<Graphics>,
+    ) -> JvmResult<()> {
+        tracing::debug!("org.kwis.msp.lwc.TextFieldComponent::paintContent({:?}, {:?})", &this, &graphics);
+
+        let (width, height) = Component::size(jvm, &this).await?;
+        let focused: bool = jvm.invoke_virtual(&this, "hasFocus", "()Z", ()).await?;
+
+        Component::fill_rect(jvm, &graphics, BACKGROUND_COLOR, 0, 0, width, height).await?;
+        let border_color = if focused { FOCUS_COLOR } else { BORDER_COLOR };
+        Component::draw_rect(jvm, &graphics, border_color, 0, 0, width - 1, height - 1).await?;
+
+        let mut text_width = width - PADDING * 4;
+        if focused {
+            let mode_label = TextComponent::mode_label(jvm, &this).await?;
+            let mode_width = Component::string_width(jvm, mode_label).await?;
+            Component::draw_string(jvm, &graphics, BORDER_COLOR, mode_label, width - PADDING * 2 - mode_width, PADDING).await?;
+
+            text_width -= mode_width + PADDING;
+        }
+
+        let mut text = TextComponent::display_text(jvm, &this).await?;
+        while !text.is_empty() && Component::string_width(jvm, &text).await? > text_width {
+            text.remove(0);
+        }
+        Component::draw_string(jvm, &graphics, FOREGROUND_COLOR, &text, PADDING * 2, PADDING).await?;
+
+        if focused {
+            let cursor_x = PADDING * 2 + Component::string_width(jvm, &text).await?;
+            Component::fill_rect(jvm, &graphics, FOREGROUND_COLOR, cursor_x, PADDING, 1, height - PADDING * 2).await?;
+        }
 
         Ok(())
     }
@@ -4,13 +4,10 @@ use bytemuck::cast_vec;
 
 use java_class_proto::{JavaFieldProto, JavaMethodProto};
 use java_runtime::classes::java::lang::String;
-use jvm::{
-    runtime::{JavaIoInputStream, JavaLangString},
-    Array, ClassInstanceRef, Jvm, Result as JvmResult,
-};
+use jvm::{runtime::JavaIoInputStream, Array, ClassInstanceRef, Jvm, Result as JvmResult};
 
 use wie_jvm_support::{WieJavaClassProto, WieJvmContext};
-use wie_midp::classes::javax::microedition::media::Player;
+use wie_midp::classes::{javax::microedition::media::Player, net::wie::AudioPlayer};
 
 use crate::classes::org::kwis::msp::media::PlayListener;
 
@@ -42,15 +39,21 @@ impl Clip {
                 ),
                 JavaMethodProto::new("setBuffer", "([BI)V", Self::set_buffer, Default::default()),
             ],
-            fields: vec![JavaFieldProto::new("player", "Ljavax/microedition/media/Player;", Default::default())],
+            fields: vec![
+                JavaFieldProto::new("player", "Ljavax/microedition/media/Player;", Default::default()),
+                JavaFieldProto::new("listener", "Lorg/kwis/msp/media/PlayListener;", Default::default()),
+                JavaFieldProto::new("type", "Ljava/lang/String;", Default::default()),
+            ],
         }
     }
 
-    async fn init(jvm: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>, r#type: ClassInstanceRef<String>) -> JvmResult<()> {
+    async fn init(jvm: &Jvm, _: &mut WieJvmContext, mut this: ClassInstanceRef<Self>, r#type: ClassInstanceRef<String>) -> JvmResult<()> {
         tracing::debug!("org.kwis.msp.media.Clip::<init>({:?}, {:?})", &this, r#type);
 
         let _: () = jvm.invoke_special(&this, "java/lang/Object", "<init>", "()V", ()).await?;
 
+        jvm.put_field(&mut this, "type", "Ljava/lang/String;", r#type).await?;
+
         Ok(())
     }
 
@@ -93,13 +96,15 @@ impl Clip {
     async fn init_with_data(
         jvm: &Jvm,
         _: &mut WieJvmContext,
-        this: ClassInstanceRef<Self>,
+        mut this: ClassInstanceRef<Self>,
         r#type: ClassInstanceRef<String>,
         data: ClassInstanceRef<Array<i8>>,
     ) -> JvmResult<()> {
         tracing::debug!("org.kwis.msp.media.Clip::<init>({:?}, {:?}, {:?})", &this, r#type, &data);
 
         let _: () = jvm.invoke_special(&this, "java/lang/Object", "<init>", "()V", ()).await?;
+
+        jvm.put_field(&mut this, "type", "Ljava/lang/String;", r#type).await?;
         let length = jvm.array_length(&data).await?;
 
         let _: () = jvm.invoke_virtual(&this, "setBuffer", "([BI)V", (data, length as i32)).await?;
@@ -110,12 +115,16 @@ impl Clip {
     async fn init_with_data_size(
         jvm: &Jvm,
         _: &mut WieJvmContext,
-        this: ClassInstanceRef<Self>,
+        mut this: ClassInstanceRef<Self>,
         r#type: ClassInstanceRef<String>,
         size: i32,
     ) -> JvmResult<()> {
         tracing::debug!("org.kwis.msp.media.Clip::<init>({:?}, {:?}, {})", &this, r#type, size);
 
+        let _: () = jvm.invoke_special(&this, "java/lang/Object", "<init>", "()V", ()).await?;
+
+        jvm.put_field(&mut this, "type", "Ljava/lang/String;", r#type).await?;
+
         let data = jvm.instantiate_array("B", size as _).await?;
 
         let _: () = jvm.invoke_virtual(&this, "setBuffer", "([BI)V", (data, size)).await?;
@@ -123,16 +132,29 @@ impl Clip {
         Ok(())
     }
 
-    async fn set_volume(_: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Clip>, level: i32) -> JvmResult<()> {
-        tracing::warn!("stub org.kwis.msp.media.Clip::setVolume({:?}, {})", &this, level);
+    async fn set_volume(jvm: &Jvm, context: &mut WieJvmContext, this: ClassInstanceRef<Clip>, level: i32) -> JvmResult<bool> {
+        tracing::debug!("org.kwis.msp.media.Clip::setVolume({:?}, {})", &this, level);
 
-        Ok(())
+        let player = Self::player(jvm, &this).await?;
+        if player.is_null() {
+            return Ok(false);
+        }
+
+        let audio_handle = AudioPlayer::audio_handle(jvm, &player).await?;
+        let result = context.system().audio().set_volume(audio_handle, level.clamp(0, 100) as _);
+
+        Ok(result.is_ok())
     }
 
-    async fn set_listener(_: &Jvm, _: &mut WieJvmContext, this: ClassInstanceRef<Self>, listener: ClassInstanceRef<PlayListener>) -> JvmResult<()> {
-        tracing::warn!("stub org.kwis.msp.media.Clip::setListener({:?}, {:?})", &this, &listener);
+    async fn set_listener(
+        jvm: &Jvm,
+        _: &mut WieJvmContext,
+        mut this: ClassInstanceRef<Self>,
+        listener: ClassInstanceRef<PlayListener>,
+    ) -> JvmResult<()> {
+        tracing::debug!("org.kwis.msp.media.Clip::setListener({:?}, {:?})", &this, &listener);
 
-        Ok(())
+        jvm.put_field(&mut this, "listener", "Lorg/kwis/msp/media/PlayListener;", listener).await
     }
 
     async fn set_buffer(
@@ -145,7 +167,8 @@ impl Clip {
         tracing::debug!("org.kwis.msp.media.Clip::setBuffer({:?}, {:?}, {})", &this, &buffer, size);
 
         let input_stream = jvm.new_class("java/io/ByteArrayInputStream", "([B)V", (buffer,)).await?;
-        let r#type = JavaLangString::from_rust_string(jvm, "application/vnd.smaf").await?;
+        // player detects format from data, falling back to clip type
+        let r#type: ClassInstanceRef<String> = jvm.get_field(&this, "type", "Ljava/lang/String;").await?;
 
         let player: ClassInstanceRef<Player> = jvm
             .invoke_static(
@@ -164,4 +187,8 @@ impl Clip {
     pub async fn player(jvm: &Jvm, this: &ClassInstanceRef<Self>) -> JvmResult<ClassInstanceRef<Player>> {
         jvm.get_field(this, "player", "Ljavax/microedition/media/Player;").await
     }
+
+    pub async fn listener(jvm: &Jvm, this: &ClassInstanceRef<Self>) -> JvmResult<ClassInstanceRef<PlayListener>> {
+        jvm.get_field(this, "listener", "Lorg/kwis/msp/media/PlayListener;").await
+    }
 }
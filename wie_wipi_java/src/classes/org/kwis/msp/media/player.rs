@@ -1,12 +1,18 @@
-use alloc::vec;
+use alloc::{boxed::Box, vec};
 
-use java_class_proto::JavaMethodProto;
+use java_class_proto::{JavaMethodProto, MethodBody};
 use java_constants::MethodAccessFlags;
-use jvm::{ClassInstanceRef, Jvm, Result as JvmResult};
+use jvm::{ClassInstanceRef, JavaError, JavaValue, Jvm, Result as JvmResult};
 
+use wie_backend::{AudioEvent, AudioHandle};
 use wie_jvm_support::{WieJavaClassProto, WieJvmContext};
+use wie_midp::classes::net::wie::AudioPlayer;
 
-use crate::classes::org::kwis::msp::media::Clip;
+use crate::classes::org::kwis::msp::media::{Clip, PlayListener};
+
+// event passed to PlayListener.update
+const EVENT_STOPPED: i32 = 0;
+const EVENT_END_OF_MEDIA: i32 = 1;
 
 // class org.kwis.msp.media.Player
 pub struct Player;
@@ -25,23 +31,128 @@ impl Player {
         }
     }
 
-    async fn play(jvm: &Jvm, _context: &mut WieJvmContext, clip: ClassInstanceRef<Clip>, repeat: bool) -> JvmResult<bool> {
+    async fn play(jvm: &Jvm, context: &mut WieJvmContext, clip: ClassInstanceRef<Clip>, repeat: bool) -> JvmResult<bool> {
         tracing::debug!("org.kwis.msp.media.Player::play({:?}, {})", &clip, repeat);
 
         let player = Clip::player(jvm, &clip).await?;
+        if player.is_null() {
+            return Ok(false);
+        }
+
+        // clip is played from the beginning, even if it's already playing. new clip's player is unrealized and can't seek yet
+        let _: () = jvm.invoke_virtual(&player, "stop", "()V", ()).await?;
+        let _: () = jvm.invoke_virtual(&player, "prefetch", "()V", ()).await?;
+        let _: i64 = jvm.invoke_virtual(&player, "setMediaTime", "(J)J", (0i64,)).await?;
+
+        let loop_count = if repeat { -1 } else { 1 };
+        let _: () = jvm.invoke_virtual(&player, "setLoopCount", "(I)V", (loop_count,)).await?;
+
+        let audio_handle = AudioPlayer::audio_handle(jvm, &player).await?;
+        let serial = context.system().audio().last_event_serial(audio_handle).unwrap_or(0);
+
+        let _: () = jvm.invoke_virtual(&player, "start", "()V", ()).await?;
+
+        let listener = Clip::listener(jvm, &clip).await?;
+        if !listener.is_null() {
+            context.spawn(
+                jvm,
+                Box::new(PlayListenerNotifier {
+                    clip,
+                    listener,
+                    audio_handle,
+                    serial,
+                }),
+            )?;
+        }
+
+        Ok(true)
+    }
+
+    async fn stop(jvm: &Jvm, _: &mut WieJvmContext, clip: ClassInstanceRef<Clip>) -> JvmResult<bool> {
+        tracing::debug!("org.kwis.msp.media.Player::stop({:?})", &clip);
+
+        let player = Clip::player(jvm, &clip).await?;
+        if player.is_null() {
+            return Ok(false);
+        }
+
+        let _: () = jvm.invoke_virtual(&player, "stop", "()V", ()).await?;
 
-        if !player.is_null() {
-            let _: () = jvm.invoke_virtual(&player, "start", "()V", ()).await?;
+        Ok(true)
+    }
+}
+
+struct PlayListenerNotifier {
+    clip: ClassInstanceRef<Clip>,
+    listener: ClassInstanceRef<PlayListener>,
+    audio_handle: AudioHandle,
+    serial: u64,
+}
 
-            Ok(true)
-        } else {
-            Ok(false)
+#[async_trait::async_trait]
+impl MethodBody<JavaError, WieJvmContext> for PlayListenerNotifier {
+    async fn call(&self, jvm: &Jvm, context: &mut WieJvmContext, _args: Box<[JavaValue]>) -> Result<JavaValue, JavaError> {
+        jvm.attach_thread()?;
+
+        let mut serial = self.serial;
+        while let Some(event) = context.system().wait_audio_event(self.audio_handle, &mut serial).await {
+            let event = match event {
+                AudioEvent::EndOfMedia => EVENT_END_OF_MEDIA,
+                AudioEvent::Stopped => EVENT_STOPPED,
+                _ => continue,
+            };
+
+            let _: () = jvm
+                .invoke_virtual(&self.listener, "update", "(Lorg/kwis/msp/media/Clip;I)V", (self.clip.clone(), event))
+                .await?;
+            break;
         }
+
+        Ok(JavaValue::Void)
     }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::{boxed::Box, vec};
+
+    use jvm::{runtime::JavaLangString, ClassInstanceRef};
+
+    use test_utils::run_jvm_test;
+    use wie_midp::classes::javax::microedition::media::Player as MidpPlayer;
+    use wie_util::Result;
+
+    use crate::{classes::org::kwis::msp::media::Clip, get_protos};
+
+    #[test]
+    fn test_play_new_clip() -> Result<()> {
+        run_jvm_test(Box::new([wie_midp::get_protos().into(), get_protos().into()]), |jvm| async move {
+            // quarter note of middle c
+            let data = vec![-2i8, 1, 60, 16];
+            let mut data_array = jvm.instantiate_array("B", data.len()).await?;
+            jvm.store_array(&mut data_array, 0, data).await?;
+
+            let r#type = JavaLangString::from_rust_string(&jvm, "tone").await?;
+            let clip: ClassInstanceRef<Clip> = jvm
+                .new_class("org/kwis/msp/media/Clip", "(Ljava/lang/String;[B)V", (r#type, data_array))
+                .await?
+                .into();
+
+            let result: bool = jvm
+                .invoke_static(
+                    "org/kwis/msp/media/Player",
+                    "play",
+                    "(Lorg/kwis/msp/media/Clip;Z)Z",
+                    (clip.clone(), false),
+                )
+                .await?;
+            assert!(result);
 
-    async fn stop(_: &Jvm, _: &mut WieJvmContext, clip: ClassInstanceRef<Clip>) -> JvmResult<bool> {
-        tracing::warn!("stub org.kwis.msp.media.Player::stop({:?})", &clip,);
+            let player: ClassInstanceRef<MidpPlayer> = Clip::player(&jvm, &clip).await?;
+            let state: i32 = jvm.invoke_virtual(&player, "getState", "()I", ()).await?;
+            assert_eq!(state, 400); // STARTED
 
-        Ok(false)
+            Ok(())
+        })
     }
 }
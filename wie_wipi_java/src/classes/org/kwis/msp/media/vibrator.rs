@@ -15,18 +15,26 @@ impl Vibrator {
             name: "org/kwis/msp/media/Vibrator",
             parent_class: Some("java/lang/Object"),
             interfaces: vec![],
-            methods: vec![JavaMethodProto::new(
-                "on",
-                "(II)V",
-                Self::on,
-                MethodAccessFlags::NATIVE | MethodAccessFlags::STATIC,
-            )],
+            methods: vec![
+                JavaMethodProto::new("on", "(II)V", Self::on, MethodAccessFlags::NATIVE | MethodAccessFlags::STATIC),
+                JavaMethodProto::new("off", "()V", Self::off, MethodAccessFlags::NATIVE | MethodAccessFlags::STATIC),
+            ],
             fields: vec![],
         }
     }
 
-    async fn on(_: &Jvm, _: &mut WieJvmContext, level: i32, duration: i32) -> JvmResult<()> {
-        tracing::warn!("stub org.kwis.msp.media.Vibrator::on({}, {})", level, duration);
+    async fn on(_: &Jvm, context: &mut WieJvmContext, level: i32, duration: i32) -> JvmResult<()> {
+        tracing::debug!("org.kwis.msp.media.Vibrator::on({}, {})", level, duration);
+
+        context.system().device().vibrate(level, duration.max(0) as _);
+
+        Ok(())
+    }
+
+    async fn off(_: &Jvm, context: &mut WieJvmContext) -> JvmResult<()> {
+        tracing::debug!("org.kwis.msp.media.Vibrator::off()");
+
+        context.system().device().stop_vibration();
 
         Ok(())
     }
@@ -6,7 +6,7 @@ pub mod classes;
 
 use wie_jvm_support::WieJavaClassProto;
 
-pub fn get_protos() -> [WieJavaClassProto; 31] {
+pub fn get_protos() -> [WieJavaClassProto; 35] {
     [
         crate::classes::org::kwis::msf::io::Network::as_proto(),
         crate::classes::org::kwis::msp::db::DataBase::as_proto(),
@@ -26,18 +26,22 @@ pub fn get_protos() -> [WieJavaClassProto; 31] {
         crate::classes::org::kwis::msp::lcdui::Jlet::as_proto(),
         crate::classes::org::kwis::msp::lcdui::JletEventListener::as_proto(),
         crate::classes::org::kwis::msp::lwc::Component::as_proto(),
+        crate::classes::org::kwis::msp::lwc::ComponentListener::as_proto(),
         crate::classes::org::kwis::msp::lwc::ContainerComponent::as_proto(),
         crate::classes::org::kwis::msp::lwc::ShellComponent::as_proto(),
         crate::classes::org::kwis::msp::lwc::AnnunciatorComponent::as_proto(),
         crate::classes::org::kwis::msp::lwc::TextComponent::as_proto(),
         crate::classes::org::kwis::msp::lwc::TextBoxComponent::as_proto(),
         crate::classes::org::kwis::msp::lwc::TextFieldComponent::as_proto(),
+        crate::classes::org::kwis::msp::lwc::ListComponent::as_proto(),
+        crate::classes::org::kwis::msp::lwc::ButtonComponent::as_proto(),
         crate::classes::org::kwis::msp::media::Clip::as_proto(),
         crate::classes::org::kwis::msp::media::Player::as_proto(),
         crate::classes::org::kwis::msp::media::PlayListener::as_proto(),
         crate::classes::org::kwis::msp::media::Vibrator::as_proto(),
         crate::classes::org::kwis::msp::media::Volume::as_proto(),
         crate::classes::net::wie::CardCanvas::as_proto(),
+        crate::classes::net::wie::ShellCard::as_proto(),
         crate::classes::net::wie::WIPIMIDlet::as_proto(),
     ]
 }